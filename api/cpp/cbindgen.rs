@@ -107,6 +107,18 @@ macro_rules! struct_file {
             writeln!(structs_priv, "using slint::StandardListViewItem;")?;
             &mut structs_pub
         }};
+        (StandardTreeViewItem) => {{
+            writeln!(structs_priv, "using slint::StandardTreeViewItem;")?;
+            &mut structs_pub
+        }};
+        (Date) => {{
+            writeln!(structs_priv, "using slint::Date;")?;
+            &mut structs_pub
+        }};
+        (Time) => {{
+            writeln!(structs_priv, "using slint::Time;")?;
+            &mut structs_pub
+        }};
         ($_:ident) => {
             &mut structs_priv
         };
@@ -197,6 +209,8 @@ fn default_config() -> cbindgen::Config {
             ("KeyEventArg".into(), "KeyEvent".into()),
             ("PointerEventArg".into(), "PointerEvent".into()),
             ("PointerScrollEventArg".into(), "PointerScrollEvent".into()),
+            ("DropEventArg".into(), "DropEvent".into()),
+            ("StringArg".into(), "SharedString".into()),
             ("PointArg".into(), "slint::LogicalPosition".into()),
             ("FloatArg".into(), "float".into()),
             ("Coord".into(), "float".into()),
@@ -256,9 +270,14 @@ fn gen_corelib(
         "ImageItem",
         "ClippedImage",
         "TouchArea",
+        "DragArea",
+        "DropArea",
+        "Shortcut",
+        "ContextMenuArea",
         "FocusScope",
         "Flickable",
         "Text",
+        "StyledText",
         "Path",
         "WindowItem",
         "TextInput",
@@ -275,11 +294,13 @@ fn gen_corelib(
         "Slice",
         "WindowAdapterRcOpaque",
         "PropertyAnimation",
+        "AnimationKeyframe",
         "EasingCurve",
         "TextHorizontalAlignment",
         "TextVerticalAlignment",
         "TextOverflow",
         "TextWrap",
+        "TextDirection",
         "ImageFit",
         "FillRule",
         "MouseCursor",
@@ -290,8 +311,11 @@ fn gen_corelib(
         "PointerEventButton",
         "PointerEvent",
         "PointerScrollEvent",
+        "DropEvent",
         "Rect",
         "SortOrder",
+        "TableCellType",
+        "PopupClosePolicy",
         "BitmapFont",
     ]
     .iter()
@@ -334,12 +358,15 @@ fn gen_corelib(
         "slint_property_listener_scope_evaluate",
         "slint_property_listener_scope_is_dirty",
         "PropertyTrackerOpaque",
+        "ChangeTrackerOpaque",
         "CallbackOpaque",
         "WindowAdapterRc",
         "VoidArg",
         "KeyEventArg",
         "PointerEventArg",
         "PointerScrollEventArg",
+        "DropEventArg",
+        "StringArg",
         "PointArg",
         "Point",
         "slint_color_brighter",
@@ -360,6 +387,7 @@ fn gen_corelib(
         "slint_timer_stop",
         "slint_timer_restart",
         "slint_timer_running",
+        "slint_timer_interval",
         "Coord",
         "LogicalRect",
         "LogicalPoint",
@@ -430,6 +458,7 @@ fn gen_corelib(
             "slint_timer_stop",
             "slint_timer_restart",
             "slint_timer_running",
+            "slint_timer_interval",
         ]
         .iter()
         .map(|s| s.to_string())
@@ -671,6 +700,17 @@ fn gen_corelib(
     ~WindowEvent() {}"
             .into(),
     );
+    config.export.body.insert(
+        "AccessibilityAction".to_owned(),
+        "/* The SetValue variant has a destructor (with SharedString), but actions are only ever passed by reference from Rust */
+    ~AccessibilityAction() {}"
+            .into(),
+    );
+    config.export.body.insert(
+        "SupportedAccessibilityAction".to_owned(),
+        "    friend constexpr SupportedAccessibilityAction operator|(SupportedAccessibilityAction a, SupportedAccessibilityAction b) { return { a._0 | b._0 }; }"
+            .into(),
+    );
     config
         .export
         .body
@@ -746,6 +786,9 @@ fn gen_backend_qt(
         "NativeComboBoxPopup",
         "NativeTabWidget",
         "NativeTab",
+        "NativeMenuBarItem",
+        "NativeMenuFrame",
+        "NativeMenuItem",
         "NativeStyleMetrics",
         "NativePalette",
     ];
@@ -257,6 +257,24 @@ box_layout_info_ortho(cbindgen_private::Slice<cbindgen_private::BoxLayoutCellDat
     return cbindgen_private::slint_box_layout_info_ortho(cells, &padding);
 }
 
+inline SharedVector<float> solve_flexbox_layout(const cbindgen_private::FlexboxLayoutData &data,
+                                                cbindgen_private::Orientation orientation,
+                                                cbindgen_private::Slice<int> repeater_indexes)
+{
+    SharedVector<float> result;
+    cbindgen_private::Slice<uint32_t> ri { reinterpret_cast<uint32_t *>(repeater_indexes.ptr),
+                                           repeater_indexes.len };
+    cbindgen_private::slint_solve_flexbox_layout(&data, orientation, ri, &result);
+    return result;
+}
+
+inline cbindgen_private::LayoutInfo
+flexbox_layout_info(const cbindgen_private::FlexboxLayoutData &data,
+                    cbindgen_private::Orientation orientation)
+{
+    return cbindgen_private::slint_flexbox_layout_info(&data, orientation);
+}
+
 /// Access the layout cache of an item within a repeater
 inline float layout_cache_access(const SharedVector<float> &cache, int offset, int repeater_index)
 {
@@ -545,6 +563,28 @@ public:
     }
 };
 
+namespace private_api {
+/// Returns a model with the indexes of the rows of \a model that contain \a filter, ignoring the
+/// case. All the rows match an empty filter.
+template<typename M>
+std::shared_ptr<Model<int>> filter_strings(const std::shared_ptr<M> &model,
+                                           const SharedString &filter)
+{
+    std::vector<int> rows;
+    if (model) {
+        model->track_row_count_changes();
+        auto lowercase_filter = filter.to_lowercase();
+        for (size_t row = 0; row < model->row_count(); ++row) {
+            if (auto text = model->row_data_tracked(row);
+                text && text->to_lowercase().contains(lowercase_filter)) {
+                rows.push_back(int(row));
+            }
+        }
+    }
+    return std::make_shared<VectorModel<int>>(std::move(rows));
+}
+} // namespace private_api
+
 template<typename ModelData>
 class FilterModel;
 
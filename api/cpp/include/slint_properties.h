@@ -4,6 +4,8 @@
 #pragma once
 #include <string_view>
 #include <memory>
+#include <optional>
+#include <type_traits>
 
 namespace slint::cbindgen_private {
 struct PropertyAnimation;
@@ -293,4 +295,59 @@ private:
     cbindgen_private::PropertyTrackerOpaque inner;
 };
 
+/// Calls a handler when the value returned by a function changes.
+/// This is used to implement the `changed` callbacks.
+struct ChangeTracker
+{
+    /// Constructs a new change tracker instance.
+    ChangeTracker() { cbindgen_private::slint_change_tracker_construct(&inner); }
+    /// Destroys the change tracker.
+    ~ChangeTracker() { cbindgen_private::slint_change_tracker_drop(&inner); }
+    /// The copy constructor is intentionally deleted, change trackers cannot be copied.
+    ChangeTracker(const ChangeTracker &) = delete;
+    /// The assignment operator is intentionally deleted, change trackers cannot be copied.
+    ChangeTracker &operator=(const ChangeTracker &) = delete;
+
+    /// Starts tracking the value returned by `eval_fn(data)`. `notify_fn(data, value)` is called
+    /// later, from the event loop, when the value changed.
+    template<typename Data, typename FnEval, typename FnNotify>
+    void init(Data data, FnEval eval_fn, FnNotify notify_fn) const
+    {
+        using Value = std::invoke_result_t<FnEval, Data>;
+        struct Inner
+        {
+            Data data;
+            FnEval eval_fn;
+            FnNotify notify_fn;
+            std::optional<Value> value;
+        };
+        cbindgen_private::slint_change_tracker_init(
+                &inner,
+                new Inner { std::move(data), std::move(eval_fn), std::move(notify_fn), {} },
+                [](void *user_data) { delete reinterpret_cast<Inner *>(user_data); },
+                [](void *user_data) {
+                    auto inner = reinterpret_cast<Inner *>(user_data);
+                    auto value = inner->eval_fn(inner->data);
+                    if (inner->value && *inner->value == value) {
+                        return false;
+                    }
+                    inner->value = std::move(value);
+                    return true;
+                },
+                [](void *user_data) {
+                    auto inner = reinterpret_cast<Inner *>(user_data);
+                    inner->notify_fn(inner->data, *inner->value);
+                });
+    }
+
+    /// Calls the handlers of all the change trackers whose value changed.
+    static void run_change_handlers()
+    {
+        cbindgen_private::slint_change_tracker_run_change_handlers();
+    }
+
+private:
+    cbindgen_private::ChangeTrackerOpaque inner;
+};
+
 } // namespace slint::private_api
@@ -132,6 +132,20 @@ struct SharedString
                 == 0;
     }
 
+    /// \return true if the string contains the specified string; false otherwise
+    bool contains(std::string_view needle) const
+    {
+        return std::string_view(*this).find(needle) != std::string_view::npos;
+    }
+
+    /// \return a copy of this string converted to lowercase, following the Unicode rules
+    SharedString to_lowercase() const
+    {
+        SharedString result;
+        cbindgen_private::slint_shared_string_to_lowercase(&result, this);
+        return result;
+    }
+
     /// Creates a new SharedString from the given number \a n. The string representation of the
     /// number uses a minimal formatting scheme: If \a n has no fractional part, the number will be
     /// formatted as an integer.
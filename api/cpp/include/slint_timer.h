@@ -59,6 +59,11 @@ struct Timer
     void restart() { cbindgen_private::slint_timer_restart(id); }
     /// Returns true if the timer is running; false otherwise.
     bool running() const { return cbindgen_private::slint_timer_running(id); }
+    /// Returns the interval of the timer.
+    std::chrono::milliseconds interval() const
+    {
+        return std::chrono::milliseconds(cbindgen_private::slint_timer_interval(id));
+    }
 
     /// Call the callback after the given duration.
     template<std::invocable F>
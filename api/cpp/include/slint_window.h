@@ -104,15 +104,19 @@ public:
     }
 
     template<typename Component, typename Parent>
-    void show_popup(const Parent *parent_component, cbindgen_private::Point p, bool close_on_click,
-                    cbindgen_private::ItemRc parent_item) const
+    uint32_t show_popup(const Parent *parent_component, cbindgen_private::Point p,
+                        cbindgen_private::PopupClosePolicy close_policy,
+                        cbindgen_private::ItemRc parent_item) const
     {
         auto popup = Component::create(parent_component).into_dyn();
-        cbindgen_private::slint_windowrc_show_popup(&inner, &popup, p, close_on_click,
-                                                    &parent_item);
+        return cbindgen_private::slint_windowrc_show_popup(&inner, &popup, p, close_policy,
+                                                           &parent_item);
     }
 
-    void close_popup() const { cbindgen_private::slint_windowrc_close_popup(&inner); }
+    void close_popup(uint32_t popup_id) const
+    {
+        cbindgen_private::slint_windowrc_close_popup(&inner, popup_id);
+    }
 
     template<std::invocable<RenderingState, GraphicsAPI> F>
     std::optional<SetRenderingNotifierError> set_rendering_notifier(F callback) const
@@ -179,6 +179,26 @@ test('set struct properties', (t) => {
   });
 })
 
+test('get/set builtin struct properties', (t) => {
+  let compiler = new private_api.ComponentCompiler;
+  let definition = compiler.buildFromSource(`
+  export component App {
+    in-out property <Date> date: { year: 2024, month: 2, day: 29 };
+    in-out property <Time> time: { hour: 13, minute: 45, second: 0 };
+  }
+  `, "");
+  t.not(definition, null);
+
+  let instance = definition!.create();
+  t.not(instance, null);
+
+  t.deepEqual(instance!.getProperty("date"), { "year": 2024, "month": 2, "day": 29 });
+  t.deepEqual(instance!.getProperty("time"), { "hour": 13, "minute": 45, "second": 0 });
+
+  instance!.setProperty("date", { "year": 2025, "month": 1, "day": 1 });
+  t.deepEqual(instance!.getProperty("date"), { "year": 2025, "month": 1, "day": 1 });
+})
+
 test('get/set image properties', async (t) => {
   let compiler = new private_api.ComponentCompiler;
   let definition = compiler.buildFromSource(`
@@ -37,6 +37,7 @@ def test_property_access():
                 title: "builtin",
                 finished: true,
             };
+            in property <Date> dateprop: { year: 2024, month: 2, day: 29 };
             in property <image> imageprop: @image-url("../../../examples/printerdemo/ui/images/cat.jpg");
 
             callback test-callback();
@@ -81,6 +82,12 @@ def test_property_access():
     assert instance.get_property("structprop") == {
         'title': 'new', 'finished': False}
 
+    assert instance.get_property("dateprop") == {
+        'year': 2024, 'month': 2, 'day': 29}
+    instance.set_property("dateprop", {'year': 2025, 'month': 1, 'day': 1})
+    assert instance.get_property("dateprop") == {
+        'year': 2025, 'month': 1, 'day': 1}
+
     imageval = instance.get_property("imageprop")
     assert imageval.width == 320
     assert imageval.height == 480
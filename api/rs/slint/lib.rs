@@ -213,9 +213,12 @@ struct MyComponent{ /*...*/ }
 pub use i_slint_core::graphics::{
     Brush, Color, Image, LoadImageError, Rgb8Pixel, Rgba8Pixel, RgbaColor, SharedPixelBuffer,
 };
+pub use i_slint_core::items::{Date, Time};
 pub use i_slint_core::model::{
-    FilterModel, MapModel, Model, ModelExt, ModelNotify, ModelPeer, ModelRc, ModelTracker,
-    ReverseModel, SortModel, StandardListViewItem, TableColumn, VecModel,
+    FilterModel, FlattenedTreeModel, MapModel, Model, ModelExt, ModelNotify, ModelPeer, ModelRc,
+    ModelTracker, ReverseModel, SortModel, StandardListViewItem, StandardTreeViewItem, TableColumn,
+    TreeIndex, TreeModel, TreeModelChangeListener, TreeModelNotify, TreeModelTracker, TreeNode,
+    VecModel, VecTreeModel,
 };
 pub use i_slint_core::sharedvector::SharedVector;
 pub use i_slint_core::timers::{Timer, TimerMode};
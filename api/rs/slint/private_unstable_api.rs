@@ -123,6 +123,26 @@ pub fn set_callback_handler<
     })
 }
 
+pub fn set_change_handler<
+    T: PartialEq + Clone + 'static,
+    StrongRef: StrongItemTreeRef + 'static,
+>(
+    change_tracker: &ChangeTracker,
+    component_strong: &StrongRef,
+    eval: fn(StrongRef) -> T,
+    handler: fn(StrongRef),
+) {
+    change_tracker.init(
+        component_strong.to_weak(),
+        move |weak| <StrongRef as StrongItemTreeRef>::from_weak(weak).map(eval),
+        move |weak, _| {
+            if let Some(strong) = <StrongRef as StrongItemTreeRef>::from_weak(weak) {
+                handler(strong)
+            }
+        },
+    )
+}
+
 pub fn debug(s: SharedString) {
     #[cfg(feature = "log")]
     log::debug!("{s}");
@@ -179,9 +199,12 @@ pub mod re_exports {
     // This one is empty when Qt is not available, which triggers a warning
     #[allow(unused_imports)]
     pub use i_slint_backend_selector::native_widgets::*;
-    pub use i_slint_core::accessibility::AccessibleStringProperty;
+    pub use i_slint_core::accessibility::{
+        AccessibilityAction, AccessibleStringProperty, SupportedAccessibilityAction,
+    };
     pub use i_slint_core::animations::{animation_tick, EasingCurve};
     pub use i_slint_core::callbacks::Callback;
+    pub use i_slint_core::date_time::first_day_of_week;
     pub use i_slint_core::graphics::*;
     pub use i_slint_core::input::{
         key_codes::Key, FocusEvent, InputEventResult, KeyEvent, KeyEventResult, KeyboardModifiers,
@@ -201,8 +224,11 @@ pub mod re_exports {
         logical_position_to_api, LogicalLength, LogicalPoint, LogicalRect,
     };
     pub use i_slint_core::model::*;
-    pub use i_slint_core::properties::{set_state_binding, Property, PropertyTracker, StateInfo};
+    pub use i_slint_core::properties::{
+        set_state_binding, ChangeTracker, Property, PropertyTracker, StateInfo,
+    };
     pub use i_slint_core::slice::Slice;
+    pub use i_slint_core::timers::{Timer, TimerMode};
     pub use i_slint_core::window::{
         InputMethodRequest, WindowAdapter, WindowAdapterRc, WindowInner,
     };
@@ -36,16 +36,36 @@ fn from_rgb(_red: u8, _green: u8, _blue: u8) -> Self {
 
 #[track_caller]
 fn do_test_render_region(renderer: &SoftwareRenderer, x: i32, y: i32, x2: i32, y2: i32) {
+    do_test_render_regions(renderer, &[(x, y, x2, y2)]);
+}
+
+/// Renders and checks that exactly the given disjoint rectangles (as `(x, y, x2, y2)`) were drawn
+#[track_caller]
+fn do_test_render_regions(renderer: &SoftwareRenderer, expected: &[(i32, i32, i32, i32)]) {
     let mut buffer = vec![TestPixel(false); 500 * 500];
     let r = renderer.render(buffer.as_mut_slice(), 500);
+
+    let (x, y, x2, y2) = expected.iter().fold((i32::MAX, i32::MAX, i32::MIN, i32::MIN), |a, e| {
+        (a.0.min(e.0), a.1.min(e.1), a.2.max(e.2), a.3.max(e.3))
+    });
     assert_eq!(r.bounding_box_size(), PhysicalSize { width: (x2 - x) as _, height: (y2 - y) as _ });
     assert_eq!(r.bounding_box_origin(), PhysicalPosition { x, y });
 
+    let mut rects = r
+        .iter()
+        .map(|(o, s)| (o.x, o.y, o.x + s.width as i32, o.y + s.height as i32))
+        .collect::<Vec<_>>();
+    rects.sort();
+    let mut expected = expected.to_vec();
+    expected.sort();
+    assert_eq!(rects, expected);
+
     for py in 0..500 {
         for px in 0..500 {
             assert_eq!(
                 buffer[py * 500 + px].0,
-                (x..x2).contains(&(px as i32)) && (y..y2).contains(&(py as i32)),
+                expected.iter().any(|(x, y, x2, y2)| (*x..*x2).contains(&(px as i32))
+                    && (*y..*y2).contains(&(py as i32))),
                 "unexpected value at {px},{py}"
             )
         }
@@ -97,7 +117,13 @@ fn simple() {
     assert!(!window.draw_if_needed(|_| { unreachable!() }));
     ui.set_c(slint::Color::from_rgb_u8(45, 12, 13));
     assert!(window.draw_if_needed(|renderer| {
-        do_test_render_region(renderer, 10 + 5, 19 + 8, 10 + 50 + 15, 19 + 80 + 13);
+        do_test_render_regions(
+            renderer,
+            &[
+                (10 + 5, 19 + 80, 10 + 5 + 12, 19 + 80 + 13),
+                (10 + 50, 19 + 8, 10 + 50 + 15, 19 + 8 + 17),
+            ],
+        );
     }));
     ui.set_c(slint::Color::from_rgb_u8(45, 12, 13));
     assert!(!window.draw_if_needed(|_| { unreachable!() }));
@@ -143,12 +169,24 @@ fn visibility() {
     assert!(!window.draw_if_needed(|_| { unreachable!() }));
     ui.set_c(false);
     assert!(window.draw_if_needed(|renderer| {
-        do_test_render_region(renderer, 10 + 5, 19 + 8, 10 + 50 + 15, 19 + 80 + 13);
+        do_test_render_regions(
+            renderer,
+            &[
+                (10 + 5, 19 + 80, 10 + 5 + 12, 19 + 80 + 13),
+                (10 + 50, 19 + 8, 10 + 50 + 15, 19 + 8 + 17),
+            ],
+        );
     }));
     assert!(!window.draw_if_needed(|_| { unreachable!() }));
     ui.set_c(true);
     assert!(window.draw_if_needed(|renderer| {
-        do_test_render_region(renderer, 10 + 5, 19 + 8, 10 + 50 + 15, 19 + 80 + 13);
+        do_test_render_regions(
+            renderer,
+            &[
+                (10 + 5, 19 + 80, 10 + 5 + 12, 19 + 80 + 13),
+                (10 + 50, 19 + 8, 10 + 50 + 15, 19 + 8 + 17),
+            ],
+        );
     }));
     assert!(!window.draw_if_needed(|_| { unreachable!() }));
 }
@@ -259,3 +297,59 @@ fn list_view() {
     }));
     assert!(!window.draw_if_needed(|_| { unreachable!() }));
 }
+
+#[cfg(feature = "software-renderer-parallel")]
+#[test]
+fn parallel_rendering() {
+    use slint::platform::software_renderer::{LineBufferProvider, RepaintBufferType};
+    use slint::Rgb8Pixel;
+
+    slint::slint! {
+        export component Ui inherits Window {
+            background: @linear-gradient(90deg, #000044 0%, #884400 100%);
+            for i in 20: Rectangle {
+                x: i * 9phx;
+                y: i * 13phx;
+                width: 60phx;
+                height: 45phx;
+                border-radius: 7phx;
+                border-width: 2phx;
+                border-color: white;
+                background: i < 10 ? #ff000080 : #00ff0080;
+            }
+        }
+    }
+
+    struct FrameBuffer<'a>(&'a mut [Rgb8Pixel]);
+    impl LineBufferProvider for FrameBuffer<'_> {
+        type TargetPixel = Rgb8Pixel;
+        fn process_line(
+            &mut self,
+            line: usize,
+            range: core::ops::Range<usize>,
+            render_fn: impl FnOnce(&mut [Self::TargetPixel]),
+        ) {
+            render_fn(&mut self.0[line * 180..][range])
+        }
+    }
+
+    slint::platform::set_platform(Box::new(TestPlatform)).ok();
+    let ui = Ui::new().unwrap();
+    let window = WINDOW.with(|x| x.clone());
+    window.set_size(slint::PhysicalSize::new(180, 260));
+    ui.show().unwrap();
+
+    let mut by_line = vec![Rgb8Pixel::default(); 180 * 260];
+    assert!(window.draw_if_needed(|renderer| {
+        renderer.set_repaint_buffer_type(RepaintBufferType::NewBuffer);
+        renderer.render_by_line(FrameBuffer(&mut by_line));
+    }));
+
+    let mut parallel = vec![Rgb8Pixel::default(); 180 * 260];
+    window.request_redraw();
+    assert!(window.draw_if_needed(|renderer| {
+        let region = renderer.render_parallel(&mut parallel, 180);
+        assert_eq!(region.bounding_box_size(), PhysicalSize { width: 180, height: 260 });
+    }));
+    assert!(by_line == parallel, "parallel rendering differs from the rendering by line");
+}
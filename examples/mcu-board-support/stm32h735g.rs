@@ -345,8 +345,18 @@ fn run_event_loop(&self) -> Result<(), slint::PlatformError> {
             if let Some(window) = self.window.borrow().clone() {
                 window.draw_if_needed(|renderer| {
                     while inner.layer.is_swap_pending() {}
-                    renderer.render(work_fb, DISPLAY_WIDTH);
-                    inner.scb.clean_dcache_by_slice(work_fb);
+                    let region = renderer.render(work_fb, DISPLAY_WIDTH);
+                    // Only write back the cache of the parts of the frame buffer that were rendered
+                    for (origin, size) in region.iter() {
+                        if size.width == 0 || size.height == 0 {
+                            continue;
+                        }
+                        let begin = origin.y as usize * DISPLAY_WIDTH + origin.x as usize;
+                        let end = (origin.y as usize + size.height as usize - 1) * DISPLAY_WIDTH
+                            + origin.x as usize
+                            + size.width as usize;
+                        inner.scb.clean_dcache_by_slice(&work_fb[begin..end]);
+                    }
                     // Safety: the frame buffer has the right size
                     unsafe { inner.layer.swap_framebuffer(work_fb.as_ptr() as *const u8) };
                     // Swap the buffer pointer so we will work now on the second buffer
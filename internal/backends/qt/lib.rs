@@ -69,7 +69,10 @@ pub extern "C" fn slint_qt_get_widget(
     (qt_widgets::NativeComboBoxPopup,
     (qt_widgets::NativeTabWidget,
     (qt_widgets::NativeTab,
-            ()))))))))))))));
+    (qt_widgets::NativeMenuBarItem,
+    (qt_widgets::NativeMenuFrame,
+    (qt_widgets::NativeMenuItem,
+            ())))))))))))))))));
 
 #[cfg(not(no_qt))]
 #[rustfmt::skip]
@@ -6,7 +6,9 @@
 use crate::accessible_generated::*;
 use crate::qt_window::QtWindow;
 
-use i_slint_core::accessibility::AccessibleStringProperty;
+use i_slint_core::accessibility::{
+    AccessibilityAction, AccessibleStringProperty, SupportedAccessibilityAction,
+};
 use i_slint_core::item_tree::{ItemRc, ItemWeak};
 use i_slint_core::properties::{PropertyDirtyHandler, PropertyTracker};
 use i_slint_core::window::WindowInner;
@@ -266,6 +268,12 @@ fn arm_focus_delegation_tracker(self: Pin<&Self>) {
     const uint32_t VALUE_STEP { VALUE_MAXIMUM + 1 };
     const uint32_t CHECKABLE { VALUE_STEP + 1 };
 
+    /// KEEP IN SYNC WITH SupportedAccessibilityAction IN RUST!
+    const uint32_t ACTION_DEFAULT { 1 << 0 };
+    const uint32_t ACTION_DECREMENT { 1 << 1 };
+    const uint32_t ACTION_INCREMENT { 1 << 2 };
+    const uint32_t ACTION_SET_VALUE { 1 << 3 };
+
     // ------------------------------------------------------------------------------
     // Helper:
     // ------------------------------------------------------------------------------
@@ -312,6 +320,15 @@ fn arm_focus_delegation_tracker(self: Pin<&Self>) {
                     i_slint_core::items::AccessibleRole::Tab => QAccessible_Role_PageTab,
                     i_slint_core::items::AccessibleRole::Text => QAccessible_Role_StaticText,
                     i_slint_core::items::AccessibleRole::ProgressIndicator => QAccessible_Role_ProgressBar,
+                    i_slint_core::items::AccessibleRole::Group => QAccessible_Role_Grouping,
+                    i_slint_core::items::AccessibleRole::List => QAccessible_Role_List,
+                    i_slint_core::items::AccessibleRole::ListItem => QAccessible_Role_ListItem,
+                    i_slint_core::items::AccessibleRole::Table => QAccessible_Role_Table,
+                    // Qt has no dedicated role for switches
+                    i_slint_core::items::AccessibleRole::Switch => QAccessible_Role_CheckBox,
+                    i_slint_core::items::AccessibleRole::TextInput => QAccessible_Role_EditableText,
+                    i_slint_core::items::AccessibleRole::Image => QAccessible_Role_Graphic,
+                    i_slint_core::items::AccessibleRole::Window => QAccessible_Role_Window,
                 }
             });
         }
@@ -359,6 +376,32 @@ fn arm_focus_delegation_tracker(self: Pin<&Self>) {
         });
     }
 
+    uint32_t item_supported_actions(void *data) {
+        return rust!(item_supported_actions_
+            [data: &SlintAccessibleItemData as "void*"] -> u32 as "uint32_t" {
+            data.item.upgrade().map_or(0, |item| item.supported_accessibility_actions().0)
+        });
+    }
+
+    void item_do_action(void *data, uint32_t action, const QString &value) {
+        rust!(item_do_action_
+            [data: &SlintAccessibleItemData as "void*", action: u32 as "uint32_t",
+             value: &QString as "const QString &"] {
+            if let Some(item) = data.item.upgrade() {
+                let action = match SupportedAccessibilityAction(action) {
+                    SupportedAccessibilityAction::Default => AccessibilityAction::Default,
+                    SupportedAccessibilityAction::Decrement => AccessibilityAction::Decrement,
+                    SupportedAccessibilityAction::Increment => AccessibilityAction::Increment,
+                    SupportedAccessibilityAction::SetValue => {
+                        AccessibilityAction::SetValue(value.to_string().into())
+                    }
+                    _ => return,
+                };
+                item.accessibility_action(&action);
+            }
+        });
+    }
+
     // ------------------------------------------------------------------------------
     // Slint_accessible:
     // ------------------------------------------------------------------------------
@@ -533,7 +576,8 @@ fn arm_focus_delegation_tracker(self: Pin<&Self>) {
     // Slint_accessible_item:
     // ------------------------------------------------------------------------------
 
-    class Slint_accessible_item : public Slint_accessible, public QAccessibleValueInterface {
+    class Slint_accessible_item : public Slint_accessible, public QAccessibleValueInterface,
+                                  public QAccessibleActionInterface {
     public:
         Slint_accessible_item(void *item, QObject *obj, QAccessible::Role role, QAccessibleInterface *parent) :
             Slint_accessible(role, parent), m_object(obj)
@@ -611,6 +655,9 @@ fn arm_focus_delegation_tracker(self: Pin<&Self>) {
             if (t == QAccessible::ValueInterface && !item_string_property(m_data, QAccessible::Value).isEmpty()) {
                 return static_cast<QAccessibleValueInterface*>(this);
             }
+            if (t == QAccessible::ActionInterface && item_supported_actions(m_data) != 0) {
+                return static_cast<QAccessibleActionInterface*>(this);
+            }
             return QAccessibleInterface::interface_cast(t);
         }
 
@@ -620,8 +667,9 @@ fn arm_focus_delegation_tracker(self: Pin<&Self>) {
         }
 
         void setCurrentValue(const QVariant &value) override {
-            // FIXME: Implement this?
-            Q_UNUSED(value);
+            if (item_supported_actions(m_data) & ACTION_SET_VALUE) {
+                item_do_action(m_data, ACTION_SET_VALUE, value.toString());
+            }
         }
 
         QVariant maximumValue() const override {
@@ -636,6 +684,37 @@ fn arm_focus_delegation_tracker(self: Pin<&Self>) {
             return item_string_property(m_data, VALUE_STEP);
         }
 
+        // AccessibleActionInterface:
+        QStringList actionNames() const override {
+            auto supported = item_supported_actions(m_data);
+            QStringList names;
+            if (supported & ACTION_DEFAULT) {
+                names << pressAction();
+            }
+            if (supported & ACTION_INCREMENT) {
+                names << increaseAction();
+            }
+            if (supported & ACTION_DECREMENT) {
+                names << decreaseAction();
+            }
+            return names;
+        }
+
+        void doAction(const QString &actionName) override {
+            if (actionName == pressAction()) {
+                item_do_action(m_data, ACTION_DEFAULT, QString());
+            } else if (actionName == increaseAction()) {
+                item_do_action(m_data, ACTION_INCREMENT, QString());
+            } else if (actionName == decreaseAction()) {
+                item_do_action(m_data, ACTION_DECREMENT, QString());
+            }
+        }
+
+        QStringList keyBindingsForAction(const QString &actionName) const override {
+            Q_UNUSED(actionName);
+            return {};
+        }
+
 
     private:
         QObject *m_object = nullptr;
@@ -319,6 +319,9 @@ pub unsafe fn new(painter: &QPainterPtr) -> Self {
 mod tabwidget;
 pub use tabwidget::*;
 
+mod menu;
+pub use menu::*;
+
 mod stylemetrics;
 pub use stylemetrics::*;
 
@@ -0,0 +1,406 @@
+// Copyright © SixtyFPS GmbH <info@slint.dev>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-Royalty-free-1.1 OR LicenseRef-Slint-commercial
+
+use i_slint_core::input::FocusEventResult;
+
+use super::*;
+
+cpp! {{
+    /// The shortcut is drawn right aligned after the tab character, as QMenu does
+    void init_menu_item_option(QStyleOptionMenuItem &option, const QString &title, const QString &shortcut,
+                               bool checkable, bool checked, bool has_submenu, bool is_separator)
+    {
+        option.menuItemType = is_separator ? QStyleOptionMenuItem::Separator
+            : has_submenu ? QStyleOptionMenuItem::SubMenu
+            : QStyleOptionMenuItem::Normal;
+        option.checkType = checkable ? QStyleOptionMenuItem::NonExclusive : QStyleOptionMenuItem::NotCheckable;
+        option.checked = checked;
+        option.text = title;
+        option.text.replace(QChar('&'), QLatin1String("&&"));
+        if (!shortcut.isEmpty()) {
+            option.text += QLatin1Char('\t') + shortcut;
+            auto shortcut_width = option.fontMetrics.horizontalAdvance(shortcut);
+#if QT_VERSION < QT_VERSION_CHECK(6, 0, 0)
+            option.tabWidth = shortcut_width;
+#else
+            option.reservedShortcutWidth = shortcut_width;
+#endif
+        }
+        option.maxIconWidth = 0;
+    }
+}}
+
+#[repr(C)]
+#[derive(FieldOffsets, Default, SlintElement)]
+#[pin]
+pub struct NativeMenuBarItem {
+    pub title: Property<SharedString>,
+    pub enabled: Property<bool>,
+    pub has_hover: Property<bool>,
+    pub pressed: Property<bool>,
+    widget_ptr: std::cell::Cell<SlintTypeErasedWidgetPtr>,
+    animation_tracker: Property<i32>,
+    pub cached_rendering_data: CachedRenderingData,
+}
+
+impl Item for NativeMenuBarItem {
+    fn init(self: Pin<&Self>, _self_rc: &ItemRc) {
+        let animation_tracker_property_ptr = Self::FIELD_OFFSETS.animation_tracker.apply_pin(self);
+        self.widget_ptr.set(cpp! { unsafe [animation_tracker_property_ptr as "void*"] -> SlintTypeErasedWidgetPtr as "std::unique_ptr<SlintTypeErasedWidget>" {
+            return make_unique_animated_widget<QMenuBar>(animation_tracker_property_ptr);
+        }});
+    }
+
+    fn layout_info(
+        self: Pin<&Self>,
+        orientation: Orientation,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+    ) -> LayoutInfo {
+        let text: qttypes::QString = self.title().as_str().into();
+        let widget: NonNull<()> = SlintTypeErasedWidgetPtr::qwidget_ptr(&self.widget_ptr);
+        let size = cpp!(unsafe [
+            text as "QString",
+            widget as "QWidget*"
+        ] -> qttypes::QSize as "QSize" {
+            ensure_initialized();
+            QStyleOptionMenuItem option;
+            option.menuItemType = QStyleOptionMenuItem::Normal;
+            option.text = text;
+            option.text.replace(QChar('&'), QLatin1String("&&"));
+            auto content_size = option.fontMetrics.size(Qt::TextShowMnemonic, option.text);
+            return qApp->style()->sizeFromContents(QStyle::CT_MenuBarItem, &option, content_size, widget);
+        });
+        let min = match orientation {
+            Orientation::Horizontal => size.width,
+            Orientation::Vertical => size.height,
+        } as f32;
+        LayoutInfo { min, preferred: min, ..LayoutInfo::default() }
+    }
+
+    fn input_event_filter_before_children(
+        self: Pin<&Self>,
+        _: MouseEvent,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+        _self_rc: &ItemRc,
+    ) -> InputEventFilterResult {
+        InputEventFilterResult::ForwardAndIgnore
+    }
+
+    fn input_event(
+        self: Pin<&Self>,
+        _: MouseEvent,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+        _self_rc: &i_slint_core::items::ItemRc,
+    ) -> InputEventResult {
+        InputEventResult::EventIgnored
+    }
+
+    fn key_event(
+        self: Pin<&Self>,
+        _: &KeyEvent,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+        _self_rc: &ItemRc,
+    ) -> KeyEventResult {
+        KeyEventResult::EventIgnored
+    }
+
+    fn focus_event(
+        self: Pin<&Self>,
+        _: &FocusEvent,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+        _self_rc: &ItemRc,
+    ) -> FocusEventResult {
+        FocusEventResult::FocusIgnored
+    }
+
+    fn_render! { this dpr size painter widget initial_state =>
+        let text: qttypes::QString = this.title().as_str().into();
+        let enabled: bool = this.enabled();
+        let has_hover: bool = this.has_hover();
+        let pressed: bool = this.pressed();
+        cpp!(unsafe [
+            painter as "QPainterPtr*",
+            widget as "QWidget*",
+            text as "QString",
+            enabled as "bool",
+            has_hover as "bool",
+            pressed as "bool",
+            size as "QSize",
+            dpr as "float",
+            initial_state as "int"
+        ] {
+            QStyleOptionMenuItem option;
+            option.styleObject = widget;
+            option.state |= QStyle::State(initial_state);
+            option.rect = QRect(QPoint(), size / dpr);
+            option.menuRect = option.rect;
+            option.menuItemType = QStyleOptionMenuItem::Normal;
+            option.text = text;
+            option.text.replace(QChar('&'), QLatin1String("&&"));
+            if (enabled) {
+                option.state |= QStyle::State_Enabled;
+                if (has_hover || pressed)
+                    option.state |= QStyle::State_Selected;
+                if (pressed)
+                    option.state |= QStyle::State_Sunken;
+            } else {
+                option.palette.setCurrentColorGroup(QPalette::Disabled);
+            }
+            auto style = qApp->style();
+            style->drawControl(QStyle::CE_MenuBarEmptyArea, &option, painter->get(), widget);
+            style->drawControl(QStyle::CE_MenuBarItem, &option, painter->get(), widget);
+        });
+    }
+}
+
+impl ItemConsts for NativeMenuBarItem {
+    const cached_rendering_data_offset: const_field_offset::FieldOffset<Self, CachedRenderingData> =
+        Self::FIELD_OFFSETS.cached_rendering_data.as_unpinned_projection();
+}
+
+declare_item_vtable! {
+fn slint_get_NativeMenuBarItemVTable() -> NativeMenuBarItemVTable for NativeMenuBarItem
+}
+
+#[repr(C)]
+#[derive(FieldOffsets, Default, SlintElement)]
+#[pin]
+pub struct NativeMenuFrame {
+    widget_ptr: std::cell::Cell<SlintTypeErasedWidgetPtr>,
+    animation_tracker: Property<i32>,
+    pub cached_rendering_data: CachedRenderingData,
+}
+
+impl Item for NativeMenuFrame {
+    fn init(self: Pin<&Self>, _self_rc: &ItemRc) {
+        let animation_tracker_property_ptr = Self::FIELD_OFFSETS.animation_tracker.apply_pin(self);
+        self.widget_ptr.set(cpp! { unsafe [animation_tracker_property_ptr as "void*"] -> SlintTypeErasedWidgetPtr as "std::unique_ptr<SlintTypeErasedWidget>" {
+            return make_unique_animated_widget<QMenu>(animation_tracker_property_ptr);
+        }});
+    }
+
+    fn layout_info(
+        self: Pin<&Self>,
+        _orientation: Orientation,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+    ) -> LayoutInfo {
+        Default::default()
+    }
+
+    fn input_event_filter_before_children(
+        self: Pin<&Self>,
+        _: MouseEvent,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+        _self_rc: &ItemRc,
+    ) -> InputEventFilterResult {
+        InputEventFilterResult::ForwardAndIgnore
+    }
+
+    fn input_event(
+        self: Pin<&Self>,
+        _: MouseEvent,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+        _self_rc: &i_slint_core::items::ItemRc,
+    ) -> InputEventResult {
+        InputEventResult::EventIgnored
+    }
+
+    fn key_event(
+        self: Pin<&Self>,
+        _: &KeyEvent,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+        _self_rc: &ItemRc,
+    ) -> KeyEventResult {
+        KeyEventResult::EventIgnored
+    }
+
+    fn focus_event(
+        self: Pin<&Self>,
+        _: &FocusEvent,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+        _self_rc: &ItemRc,
+    ) -> FocusEventResult {
+        FocusEventResult::FocusIgnored
+    }
+
+    fn_render! { _this dpr size painter widget initial_state =>
+        cpp!(unsafe [
+            painter as "QPainterPtr*",
+            widget as "QWidget*",
+            size as "QSize",
+            dpr as "float",
+            initial_state as "int"
+        ] {
+            auto style = qApp->style();
+            QStyleOption option;
+            option.styleObject = widget;
+            option.state |= QStyle::State(initial_state);
+            option.rect = QRect(QPoint(), size / dpr);
+            painter->get()->fillRect(option.rect, option.palette.window());
+            style->drawPrimitive(QStyle::PE_PanelMenu, &option, painter->get(), widget);
+
+            QStyleOptionFrame frame;
+            frame.styleObject = widget;
+            frame.rect = option.rect;
+            frame.lineWidth = style->pixelMetric(QStyle::PM_MenuPanelWidth, nullptr, widget);
+            frame.midLineWidth = 0;
+            style->drawPrimitive(QStyle::PE_FrameMenu, &frame, painter->get(), widget);
+        });
+    }
+}
+
+impl ItemConsts for NativeMenuFrame {
+    const cached_rendering_data_offset: const_field_offset::FieldOffset<Self, CachedRenderingData> =
+        Self::FIELD_OFFSETS.cached_rendering_data.as_unpinned_projection();
+}
+
+declare_item_vtable! {
+fn slint_get_NativeMenuFrameVTable() -> NativeMenuFrameVTable for NativeMenuFrame
+}
+
+#[repr(C)]
+#[derive(FieldOffsets, Default, SlintElement)]
+#[pin]
+pub struct NativeMenuItem {
+    pub title: Property<SharedString>,
+    pub shortcut: Property<SharedString>,
+    pub enabled: Property<bool>,
+    pub checkable: Property<bool>,
+    pub checked: Property<bool>,
+    pub has_submenu: Property<bool>,
+    pub is_separator: Property<bool>,
+    pub has_hover: Property<bool>,
+    widget_ptr: std::cell::Cell<SlintTypeErasedWidgetPtr>,
+    animation_tracker: Property<i32>,
+    pub cached_rendering_data: CachedRenderingData,
+}
+
+impl Item for NativeMenuItem {
+    fn init(self: Pin<&Self>, _self_rc: &ItemRc) {
+        let animation_tracker_property_ptr = Self::FIELD_OFFSETS.animation_tracker.apply_pin(self);
+        self.widget_ptr.set(cpp! { unsafe [animation_tracker_property_ptr as "void*"] -> SlintTypeErasedWidgetPtr as "std::unique_ptr<SlintTypeErasedWidget>" {
+            return make_unique_animated_widget<QMenu>(animation_tracker_property_ptr);
+        }});
+    }
+
+    fn layout_info(
+        self: Pin<&Self>,
+        orientation: Orientation,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+    ) -> LayoutInfo {
+        let title: qttypes::QString = self.title().as_str().into();
+        let shortcut: qttypes::QString = self.shortcut().as_str().into();
+        let checkable: bool = self.checkable();
+        let has_submenu: bool = self.has_submenu();
+        let is_separator: bool = self.is_separator();
+        let widget: NonNull<()> = SlintTypeErasedWidgetPtr::qwidget_ptr(&self.widget_ptr);
+        let size = cpp!(unsafe [
+            title as "QString",
+            shortcut as "QString",
+            checkable as "bool",
+            has_submenu as "bool",
+            is_separator as "bool",
+            widget as "QWidget*"
+        ] -> qttypes::QSize as "QSize" {
+            ensure_initialized();
+            QStyleOptionMenuItem option;
+            init_menu_item_option(option, title, shortcut, checkable, false, has_submenu, is_separator);
+            // Same as QMenu: the text, the space reserved for the shortcut, and the height of a line
+            auto shortcut_width = shortcut.isEmpty() ? 0 : option.fontMetrics.horizontalAdvance(shortcut);
+            QSize content_size(option.fontMetrics.horizontalAdvance(title) + shortcut_width,
+                               is_separator ? 0 : option.fontMetrics.height());
+            return qApp->style()->sizeFromContents(QStyle::CT_MenuItem, &option, content_size, widget);
+        });
+        let min = match orientation {
+            Orientation::Horizontal => size.width,
+            Orientation::Vertical => size.height,
+        } as f32;
+        LayoutInfo { min, preferred: min, ..LayoutInfo::default() }
+    }
+
+    fn input_event_filter_before_children(
+        self: Pin<&Self>,
+        _: MouseEvent,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+        _self_rc: &ItemRc,
+    ) -> InputEventFilterResult {
+        InputEventFilterResult::ForwardAndIgnore
+    }
+
+    fn input_event(
+        self: Pin<&Self>,
+        _: MouseEvent,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+        _self_rc: &i_slint_core::items::ItemRc,
+    ) -> InputEventResult {
+        InputEventResult::EventIgnored
+    }
+
+    fn key_event(
+        self: Pin<&Self>,
+        _: &KeyEvent,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+        _self_rc: &ItemRc,
+    ) -> KeyEventResult {
+        KeyEventResult::EventIgnored
+    }
+
+    fn focus_event(
+        self: Pin<&Self>,
+        _: &FocusEvent,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+        _self_rc: &ItemRc,
+    ) -> FocusEventResult {
+        FocusEventResult::FocusIgnored
+    }
+
+    fn_render! { this dpr size painter widget initial_state =>
+        let title: qttypes::QString = this.title().as_str().into();
+        let shortcut: qttypes::QString = this.shortcut().as_str().into();
+        let enabled: bool = this.enabled();
+        let checkable: bool = this.checkable();
+        let checked: bool = this.checked();
+        let has_submenu: bool = this.has_submenu();
+        let is_separator: bool = this.is_separator();
+        let has_hover: bool = this.has_hover();
+        cpp!(unsafe [
+            painter as "QPainterPtr*",
+            widget as "QWidget*",
+            title as "QString",
+            shortcut as "QString",
+            enabled as "bool",
+            checkable as "bool",
+            checked as "bool",
+            has_submenu as "bool",
+            is_separator as "bool",
+            has_hover as "bool",
+            size as "QSize",
+            dpr as "float",
+            initial_state as "int"
+        ] {
+            QStyleOptionMenuItem option;
+            option.styleObject = widget;
+            option.state |= QStyle::State(initial_state);
+            option.rect = QRect(QPoint(), size / dpr);
+            option.menuRect = option.rect;
+            init_menu_item_option(option, title, shortcut, checkable, checked, has_submenu, is_separator);
+            if (enabled) {
+                option.state |= QStyle::State_Enabled;
+                if (has_hover && !is_separator)
+                    option.state |= QStyle::State_Selected;
+            } else {
+                option.palette.setCurrentColorGroup(QPalette::Disabled);
+            }
+            qApp->style()->drawControl(QStyle::CE_MenuItem, &option, painter->get(), widget);
+        });
+    }
+}
+
+impl ItemConsts for NativeMenuItem {
+    const cached_rendering_data_offset: const_field_offset::FieldOffset<Self, CachedRenderingData> =
+        Self::FIELD_OFFSETS.cached_rendering_data.as_unpinned_projection();
+}
+
+declare_item_vtable! {
+fn slint_get_NativeMenuItemVTable() -> NativeMenuItemVTable for NativeMenuItem
+}
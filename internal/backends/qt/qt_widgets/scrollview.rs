@@ -221,6 +221,9 @@ fn input_event(
                     }
                     InputEventResult::EventAccepted
                 }
+                MouseEvent::DragMove { .. } | MouseEvent::Drop { .. } => {
+                    InputEventResult::EventIgnored
+                }
             };
             self.data.set(data);
             result
@@ -17,7 +17,7 @@
 use i_slint_core::item_tree::{ItemTreeRc, ItemTreeRef};
 use i_slint_core::items::{
     self, FillRule, ImageRendering, ItemRc, ItemRef, Layer, MouseCursor, Opacity,
-    PointerEventButton, RenderingResult, TextOverflow, TextWrap,
+    PointerEventButton, RenderingResult, TextDirection, TextOverflow, TextWrap,
 };
 use i_slint_core::layout::Orientation;
 use i_slint_core::lengths::{
@@ -157,12 +157,13 @@ struct SlintWidget : QWidget {
             isMouseButtonDown = false;
 
             void *parent_of_popup_to_close = nullptr;
+            uint32_t popup_to_close = 0;
             if (auto p = dynamic_cast<const SlintWidget*>(parent())) {
                 void *parent_window = p->rust_window;
-                bool close_popup = rust!(Slint_mouseReleaseEventPopup [parent_window: &QtWindow as "void*"] -> bool as "bool" {
-                    parent_window.close_popup_on_click()
+                popup_to_close = rust!(Slint_mouseReleaseEventPopup [parent_window: &QtWindow as "void*"] -> u32 as "uint32_t" {
+                    parent_window.top_popup_to_close_on_click().map_or(0, |id| id.get())
                 });
-                if (close_popup) {
+                if (popup_to_close) {
                     parent_of_popup_to_close = parent_window;
                 }
             }
@@ -175,8 +176,8 @@ struct SlintWidget : QWidget {
                 rust_window.mouse_event(MouseEvent::Released{ position, button, click_count: 0 })
             });
             if (parent_of_popup_to_close) {
-                rust!(Slint_mouseReleaseEventClosePopup [parent_of_popup_to_close: &QtWindow as "void*"] {
-                    parent_of_popup_to_close.close_popup();
+                rust!(Slint_mouseReleaseEventClosePopup [parent_of_popup_to_close: &QtWindow as "void*", popup_to_close: u32 as "uint32_t"] {
+                    parent_of_popup_to_close.close_popup(popup_to_close);
                 });
             }
         }
@@ -219,6 +220,15 @@ struct SlintWidget : QWidget {
             rust!(Slint_keyPress [rust_window: &QtWindow as "void*", key: i32 as "int", text: qttypes::QString as "QString", repeat: bool as "bool"] {
                 rust_window.key_event(key, text.clone(), false, repeat);
             });
+            // The popups are tracked by the window that shows them
+            if (key == Qt::Key_Escape) {
+                if (auto p = dynamic_cast<const SlintWidget*>(parent())) {
+                    void *parent_window = p->rust_window;
+                    rust!(Slint_keyPressClosePopup [parent_window: &QtWindow as "void*"] {
+                        parent_window.close_top_popup_on_escape();
+                    });
+                }
+            }
         }
         void keyReleaseEvent(QKeyEvent *event) override {
             if (!rust_window)
@@ -361,6 +371,10 @@ struct SlintWidget : QWidget {
             options.setAlignment(Qt::AlignLeft);
         else if (flags & Qt::AlignRight)
             options.setAlignment(Qt::AlignRight);
+        if (flags & Qt::TextForceLeftToRight)
+            options.setTextDirection(Qt::LeftToRight);
+        else if (flags & Qt::TextForceRightToLeft)
+            options.setTextDirection(Qt::RightToLeft);
         options.setFlags(QTextOption::IncludeTrailingSpaces);
         layout.setTextOption(options);
         layout.setCacheEnabled(true);
@@ -673,7 +687,7 @@ fn draw_text(&mut self, text: std::pin::Pin<&items::Text>, _: &ItemRc, size: Log
         } | match text.wrap() {
             TextWrap::NoWrap => 0,
             TextWrap::WordWrap => key_generated::Qt_TextFlag_TextWordWrap,
-        };
+        } | text_direction_flag(text.text_direction());
         let elide = text.overflow() == TextOverflow::Elide;
         let painter: &mut QPainterPtr = &mut self.painter;
         cpp! { unsafe [painter as "QPainterPtr*", rect as "QRectF", fill_brush as "QBrush", mut string as "QString", flags as "int", font as "QFont", elide as "bool"] {
@@ -735,6 +749,39 @@ fn draw_text(&mut self, text: std::pin::Pin<&items::Text>, _: &ItemRc, size: Log
         }}
     }
 
+    fn draw_styled_text(&mut self, text: Pin<&items::StyledText>, _: &ItemRc, size: LogicalSize) {
+        let _: qttypes::QRectF = check_geometry!(size);
+        let window_adapter = WindowInner::from_pub(self.window).window_adapter();
+        for run in text.render_runs(size, &window_adapter) {
+            let to_qrectf = |r: &LogicalRect| qttypes::QRectF {
+                x: r.origin.x as _,
+                y: r.origin.y as _,
+                width: r.size.width as _,
+                height: r.size.height as _,
+            };
+            let rect = to_qrectf(&run.rect);
+            let fill_brush: qttypes::QBrush =
+                into_qbrush(run.brush.clone(), rect.width, rect.height);
+            let string: qttypes::QString = run.text.as_str().into();
+            let font: QFont = get_font(run.font_request.clone());
+            let painter: &mut QPainterPtr = &mut self.painter;
+            cpp! { unsafe [painter as "QPainterPtr*", rect as "QRectF", fill_brush as "QBrush", string as "QString", font as "QFont"] {
+                (*painter)->setFont(font);
+                (*painter)->setPen(QPen(fill_brush, 0));
+                (*painter)->setBrush(Qt::NoBrush);
+                (*painter)->drawText(rect, Qt::AlignLeft | Qt::AlignTop | Qt::TextDontClip, string);
+            }}
+            for decoration in run.decorations.iter() {
+                let decoration = to_qrectf(decoration);
+                let brush: qttypes::QBrush =
+                    into_qbrush(run.brush.clone(), decoration.width, decoration.height);
+                cpp! { unsafe [painter as "QPainterPtr*", decoration as "QRectF", brush as "QBrush"] {
+                    (*painter)->fillRect(decoration, brush);
+                }}
+            }
+        }
+    }
+
     fn draw_text_input(
         &mut self,
         text_input: std::pin::Pin<&items::TextInput>,
@@ -757,7 +804,7 @@ fn draw_text_input(
         } | match text_input.wrap() {
             TextWrap::NoWrap => 0,
             TextWrap::WordWrap => key_generated::Qt_TextFlag_TextWordWrap,
-        };
+        } | text_direction_flag(text_input.text_direction());
 
         let visual_representation = text_input.visual_representation(Some(qt_password_character));
 
@@ -1161,6 +1208,13 @@ fn rotate(&mut self, angle_in_degrees: f32) {
         }}
     }
 
+    fn scale(&mut self, x_factor: f32, y_factor: f32) {
+        let painter: &mut QPainterPtr = &mut self.painter;
+        cpp! { unsafe [painter as "QPainterPtr*", x_factor as "float", y_factor as "float"] {
+            (*painter)->scale(x_factor, y_factor);
+        }}
+    }
+
     fn apply_opacity(&mut self, opacity: f32) {
         let painter: &mut QPainterPtr = &mut self.painter;
         cpp! { unsafe [painter as "QPainterPtr*", opacity as "float"] {
@@ -1633,12 +1687,18 @@ fn key_event(&self, key: i32, text: qttypes::QString, released: bool, repeat: bo
         timer_event();
     }
 
-    fn close_popup(&self) {
-        WindowInner::from_pub(&self.window).close_popup();
+    fn close_popup(&self, popup_id: u32) {
+        if let Some(popup_id) = core::num::NonZeroU32::new(popup_id) {
+            WindowInner::from_pub(&self.window).close_popup(popup_id);
+        }
+    }
+
+    fn top_popup_to_close_on_click(&self) -> Option<core::num::NonZeroU32> {
+        WindowInner::from_pub(&self.window).top_popup_to_close_on_click()
     }
 
-    fn close_popup_on_click(&self) -> bool {
-        WindowInner::from_pub(&self.window).close_popup_on_click()
+    fn close_top_popup_on_escape(&self) -> bool {
+        WindowInner::from_pub(&self.window).close_top_popup_on_escape()
     }
 
     fn window_state_event(&self) {
@@ -2074,7 +2134,7 @@ fn text_input_byte_offset_for_position(
         } | match text_input.wrap() {
             TextWrap::NoWrap => 0,
             TextWrap::WordWrap => key_generated::Qt_TextFlag_TextWordWrap,
-        };
+        } | text_direction_flag(text_input.text_direction());
         let single_line: bool = text_input.single_line();
         let byte_offset = cpp! { unsafe [font as "QFont", string as "QString", pos as "QPointF", flags as "int",
                 rect as "QRectF", single_line as "bool"] -> usize as "size_t" {
@@ -2131,7 +2191,7 @@ fn text_input_cursor_rect_for_byte_offset(
         } | match text_input.wrap() {
             TextWrap::NoWrap => 0,
             TextWrap::WordWrap => key_generated::Qt_TextFlag_TextWordWrap,
-        };
+        } | text_direction_flag(text_input.text_direction());
         let single_line: bool = text_input.single_line();
         let r = cpp! { unsafe [font as "QFont", mut string as "QString", offset as "int", flags as "int", rect as "QRectF", single_line as "bool"]
                 -> qttypes::QRectF as "QRectF" {
@@ -2429,6 +2489,15 @@ pub extern "C" fn slint_qt_get_widget(
     }
 }
 
+/// The Qt text flag forcing the base direction of the text, if any
+fn text_direction_flag(direction: TextDirection) -> key_generated::Qt_TextFlag {
+    match direction {
+        TextDirection::Auto => 0,
+        TextDirection::LeftToRight => key_generated::Qt_TextFlag_TextForceLeftToRight,
+        TextDirection::RightToLeft => key_generated::Qt_TextFlag_TextForceRightToLeft,
+    }
+}
+
 fn utf8_byte_offset_to_utf16_units(str: &str, byte_offset: usize) -> usize {
     let mut current_offset = 0;
     let mut utf16_units = 0;
@@ -9,9 +9,11 @@
 use std::sync::{Arc, Condvar, Mutex};
 
 use accesskit::{
-    Action, ActionRequest, Checked, Node, NodeBuilder, NodeId, Role, Tree, TreeUpdate,
+    Action, ActionData, ActionRequest, Checked, Node, NodeBuilder, NodeId, Role, Tree, TreeUpdate,
+};
+use i_slint_core::accessibility::{
+    AccessibilityAction, AccessibleStringProperty, SupportedAccessibilityAction,
 };
-use i_slint_core::accessibility::AccessibleStringProperty;
 use i_slint_core::item_tree::{ItemTreeRc, ItemTreeRef, ItemTreeWeak};
 use i_slint_core::items::{ItemRc, WindowItem};
 use i_slint_core::lengths::ScaleFactor;
@@ -119,10 +121,23 @@ fn focus_node(&self) -> NodeId {
 
     fn handle_request(&self, request: ActionRequest) {
         let Some(window_adapter) = self.window_adapter_weak.upgrade() else { return };
-        if request.action == Action::Focus {
-            if let Some(item) = self.item_rc_for_node_id(request.target) {
+        let Some(item) = self.item_rc_for_node_id(request.target) else { return };
+        match request.action {
+            Action::Focus => {
                 WindowInner::from_pub(window_adapter.window()).set_focus_item(&item);
             }
+            Action::Default => item.accessibility_action(&AccessibilityAction::Default),
+            Action::Decrement => item.accessibility_action(&AccessibilityAction::Decrement),
+            Action::Increment => item.accessibility_action(&AccessibilityAction::Increment),
+            Action::SetValue => {
+                let value = match request.data {
+                    Some(ActionData::Value(value)) => value.as_ref().into(),
+                    Some(ActionData::NumericValue(value)) => value.to_string().into(),
+                    _ => return,
+                };
+                item.accessibility_action(&AccessibilityAction::SetValue(value));
+            }
+            _ => {}
         }
     }
 
@@ -364,6 +379,14 @@ fn build_node_without_children(&self, item: &ItemRc, scale_factor: ScaleFactor)
                     i_slint_core::items::AccessibleRole::ProgressIndicator => {
                         Role::ProgressIndicator
                     }
+                    i_slint_core::items::AccessibleRole::Group => Role::Group,
+                    i_slint_core::items::AccessibleRole::List => Role::List,
+                    i_slint_core::items::AccessibleRole::ListItem => Role::ListItem,
+                    i_slint_core::items::AccessibleRole::Table => Role::Table,
+                    i_slint_core::items::AccessibleRole::Switch => Role::Switch,
+                    i_slint_core::items::AccessibleRole::TextInput => Role::TextInput,
+                    i_slint_core::items::AccessibleRole::Image => Role::Image,
+                    i_slint_core::items::AccessibleRole::Window => Role::Window,
                 },
                 item.accessible_string_property(
                     i_slint_core::accessibility::AccessibleStringProperty::Label,
@@ -409,10 +432,25 @@ fn build_node_without_children(&self, item: &ItemRc, scale_factor: ScaleFactor)
                 | Role::Slider
                 | Role::SpinButton
                 | Role::Tab
+                | Role::ListItem
+                | Role::Switch
+                | Role::TextInput
         ) {
             builder.add_action(Action::Focus);
         }
 
+        let supported = item.supported_accessibility_actions();
+        for (flag, action) in [
+            (SupportedAccessibilityAction::Default, Action::Default),
+            (SupportedAccessibilityAction::Decrement, Action::Decrement),
+            (SupportedAccessibilityAction::Increment, Action::Increment),
+            (SupportedAccessibilityAction::SetValue, Action::SetValue),
+        ] {
+            if supported.contains(flag) {
+                builder.add_action(action);
+            }
+        }
+
         let min = item.accessible_string_property(AccessibleStringProperty::ValueMinimum);
         let max = item.accessible_string_property(AccessibleStringProperty::ValueMaximum);
         let step = item.accessible_string_property(AccessibleStringProperty::ValueStep);
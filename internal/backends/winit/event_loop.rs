@@ -240,17 +240,49 @@ fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
     }
 }
 
+/// Returns the `file://` URI for the path, as used in the `text/uri-list` mime type
+fn file_uri(path: &std::path::Path) -> String {
+    let path = path.to_string_lossy();
+    let mut uri = String::from("file://");
+    if !path.starts_with('/') {
+        // Windows paths such as C:\foo
+        uri.push('/');
+    }
+    for b in path.bytes() {
+        match b {
+            b'\\' => uri.push('/'),
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'/' | b':' => {
+                uri.push(b as char)
+            }
+            _ => uri.push_str(&format!("%{b:02X}")),
+        }
+    }
+    uri
+}
+
+/// Files dragged over or dropped on a window. winit sends one event per file, so they
+/// are collected and dispatched together once the batch of events is processed.
+struct PendingFileDrag {
+    window_id: winit::window::WindowId,
+    dropped: bool,
+    uris: Vec<String>,
+}
+
 #[derive(Default)]
 pub struct EventLoopState {
     // last seen cursor position
     cursor_pos: LogicalPoint,
     pressed: bool,
+    pending_file_drag: Option<PendingFileDrag>,
 
     loop_error: Option<PlatformError>,
 }
 
 impl EventLoopState {
     fn process_window_event(&mut self, window: Rc<WinitWindowAdapter>, event: WindowEvent) {
+        if !matches!(event, WindowEvent::HoveredFile(_) | WindowEvent::DroppedFile(_)) {
+            self.flush_file_drag();
+        }
         let runtime_window = WindowInner::from_pub(window.window());
         match event {
             WindowEvent::RedrawRequested => {
@@ -432,10 +464,56 @@ macro_rules! winit_key_to_char {
                 // In addition to the hack done for WindowEvent::Resize, also do it for Occluded so we handle Minimized change
                 window.window_state_event();
             }
+            WindowEvent::HoveredFile(path) => {
+                self.add_file_to_drag(window.winit_window().id(), false, &path);
+            }
+            WindowEvent::HoveredFileCancelled => {
+                window.window().dispatch_event(corelib::platform::WindowEvent::DragExited);
+            }
+            WindowEvent::DroppedFile(path) => {
+                self.add_file_to_drag(window.winit_window().id(), true, &path);
+            }
             _ => {}
         }
     }
 
+    fn add_file_to_drag(
+        &mut self,
+        window_id: winit::window::WindowId,
+        dropped: bool,
+        path: &std::path::Path,
+    ) {
+        if self
+            .pending_file_drag
+            .as_ref()
+            .is_some_and(|p| p.window_id != window_id || p.dropped != dropped)
+        {
+            self.flush_file_drag();
+        }
+        self.pending_file_drag
+            .get_or_insert_with(|| PendingFileDrag { window_id, dropped, uris: Vec::new() })
+            .uris
+            .push(file_uri(path));
+    }
+
+    /// Dispatch the files collected by add_file_to_drag as a single event
+    fn flush_file_drag(&mut self) {
+        let Some(PendingFileDrag { window_id, dropped, uris }) = self.pending_file_drag.take()
+        else {
+            return;
+        };
+        let Some(window) = window_by_id(window_id) else { return };
+        // winit doesn't report the position of the files being dragged, so use the last known cursor position
+        let position = corelib::api::LogicalPosition::new(self.cursor_pos.x, self.cursor_pos.y);
+        let mime_type = "text/uri-list".into();
+        let data = uris.join("\n").into();
+        window.window().dispatch_event(if dropped {
+            corelib::platform::WindowEvent::Dropped { position, mime_type, data }
+        } else {
+            corelib::platform::WindowEvent::DragMoved { position, mime_type, data }
+        });
+    }
+
     fn process_event(
         &mut self,
         event: Event<SlintUserEvent>,
@@ -486,6 +564,8 @@ fn process_event(
             }),
 
             Event::AboutToWait => {
+                self.flush_file_drag();
+
                 if !event_loop_target.exiting() {
                     ALL_WINDOWS.with(|windows| {
                         for w in windows.borrow().iter().filter_map(|(_, w)| w.upgrade()) {
@@ -86,6 +86,20 @@ struct PointerScrollEvent {
                 }
             }
 
+            /// Represents the data of a drag and drop operation.
+            /// This structure is passed to the `drag-enter` and `dropped` callbacks of the `DropArea` element.
+            struct DropEvent {
+                @name = "slint::private_api::DropEvent"
+                export {
+                    /// The mime type of the data, for example `text/plain` or `text/uri-list` for files
+                    mime_type: SharedString,
+                    /// The data being dragged
+                    data: SharedString,
+                }
+                private {
+                }
+            }
+
             /// This structure is generated and passed to the key press and release callbacks of the `FocusScope` element.
             struct KeyEvent {
                 @name = "slint::private_api::KeyEvent"
@@ -122,6 +136,58 @@ struct StandardListViewItem {
                 export {
                     /// The text content of the item
                     text: SharedString,
+                    /// True if the check box of the item is checked, in a column of a StandardTableView showing check boxes
+                    checked: bool,
+                }
+                private {
+                }
+            }
+
+            /// Represents a row in a StandardTreeView.
+            #[non_exhaustive]
+            struct StandardTreeViewItem {
+                @name = "slint::StandardTreeViewItem"
+                export {
+                    /// The text content of the item
+                    text: SharedString,
+                    /// The depth of the item in the tree, 0 for the top level items
+                    level: i32,
+                    /// True if the item has children and can be expanded
+                    has_children: bool,
+                    /// True if the children of the item are shown
+                    expanded: bool,
+                }
+                private {
+                }
+            }
+
+            /// Represents a calendar date, as used by the `DatePicker` widget.
+            #[derive(Copy, Eq)]
+            struct Date {
+                @name = "slint::Date"
+                export {
+                    /// The year, for example 2024
+                    year: i32,
+                    /// The month, from 1 (January) to 12 (December)
+                    month: i32,
+                    /// The day of the month, from 1 to 31
+                    day: i32,
+                }
+                private {
+                }
+            }
+
+            /// Represents a time of the day, as used by the `TimePicker` widget.
+            #[derive(Copy, Eq)]
+            struct Time {
+                @name = "slint::Time"
+                export {
+                    /// The hour, from 0 to 23
+                    hour: i32,
+                    /// The minute, from 0 to 59
+                    minute: i32,
+                    /// The second, from 0 to 59
+                    second: i32,
                 }
                 private {
                 }
@@ -142,6 +208,10 @@ struct TableColumn {
                     sort_order: SortOrder,
                     /// the actual width of the column (logical length)
                     width: Coord,
+                    /// How the cells of the column are shown
+                    cell_type: TableCellType,
+                    /// True if the user can edit the cells of the column
+                    editable: bool,
                 }
                 private {
                 }
@@ -56,6 +56,18 @@ enum TextOverflow {
                 Elide,
             }
 
+            /// This enum describes the base direction of the paragraphs of a [`Text`](elements.md#text) or
+            /// [`TextInput`](elements.md#textinput) element, used by the Unicode bidirectional algorithm to order
+            /// mixed left-to-right and right-to-left text.
+            enum TextDirection {
+                /// The direction is determined by the first strong directional character of each paragraph.
+                Auto,
+                /// The paragraphs are laid out from left to right.
+                LeftToRight,
+                /// The paragraphs are laid out from right to left.
+                RightToLeft,
+            }
+
             /// This enum describes whether an event was rejected or accepted by an event handler.
             enum EventResult {
                 /// The event is rejected by this event handler and may then be handled by the parent item
@@ -307,6 +319,37 @@ enum LayoutAlignment {
                 SpaceAround,
             }
 
+            /// This enum describes the direction of the main axis of a [`FlexboxLayout`](elements.md#flexboxlayout),
+            /// along which the elements are placed before they wrap.
+            enum FlexDirection {
+                /// The elements are placed from left to right, and wrap into new rows.
+                Row,
+                /// The elements are placed from top to bottom, and wrap into new columns.
+                Column,
+            }
+
+            /// This enum describes whether the elements of a [`FlexboxLayout`](elements.md#flexboxlayout)
+            /// wrap into several lines when they don't fit.
+            enum FlexWrap {
+                /// The elements wrap into a new line when there isn't enough space left along the main axis.
+                Wrap,
+                /// All elements are placed on a single line, like in a `HorizontalLayout` or `VerticalLayout`.
+                NoWrap,
+            }
+
+            /// This enum describes how the elements of a [`FlexboxLayout`](elements.md#flexboxlayout) are
+            /// aligned across the main axis, within their line.
+            enum FlexAlignment {
+                /// The elements are stretched to the size of their line, within their minimum and maximum size.
+                Stretch,
+                /// The elements use their preferred size and are placed at the start of their line.
+                Start,
+                /// The elements use their preferred size and are centered within their line.
+                Center,
+                /// The elements use their preferred size and are placed at the end of their line.
+                End,
+            }
+
             /// PathEvent is a low-level data structure describing the composition of a path. Typically it is
             /// generated at compile time from a higher-level description, such as SVG commands.
             enum PathEvent {
@@ -345,6 +388,23 @@ enum AccessibleRole {
                 Text,
                 /// The element is a [`ProgressIndicator`](../widgets/progressindicator.md) or behaves like one.
                 ProgressIndicator,
+                /// The element groups other elements, like a [`ToolTip`](../widgets/tooltip.md) for the elements it describes.
+                Group,
+                /// The element is a [`ListView`](../widgets/listview.md) or behaves like one.
+                List,
+                /// The element is an item of a [`ListView`](../widgets/listview.md) or of another list.
+                ListItem,
+                /// The element is a [`StandardTableView`](../widgets/standardtableview.md) or behaves like one.
+                Table,
+                /// The element is a [`Switch`](../widgets/switch.md) or behaves like one.
+                Switch,
+                /// The role for a [`TextInput`](elements.md#textinput) element, or a widget such as a
+                /// [`LineEdit`](../widgets/lineedit.md) that lets the user edit text.
+                TextInput,
+                /// The element is an [`Image`](elements.md#image) or behaves like one.
+                Image,
+                /// The element is the root of a [`Window`](elements.md#window) or of a dialog.
+                Window,
             }
 
             /// This enum represents the different values of the `sort-order` property.
@@ -360,6 +420,22 @@ enum SortOrder {
                 Descending,
             }
 
+            /// This enum describes how the cells of a column of a [`StandardTableView`](../widgets/standardtableview.md)
+            /// are shown, with the `cell-type` field of the [`TableColumn`](structs.md#tablecolumn).
+            enum TableCellType {
+                /// The cell shows the `text` of the item.
+                Text,
+
+                /// The cell shows a check box, checked according to the `checked` field of the item.
+                CheckBox,
+
+                /// The cell shows the image returned by the `cell-image` callback of the table view.
+                Image,
+
+                /// The cell shows the children of the table view, which get the row index, the column index and the item of the cell.
+                Custom,
+            }
+
             /// Represents the orientation of an element or widget such as the [`Slider`](../widgets/slider.md).
             enum Orientation {
                 /// Element is oriented horizontally.
@@ -367,6 +443,45 @@ enum Orientation {
                 /// Element is oriented vertically.
                 Vertical,
             }
+
+            /// This enum describes when a [`PopupWindow`](elements.md#popupwindow) is closed automatically.
+            enum PopupClosePolicy {
+                /// Closes the `PopupWindow` when the user clicks inside or outside of it, or presses Escape.
+                CloseOnClick,
+                /// Closes the `PopupWindow` only when the user clicks outside of it, or presses Escape.
+                CloseOnClickOutside,
+                /// The `PopupWindow` is never closed automatically. Call its `close()` function to close it.
+                NoAutoClose,
+                /// The `PopupWindow` doesn't receive any input and lets it through to the rest of the window,
+                /// like a tooltip. It's closed when the user presses a key or a mouse button.
+                CloseOnInput,
+            }
+
+            /// This enum describes in which direction the iterations of an [animation](../syntax/animations.md) are played.
+            enum AnimationDirection {
+                /// Every iteration goes from the start to the end.
+                Normal,
+                /// Every iteration goes from the end to the start.
+                Reverse,
+                /// The first iteration goes from the start to the end, the next one backwards, and so on.
+                Alternate,
+                /// The first iteration goes from the end to the start, the next one forwards, and so on.
+                AlternateReverse,
+            }
+
+            /// This enum describes which value an [animation](../syntax/animations.md) gives to the property
+            /// during its `delay` and once it's finished.
+            enum AnimationFillMode {
+                /// The property keeps its previous value during the delay, and gets its new value once the
+                /// animation is finished.
+                None,
+                /// The property keeps the value of the last frame of the animation once it's finished.
+                Forwards,
+                /// The property has the value of the first frame of the animation during the delay.
+                Backwards,
+                /// Both `forwards` and `backwards` apply.
+                Both,
+            }
         ];
     };
 }
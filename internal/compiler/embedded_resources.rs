@@ -20,6 +20,12 @@ pub enum PixelFormat {
     RgbaPremultiplied,
     // 8bit alpha map with a given color
     AlphaMap([u8; 3]),
+    // 8bit grayscale
+    Gray8,
+    // 4bit grayscale, two pixels per byte and each line starting on a new byte
+    Gray4,
+    // 1bit black and white, eight pixels per byte and each line starting on a new byte
+    Gray1,
 }
 
 #[cfg(feature = "software-renderer")]
@@ -56,6 +56,8 @@ pub enum BuiltinFunction {
     ArrayLength,
     Rgb,
     DarkColorScheme,
+    FirstDayOfWeek,
+    FilterStrings,
     TextInputFocused,
     SetTextInputFocused,
     ImplicitLayoutInfo(Orientation),
@@ -64,6 +66,8 @@ pub enum BuiltinFunction {
     RegisterCustomFontByMemory,
     RegisterBitmapFont,
     Translate,
+    /// Start, stop or restart the timers of the component according to their properties
+    UpdateTimers,
 }
 
 #[derive(Debug, Clone)]
@@ -196,6 +200,13 @@ pub fn ty(&self) -> Type {
             BuiltinFunction::DarkColorScheme => {
                 Type::Function { return_type: Box::new(Type::Bool), args: vec![] }
             }
+            BuiltinFunction::FirstDayOfWeek => {
+                Type::Function { return_type: Box::new(Type::Int32), args: vec![] }
+            }
+            BuiltinFunction::FilterStrings => Type::Function {
+                return_type: Box::new(Type::Array(Box::new(Type::Int32))),
+                args: vec![Type::Array(Box::new(Type::String)), Type::String],
+            },
             BuiltinFunction::TextInputFocused => {
                 Type::Function { return_type: Box::new(Type::Bool), args: vec![] }
             }
@@ -225,6 +236,9 @@ pub fn ty(&self) -> Type {
                     Type::Array(Type::String.into()),
                 ],
             },
+            BuiltinFunction::UpdateTimers => {
+                Type::Function { return_type: Box::new(Type::Void), args: vec![] }
+            }
         }
     }
 
@@ -235,6 +249,8 @@ fn is_const(&self) -> bool {
             BuiltinFunction::GetWindowDefaultFontSize => false,
             BuiltinFunction::AnimationTick => false,
             BuiltinFunction::DarkColorScheme => false,
+            BuiltinFunction::FirstDayOfWeek => false,
+            BuiltinFunction::FilterStrings => true,
             // Even if it is not pure, we optimize it away anyway
             BuiltinFunction::Debug => true,
             BuiltinFunction::Mod
@@ -279,6 +295,7 @@ fn is_const(&self) -> bool {
             | BuiltinFunction::RegisterCustomFontByMemory
             | BuiltinFunction::RegisterBitmapFont => false,
             BuiltinFunction::Translate => false,
+            BuiltinFunction::UpdateTimers => false,
         }
     }
 
@@ -289,6 +306,8 @@ pub fn is_pure(&self) -> bool {
             BuiltinFunction::GetWindowDefaultFontSize => true,
             BuiltinFunction::AnimationTick => true,
             BuiltinFunction::DarkColorScheme => true,
+            BuiltinFunction::FirstDayOfWeek => true,
+            BuiltinFunction::FilterStrings => true,
             // Even if it has technically side effect, we still consider it as pure for our purpose
             BuiltinFunction::Debug => true,
             BuiltinFunction::Mod
@@ -326,6 +345,7 @@ pub fn is_pure(&self) -> bool {
             | BuiltinFunction::RegisterCustomFontByMemory
             | BuiltinFunction::RegisterBitmapFont => false,
             BuiltinFunction::Translate => true,
+            BuiltinFunction::UpdateTimers => false,
         }
     }
 }
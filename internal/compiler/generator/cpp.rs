@@ -1366,6 +1366,38 @@ fn generate_item_tree(
         }),
     ));
 
+    target_struct.members.push((
+        Access::Private,
+        Declaration::Function(Function {
+            name: "accessibility_action".into(),
+            signature:
+                "([[maybe_unused]] slint::private_api::ItemTreeRef component, uint32_t index, const slint::cbindgen_private::AccessibilityAction *action) -> void"
+                    .into(),
+            is_static: true,
+            statements: Some(vec![format!(
+                "reinterpret_cast<const {}*>(component.instance)->accessibility_action(index, *action);",
+                item_tree_class_name
+            )]),
+            ..Default::default()
+        }),
+    ));
+
+    target_struct.members.push((
+        Access::Private,
+        Declaration::Function(Function {
+            name: "supported_accessibility_actions".into(),
+            signature:
+                "([[maybe_unused]] slint::private_api::ItemTreeRef component, uint32_t index) -> slint::cbindgen_private::SupportedAccessibilityAction"
+                    .into(),
+            is_static: true,
+            statements: Some(vec![format!(
+                "return reinterpret_cast<const {}*>(component.instance)->supported_accessibility_actions(index);",
+                item_tree_class_name
+            )]),
+            ..Default::default()
+        }),
+    ));
+
     target_struct.members.push((
         Access::Private,
         Declaration::Function(Function {
@@ -1396,7 +1428,8 @@ fn generate_item_tree(
         init: Some(format!(
             "{{ visit_children, get_item_ref, get_subtree_range, get_subtree, \
                 get_item_tree, parent_node, embed_component, subtree_index, layout_info, \
-                item_geometry, accessible_role, accessible_string_property, window_adapter, \
+                item_geometry, accessible_role, accessible_string_property, accessibility_action, \
+                supported_accessibility_actions, window_adapter, \
                 slint::private_api::drop_in_place<{}>, slint::private_api::dealloc }}",
             item_tree_class_name
         )),
@@ -1787,6 +1820,66 @@ fn generate_sub_component(
 
     init.extend(properties_init_code);
 
+    for (i, (prop, expression)) in component.change_callbacks.iter().enumerate() {
+        let change_tracker = format!("change_tracker{}", i);
+        let prop = access_member(prop, &ctx);
+        let code = compile_expression(&expression.borrow(), &ctx);
+        user_init.push(format!(
+            "self->{change_tracker}.init(self,
+                    []([[maybe_unused]] auto self) {{ return {prop}.get(); }},
+                    []([[maybe_unused]] auto self, const auto &) {{ {code}; }});"
+        ));
+        target_struct.members.push((
+            field_access,
+            Declaration::Var(Var {
+                ty: "slint::private_api::ChangeTracker".into(),
+                name: change_tracker,
+                ..Default::default()
+            }),
+        ));
+    }
+
+    for i in 0..component.popup_windows.len() {
+        target_struct.members.push((
+            field_access,
+            Declaration::Var(Var {
+                ty: "mutable std::optional<uint32_t>".into(),
+                name: format!("popup_id_{}", i),
+                ..Default::default()
+            }),
+        ));
+    }
+
+    if !component.timers.is_empty() {
+        let mut update_timers = vec!["auto self = this;".into()];
+        for (i, tmr) in component.timers.iter().enumerate() {
+            let name = format!("timer{}", i);
+            let running = compile_expression(&tmr.running.borrow(), &ctx);
+            let interval = compile_expression(&tmr.interval.borrow(), &ctx);
+            let callback = compile_expression(&tmr.triggered.borrow(), &ctx);
+            update_timers.push(format!("if (auto interval = std::chrono::milliseconds(int64_t({interval})); {running} && interval > std::chrono::milliseconds::zero()) {{"));
+            update_timers.push(format!(
+                "   if (!self->{name}.running() || self->{name}.interval() != interval)"
+            ));
+            update_timers.push(format!("       self->{name}.start(slint::TimerMode::Repeated, interval, [self] {{ {callback}; }});"));
+            update_timers.push(format!("}} else {{ self->{name}.stop(); }}"));
+            target_struct.members.push((
+                field_access,
+                Declaration::Var(Var { ty: "slint::Timer".into(), name, ..Default::default() }),
+            ));
+        }
+        target_struct.members.push((
+            field_access,
+            Declaration::Function(Function {
+                name: "update_timers".to_owned(),
+                signature: "() -> void".into(),
+                statements: Some(update_timers),
+                ..Default::default()
+            }),
+        ));
+        user_init.push("self->update_timers();".into());
+    }
+
     user_init.extend(component.init_code.iter().map(|e| {
         let mut expr_str = compile_expression(&e.borrow(), &ctx);
         expr_str.push(';');
@@ -1864,7 +1957,11 @@ fn generate_sub_component(
             }
             else_ = "} else ";
         }
-        code.push(format!("{else_}return {{}};"));
+        if signature.ends_with("-> void") {
+            code.push(format!("{else_}return;"));
+        } else {
+            code.push(format!("{else_}return {{}};"));
+        }
         target_struct.members.push((
             field_access,
             Declaration::Function(Function {
@@ -1901,16 +1998,40 @@ fn generate_sub_component(
 
     let mut accessible_role_cases = vec!["switch (index) {".into()];
     let mut accessible_string_cases = vec!["switch ((index << 8) | uintptr_t(what)) {".into()];
+    let mut accessibility_action_cases =
+        vec!["switch ((index << 8) | uintptr_t(action.tag)) {".into()];
+    let mut supported_accessibility_actions = BTreeMap::<u32, Vec<&str>>::new();
     for ((index, what), expr) in &component.accessible_prop {
+        let has_args = matches!(&*expr.borrow(), llr::Expression::CallBackCall { arguments, .. } if !arguments.is_empty());
         let expr = compile_expression(&expr.borrow(), &ctx);
         if what == "Role" {
             accessible_role_cases.push(format!("    case {index}: return {expr};"));
+        } else if let Some(what) = what.strip_prefix("Action") {
+            let arg = if has_args {
+                "[[maybe_unused]] const auto &arg_0 = action.set_value._0; "
+            } else {
+                ""
+            };
+            accessibility_action_cases.push(format!("    case ({index} << 8) | uintptr_t(slint::cbindgen_private::AccessibilityAction::Tag::{what}): {{ {arg}{expr}; return; }}"));
+            supported_accessibility_actions.entry(*index).or_default().push(what);
         } else {
             accessible_string_cases.push(format!("    case ({index} << 8) | uintptr_t(slint::cbindgen_private::AccessibleStringProperty::{what}): return {expr};"));
         }
     }
     accessible_role_cases.push("}".into());
     accessible_string_cases.push("}".into());
+    accessibility_action_cases.push("}".into());
+    let mut supported_accessibility_actions_cases = vec!["switch (index) {".into()];
+    supported_accessibility_actions_cases.extend(supported_accessibility_actions.into_iter().map(
+        |(index, values)| {
+            let values = values
+                .iter()
+                .map(|v| format!("slint::cbindgen_private::SupportedAccessibilityAction_{v}"))
+                .join(" | ");
+            format!("    case {index}: return {values};")
+        },
+    ));
+    supported_accessibility_actions_cases.push("}".into());
 
     dispatch_item_function(
         "accessible_role",
@@ -1924,6 +2045,18 @@ fn generate_sub_component(
         ", what",
         accessible_string_cases,
     );
+    dispatch_item_function(
+        "accessibility_action",
+        "(uint32_t index, const slint::cbindgen_private::AccessibilityAction &action) const -> void",
+        ", action",
+        accessibility_action_cases,
+    );
+    dispatch_item_function(
+        "supported_accessibility_actions",
+        "(uint32_t index) const -> slint::cbindgen_private::SupportedAccessibilityAction",
+        "",
+        supported_accessibility_actions_cases,
+    );
 
     if !children_visitor_cases.is_empty() {
         target_struct.members.push((
@@ -2631,7 +2764,7 @@ fn compile_expression(expr: &llr::Expression, ctx: &EvaluationContext) -> String
                     ) =>
                 {
                     let path_elements = match from.as_ref() {
-                        Expression::Array { element_ty: _, values, as_model: _ } => values
+                        Expression::Array { element_ty: _, values, output: _ } => values
                             .iter()
                             .map(|path_elem_expr| {
                                 let (field_count, qualified_elem_type_name) = match path_elem_expr.ty(ctx) {
@@ -2798,23 +2931,27 @@ fn compile_expression(expr: &llr::Expression, ctx: &EvaluationContext) -> String
                 false_code
             )
         }
-        Expression::Array { element_ty, values, as_model } => {
+        Expression::Array { element_ty, values, output } => {
             let ty = element_ty.cpp_type().unwrap();
             let mut val = values.iter().map(|e| format!("{ty} ( {expr} )", expr = compile_expression(e, ctx), ty = ty));
-            if *as_model {
-                format!(
+            match output {
+                llr::ArrayOutput::Model => format!(
                     "std::make_shared<slint::private_api::ArrayModel<{count},{ty}>>({val})",
                     count = values.len(),
                     ty = ty,
                     val = val.join(", ")
-                )
-            } else {
-                format!(
+                ),
+                llr::ArrayOutput::Slice => format!(
                     "slint::cbindgen_private::Slice<{ty}>{{ std::array<{ty}, {count}>{{ {val} }}.data(), {count} }}",
                     count = values.len(),
                     ty = ty,
                     val = val.join(", ")
-                )
+                ),
+                llr::ArrayOutput::Vector => format!(
+                    "slint::SharedVector<{ty}>{{ {val} }}",
+                    ty = ty,
+                    val = val.join(", ")
+                ),
             }
         }
         Expression::Struct { ty, values } => {
@@ -3080,6 +3217,16 @@ fn compile_builtin_function_call(
         BuiltinFunction::DarkColorScheme => {
             format!("{}.dark_color_scheme()", access_window_field(ctx))
         }
+        BuiltinFunction::FirstDayOfWeek => {
+            "slint::cbindgen_private::slint_date_time_first_day_of_week()".into()
+        }
+        BuiltinFunction::FilterStrings => {
+            format!(
+                "slint::private_api::filter_strings({}, {})",
+                a.next().unwrap(),
+                a.next().unwrap()
+            )
+        }
         BuiltinFunction::SetTextInputFocused => {
             format!("{}.set_text_input_focused({})", access_window_field(ctx), a.next().unwrap())
         }
@@ -3087,7 +3234,7 @@ fn compile_builtin_function_call(
             format!("{}.text_input_focused()", access_window_field(ctx))
         }
         BuiltinFunction::ShowPopupWindow => {
-            if let [llr::Expression::NumberLiteral(popup_index), x, y, close_on_click, llr::Expression::PropertyReference(parent_ref)] =
+            if let [llr::Expression::NumberLiteral(popup_index), x, y, close_policy, llr::Expression::PropertyReference(parent_ref)] =
                 arguments
             {
                 let mut parent_ctx = ctx;
@@ -3107,17 +3254,31 @@ fn compile_builtin_function_call(
                 let parent_component = access_item_rc(parent_ref, ctx);
                 let x = compile_expression(x, ctx);
                 let y = compile_expression(y, ctx);
-                let close_on_click = compile_expression(close_on_click, ctx);
+                let close_policy = compile_expression(close_policy, ctx);
+                let popup_id = format!("{component_access}->popup_id_{}", *popup_index as usize);
                 format!(
-                    "{window}.show_popup<{popup_window_id}>({component_access}, {{ static_cast<float>({x}), static_cast<float>({y}) }}, {close_on_click}, {{ {parent_component} }})"
+                    "[&] {{ if (auto id = std::exchange({popup_id}, std::nullopt)) {{ {window}.close_popup(*id); }} {popup_id} = {window}.show_popup<{popup_window_id}>({component_access}, {{ static_cast<float>({x}), static_cast<float>({y}) }}, {close_policy}, {{ {parent_component} }}); }}()"
                 )
             } else {
                 panic!("internal error: invalid args to ShowPopupWindow {:?}", arguments)
             }
         }
         BuiltinFunction::ClosePopupWindow => {
-            let window = access_window_field(ctx);
-            format!("{window}.close_popup()")
+            if let [llr::Expression::NumberLiteral(popup_index), llr::Expression::PropertyReference(parent_ref)] =
+                arguments
+            {
+                let mut component_access = "self".into();
+                if let llr::PropertyReference::InParent { level, .. } = parent_ref {
+                    for _ in 0..level.get() {
+                        component_access = format!("{}->parent", component_access);
+                    }
+                };
+                let window = access_window_field(ctx);
+                let popup_id = format!("{component_access}->popup_id_{}", *popup_index as usize);
+                format!("[&] {{ if (auto id = std::exchange({popup_id}, std::nullopt)) {{ {window}.close_popup(*id); }} }}()")
+            } else {
+                panic!("internal error: invalid args to ClosePopupWindow {:?}", arguments)
+            }
         }
         BuiltinFunction::SetSelectionOffsets => {
             if let [llr::Expression::PropertyReference(pr), from, to] = arguments {
@@ -3188,6 +3349,7 @@ fn compile_builtin_function_call(
                 panic!("internal error: invalid args to RegisterBitmapFont {:?}", arguments)
             }
         }
+        BuiltinFunction::UpdateTimers => "self->update_timers()".into(),
         BuiltinFunction::ImplicitLayoutInfo(orient) => {
             if let [llr::Expression::PropertyReference(pr)] = arguments {
                 let native = native_item(pr, ctx);
@@ -62,6 +62,9 @@ fn to_tokens(&self, tokens: &mut TokenStream) {
                 quote!(sp::PixelFormat::RgbaPremultiplied)
             }
             AlphaMap(_) => quote!(sp::PixelFormat::AlphaMap),
+            Gray8 => quote!(sp::PixelFormat::Gray8),
+            Gray4 => quote!(sp::PixelFormat::Gray4),
+            Gray1 => quote!(sp::PixelFormat::Gray1),
         };
         tokens.extend(tks);
     }
@@ -788,10 +791,21 @@ fn generate_sub_component(
 
     let mut accessible_role_branch = vec![];
     let mut accessible_string_property_branch = vec![];
+    let mut accessibility_action_branch = vec![];
+    let mut supported_accessibility_actions = BTreeMap::<u32, Vec<Ident>>::new();
     for ((index, what), expr) in &component.accessible_prop {
+        let has_args = matches!(&*expr.borrow(), Expression::CallBackCall { arguments, .. } if !arguments.is_empty());
         let expr = compile_expression(&expr.borrow(), &ctx);
         if what == "Role" {
             accessible_role_branch.push(quote!(#index => #expr,));
+        } else if let Some(what) = what.strip_prefix("Action") {
+            let what = ident(what);
+            accessibility_action_branch.push(if has_args {
+                quote!((#index, sp::AccessibilityAction::#what(args)) => { let args = (args.clone(),); #expr; })
+            } else {
+                quote!((#index, sp::AccessibilityAction::#what) => { #expr; })
+            });
+            supported_accessibility_actions.entry(*index).or_default().push(what);
         } else {
             let what = ident(what);
             accessible_string_property_branch
@@ -799,6 +813,11 @@ fn generate_sub_component(
         }
     }
 
+    let mut supported_accessibility_actions_branch = supported_accessibility_actions
+        .into_iter()
+        .map(|(index, values)| quote!(#index => #(sp::SupportedAccessibilityAction::#values)|*,))
+        .collect::<Vec<_>>();
+
     let mut item_geometry_branch = component
         .geometries
         .iter()
@@ -875,6 +894,12 @@ fn generate_sub_component(
         accessible_string_property_branch.push(quote!(
             (#local_tree_index, _) => #sub_compo_field.apply_pin(_self).accessible_string_property(0, what),
         ));
+        accessibility_action_branch.push(quote!(
+            (#local_tree_index, _) => #sub_compo_field.apply_pin(_self).accessibility_action(0, action),
+        ));
+        supported_accessibility_actions_branch.push(quote!(
+            #local_tree_index => #sub_compo_field.apply_pin(_self).supported_accessibility_actions(0),
+        ));
         if sub_items_count > 1 {
             let range_begin = local_index_of_first_child;
             let range_end = range_begin + sub_items_count - 2 + sub.ty.repeater_count();
@@ -884,6 +909,12 @@ fn generate_sub_component(
             accessible_string_property_branch.push(quote!(
                 (#range_begin..=#range_end, _) => #sub_compo_field.apply_pin(_self).accessible_string_property(index - #range_begin + 1, what),
             ));
+            accessibility_action_branch.push(quote!(
+                (#range_begin..=#range_end, _) => #sub_compo_field.apply_pin(_self).accessibility_action(index - #range_begin + 1, action),
+            ));
+            supported_accessibility_actions_branch.push(quote!(
+                #range_begin..=#range_end => #sub_compo_field.apply_pin(_self).supported_accessibility_actions(index - #range_begin + 1),
+            ));
             item_geometry_branch.push(quote!(
                 #range_begin..=#range_end => return #sub_compo_field.apply_pin(_self).item_geometry(index - #range_begin + 1),
             ));
@@ -928,6 +959,56 @@ fn generate_sub_component(
         quote!(sp::VWeakMapped::<sp::ItemTreeVTable, #parent_component_id>)
     });
 
+    let mut change_tracker_names = vec![];
+    for (i, (prop, expression)) in component.change_callbacks.iter().enumerate() {
+        let change_tracker = format_ident!("change_tracker{}", i);
+        let prop = access_member(prop, &ctx);
+        let code = compile_expression(&expression.borrow(), &ctx);
+        user_init_code.push(quote! {
+            #[allow(unreachable_code, unused)]
+            slint::private_unstable_api::set_change_handler(&_self.#change_tracker, &self_rc, |self_rc| {
+                let _self = self_rc.as_pin_ref();
+                #prop.get()
+            }, |self_rc| {
+                let _self = self_rc.as_pin_ref();
+                #code;
+            });
+        });
+        change_tracker_names.push(change_tracker);
+    }
+
+    let timer_names =
+        (0..component.timers.len()).map(|i| format_ident!("timer{}", i)).collect::<Vec<_>>();
+    let update_timers = component.timers.iter().zip(&timer_names).map(|(t, name)| {
+        let running = compile_expression(&t.running.borrow(), &ctx);
+        let interval = compile_expression(&t.interval.borrow(), &ctx);
+        let triggered = compile_expression(&t.triggered.borrow(), &ctx);
+        quote!(
+            let interval: i64 = (#interval) as _;
+            if #running && interval > 0 {
+                let interval = ::core::time::Duration::from_millis(interval as u64);
+                if !_self.#name.running() || _self.#name.interval() != interval {
+                    let self_weak = _self.self_weak.get().unwrap().clone();
+                    _self.#name.start(sp::TimerMode::Repeated, interval, move || {
+                        if let Some(self_rc) = self_weak.upgrade() {
+                            let _self = self_rc.as_pin_ref();
+                            #triggered;
+                        }
+                    });
+                }
+            } else {
+                _self.#name.stop();
+            }
+        )
+    });
+    if !timer_names.is_empty() {
+        user_init_code.push(quote!(_self.update_timers();));
+    }
+
+    let popup_id_names = (0..component.popup_windows.len())
+        .map(|i| format_ident!("popup_id_{}", i))
+        .collect::<Vec<_>>();
+
     user_init_code.extend(component.init_code.iter().map(|e| {
         let code = compile_expression(&e.borrow(), &ctx);
         quote!(#code;)
@@ -964,6 +1045,9 @@ struct #inner_component_id {
             #(#declared_property_vars : sp::Property<#declared_property_types>,)*
             #(#declared_callbacks : sp::Callback<(#(#declared_callbacks_types,)*), #declared_callbacks_ret>,)*
             #(#repeated_element_names : sp::Repeater<#repeated_element_components>,)*
+            #(#change_tracker_names : sp::ChangeTracker,)*
+            #(#timer_names : sp::Timer,)*
+            #(#popup_id_names : ::core::cell::Cell<Option<::core::num::NonZeroU32>>,)*
             self_weak : sp::OnceCell<sp::VWeakMapped<sp::ItemTreeVTable, #inner_component_id>>,
             #(parent : #parent_component_type,)*
             root : sp::OnceCell<sp::VWeak<sp::ItemTreeVTable, #root_component_id>>,
@@ -1072,6 +1156,30 @@ fn accessible_string_property(
                 }
             }
 
+            fn accessibility_action(self: ::core::pin::Pin<&Self>, index: u32, action: &sp::AccessibilityAction) {
+                #![allow(unused)]
+                let _self = self;
+                match (index, action) {
+                    #(#accessibility_action_branch)*
+                    _ => (),
+                }
+            }
+
+            fn supported_accessibility_actions(self: ::core::pin::Pin<&Self>, index: u32) -> sp::SupportedAccessibilityAction {
+                #![allow(unused)]
+                let _self = self;
+                match index {
+                    #(#supported_accessibility_actions_branch)*
+                    _ => ::core::default::Default::default(),
+                }
+            }
+
+            fn update_timers(self: ::core::pin::Pin<&Self>) {
+                #![allow(unused)]
+                let _self = self;
+                #(#update_timers)*
+            }
+
             #(#declared_functions)*
         }
 
@@ -1516,6 +1624,14 @@ fn accessible_string_property(
                 *result = self.accessible_string_property(index, what);
             }
 
+            fn accessibility_action(self: ::core::pin::Pin<&Self>, index: u32, action: &sp::AccessibilityAction) {
+                self.accessibility_action(index, action);
+            }
+
+            fn supported_accessibility_actions(self: ::core::pin::Pin<&Self>, index: u32) -> sp::SupportedAccessibilityAction {
+                self.supported_accessibility_actions(index)
+            }
+
             fn window_adapter(
                 self: ::core::pin::Pin<&Self>,
                 do_create: bool,
@@ -1921,7 +2037,7 @@ fn compile_expression(expr: &Expression, ctx: &EvaluationContext) -> TokenStream
                     ) =>
                 {
                     let path_elements = match from.as_ref() {
-                        Expression::Array { element_ty: _, values, as_model: _ } => values
+                        Expression::Array { element_ty: _, values, output: _ } => values
                             .iter()
                             .map(|path_elem_expr|
                                 // Close{} is a struct with no fields in markup, and PathElement::Close has no fields, so map to an empty token stream
@@ -2157,17 +2273,22 @@ fn compile_expression(expr: &Expression, ctx: &EvaluationContext) -> TokenStream
                 }
             )
         }
-        Expression::Array { values, element_ty, as_model } => {
+        Expression::Array { values, element_ty, output } => {
             let val = values.iter().map(|e| compile_expression(e, ctx));
-            if *as_model {
-                let rust_element_ty = rust_primitive_type(element_ty).unwrap();
-                quote!(sp::ModelRc::new(
-                    sp::VecModel::<#rust_element_ty>::from(
-                        sp::vec![#(#val as _),*]
-                    )
-                ))
-            } else {
-                quote!(sp::Slice::from_slice(&[#(#val),*]))
+            match output {
+                llr::ArrayOutput::Model => {
+                    let rust_element_ty = rust_primitive_type(element_ty).unwrap();
+                    quote!(sp::ModelRc::new(
+                        sp::VecModel::<#rust_element_ty>::from(
+                            sp::vec![#(#val as _),*]
+                        )
+                    ))
+                }
+                llr::ArrayOutput::Slice => quote!(sp::Slice::from_slice(&[#(#val),*])),
+                llr::ArrayOutput::Vector => {
+                    let rust_element_ty = rust_primitive_type(element_ty).unwrap();
+                    quote!(sp::SharedVector::<#rust_element_ty>::from_slice(&[#(#val as _),*]))
+                }
             }
         }
         Expression::Struct { ty, values } => {
@@ -2343,7 +2464,7 @@ fn compile_builtin_function_call(
             }
         }
         BuiltinFunction::ShowPopupWindow => {
-            if let [Expression::NumberLiteral(popup_index), x, y, close_on_click, Expression::PropertyReference(parent_ref)] =
+            if let [Expression::NumberLiteral(popup_index), x, y, close_policy, Expression::PropertyReference(parent_ref)] =
                 arguments
             {
                 let mut parent_ctx = ctx;
@@ -2359,32 +2480,56 @@ fn compile_builtin_function_call(
                 let popup_window_id = inner_component_id(
                     &current_sub_component.popup_windows[*popup_index as usize].root,
                 );
+                let popup_id_name = format_ident!("popup_id_{}", *popup_index as usize);
                 let parent_component = access_item_rc(parent_ref, ctx);
                 let x = compile_expression(x, ctx);
                 let y = compile_expression(y, ctx);
-                let close_on_click = compile_expression(close_on_click, ctx);
+                let close_policy = compile_expression(close_policy, ctx);
                 let window_adapter_tokens = access_window_adapter_field(ctx);
-                quote!(
-                    sp::WindowInner::from_pub(#window_adapter_tokens.window()).show_popup(
+                quote!({
+                    let window_adapter = #window_adapter_tokens;
+                    let window = sp::WindowInner::from_pub(window_adapter.window());
+                    // Showing the popup again replaces the previous instance
+                    if let Some(popup_id) = #component_access_tokens.#popup_id_name.take() {
+                        window.close_popup(popup_id);
+                    }
+                    let popup_id = window.show_popup(
                         &sp::VRc::into_dyn({
                             let instance = #popup_window_id::new(#component_access_tokens.self_weak.get().unwrap().clone()).unwrap();
                             #popup_window_id::user_init(sp::VRc::map(instance.clone(), |x| x));
                             instance.into()
                         }),
                         sp::Point::new(#x as sp::Coord, #y as sp::Coord),
-                        #close_on_click,
+                        #close_policy,
                         #parent_component
-                    )
-                )
+                    );
+                    #component_access_tokens.#popup_id_name.set(Some(popup_id));
+                })
             } else {
                 panic!("internal error: invalid args to ShowPopupWindow {:?}", arguments)
             }
         }
         BuiltinFunction::ClosePopupWindow => {
-            let window_adapter_tokens = access_window_adapter_field(ctx);
-            quote!(
-                sp::WindowInner::from_pub(#window_adapter_tokens.window()).close_popup()
-            )
+            if let [Expression::NumberLiteral(popup_index), Expression::PropertyReference(parent_ref)] =
+                arguments
+            {
+                let mut component_access_tokens = quote!(_self);
+                if let llr::PropertyReference::InParent { level, .. } = parent_ref {
+                    for _ in 0..level.get() {
+                        component_access_tokens =
+                            quote!(#component_access_tokens.parent.upgrade().unwrap().as_pin_ref());
+                    }
+                }
+                let popup_id_name = format_ident!("popup_id_{}", *popup_index as usize);
+                let window_adapter_tokens = access_window_adapter_field(ctx);
+                quote!(
+                    if let Some(popup_id) = #component_access_tokens.#popup_id_name.take() {
+                        sp::WindowInner::from_pub(#window_adapter_tokens.window()).close_popup(popup_id);
+                    }
+                )
+            } else {
+                panic!("internal error: invalid args to ClosePopupWindow {:?}", arguments)
+            }
         }
         BuiltinFunction::SetSelectionOffsets => {
             if let [llr::Expression::PropertyReference(pr), from, to] = arguments {
@@ -2541,6 +2686,13 @@ fn compile_builtin_function_call(
             let window_adapter_tokens = access_window_adapter_field(ctx);
             quote!(sp::WindowInner::from_pub(#window_adapter_tokens.window()).dark_color_scheme())
         }
+        BuiltinFunction::FirstDayOfWeek => {
+            quote!(sp::first_day_of_week())
+        }
+        BuiltinFunction::FilterStrings => {
+            let (model, filter) = (a.next().unwrap(), a.next().unwrap());
+            quote!(sp::filter_strings(&#model, &#filter))
+        }
         BuiltinFunction::TextInputFocused => {
             let window_adapter_tokens = access_window_adapter_field(ctx);
             quote!(sp::WindowInner::from_pub(#window_adapter_tokens.window()).text_input_focused())
@@ -2552,6 +2704,9 @@ fn compile_builtin_function_call(
         BuiltinFunction::Translate => {
             quote!(slint::private_unstable_api::translate(#((#a) as _),*))
         }
+        BuiltinFunction::UpdateTimers => {
+            quote!(_self.update_timers())
+        }
         BuiltinFunction::ItemAbsolutePosition => {
             if let [Expression::PropertyReference(pr)] = arguments {
                 let item_rc = access_item_rc(pr, ctx);
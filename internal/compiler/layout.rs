@@ -21,6 +21,7 @@ pub enum Orientation {
 pub enum Layout {
     GridLayout(GridLayout),
     BoxLayout(BoxLayout),
+    FlexboxLayout(FlexboxLayout),
 }
 
 impl Layout {
@@ -28,18 +29,21 @@ pub fn rect(&self) -> &LayoutRect {
         match self {
             Layout::GridLayout(g) => &g.geometry.rect,
             Layout::BoxLayout(g) => &g.geometry.rect,
+            Layout::FlexboxLayout(g) => &g.geometry.rect,
         }
     }
     pub fn rect_mut(&mut self) -> &mut LayoutRect {
         match self {
             Layout::GridLayout(g) => &mut g.geometry.rect,
             Layout::BoxLayout(g) => &mut g.geometry.rect,
+            Layout::FlexboxLayout(g) => &mut g.geometry.rect,
         }
     }
     pub fn geometry(&self) -> &LayoutGeometry {
         match self {
             Layout::GridLayout(l) => &l.geometry,
             Layout::BoxLayout(l) => &l.geometry,
+            Layout::FlexboxLayout(l) => &l.geometry,
         }
     }
 }
@@ -50,6 +54,7 @@ pub fn visit_named_references(&mut self, visitor: &mut impl FnMut(&mut NamedRefe
         match self {
             Layout::GridLayout(grid) => grid.visit_named_references(visitor),
             Layout::BoxLayout(l) => l.visit_named_references(visitor),
+            Layout::FlexboxLayout(l) => l.visit_named_references(visitor),
         }
     }
 }
@@ -481,6 +486,45 @@ fn visit_named_references(&mut self, visitor: &mut impl FnMut(&mut NamedReferenc
     }
 }
 
+/// Internal representation of a FlexboxLayout
+#[derive(Debug, Clone)]
+pub struct FlexboxLayout {
+    /// The orientation of the main axis, along which the items are placed before they wrap
+    pub direction: Orientation,
+    pub elems: Vec<LayoutItem>,
+    /// The `alignment` of the geometry is the `justify-content` property
+    pub geometry: LayoutGeometry,
+    pub wrap: Option<NamedReference>,
+    pub align_items: Option<NamedReference>,
+}
+
+impl FlexboxLayout {
+    pub fn new(layout_element: &ElementRc, direction: Orientation) -> Self {
+        let mut geometry = LayoutGeometry::new(layout_element);
+        geometry.alignment = binding_reference(layout_element, "justify-content");
+        Self {
+            direction,
+            elems: Default::default(),
+            geometry,
+            wrap: binding_reference(layout_element, "wrap"),
+            align_items: binding_reference(layout_element, "align-items"),
+        }
+    }
+
+    fn visit_named_references(&mut self, visitor: &mut impl FnMut(&mut NamedReference)) {
+        for cell in &mut self.elems {
+            cell.constraints.visit_named_references(visitor);
+        }
+        self.geometry.visit_named_references(visitor);
+        if let Some(e) = self.wrap.as_mut() {
+            visitor(&mut *e);
+        }
+        if let Some(e) = self.align_items.as_mut() {
+            visitor(&mut *e);
+        }
+    }
+}
+
 /// The [`Type`] for a runtime LayoutInfo structure
 pub fn layout_info_type() -> Type {
     Type::Struct {
@@ -524,6 +568,8 @@ pub fn implicit_layout_info_call(elem: &ElementRc, orientation: Orientation) ->
                     "Rectangle"
                         | "Empty"
                         | "TouchArea"
+                        | "DragArea"
+                        | "DropArea"
                         | "FocusScope"
                         | "Opacity"
                         | "Layer"
@@ -86,6 +86,10 @@ pub struct CompilerConfiguration {
     /// Compile time scale factor to apply to embedded resources such as images and glyphs.
     pub scale_factor: f64,
 
+    /// The grayscale format (`Gray8`, `Gray4` or `Gray1`) to which opaque images are converted
+    /// when embedding textures, or None to keep them in color.
+    pub grayscale_textures: Option<crate::embedded_resources::PixelFormat>,
+
     /// expose the accessible role and properties
     pub accessibility: bool,
 
@@ -140,6 +144,16 @@ pub fn new(output_format: crate::generator::OutputFormat) -> Self {
             .filter(|f| *f > 0.)
             .unwrap_or(1.);
 
+        let grayscale_textures = std::env::var("SLINT_EMBED_TEXTURES_GRAYSCALE").ok().map(|var| {
+            use crate::embedded_resources::PixelFormat;
+            match var.as_str() {
+                "8" => PixelFormat::Gray8,
+                "4" => PixelFormat::Gray4,
+                "1" => PixelFormat::Gray1,
+                _ => panic!("SLINT_EMBED_TEXTURES_GRAYSCALE has incorrect value. Must be either unset, '8', '4' or '1'"),
+            }
+        });
+
         let enable_experimental_features =
             std::env::var_os("SLINT_ENABLE_EXPERIMENTAL_FEATURES").is_some();
 
@@ -166,6 +180,7 @@ pub fn new(output_format: crate::generator::OutputFormat) -> Self {
             resource_url_mapper: None,
             inline_all_elements,
             scale_factor,
+            grayscale_textures,
             accessibility: true,
             enable_component_containers,
             translation_domain: None,
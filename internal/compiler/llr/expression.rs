@@ -130,8 +130,8 @@ pub enum Expression {
     Array {
         element_ty: Type,
         values: Vec<Expression>,
-        /// When true, this should be converted to a model. When false, this should stay as a slice
-        as_model: bool,
+        /// How the array should be produced by the generators
+        output: ArrayOutput,
     },
     Struct {
         ty: Type,
@@ -189,6 +189,17 @@ pub enum Expression {
     },
 }
 
+/// How an [`Expression::Array`] is produced by the generators
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArrayOutput {
+    /// The array is converted to a model
+    Model,
+    /// The array stays as a slice, only valid within the enclosing expression
+    Slice,
+    /// The array is collected in a `SharedVector`
+    Vector,
+}
+
 impl Expression {
     pub fn default_value_for_type(ty: &Type) -> Option<Self> {
         Some(match ty {
@@ -224,7 +235,7 @@ pub fn default_value_for_type(ty: &Type) -> Option<Self> {
             Type::Array(element_ty) => Expression::Array {
                 element_ty: (**element_ty).clone(),
                 values: vec![],
-                as_model: true,
+                output: ArrayOutput::Model,
             },
             Type::Struct { fields, .. } => Expression::Struct {
                 ty: ty.clone(),
@@ -223,6 +223,13 @@ fn visit_in_array_recursive(
     }
 }
 
+#[derive(Debug)]
+pub struct Timer {
+    pub interval: MutExpression,
+    pub running: MutExpression,
+    pub triggered: MutExpression,
+}
+
 #[derive(Debug)]
 pub struct SubComponent {
     pub name: String,
@@ -232,6 +239,7 @@ pub struct SubComponent {
     pub repeated: Vec<RepeatedElement>,
     pub component_containers: Vec<ComponentContainerElement>,
     pub popup_windows: Vec<ItemTree>,
+    pub timers: Vec<Timer>,
     pub sub_components: Vec<SubComponentInstance>,
     /// The initial value or binding for properties.
     /// This is ordered in the order they must be set.
@@ -242,6 +250,8 @@ pub struct SubComponent {
     pub const_properties: Vec<PropertyReference>,
     /// Code that is run in the sub component constructor, after property initializations
     pub init_code: Vec<MutExpression>,
+    /// The `changed` callbacks: the code to run when the property has changed
+    pub change_callbacks: Vec<(PropertyReference, MutExpression)>,
 
     /// For each node, an expression that returns a `{x: length, y: length, width: length, height: length}`
     pub geometries: Vec<Option<MutExpression>>,
@@ -361,6 +371,14 @@ pub fn for_each_expression<'a>(
             for e in &sc.init_code {
                 visitor(e, ctx);
             }
+            for (_, e) in &sc.change_callbacks {
+                visitor(e, ctx);
+            }
+            for t in &sc.timers {
+                visitor(&t.interval, ctx);
+                visitor(&t.running, ctx);
+                visitor(&t.triggered, ctx);
+            }
             for (_, e) in &sc.property_init {
                 visitor(&e.expression, ctx);
             }
@@ -13,7 +13,7 @@
 use crate::expression_tree::{BuiltinFunction, Expression as tree_Expression};
 use crate::langtype::{EnumerationValue, Type};
 use crate::layout::Orientation;
-use crate::llr::Expression as llr_Expression;
+use crate::llr::{ArrayOutput, Expression as llr_Expression};
 use crate::namedreference::NamedReference;
 use crate::object_tree::{Element, ElementRc, PropertyAnimation};
 
@@ -117,18 +117,14 @@ pub fn lower_expression(
                 lower_show_popup(arguments, ctx)
             }
             tree_Expression::BuiltinFunctionReference(BuiltinFunction::ClosePopupWindow, _) => {
-                // FIXME: right now, `popup.close()` will close any visible popup, as the popup argument is ignored
-                llr_Expression::BuiltinFunctionCall {
-                    function: BuiltinFunction::ClosePopupWindow,
-                    arguments: vec![],
-                }
+                lower_close_popup(arguments, ctx)
             }
             tree_Expression::BuiltinFunctionReference(f, _) => {
                 let mut arguments =
                     arguments.iter().map(|e| lower_expression(e, ctx)).collect::<Vec<_>>();
                 if *f == BuiltinFunction::Translate {
-                    if let llr_Expression::Array { as_model, .. } = &mut arguments[3] {
-                        *as_model = false;
+                    if let llr_Expression::Array { output, .. } = &mut arguments[3] {
+                        *output = ArrayOutput::Slice;
                     }
                 }
                 llr_Expression::BuiltinFunctionCall { function: f.clone(), arguments }
@@ -170,7 +166,7 @@ pub fn lower_expression(
         tree_Expression::Array { element_ty, values } => llr_Expression::Array {
             element_ty: element_ty.clone(),
             values: values.iter().map(|e| lower_expression(e, ctx)).collect::<_>(),
-            as_model: true,
+            output: ArrayOutput::Model,
         },
         tree_Expression::Struct { ty, values } => llr_Expression::Struct {
             ty: ty.clone(),
@@ -351,49 +347,62 @@ fn repeater_special_property(
     llr_Expression::PropertyReference(r)
 }
 
+/// Returns the index of the popup in the popup_windows of the component that contains it,
+/// and the PopupWindow itself
+fn find_popup(args: &[tree_Expression]) -> (usize, crate::object_tree::PopupWindow) {
+    let [tree_Expression::ElementReference(e)] = args else {
+        panic!("invalid arguments to a PopupWindow function");
+    };
+    let popup_window = e.upgrade().unwrap();
+    let pop_comp = popup_window.borrow().enclosing_component.upgrade().unwrap();
+    let parent_component =
+        pop_comp.parent_element.upgrade().unwrap().borrow().enclosing_component.upgrade().unwrap();
+    let popup_list = parent_component.popup_windows.borrow();
+    popup_list
+        .iter()
+        .enumerate()
+        .find(|(_, p)| Rc::ptr_eq(&p.component, &pop_comp))
+        .map(|(index, popup)| (index, popup.clone()))
+        .unwrap()
+}
+
 fn lower_show_popup(args: &[tree_Expression], ctx: &ExpressionContext) -> llr_Expression {
-    if let [tree_Expression::ElementReference(e)] = args {
-        let popup_window = e.upgrade().unwrap();
-        let pop_comp = popup_window.borrow().enclosing_component.upgrade().unwrap();
-        let parent_component = pop_comp
-            .parent_element
-            .upgrade()
-            .unwrap()
-            .borrow()
-            .enclosing_component
-            .upgrade()
-            .unwrap();
-        let popup_list = parent_component.popup_windows.borrow();
-        let (popup_index, popup) = popup_list
-            .iter()
-            .enumerate()
-            .find(|(_, p)| Rc::ptr_eq(&p.component, &pop_comp))
-            .unwrap();
-        let x = llr_Expression::PropertyReference(ctx.map_property_reference(&popup.x));
-        let y = llr_Expression::PropertyReference(ctx.map_property_reference(&popup.y));
-        let item_ref = lower_expression(
-            &tree_Expression::ElementReference(Rc::downgrade(&popup.parent_element)),
-            ctx,
-        );
-        llr_Expression::BuiltinFunctionCall {
-            function: BuiltinFunction::ShowPopupWindow,
-            arguments: vec![
-                llr_Expression::NumberLiteral(popup_index as _),
-                x,
-                y,
-                llr_Expression::BoolLiteral(popup.close_on_click),
-                item_ref,
-            ],
-        }
-    } else {
-        panic!("invalid arguments to ShowPopupWindow");
+    let (popup_index, popup) = find_popup(args);
+    let x = llr_Expression::PropertyReference(ctx.map_property_reference(&popup.x));
+    let y = llr_Expression::PropertyReference(ctx.map_property_reference(&popup.y));
+    let item_ref = lower_expression(
+        &tree_Expression::ElementReference(Rc::downgrade(&popup.parent_element)),
+        ctx,
+    );
+    llr_Expression::BuiltinFunctionCall {
+        function: BuiltinFunction::ShowPopupWindow,
+        arguments: vec![
+            llr_Expression::NumberLiteral(popup_index as _),
+            x,
+            y,
+            llr_Expression::EnumerationValue(popup.close_policy),
+            item_ref,
+        ],
+    }
+}
+
+fn lower_close_popup(args: &[tree_Expression], ctx: &ExpressionContext) -> llr_Expression {
+    let (popup_index, popup) = find_popup(args);
+    // The reference to the parent element is used to find the component that holds the popup
+    let item_ref = lower_expression(
+        &tree_Expression::ElementReference(Rc::downgrade(&popup.parent_element)),
+        ctx,
+    );
+    llr_Expression::BuiltinFunctionCall {
+        function: BuiltinFunction::ClosePopupWindow,
+        arguments: vec![llr_Expression::NumberLiteral(popup_index as _), item_ref],
     }
 }
 
 pub fn lower_animation(a: &PropertyAnimation, ctx: &ExpressionContext<'_>) -> Animation {
     fn lower_animation_element(a: &ElementRc, ctx: &ExpressionContext<'_>) -> llr_Expression {
-        llr_Expression::Struct {
-            values: animation_fields()
+        animation_struct(
+            animation_fields()
                 .map(|(k, ty)| {
                     let e = a.borrow().bindings.get(&k).map_or_else(
                         || llr_Expression::default_value_for_type(&ty).unwrap(),
@@ -401,17 +410,36 @@ fn lower_animation_element(a: &ElementRc, ctx: &ExpressionContext<'_>) -> llr_Ex
                     );
                     (k, e)
                 })
-                .collect::<_>(),
-            ty: animation_ty(),
+                .collect(),
+        )
+    }
+
+    fn animation_struct(mut values: HashMap<String, llr_Expression>) -> llr_Expression {
+        // The keyframes are stored in a SharedVector. The parser made sure that they are an array literal.
+        if let Some(llr_Expression::Array { output, .. }) = values.get_mut("keyframes") {
+            *output = ArrayOutput::Vector;
         }
+        llr_Expression::Struct { values, ty: animation_ty() }
     }
 
     fn animation_fields() -> impl Iterator<Item = (String, Type)> {
+        let (direction, fill_mode) = crate::typeregister::BUILTIN_ENUMS.with(|e| {
+            (
+                Type::Enumeration(e.AnimationDirection.clone()),
+                Type::Enumeration(e.AnimationFillMode.clone()),
+            )
+        });
         IntoIterator::into_iter([
             ("duration".to_string(), Type::Int32),
             ("iteration-count".to_string(), Type::Float32),
             ("easing".to_string(), Type::Easing),
             ("delay".to_string(), Type::Int32),
+            ("direction".to_string(), direction),
+            ("fill-mode".to_string(), fill_mode),
+            (
+                "keyframes".to_string(),
+                Type::Array(Box::new(crate::typeregister::animation_keyframe_type())),
+            ),
         ])
     }
 
@@ -432,7 +460,11 @@ fn animation_ty() -> Type {
                 value: Box::new(lower_expression(state_ref, ctx)),
             };
             let animation_ty = animation_ty();
-            let mut get_anim = llr_Expression::default_value_for_type(&animation_ty).unwrap();
+            let mut get_anim = animation_struct(
+                animation_fields()
+                    .map(|(k, ty)| (k, llr_Expression::default_value_for_type(&ty).unwrap()))
+                    .collect(),
+            );
             for tr in animations.iter().rev() {
                 let condition = lower_expression(
                     &tr.condition(tree_Expression::ReadLocalVariable {
@@ -523,6 +555,15 @@ fn compute_layout_info(
                 None => sub_expression,
             }
         }
+        crate::layout::Layout::FlexboxLayout(layout) => {
+            flexbox_layout_function(layout, o, false, ctx, |data, _| {
+                llr_Expression::ExtraBuiltinFunctionCall {
+                    function: "flexbox_layout_info".into(),
+                    arguments: vec![data, orientation_value(o)],
+                    return_ty: crate::layout::layout_info_type(),
+                }
+            })
+        }
     }
 }
 
@@ -555,7 +596,7 @@ fn solve_layout(
                         roles: llr_Expression::Array {
                             element_ty: Type::Enumeration(e),
                             values: roles,
-                            as_model: false,
+                            output: ArrayOutput::Slice,
                         }
                         .into(),
                         unsorted_cells: Box::new(cells),
@@ -641,16 +682,178 @@ fn solve_layout(
                         llr_Expression::Array {
                             element_ty: Type::Int32,
                             values: vec![],
-                            as_model: false,
+                            output: ArrayOutput::Slice,
                         },
                     ],
                     return_ty: Type::LayoutCache,
                 },
             }
         }
+        crate::layout::Layout::FlexboxLayout(layout) => {
+            flexbox_layout_function(layout, o, true, ctx, |data, repeated_indices| {
+                llr_Expression::ExtraBuiltinFunctionCall {
+                    function: "solve_flexbox_layout".into(),
+                    arguments: vec![data, orientation_value(o), repeated_indices],
+                    return_ty: Type::LayoutCache,
+                }
+            })
+        }
     }
 }
 
+/// Create the FlexboxLayoutData to solve the layout (if `solve` is true) or compute its layout info
+/// in the given orientation, and call `f` with it and the repeated indices of the cells.
+///
+/// Across the main axis, the lines depend on the items and the size along the main axis, so these
+/// are also needed. What isn't needed is left empty to avoid unnecessary dependencies.
+fn flexbox_layout_function(
+    layout: &crate::layout::FlexboxLayout,
+    o: Orientation,
+    solve: bool,
+    ctx: &ExpressionContext,
+    f: impl FnOnce(llr_Expression, llr_Expression) -> llr_Expression,
+) -> llr_Expression {
+    let needs_cells = |orientation| orientation == o || orientation == layout.direction;
+    let needs_size = |orientation| {
+        if solve {
+            needs_cells(orientation)
+        } else {
+            orientation == layout.direction && o != layout.direction
+        }
+    };
+    let [cells_h, cells_v] =
+        [(Orientation::Horizontal, "cells_h"), (Orientation::Vertical, "cells_v")].map(
+            |(orientation, cells_variable)| {
+                if needs_cells(orientation) {
+                    box_layout_cells(&layout.elems, orientation, cells_variable, ctx)
+                } else {
+                    let empty = llr_Expression::Array {
+                        element_ty: box_layout_cell_data_ty(),
+                        values: vec![],
+                        output: ArrayOutput::Slice,
+                    };
+                    (empty, None)
+                }
+            },
+        );
+    let size = |orientation| {
+        if needs_size(orientation) {
+            layout_geometry_size(&layout.geometry.rect, orientation, ctx)
+        } else {
+            llr_Expression::NumberLiteral(0.)
+        }
+    };
+    let (padding_h, spacing_h) =
+        generate_layout_padding_and_spacing(&layout.geometry, Orientation::Horizontal, ctx);
+    let (padding_v, spacing_v) =
+        generate_layout_padding_and_spacing(&layout.geometry, Orientation::Vertical, ctx);
+    let enum_property = |nr: &Option<NamedReference>, e: Rc<crate::langtype::Enumeration>| {
+        if let Some(nr) = nr {
+            llr_Expression::PropertyReference(ctx.map_property_reference(nr))
+        } else {
+            llr_Expression::EnumerationValue(EnumerationValue {
+                value: e.default_value,
+                enumeration: e,
+            })
+        }
+    };
+    let (direction_enum, wrap_enum, justify_enum, align_enum) = crate::typeregister::BUILTIN_ENUMS
+        .with(|e| {
+            (
+                e.FlexDirection.clone(),
+                e.FlexWrap.clone(),
+                e.LayoutAlignment.clone(),
+                e.FlexAlignment.clone(),
+            )
+        });
+    let direction = llr_Expression::EnumerationValue(EnumerationValue {
+        value: match layout.direction {
+            Orientation::Horizontal => 0,
+            Orientation::Vertical => 1,
+        },
+        enumeration: direction_enum.clone(),
+    });
+    let data = make_struct(
+        "FlexboxLayoutData",
+        [
+            ("width", Type::Float32, size(Orientation::Horizontal)),
+            ("height", Type::Float32, size(Orientation::Vertical)),
+            ("spacing_horizontal", Type::Float32, spacing_h),
+            ("spacing_vertical", Type::Float32, spacing_v),
+            ("padding_horizontal", padding_h.ty(ctx), padding_h),
+            ("padding_vertical", padding_v.ty(ctx), padding_v),
+            ("direction", Type::Enumeration(direction_enum), direction),
+            ("wrap", Type::Enumeration(wrap_enum.clone()), enum_property(&layout.wrap, wrap_enum)),
+            (
+                "justify_content",
+                Type::Enumeration(justify_enum.clone()),
+                enum_property(&layout.geometry.alignment, justify_enum),
+            ),
+            (
+                "align_items",
+                Type::Enumeration(align_enum.clone()),
+                enum_property(&layout.align_items, align_enum),
+            ),
+            ("cells_horizontal", cells_h.0.ty(ctx), cells_h.0),
+            ("cells_vertical", cells_v.0.ty(ctx), cells_v.0),
+        ],
+    );
+
+    // The cells in the orientation `o` are computed last, so they provide the repeated indices
+    let (compute_cells_o, compute_cells_other) = match o {
+        Orientation::Horizontal => (cells_h.1, cells_v.1),
+        Orientation::Vertical => (cells_v.1, cells_h.1),
+    };
+    let other_orientation = match o {
+        Orientation::Horizontal => Orientation::Vertical,
+        Orientation::Vertical => Orientation::Horizontal,
+    };
+    let mut expression = match compute_cells_o {
+        Some((cells_variable, elements)) => llr_Expression::BoxLayoutFunction {
+            cells_variable,
+            repeater_indices: solve.then(|| "repeated_indices".into()),
+            elements,
+            orientation: o,
+            sub_expression: Box::new(f(
+                data,
+                llr_Expression::ReadLocalVariable {
+                    name: "repeated_indices".into(),
+                    ty: Type::Array(Type::Int32.into()),
+                },
+            )),
+        },
+        None => f(
+            data,
+            llr_Expression::Array {
+                element_ty: Type::Int32,
+                values: vec![],
+                output: ArrayOutput::Slice,
+            },
+        ),
+    };
+    if let Some((cells_variable, elements)) = compute_cells_other {
+        expression = llr_Expression::BoxLayoutFunction {
+            cells_variable,
+            repeater_indices: None,
+            elements,
+            orientation: other_orientation,
+            sub_expression: Box::new(expression),
+        };
+    }
+    expression
+}
+
+fn orientation_value(o: Orientation) -> llr_Expression {
+    let e = crate::typeregister::BUILTIN_ENUMS.with(|e| e.Orientation.clone());
+    llr_Expression::EnumerationValue(EnumerationValue {
+        value: match o {
+            Orientation::Horizontal => 0,
+            Orientation::Vertical => 1,
+        },
+        enumeration: e,
+    })
+}
+
 struct BoxLayoutDataResult {
     alignment: llr_Expression,
     cells: llr_Expression,
@@ -673,25 +876,24 @@ fn box_layout_data(
             enumeration: e,
         })
     };
+    let (cells, compute_cells) = box_layout_cells(&layout.elems, orientation, "cells", ctx);
+    BoxLayoutDataResult { alignment, cells, compute_cells }
+}
 
-    let repeater_count =
-        layout.elems.iter().filter(|i| i.element.borrow().repeated.is_some()).count();
-
-    let element_ty = Type::Struct {
-        fields: IntoIterator::into_iter([(
-            "constraint".to_string(),
-            crate::layout::layout_info_type(),
-        )])
-        .collect(),
-        name: Some("BoxLayoutCellData".into()),
-        node: None,
-        rust_attributes: None,
-    };
+/// Return the array of BoxLayoutCellData for the items.
+/// When there are repeater involved, the cells are in the `cells_variable`, which must be computed
+/// with a BoxLayoutFunction with the returned elements
+fn box_layout_cells(
+    elems: &[crate::layout::LayoutItem],
+    orientation: Orientation,
+    cells_variable: &str,
+    ctx: &ExpressionContext,
+) -> (llr_Expression, Option<(String, Vec<Either<llr_Expression, u32>>)>) {
+    let repeater_count = elems.iter().filter(|i| i.element.borrow().repeated.is_some()).count();
 
     if repeater_count == 0 {
         let cells = llr_Expression::Array {
-            values: layout
-                .elems
+            values: elems
                 .iter()
                 .map(|li| {
                     let layout_info =
@@ -702,13 +904,13 @@ fn box_layout_data(
                     )
                 })
                 .collect(),
-            element_ty,
-            as_model: false,
+            element_ty: box_layout_cell_data_ty(),
+            output: ArrayOutput::Slice,
         };
-        BoxLayoutDataResult { alignment, cells, compute_cells: None }
+        (cells, None)
     } else {
         let mut elements = vec![];
-        for item in &layout.elems {
+        for item in elems {
             if item.element.borrow().repeated.is_some() {
                 let repeater_index =
                     match ctx.mapping.element_mapping.get(&item.element.clone().into()).unwrap() {
@@ -726,10 +928,23 @@ fn box_layout_data(
             }
         }
         let cells = llr_Expression::ReadLocalVariable {
-            name: "cells".into(),
+            name: cells_variable.into(),
             ty: Type::Array(Box::new(crate::layout::layout_info_type())),
         };
-        BoxLayoutDataResult { alignment, cells, compute_cells: Some(("cells".into(), elements)) }
+        (cells, Some((cells_variable.into(), elements)))
+    }
+}
+
+fn box_layout_cell_data_ty() -> Type {
+    Type::Struct {
+        fields: IntoIterator::into_iter([(
+            "constraint".to_string(),
+            crate::layout::layout_info_type(),
+        )])
+        .collect(),
+        name: Some("BoxLayoutCellData".into()),
+        node: None,
+        rust_attributes: None,
     }
 }
 
@@ -758,7 +973,7 @@ fn grid_layout_cell_data(
                 )
             })
             .collect(),
-        as_model: false,
+        output: ArrayOutput::Slice,
     }
 }
 
@@ -872,7 +1087,7 @@ fn llr_path_elements(elements: Vec<llr_Expression>) -> llr_Expression {
                     rust_attributes: None,
                 },
                 values: elements,
-                as_model: false,
+                output: ArrayOutput::Slice,
             }
             .into(),
             to: Type::PathData,
@@ -953,7 +1168,7 @@ fn llr_path_elements(elements: Vec<llr_Expression>) -> llr_Expression {
                             llr_Expression::Array {
                                 element_ty: event_type,
                                 values: events,
-                                as_model: false,
+                                output: ArrayOutput::Slice,
                             },
                         ),
                         (
@@ -961,7 +1176,7 @@ fn llr_path_elements(elements: Vec<llr_Expression>) -> llr_Expression {
                             llr_Expression::Array {
                                 element_ty: point_type,
                                 values: points,
-                                as_model: false,
+                                output: ArrayOutput::Slice,
                             },
                         ),
                     ])
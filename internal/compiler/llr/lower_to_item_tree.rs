@@ -189,12 +189,14 @@ fn lower_sub_component(
         repeated: Default::default(),
         component_containers: Default::default(),
         popup_windows: Default::default(),
+        timers: Default::default(),
         sub_components: Default::default(),
         property_init: Default::default(),
         animations: Default::default(),
         two_way_bindings: Default::default(),
         const_properties: Default::default(),
         init_code: Default::default(),
+        change_callbacks: Default::default(),
         geometries: Default::default(),
         // just initialize to dummy expression right now and it will be set later
         layout_info_h: super::Expression::BoolLiteral(false).into(),
@@ -413,6 +415,8 @@ fn lower_sub_component(
         .map(|popup| lower_popup_component(&popup.component, &ctx))
         .collect();
 
+    sub_component.timers = component.timers.borrow().iter().map(|t| lower_timer(t, &ctx)).collect();
+
     crate::generator::for_each_const_properties(component, |elem, n| {
         let x = ctx.map_property_reference(&NamedReference::new(elem, n));
         sub_component.const_properties.push(x);
@@ -443,7 +447,8 @@ fn lower_sub_component(
     sub_component.accessible_prop = accessible_prop
         .into_iter()
         .map(|(idx, key, nr)| {
-            let mut expr = super::Expression::PropertyReference(ctx.map_property_reference(&nr));
+            let prop = ctx.map_property_reference(&nr);
+            let mut expr = super::Expression::PropertyReference(prop.clone());
             match nr.ty() {
                 Type::Bool => {
                     expr = super::Expression::Condition {
@@ -457,6 +462,14 @@ fn lower_sub_component(
                 }
                 Type::String => {}
                 Type::Enumeration(e) if e.name == "AccessibleRole" => {}
+                Type::Callback { args, .. } => {
+                    expr = super::Expression::CallBackCall {
+                        callback: prop,
+                        arguments: (0..args.len())
+                            .map(|index| super::Expression::FunctionParameterReference { index })
+                            .collect(),
+                    };
+                }
                 _ => panic!("Invalid type for accessible property"),
             }
 
@@ -477,6 +490,19 @@ fn lower_sub_component(
         sub_component.geometries[item_index] = Some(lower_geometry(geom, &ctx).into());
     });
 
+    crate::object_tree::recurse_elem(&component.root_element, &(), &mut |element, _| {
+        let elem = element.borrow();
+        if elem.repeated.is_some() {
+            return;
+        };
+        for (nr, expr) in &elem.change_callbacks {
+            sub_component.change_callbacks.push((
+                ctx.map_property_reference(nr),
+                super::lower_expression::lower_expression(expr, &ctx).into(),
+            ));
+        }
+    });
+
     LoweredSubComponent { sub_component: Rc::new(sub_component), mapping }
 }
 
@@ -498,6 +524,20 @@ fn lower_geometry(
     }
 }
 
+fn lower_timer(timer: &crate::object_tree::Timer, ctx: &ExpressionContext) -> Timer {
+    Timer {
+        interval: super::Expression::PropertyReference(ctx.map_property_reference(&timer.interval))
+            .into(),
+        running: super::Expression::PropertyReference(ctx.map_property_reference(&timer.running))
+            .into(),
+        triggered: super::Expression::CallBackCall {
+            callback: ctx.map_property_reference(&timer.triggered),
+            arguments: vec![],
+        }
+        .into(),
+    }
+}
+
 fn get_property_analysis(elem: &ElementRc, p: &str) -> crate::object_tree::PropertyAnalysis {
     let mut a = elem.borrow().property_analysis.borrow().get(p).cloned().unwrap_or_default();
     let mut elem = elem.clone();
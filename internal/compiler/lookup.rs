@@ -803,19 +803,25 @@ fn for_each_entry<R>(
         ctx: &LookupCtx,
         f: &mut impl FnMut(&str, LookupResult) -> Option<R>,
     ) -> Option<R> {
-        f(
-            "dark-color-scheme",
-            Expression::FunctionCall {
-                function: Expression::BuiltinFunctionReference(
-                    BuiltinFunction::DarkColorScheme,
-                    None,
-                )
-                .into(),
+        let sl = || ctx.current_token.as_ref().map(|t| t.to_source_location());
+        let call = |function| {
+            LookupResult::from(Expression::FunctionCall {
+                function: Expression::BuiltinFunctionReference(function, None).into(),
                 arguments: vec![],
-                source_location: ctx.current_token.as_ref().map(|t| t.to_source_location()),
-            }
-            .into(),
-        )
+                source_location: sl(),
+            })
+        };
+        f("dark-color-scheme", call(BuiltinFunction::DarkColorScheme))
+            .or_else(|| f("first-day-of-week", call(BuiltinFunction::FirstDayOfWeek)))
+            .or_else(|| {
+                f(
+                    "filter-strings",
+                    LookupResult::from(Expression::BuiltinFunctionReference(
+                        BuiltinFunction::FilterStrings,
+                        sl(),
+                    )),
+                )
+            })
     }
 }
 
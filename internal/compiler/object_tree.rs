@@ -9,7 +9,7 @@
 
 use crate::diagnostics::{BuildDiagnostics, SourceLocation, Spanned};
 use crate::expression_tree::{self, BindingExpression, Expression, Unit};
-use crate::langtype::{BuiltinElement, Enumeration, NativeClass, Type};
+use crate::langtype::{BuiltinElement, Enumeration, EnumerationValue, NativeClass, Type};
 use crate::langtype::{ElementType, PropertyLookupResult};
 use crate::layout::{LayoutConstraints, Orientation};
 use crate::namedreference::NamedReference;
@@ -254,10 +254,18 @@ pub struct PopupWindow {
     pub component: Rc<Component>,
     pub x: NamedReference,
     pub y: NamedReference,
-    pub close_on_click: bool,
+    pub close_policy: EnumerationValue,
     pub parent_element: ElementRc,
 }
 
+/// A `Timer` element, lowered by the `lower_timers` pass
+#[derive(Debug, Clone)]
+pub struct Timer {
+    pub interval: NamedReference,
+    pub running: NamedReference,
+    pub triggered: NamedReference,
+}
+
 type ChildrenInsertionPoint = (ElementRc, syntax_nodes::ChildrenPlaceholder);
 
 /// Used sub types for a root component
@@ -336,6 +344,7 @@ pub struct Component {
     /// (This only make sense on the root component)
     pub used_types: RefCell<UsedSubTypes>,
     pub popup_windows: RefCell<Vec<PopupWindow>>,
+    pub timers: RefCell<Vec<Timer>>,
 
     /// The names under which this component should be accessible
     /// if it is a global singleton and exported.
@@ -618,6 +627,10 @@ pub struct Element {
     pub states: Vec<State>,
     pub transitions: Vec<Transition>,
 
+    /// The `changed <property> => { ... }` handlers of this element, in declaration order.
+    /// The property may be in another element after the move_declarations pass.
+    pub change_callbacks: Vec<(NamedReference, Expression)>,
+
     /// true when this item's geometry is handled by a layout
     pub child_of_layout: bool,
     /// The property pointing to the layout info. `(horizontal, vertical)`
@@ -888,6 +901,7 @@ pub fn from_node(
                     error_on(&cb, "an 'init' callback")
                 }
             });
+            node.PropertyChangedCallback().for_each(|n| error_on(&n, "'changed' callbacks"));
 
             ElementType::Global
         } else if parent_type != ElementType::Error {
@@ -1376,6 +1390,36 @@ pub fn from_node(
             r.borrow_mut().states.push(s);
         }
 
+        for changed in node.PropertyChangedCallback() {
+            let name_node = changed.DeclaredIdentifier();
+            let unresolved_name = unwrap_or_continue!(parser::identifier_text(&name_node); diag);
+            let lookup_result = r.borrow().lookup_property(&unresolved_name);
+            if !lookup_result.is_valid() {
+                diag.push_error(
+                    format!(
+                        "Property '{}' does not exist in {}",
+                        unresolved_name,
+                        r.borrow().base_type
+                    ),
+                    &name_node,
+                );
+                continue;
+            }
+            if !lookup_result.property_type.is_property_type() {
+                diag.push_error(format!("'{}' is not a property", unresolved_name), &name_node);
+                continue;
+            }
+            let nr = NamedReference::new(&r, &lookup_result.resolved_name);
+            if r.borrow().change_callbacks.iter().any(|(n, _)| *n == nr) {
+                diag.push_error(
+                    format!("Duplicated change callback on '{}'", unresolved_name),
+                    &name_node,
+                );
+                continue;
+            }
+            r.borrow_mut().change_callbacks.push((nr, Expression::Uncompiled(changed.into())));
+        }
+
         for ts in node.Transitions() {
             if !is_legacy_syntax {
                 diag.push_error("'transitions' block are no longer supported. Use 'in {...}' and 'out {...}' directly in the state definition".into(), &ts);
@@ -1779,6 +1823,18 @@ fn animation_element_from_node(
         );
         None
     } else {
+        for b in anim.Binding() {
+            // The keyframes are stored in the animation as a vector, not as a model
+            if crate::parser::identifier_text(&b).is_some_and(|n| n == "keyframes") {
+                match b.BindingExpression().Expression().and_then(|e| e.Array()) {
+                    Some(array) => check_keyframe_positions(&array, diag),
+                    None => diag.push_error(
+                        "The keyframes of an animation must be an array literal".into(),
+                        &b.BindingExpression(),
+                    ),
+                }
+            }
+        }
         let mut anim_element =
             Element { id: "".into(), base_type: anim_type, ..Default::default() };
         anim_element.parse_bindings(
@@ -1795,6 +1851,54 @@ fn animation_element_from_node(
     }
 }
 
+/// Report the keyframe positions written as literals that are out of range or not sorted.
+fn check_keyframe_positions(array: &syntax_nodes::Array, diag: &mut BuildDiagnostics) {
+    let mut previous = None;
+    for keyframe in array.Expression() {
+        let Some(position) = keyframe.ObjectLiteral().and_then(|o| {
+            o.ObjectMember().find(|m| parser::identifier_text(m).is_some_and(|n| n == "position"))
+        }) else {
+            continue;
+        };
+        let position = position.Expression();
+        let Some(value) = keyframe_position_literal(&position) else { continue };
+        if !(0. ..=1.).contains(&value) {
+            diag.push_error(
+                "The position of a keyframe must be between 0% and 100%".into(),
+                &position,
+            );
+        } else if previous.is_some_and(|previous| value < previous) {
+            diag.push_error(
+                "The keyframes of an animation must be sorted by position".into(),
+                &position,
+            );
+        }
+        previous = Some(value);
+    }
+}
+
+/// The value of a keyframe position written as a number or percentage literal, as a fraction of the iteration
+fn keyframe_position_literal(node: &syntax_nodes::Expression) -> Option<f64> {
+    if let Some(unary) = node.UnaryOpExpression() {
+        let value = keyframe_position_literal(&unary.Expression())?;
+        return if unary.child_token(SyntaxKind::Minus).is_some() {
+            Some(-value)
+        } else {
+            unary.child_token(SyntaxKind::Plus).map(|_| value)
+        };
+    }
+    if let Some(inner) = node.Expression() {
+        // Parenthesized expression
+        return keyframe_position_literal(&inner);
+    }
+    let literal = node.child_token(SyntaxKind::NumberLiteral)?;
+    match crate::literals::parse_number_literal(literal.text().into()).ok()? {
+        Expression::NumberLiteral(value, Unit::Percent) => Some(value / 100.),
+        Expression::NumberLiteral(value, Unit::None) => Some(value),
+        _ => None,
+    }
+}
+
 #[derive(Default, Debug, Clone)]
 pub struct QualifiedTypeName {
     pub members: Vec<String>,
@@ -2067,6 +2171,12 @@ fn visit_element_expressions_simple(
     }
     elem.borrow_mut().transitions = transitions;
 
+    let mut change_callbacks = std::mem::take(&mut elem.borrow_mut().change_callbacks);
+    for (_, e) in &mut change_callbacks {
+        vis(e, None, &|| Type::Void);
+    }
+    elem.borrow_mut().change_callbacks = change_callbacks;
+
     let component = elem.borrow().enclosing_component.upgrade().unwrap();
     if Rc::ptr_eq(&component.root_element, elem) {
         for e in component.init_code.borrow_mut().iter_mut() {
@@ -2124,6 +2234,11 @@ pub fn visit_all_named_references_in_element(
         }
     }
     elem.borrow_mut().transitions = transitions;
+    let mut change_callbacks = std::mem::take(&mut elem.borrow_mut().change_callbacks);
+    for (r, _) in &mut change_callbacks {
+        vis(r);
+    }
+    elem.borrow_mut().change_callbacks = change_callbacks;
     let mut repeated = std::mem::take(&mut elem.borrow_mut().repeated);
     if let Some(r) = &mut repeated {
         if let Some(lv) = &mut r.is_listview {
@@ -2189,6 +2304,11 @@ pub fn visit_all_named_references(
                     vis(&mut p.x);
                     vis(&mut p.y);
                 });
+                compo.timers.borrow_mut().iter_mut().for_each(|t| {
+                    vis(&mut t.interval);
+                    vis(&mut t.running);
+                    vis(&mut t.triggered);
+                });
             }
             compo
         },
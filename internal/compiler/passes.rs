@@ -6,7 +6,6 @@
 mod border_radius;
 mod check_expressions;
 mod check_public_api;
-mod check_rotation;
 mod clip;
 mod collect_custom_fonts;
 mod collect_globals;
@@ -30,12 +29,16 @@
 mod lower_accessibility;
 mod lower_component_container;
 mod lower_layout;
+mod lower_menus;
 mod lower_popups;
 mod lower_property_to_element;
 mod lower_shadows;
+mod lower_splitter;
 mod lower_states;
+mod lower_tableview;
 mod lower_tabwidget;
 mod lower_text_input_interface;
+mod lower_timers;
 pub mod materialize_fake_properties;
 pub mod move_declarations;
 mod optimize_useless_rectangles;
@@ -81,6 +84,16 @@ pub async fn run_passes(
     run_import_passes(doc, type_loader, diag);
     check_public_api::check_public_api(doc, diag);
 
+    collect_subcomponents::collect_subcomponents(root_component);
+    for component in (root_component.used_types.borrow().sub_components.iter())
+        .chain(std::iter::once(root_component))
+    {
+        lower_tabwidget::lower_tabwidget(component, type_loader, diag).await;
+        lower_menus::lower_menus(component, type_loader, diag).await;
+        lower_splitter::lower_splitter(component, type_loader, diag).await;
+    }
+
+    // The lowering above may have introduced new sub-components from the style
     collect_subcomponents::collect_subcomponents(root_component);
     for component in (root_component.used_types.borrow().sub_components.iter())
         .chain(std::iter::once(root_component))
@@ -91,7 +104,6 @@ pub async fn run_passes(
             type_loader.compiler_config.embed_resources,
             diag,
         );
-        lower_tabwidget::lower_tabwidget(component, type_loader, diag).await;
         apply_default_properties_from_style::apply_default_properties_from_style(
             component,
             &style_metrics,
@@ -101,6 +113,12 @@ pub async fn run_passes(
         lower_text_input_interface::lower_text_input_interface(component);
     }
 
+    for component in (root_component.used_types.borrow().sub_components.iter())
+        .chain(std::iter::once(root_component))
+    {
+        lower_tableview::lower_tableview(component, type_loader, diag).await;
+    }
+
     inlining::inline(doc, inlining::InlineSelection::InlineOnlyRequiredComponents);
     collect_subcomponents::collect_subcomponents(root_component);
 
@@ -115,6 +133,7 @@ pub async fn run_passes(
         flickable::handle_flickable(component, &global_type_registry.borrow());
         repeater_component::process_repeater_components(component);
         lower_popups::lower_popups(component, &doc.local_registry, diag);
+        lower_timers::lower_timers(component, diag);
         lower_component_container::lower_component_container(component, &doc.local_registry, diag);
 
         lower_layout::lower_layouts(component, type_loader, diag).await;
@@ -143,8 +162,8 @@ pub async fn run_passes(
         lower_shadows::lower_shadow_properties(component, &doc.local_registry, diag);
         lower_property_to_element::lower_property_to_element(
             component,
-            crate::typeregister::RESERVED_ROTATION_PROPERTIES[0].0,
-            crate::typeregister::RESERVED_ROTATION_PROPERTIES[1..]
+            crate::typeregister::RESERVED_TRANSFORM_PROPERTIES[0].0,
+            crate::typeregister::RESERVED_TRANSFORM_PROPERTIES[1..]
                 .iter()
                 .map(|(prop_name, _)| *prop_name),
             Some(&|e, prop| Expression::BinaryExpression {
@@ -153,6 +172,9 @@ pub async fn run_passes(
                     match prop {
                         "rotation-origin-x" => "width",
                         "rotation-origin-y" => "height",
+                        "scale-x" | "scale-y" => {
+                            return Expression::NumberLiteral(1., Default::default())
+                        }
                         "rotation-angle" => return Expression::Invalid,
                         _ => unreachable!(),
                     },
@@ -221,6 +243,7 @@ pub async fn run_passes(
         root_component,
         type_loader.compiler_config.embed_resources,
         type_loader.compiler_config.scale_factor,
+        type_loader.compiler_config.grayscale_textures,
         &type_loader.compiler_config.resource_url_mapper,
         diag,
     )
@@ -279,6 +302,5 @@ pub fn run_import_passes(
     focus_handling::replace_forward_focus_bindings_with_focus_functions(doc, diag);
     check_expressions::check_expressions(doc, diag);
     purity_check::purity_check(doc, diag);
-    check_rotation::check_rotation(doc, diag);
     unique_id::check_unique_id(doc, diag);
 }
@@ -179,6 +179,13 @@ fn analyze_element(
         }
     }
 
+    for (nr, expr) in &elem.borrow().change_callbacks {
+        process_property(&nr.clone().into(), context, reverse_aliases, diag);
+        recurse_expression(expr, &mut |prop| {
+            process_property(prop, context, reverse_aliases, diag);
+        });
+    }
+
     if let Some(repeated) = &elem.borrow().repeated {
         recurse_expression(&repeated.model, &mut |prop| {
             process_property(prop, context, reverse_aliases, diag);
@@ -392,6 +399,19 @@ fn recurse_expression(expr: &Expression, vis: &mut impl FnMut(&PropertyPath)) {
                 crate::layout::Layout::BoxLayout(l) => {
                     visit_layout_items_dependencies(l.elems.iter(), *o, vis)
                 }
+                crate::layout::Layout::FlexboxLayout(l) => {
+                    visit_layout_items_dependencies(l.elems.iter(), *o, vis);
+                    if *o != l.direction {
+                        // Across the main axis, the lines depend on the items and the size along the main axis
+                        visit_layout_items_dependencies(l.elems.iter(), l.direction, vis);
+                        if let Some(nr) = l.geometry.rect.size_reference(l.direction) {
+                            vis(&nr.clone().into());
+                        }
+                    }
+                    for nr in l.wrap.iter().chain(l.align_items.iter()) {
+                        vis(&nr.clone().into());
+                    }
+                }
             }
 
             let mut g = l.geometry().clone();
@@ -495,6 +515,24 @@ fn visit_implicit_layout_info_dependencies(
                 vis(&NamedReference::new(item, "overflow").into());
             }
         }
+        "StyledText" => {
+            vis(&NamedReference::new(item, "text").into());
+            vis(&NamedReference::new(item, "font-family").into());
+            vis(&NamedReference::new(item, "font-size").into());
+            vis(&NamedReference::new(item, "font-weight").into());
+            vis(&NamedReference::new(item, "font-italic").into());
+            vis(&NamedReference::new(item, "wrap").into());
+            let wrap_set = item.borrow().is_binding_set("wrap", false)
+                || item
+                    .borrow()
+                    .property_analysis
+                    .borrow()
+                    .get("wrap")
+                    .map_or(false, |a| a.is_set || a.is_set_externally);
+            if wrap_set && orientation == Orientation::Vertical {
+                vis(&NamedReference::new(item, "width").into());
+            }
+        }
 
         _ => (),
     }
@@ -89,18 +89,23 @@ fn create_clip_element(parent_elem: &ElementRc, native_clip: &Rc<NativeClass>) {
     ]
     .iter()
     {
-        if parent_elem.borrow().bindings.contains_key(*optional_binding) {
-            clip.borrow_mut().bindings.insert(
-                optional_binding.to_string(),
-                RefCell::new(
-                    Expression::PropertyReference(NamedReference::new(
-                        parent_elem,
-                        optional_binding,
-                    ))
+        let parent_binding = if parent_elem.borrow().bindings.contains_key(*optional_binding) {
+            *optional_binding
+        } else if optional_binding.ends_with("-radius")
+            && parent_elem.borrow().bindings.contains_key("border-radius")
+        {
+            // The corners default to the border-radius, like for the Rectangle
+            "border-radius"
+        } else {
+            continue;
+        };
+        clip.borrow_mut().bindings.insert(
+            optional_binding.to_string(),
+            RefCell::new(
+                Expression::PropertyReference(NamedReference::new(parent_elem, parent_binding))
                     .into(),
-                ),
-            );
-        }
+            ),
+        );
     }
     clip.borrow_mut().bindings.insert(
         "clip".to_owned(),
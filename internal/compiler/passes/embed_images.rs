@@ -18,6 +18,7 @@ pub async fn embed_images(
     component: &Rc<Component>,
     embed_files: EmbedResourcesKind,
     scale_factor: f64,
+    grayscale_textures: Option<PixelFormat>,
     resource_url_mapper: &Option<Rc<dyn Fn(&str) -> Pin<Box<dyn Future<Output = Option<String>>>>>>,
     diag: &mut BuildDiagnostics,
 ) {
@@ -62,6 +63,7 @@ pub async fn embed_images(
                 global_embedded_resources,
                 embed_files,
                 scale_factor,
+                grayscale_textures,
                 diag,
             )
         });
@@ -84,6 +86,7 @@ fn embed_images_from_expression(
     global_embedded_resources: &RefCell<HashMap<String, EmbeddedResources>>,
     embed_files: EmbedResourcesKind,
     scale_factor: f64,
+    grayscale_textures: Option<PixelFormat>,
     diag: &mut BuildDiagnostics,
 ) {
     if let Expression::ImageReference { ref mut resource_ref, source_location, nine_slice: _ } = e {
@@ -102,6 +105,7 @@ fn embed_images_from_expression(
                         embed_files,
                         &path,
                         scale_factor,
+                        grayscale_textures,
                         diag,
                         source_location,
                     );
@@ -118,6 +122,7 @@ fn embed_images_from_expression(
             global_embedded_resources,
             embed_files,
             scale_factor,
+            grayscale_textures,
             diag,
         )
     });
@@ -128,6 +133,7 @@ fn embed_image(
     _embed_files: EmbedResourcesKind,
     path: &str,
     _scale_factor: f64,
+    _grayscale_textures: Option<PixelFormat>,
     diag: &mut BuildDiagnostics,
     source_location: &Option<crate::diagnostics::SourceLocation>,
 ) -> ImageReference {
@@ -148,6 +154,7 @@ fn embed_image(
                                 img,
                                 source_format,
                                 original_size,
+                                _grayscale_textures,
                             ))
                         }
                         Err(err) => {
@@ -202,6 +209,7 @@ fn generate_texture(
     image: image::RgbaImage,
     source_format: SourceFormat,
     original_size: Size,
+    grayscale: Option<PixelFormat>,
 ) -> Texture {
     // Analyze each pixels
     let mut top = 0;
@@ -293,6 +301,8 @@ enum ColorState {
 
     let format = if let ColorState::Rgb(c) = color {
         PixelFormat::AlphaMap(c)
+    } else if let Some(grayscale) = grayscale.filter(|_| is_opaque) {
+        grayscale
     } else if is_opaque {
         PixelFormat::Rgb
     } else {
@@ -346,9 +356,36 @@ fn convert_image(
             })
             .collect(),
         (_, PixelFormat::AlphaMap(_)) => i.pixels().map(|(_, _, p)| p[3]).collect(),
+        // Only used for opaque images, so the premultiplication doesn't matter
+        (_, PixelFormat::Gray8) => i.pixels().map(|(_, _, p)| luminance(&p)).collect(),
+        // Each line starts on a new byte, with the first pixel in the most significant bits
+        (_, PixelFormat::Gray4 | PixelFormat::Gray1) => {
+            let bits = if matches!(format, PixelFormat::Gray4) { 4 } else { 1 };
+            let max = (1 << bits) - 1;
+            let mut data = Vec::new();
+            for y in 0..i.height() {
+                let mut byte = 0;
+                for x in 0..i.width() {
+                    let value = (luminance(&i.get_pixel(x, y)) as u32 * max + 127) / 255;
+                    let shift = 8 - bits - (x * bits) % 8;
+                    byte |= (value << shift) as u8;
+                    if shift == 0 || x == i.width() - 1 {
+                        data.push(byte);
+                        byte = 0;
+                    }
+                }
+            }
+            data
+        }
     }
 }
 
+/// The luminance of an opaque pixel, using the Rec. 601 weights
+#[cfg(feature = "software-renderer")]
+fn luminance(p: &image::Rgba<u8>) -> u8 {
+    ((p[0] as u32 * 77 + p[1] as u32 * 150 + p[2] as u32 * 29) >> 8) as u8
+}
+
 #[cfg(feature = "software-renderer")]
 enum SourceFormat {
     RgbaPremultiplied,
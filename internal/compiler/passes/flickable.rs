@@ -137,7 +137,10 @@ fn fixup_geometry(flickable_elem: &ElementRc) {
 /// Return true if this type is a layout that has constraints
 fn is_layout(base_type: &ElementType) -> bool {
     if let ElementType::Builtin(be) = base_type {
-        matches!(be.name.as_str(), "GridLayout" | "HorizontalLayout" | "VerticalLayout")
+        matches!(
+            be.name.as_str(),
+            "GridLayout" | "HorizontalLayout" | "VerticalLayout" | "FlexboxLayout"
+        )
     } else {
         false
     }
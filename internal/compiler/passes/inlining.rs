@@ -69,7 +69,7 @@ fn element_key(e: ElementRc) -> ByAddress<ElementRc> {
     ByAddress(e)
 }
 
-fn inline_element(
+pub(super) fn inline_element(
     elem: &ElementRc,
     inlined_component: &Rc<Component>,
     root_component: &Rc<Component>,
@@ -97,6 +97,12 @@ fn inline_element(
     debug_assert!(inlined_component.root_element.borrow().states.is_empty());
     debug_assert!(inlined_component.root_element.borrow().transitions.is_empty());
 
+    // The change callbacks of the base run before the ones of the element.
+    // The references are fixed up later with the mapping.
+    let mut change_callbacks = inlined_component.root_element.borrow().change_callbacks.clone();
+    change_callbacks.append(&mut elem_mut.change_callbacks);
+    elem_mut.change_callbacks = change_callbacks;
+
     // Map the old element to the new
     let mut mapping = HashMap::new();
     mapping.insert(element_key(inlined_component.root_element.clone()), elem.clone());
@@ -151,6 +157,7 @@ fn inline_element(
             .iter()
             .map(|p| duplicate_popup(p, &mut mapping, priority_delta)),
     );
+    root_component.timers.borrow_mut().extend(inlined_component.timers.borrow().iter().cloned());
 
     // When inlining a component before the collect_init_code phase, do the collect_init_code phase for
     // the init callback in the inlined component manually, by cloning the expression into the init_code
@@ -236,6 +243,9 @@ fn inline_element(
         fixup_reference(&mut p.x, &mapping);
         fixup_reference(&mut p.y, &mapping);
     }
+    for t in root_component.timers.borrow_mut().iter_mut() {
+        fixup_timer(t, &mapping);
+    }
 }
 
 // Duplicate the element elem and all its children. And fill the mapping to point from the old to the new
@@ -272,6 +282,7 @@ fn duplicate_element_with_mapping(
             .iter()
             .map(|t| duplicate_transition(t, mapping, root_component, priority_delta))
             .collect(),
+        change_callbacks: elem.change_callbacks.clone(),
         child_of_layout: elem.child_of_layout,
         layout_info_prop: elem.layout_info_prop.clone(),
         default_fill_parent: elem.default_fill_parent,
@@ -335,6 +346,7 @@ fn duplicate_sub_component(
         init_code: component_to_duplicate.init_code.clone(),
         used_types: Default::default(),
         popup_windows: Default::default(),
+        timers: component_to_duplicate.timers.clone(),
         exported_global_names: component_to_duplicate.exported_global_names.clone(),
         is_root_component: Default::default(),
         private_properties: Default::default(),
@@ -355,6 +367,9 @@ fn duplicate_sub_component(
         fixup_reference(&mut p.x, mapping);
         fixup_reference(&mut p.y, mapping);
     }
+    for t in new_component.timers.borrow_mut().iter_mut() {
+        fixup_timer(t, mapping);
+    }
     new_component
         .root_constraints
         .borrow_mut()
@@ -374,7 +389,7 @@ fn duplicate_popup(
     PopupWindow {
         x: p.x.clone(),
         y: p.y.clone(),
-        close_on_click: p.close_on_click,
+        close_policy: p.close_policy.clone(),
         component: duplicate_sub_component(&p.component, &parent, mapping, priority_delta),
         parent_element: mapping
             .get(&element_key(p.parent_element.clone()))
@@ -444,6 +459,12 @@ fn fixup_reference(nr: &mut NamedReference, mapping: &HashMap<ByAddress<ElementR
     }
 }
 
+fn fixup_timer(timer: &mut Timer, mapping: &HashMap<ByAddress<ElementRc>, ElementRc>) {
+    fixup_reference(&mut timer.interval, mapping);
+    fixup_reference(&mut timer.running, mapping);
+    fixup_reference(&mut timer.triggered, mapping);
+}
+
 fn fixup_element_references(
     expr: &mut Expression,
     mapping: &HashMap<ByAddress<ElementRc>, ElementRc>,
@@ -471,6 +492,11 @@ fn fixup_element_references(
                     fxe(&mut e.element);
                 }
             }
+            crate::layout::Layout::FlexboxLayout(l) => {
+                for e in &mut l.elems {
+                    fxe(&mut e.element);
+                }
+            }
         },
         Expression::RepeaterModelReference { element }
         | Expression::RepeaterIndexReference { element } => fx(element),
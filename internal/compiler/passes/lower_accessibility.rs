@@ -39,8 +39,7 @@ pub fn lower_accessibility_properties(component: &Rc<Component>, diag: &mut Buil
                 None => elem.borrow().is_binding_set("accessible-role", false),
             };
 
-            for prop_name in crate::typeregister::RESERVED_ACCESSIBILITY_PROPERTIES
-                .iter()
+            for prop_name in crate::typeregister::reserved_accessibility_properties()
                 .map(|x| x.0)
                 .chain(std::iter::once("accessible-role"))
             {
@@ -115,6 +115,7 @@ fn lower_element_layout(
         "GridLayout" => lower_grid_layout(component, elem, diag),
         "HorizontalLayout" => lower_box_layout(elem, diag, Orientation::Horizontal),
         "VerticalLayout" => lower_box_layout(elem, diag, Orientation::Vertical),
+        "FlexboxLayout" => lower_flexbox_layout(elem, diag),
         "Dialog" => {
             lower_dialog_layout(elem, style_metrics, diag);
             return; // the Dialog stays in the tree as a Dialog
@@ -139,7 +140,7 @@ fn lower_element_layout(
 }
 
 pub fn is_layout_element(element: &ElementRc) -> bool {
-    matches!(&element.borrow().base_type, ElementType::Builtin(n) if n.name == "GridLayout" || n.name == "HorizontalLayout" || n.name == "VerticalLayout")
+    matches!(&element.borrow().base_type, ElementType::Builtin(n) if n.name == "GridLayout" || n.name == "HorizontalLayout" || n.name == "VerticalLayout" || n.name == "FlexboxLayout")
 }
 
 fn lower_grid_layout(
@@ -180,6 +181,10 @@ fn lower_grid_layout(
             }
             let row_children = std::mem::take(&mut layout_child.borrow_mut().children);
             for x in row_children {
+                if is_shortcut(&x) {
+                    collected_children.push(x);
+                    continue;
+                }
                 grid.add_element(
                     &x,
                     (&mut row, &mut col),
@@ -195,6 +200,8 @@ fn lower_grid_layout(
                 col = 0;
             }
             component.optimized_elements.borrow_mut().push(layout_child);
+        } else if is_shortcut(&layout_child) {
+            collected_children.push(layout_child);
         } else {
             grid.add_element(
                 &layout_child,
@@ -441,6 +448,97 @@ fn lower_box_layout(
     }
 }
 
+fn lower_flexbox_layout(layout_element: &ElementRc, diag: &mut BuildDiagnostics) {
+    // The direction decides which constraints the items depend on, so it must be known at compile time
+    let direction = match layout_element.borrow_mut().bindings.remove("direction") {
+        None => Orientation::Horizontal,
+        Some(binding) => {
+            let binding = binding.into_inner();
+            match &binding.expression {
+                Expression::EnumerationValue(val) if val.to_string() == "column" => {
+                    Orientation::Vertical
+                }
+                Expression::EnumerationValue(_) => Orientation::Horizontal,
+                _ => {
+                    diag.push_error(
+                        "The `direction` property of a FlexboxLayout must be known at compile-time"
+                            .into(),
+                        &binding,
+                    );
+                    Orientation::Horizontal
+                }
+            }
+        }
+    };
+    let mut layout = FlexboxLayout::new(layout_element, direction);
+
+    let layout_cache_prop_h = create_new_prop(layout_element, "layout-cache-h", Type::LayoutCache);
+    let layout_cache_prop_v = create_new_prop(layout_element, "layout-cache-v", Type::LayoutCache);
+    let layout_info_prop_h = create_new_prop(layout_element, "layoutinfo-h", layout_info_type());
+    let layout_info_prop_v = create_new_prop(layout_element, "layoutinfo-v", layout_info_type());
+
+    let layout_children = std::mem::take(&mut layout_element.borrow_mut().children);
+    for layout_child in &layout_children {
+        if let Some(item) = create_layout_item(layout_child, diag) {
+            let index = layout.elems.len() * 2;
+            let rep_idx = &item.repeater_index;
+            let actual_elem = &item.elem;
+            set_prop_from_cache(actual_elem, "x", &layout_cache_prop_h, index, rep_idx, diag);
+            if !item.item.constraints.fixed_width {
+                set_prop_from_cache(
+                    actual_elem,
+                    "width",
+                    &layout_cache_prop_h,
+                    index + 1,
+                    rep_idx,
+                    diag,
+                );
+            }
+            set_prop_from_cache(actual_elem, "y", &layout_cache_prop_v, index, rep_idx, diag);
+            if !item.item.constraints.fixed_height {
+                set_prop_from_cache(
+                    actual_elem,
+                    "height",
+                    &layout_cache_prop_v,
+                    index + 1,
+                    rep_idx,
+                    diag,
+                );
+            }
+            layout.elems.push(item.item);
+        }
+    }
+    layout_element.borrow_mut().children = layout_children;
+    let span = layout_element.borrow().to_source_location();
+    for (prop, expression) in [
+        (
+            &layout_cache_prop_h,
+            Expression::SolveLayout(layout.clone().into(), Orientation::Horizontal),
+        ),
+        (
+            &layout_cache_prop_v,
+            Expression::SolveLayout(layout.clone().into(), Orientation::Vertical),
+        ),
+        (
+            &layout_info_prop_h,
+            Expression::ComputeLayoutInfo(layout.clone().into(), Orientation::Horizontal),
+        ),
+        (
+            &layout_info_prop_v,
+            Expression::ComputeLayoutInfo(layout.clone().into(), Orientation::Vertical),
+        ),
+    ] {
+        prop.element().borrow_mut().bindings.insert(
+            prop.name().into(),
+            BindingExpression::new_with_span(expression, span.clone()).into(),
+        );
+    }
+    layout_element.borrow_mut().layout_info_prop = Some((layout_info_prop_h, layout_info_prop_v));
+    for d in layout_element.borrow_mut().debug.iter_mut() {
+        d.1 = Some(Layout::FlexboxLayout(layout.clone()));
+    }
+}
+
 fn lower_dialog_layout(
     dialog_element: &ElementRc,
     style_metrics: &Option<Rc<Component>>,
@@ -654,6 +752,11 @@ fn lower_dialog_layout(
     }
 }
 
+/// A Shortcut has no geometry, so it doesn't take space in the layout
+fn is_shortcut(elem: &ElementRc) -> bool {
+    matches!(&elem.borrow().base_type, ElementType::Builtin(b) if b.name == "Shortcut")
+}
+
 struct CreateLayoutItemResult {
     item: LayoutItem,
     elem: ElementRc,
@@ -665,6 +768,10 @@ fn create_layout_item(
     item_element: &ElementRc,
     diag: &mut BuildDiagnostics,
 ) -> Option<CreateLayoutItemResult> {
+    if is_shortcut(item_element) {
+        return None;
+    }
+
     let fix_explicit_percent = |prop: &str, item: &ElementRc| {
         if !item.borrow().bindings.get(prop).map_or(false, |b| b.borrow().ty() == Type::Percent) {
             return;
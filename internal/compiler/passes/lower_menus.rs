@@ -0,0 +1,482 @@
+// Copyright © SixtyFPS GmbH <info@slint.dev>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-Royalty-free-1.1 OR LicenseRef-Slint-commercial
+
+//! Pass that lowers the MenuBar and the ContextMenuArea, and the Menu, MenuItem and
+//! MenuSeparator they contain.
+//!
+//! The Menu and MenuItem elements only hold data. They become Empty elements that stay
+//! outside of the popups, and each Menu gets a PopupWindow filled with the components
+//! of the style that read that data. The MenuItem and MenuSeparator created with `if` or
+//! `for` can't be referred to from the popup, so they are moved into it instead.
+//!
+//! Must be done before inlining and many other passes because the lowered code must
+//! be further inlined, just like for the TabWidget.
+
+use crate::diagnostics::BuildDiagnostics;
+use crate::expression_tree::{BuiltinFunction, Expression, NamedReference, Unit};
+use crate::langtype::{BuiltinElement, ElementType, Type};
+use crate::object_tree::*;
+use crate::typeregister::BUILTIN_ENUMS;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+struct MenuTypes {
+    menubar_impl: ElementType,
+    menubar_item_impl: ElementType,
+    menu_frame_impl: ElementType,
+    menu_item_impl: ElementType,
+    menu_separator_impl: ElementType,
+    popup_window: ElementType,
+    shortcut: ElementType,
+    empty: ElementType,
+}
+
+pub async fn lower_menus(
+    component: &Rc<Component>,
+    type_loader: &mut crate::typeloader::TypeLoader,
+    diag: &mut BuildDiagnostics,
+) {
+    let mut has_menus = false;
+    recurse_elem_including_sub_components_no_borrow(component, &(), &mut |elem, _| {
+        has_menus |= menu_type_name(elem).is_some();
+    });
+    if !has_menus {
+        return;
+    }
+
+    // Ignore import errors
+    let mut build_diags_to_ignore = BuildDiagnostics::default();
+    let mut impls = Vec::new();
+    for name in
+        ["MenuBarImpl", "MenuBarItemImpl", "MenuFrameImpl", "MenuItemImpl", "MenuSeparatorImpl"]
+    {
+        let component = type_loader
+            .import_component("std-widgets.slint", name, &mut build_diags_to_ignore)
+            .await
+            .unwrap_or_else(|| panic!("can't load {name} from std-widgets.slint"));
+        impls.push(ElementType::Component(component));
+    }
+    let [menubar_impl, menubar_item_impl, menu_frame_impl, menu_item_impl, menu_separator_impl] =
+        <[_; 5]>::try_from(impls).unwrap();
+    let types = {
+        let registry = type_loader.global_type_registry.borrow();
+        MenuTypes {
+            menubar_impl,
+            menubar_item_impl,
+            menu_frame_impl,
+            menu_item_impl,
+            menu_separator_impl,
+            popup_window: registry.lookup_builtin_element("PopupWindow").unwrap(),
+            shortcut: registry.lookup_builtin_element("Shortcut").unwrap(),
+            empty: registry.empty_type(),
+        }
+    };
+
+    recurse_elem_including_sub_components_no_borrow(component, &(), &mut |elem, _| {
+        match menu_type_name(elem).as_deref() {
+            Some("MenuBar") => process_menubar(elem, &types, diag),
+            Some("ContextMenuArea") => process_context_menu_area(elem, &types, diag),
+            Some(name @ ("Menu" | "MenuItem" | "MenuSeparator")) => {
+                // The ones within a MenuBar or a ContextMenuArea were already turned into Empty
+                let parents = if name == "Menu" { "MenuBar or ContextMenuArea" } else { "Menu" };
+                diag.push_error(
+                    format!("{name} can only be used within a {parents}"),
+                    &*elem.borrow(),
+                );
+            }
+            _ => {}
+        }
+    })
+}
+
+/// Returns the name of the builtin menu element this element is, unless it is the
+/// definition of the component that the style re-exports
+fn menu_type_name(elem: &ElementRc) -> Option<String> {
+    let elem = elem.borrow();
+    if matches!(elem.base_type, ElementType::Builtin(_)) {
+        return None;
+    }
+    let builtin = elem.builtin_type()?;
+    matches!(
+        builtin.name.as_str(),
+        "MenuBar" | "Menu" | "MenuItem" | "MenuSeparator" | "ContextMenuArea"
+    )
+    .then(|| builtin.name.clone())
+}
+
+fn process_menubar(elem: &ElementRc, types: &MenuTypes, diag: &mut BuildDiagnostics) {
+    elem.borrow_mut().base_type = types.menubar_impl.clone();
+    let children = std::mem::take(&mut elem.borrow_mut().children);
+    for (index, menu) in children.into_iter().enumerate() {
+        if !check_menu_child(&menu, "MenuBar", &["Menu"], diag) {
+            continue;
+        }
+        let bar_item = new_element(
+            format!("{}-menu{}", elem.borrow().id, index),
+            types.menubar_item_impl.clone(),
+            elem,
+        );
+        set_binding(&bar_item, "title", property_reference(&menu, "title"));
+        set_binding(&bar_item, "enabled", property_reference(&menu, "enabled"));
+        let popup = lower_menu(
+            &menu,
+            &bar_item,
+            Expression::NumberLiteral(0., Unit::Px),
+            property_reference(&bar_item, "height"),
+            None,
+            None,
+            types,
+            diag,
+        );
+        set_binding(
+            &bar_item,
+            "clicked",
+            popup_function_call(BuiltinFunction::ShowPopupWindow, &popup),
+        );
+        bar_item.borrow_mut().children = vec![menu, popup];
+        elem.borrow_mut().children.push(bar_item);
+    }
+}
+
+fn process_context_menu_area(elem: &ElementRc, types: &MenuTypes, diag: &mut BuildDiagnostics) {
+    let builtin = elem.borrow().builtin_type().unwrap();
+    elem.borrow_mut().base_type = ElementType::Builtin(builtin.clone());
+    apply_builtin_default_values(elem, &builtin);
+
+    let (menus, content): (Vec<_>, Vec<_>) = std::mem::take(&mut elem.borrow_mut().children)
+        .into_iter()
+        .partition(|child| menu_type_name(child).as_deref() == Some("Menu"));
+    elem.borrow_mut().children = content;
+    let mut menus = menus.into_iter();
+    let Some(menu) = menus.next() else {
+        diag.push_error("ContextMenuArea must contain a Menu".into(), &*elem.borrow());
+        return;
+    };
+    for extra_menu in menus {
+        diag.push_error("ContextMenuArea can only contain one Menu".into(), &*extra_menu.borrow());
+    }
+    if !check_menu_child(&menu, "ContextMenuArea", &["Menu"], diag) {
+        return;
+    }
+    if let Some(binding) = elem.borrow().bindings.get("show") {
+        diag.push_error(
+            "The 'show' callback of a ContextMenuArea cannot be set".into(),
+            &binding.borrow().span,
+        );
+        return;
+    }
+
+    // The menu is shown where the right mouse button was pressed
+    for coord in ["menu-x", "menu-y"] {
+        elem.borrow_mut().property_declarations.insert(coord.into(), Type::LogicalLength.into());
+    }
+    let popup = lower_menu(
+        &menu,
+        elem,
+        property_reference(elem, "menu-x"),
+        property_reference(elem, "menu-y"),
+        None,
+        None,
+        types,
+        diag,
+    );
+    let position = Expression::FunctionParameterReference {
+        index: 0,
+        ty: crate::typeregister::logical_point_type(),
+    };
+    let show = Expression::Condition {
+        condition: property_reference(&menu, "enabled").into(),
+        true_expr: Expression::CodeBlock(
+            [("menu-x", "x"), ("menu-y", "y")]
+                .into_iter()
+                .map(|(prop, field)| {
+                    let nr = NamedReference::new(elem, prop);
+                    nr.mark_as_set();
+                    Expression::SelfAssignment {
+                        lhs: Expression::PropertyReference(nr).into(),
+                        rhs: Expression::StructFieldAccess {
+                            base: position.clone().into(),
+                            name: field.into(),
+                        }
+                        .into(),
+                        op: '=',
+                        node: None,
+                    }
+                })
+                .chain(std::iter::once(popup_function_call(
+                    BuiltinFunction::ShowPopupWindow,
+                    &popup,
+                )))
+                .collect(),
+        )
+        .into(),
+        false_expr: Expression::CodeBlock(vec![]).into(),
+    };
+    set_binding(elem, "show", show);
+    elem.borrow_mut().children.splice(0..0, [menu, popup]);
+}
+
+/// Turns the `menu` into an Empty data element, and returns the PopupWindow showing it.
+///
+/// The popup is placed at (`x`, `y`) relatively to `anchor`, which must become its parent.
+/// `root_popup` is the popup of the outermost menu, closed when an item is activated, and
+/// `enabled` is the condition for the menus around this one to be enabled.
+#[allow(clippy::too_many_arguments)]
+fn lower_menu(
+    menu: &ElementRc,
+    anchor: &ElementRc,
+    x: Expression,
+    y: Expression,
+    root_popup: Option<&ElementRc>,
+    enabled: Option<Expression>,
+    types: &MenuTypes,
+    diag: &mut BuildDiagnostics,
+) -> ElementRc {
+    convert_to_data_element(menu, &types.empty);
+    let enabled = and(enabled, property_reference(menu, "enabled"));
+
+    let popup =
+        new_element(format!("{}-popup", menu.borrow().id), types.popup_window.clone(), anchor);
+    set_binding(&popup, "x", x);
+    set_binding(&popup, "y", y);
+    // The items close the menus themselves, so that clicking a separator or a disabled item
+    // doesn't close the menu
+    let close_policy = BUILTIN_ENUMS.with(|e| e.PopupClosePolicy.clone());
+    set_binding(
+        &popup,
+        "close-policy",
+        Expression::EnumerationValue(
+            close_policy.try_value_from_string("close-on-click-outside").unwrap(),
+        ),
+    );
+    let root_popup = root_popup.unwrap_or(&popup).clone();
+
+    let frame =
+        new_element(format!("{}-frame", menu.borrow().id), types.menu_frame_impl.clone(), anchor);
+    let children = menu.borrow().children.clone();
+    for (index, child) in children.into_iter().enumerate() {
+        if !check_menu_child(&child, "Menu", &["Menu", "MenuItem", "MenuSeparator"], diag) {
+            menu.borrow_mut().children.retain(|c| !Rc::ptr_eq(c, &child));
+            continue;
+        }
+        if child.borrow().repeated.is_some() {
+            // The rows can't refer to an element created with `if` or `for`, so it becomes its own row
+            menu.borrow_mut().children.retain(|c| !Rc::ptr_eq(c, &child));
+            lower_repeated_menu_item(&child, &root_popup, types, diag);
+            frame.borrow_mut().children.push(child);
+            continue;
+        }
+        let row_id = format!("{}-item{}", menu.borrow().id, index);
+        let row = match menu_type_name(&child).unwrap().as_str() {
+            "MenuSeparator" => {
+                convert_to_data_element(&child, &types.empty);
+                new_element(row_id, types.menu_separator_impl.clone(), anchor)
+            }
+            "MenuItem" => {
+                convert_to_data_element(&child, &types.empty);
+                let row = new_element(row_id, types.menu_item_impl.clone(), anchor);
+                for prop in ["title", "shortcut", "enabled", "checkable", "checked"] {
+                    set_binding(&row, prop, property_reference(&child, prop));
+                }
+                set_binding(
+                    &row,
+                    "activated",
+                    // Activate before closing, as closing the root popup destroys the submenus
+                    Expression::CodeBlock(vec![
+                        activate_menu_item(&child),
+                        popup_function_call(BuiltinFunction::ClosePopupWindow, &root_popup),
+                    ]),
+                );
+                if child.borrow().is_binding_set("shortcut", true) {
+                    let shortcut = new_element(
+                        format!("{}-shortcut", child.borrow().id),
+                        types.shortcut.clone(),
+                        anchor,
+                    );
+                    set_binding(&shortcut, "keys", property_reference(&child, "shortcut"));
+                    set_binding(
+                        &shortcut,
+                        "enabled",
+                        and(Some(enabled.clone()), property_reference(&child, "enabled")),
+                    );
+                    set_binding(&shortcut, "activated", activate_menu_item(&child));
+                    child.borrow_mut().children.push(shortcut);
+                }
+                row
+            }
+            _ => {
+                let row = new_element(row_id, types.menu_item_impl.clone(), anchor);
+                set_binding(&row, "title", property_reference(&child, "title"));
+                set_binding(&row, "enabled", property_reference(&child, "enabled"));
+                set_binding(&row, "has-submenu", Expression::BoolLiteral(true));
+                let submenu = lower_menu(
+                    &child,
+                    &row,
+                    property_reference(&row, "width"),
+                    Expression::NumberLiteral(0., Unit::Px),
+                    Some(&root_popup),
+                    Some(enabled.clone()),
+                    types,
+                    diag,
+                );
+                set_binding(
+                    &row,
+                    "activated",
+                    popup_function_call(BuiltinFunction::ShowPopupWindow, &submenu),
+                );
+                row.borrow_mut().children.push(submenu);
+                row
+            }
+        };
+        frame.borrow_mut().children.push(row);
+    }
+
+    popup.borrow_mut().children.push(frame);
+    popup
+}
+
+/// Turns a MenuItem or a MenuSeparator created with `if` or `for` into a row of the popup.
+///
+/// Its bindings are kept, and apply to the properties of the same name of the row.
+fn lower_repeated_menu_item(
+    item: &ElementRc,
+    root_popup: &ElementRc,
+    types: &MenuTypes,
+    diag: &mut BuildDiagnostics,
+) {
+    let builtin = item.borrow().builtin_type().unwrap();
+    if builtin.name == "MenuSeparator" {
+        item.borrow_mut().base_type = types.menu_separator_impl.clone();
+        return;
+    }
+    item.borrow_mut().base_type = types.menu_item_impl.clone();
+    apply_builtin_default_values(item, &builtin);
+    if let Some(binding) = item.borrow().bindings.get("shortcut") {
+        diag.push_warning(
+            "The shortcut of a MenuItem created with 'if' or 'for' is shown, but doesn't activate the item".into(),
+            &binding.borrow().span,
+        );
+    }
+    let activated = item.borrow_mut().bindings.remove("activated");
+    // Activate before closing, as closing the root popup destroys the row
+    let mut code = vec![toggle_checked(item)];
+    code.extend(activated.map(|b| b.into_inner().expression));
+    code.push(popup_function_call(BuiltinFunction::ClosePopupWindow, root_popup));
+    set_binding(item, "activated", Expression::CodeBlock(code));
+}
+
+/// Checks that `child` is one of the `allowed` menu elements within `parent_name`
+fn check_menu_child(
+    child: &ElementRc,
+    parent_name: &str,
+    allowed: &[&str],
+    diag: &mut BuildDiagnostics,
+) -> bool {
+    let name = menu_type_name(child);
+    if name.as_ref().map_or(true, |name| !allowed.contains(&name.as_str())) {
+        diag.push_error(
+            format!(
+                "{} is not allowed within {}. Only {} are valid children",
+                child.borrow().base_type,
+                parent_name,
+                allowed.join(" ")
+            ),
+            &*child.borrow(),
+        );
+        return false;
+    }
+    if child.borrow().repeated.is_some() && name.as_deref() == Some("Menu") {
+        diag.push_error(
+            "Menu can't be created with 'if' or 'for'. Only MenuItem and MenuSeparator can".into(),
+            &*child.borrow(),
+        );
+        return false;
+    }
+    true
+}
+
+/// Makes the element an Empty element that declares the properties of its builtin type
+fn convert_to_data_element(elem: &ElementRc, empty_type: &ElementType) {
+    let builtin = elem.borrow().builtin_type().unwrap();
+    elem.borrow_mut().base_type = empty_type.clone();
+    elem.borrow_mut().property_declarations.extend(builtin.properties.iter().map(|(k, v)| {
+        (k.clone(), PropertyDeclaration { property_type: v.ty.clone(), ..Default::default() })
+    }));
+    apply_builtin_default_values(elem, &builtin);
+}
+
+/// The default values of the builtin are set on the component re-exported by the style, so they
+/// must be set again when the element no longer inherits from that component
+fn apply_builtin_default_values(elem: &ElementRc, builtin: &BuiltinElement) {
+    for (prop, info) in &builtin.properties {
+        if let Some(expr) = &info.default_value {
+            elem.borrow_mut()
+                .bindings
+                .entry(prop.clone())
+                .or_insert_with(|| RefCell::new(expr.clone().into()));
+        }
+    }
+}
+
+/// Toggles a checkable item, and invokes its `activated` callback
+fn activate_menu_item(item: &ElementRc) -> Expression {
+    Expression::CodeBlock(vec![
+        toggle_checked(item),
+        Expression::FunctionCall {
+            function: Expression::CallbackReference(NamedReference::new(item, "activated"), None)
+                .into(),
+            arguments: vec![],
+            source_location: None,
+        },
+    ])
+}
+
+/// Toggles the `checked` property of the item if it is checkable
+fn toggle_checked(item: &ElementRc) -> Expression {
+    let checked = NamedReference::new(item, "checked");
+    checked.mark_as_set();
+    let checked = Expression::PropertyReference(checked);
+    Expression::Condition {
+        condition: property_reference(item, "checkable").into(),
+        true_expr: Expression::SelfAssignment {
+            lhs: checked.clone().into(),
+            rhs: Expression::UnaryOp { sub: checked.into(), op: '!' }.into(),
+            op: '=',
+            node: None,
+        }
+        .into(),
+        false_expr: Expression::CodeBlock(vec![]).into(),
+    }
+}
+
+fn new_element(id: String, base_type: ElementType, parent: &ElementRc) -> ElementRc {
+    Element::make_rc(Element {
+        id,
+        base_type,
+        enclosing_component: parent.borrow().enclosing_component.clone(),
+        ..Default::default()
+    })
+}
+
+fn set_binding(elem: &ElementRc, prop: &str, expression: Expression) {
+    elem.borrow_mut().bindings.insert(prop.into(), RefCell::new(expression.into()));
+}
+
+fn property_reference(elem: &ElementRc, prop: &str) -> Expression {
+    Expression::PropertyReference(NamedReference::new(elem, prop))
+}
+
+fn popup_function_call(function: BuiltinFunction, popup: &ElementRc) -> Expression {
+    Expression::FunctionCall {
+        function: Expression::BuiltinFunctionReference(function, None).into(),
+        arguments: vec![Expression::ElementReference(Rc::downgrade(popup))],
+        source_location: None,
+    }
+}
+
+fn and(lhs: Option<Expression>, rhs: Expression) -> Expression {
+    match lhs {
+        Some(lhs) => Expression::BinaryExpression { lhs: lhs.into(), rhs: rhs.into(), op: '&' },
+        None => rhs,
+    }
+}
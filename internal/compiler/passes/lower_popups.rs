@@ -7,7 +7,7 @@
 use crate::expression_tree::{Expression, NamedReference};
 use crate::langtype::{ElementType, Type};
 use crate::object_tree::*;
-use crate::typeregister::TypeRegister;
+use crate::typeregister::{TypeRegister, BUILTIN_ENUMS};
 use std::cell::RefCell;
 use std::rc::Rc;
 
@@ -69,23 +69,42 @@ fn lower_popup_window(
 
     popup_window_element.borrow_mut().base_type = window_type.clone();
 
-    let close_on_click =
-        match popup_window_element.borrow_mut().bindings.remove("close-on-click").map_or_else(
-            || Ok(true),
-            |binding| match binding.borrow().expression {
-                Expression::BoolLiteral(value) => Ok(value),
-                _ => Err(binding.borrow().span.clone()),
-            },
-        ) {
-            Ok(coc) => coc,
-            Err(location) => {
+    let close_policy_enum = BUILTIN_ENUMS.with(|e| e.PopupClosePolicy.clone());
+    let close_on_click = popup_window_element.borrow_mut().bindings.remove("close-on-click");
+    let close_policy = popup_window_element.borrow_mut().bindings.remove("close-policy");
+    let close_policy = match (close_on_click, close_policy) {
+        (Some(_), Some(binding)) => {
+            diag.push_error(
+                "close-policy and close-on-click cannot be set at the same time".into(),
+                &binding.borrow().span,
+            );
+            return;
+        }
+        (Some(binding), None) => match binding.borrow().expression {
+            Expression::BoolLiteral(true) => close_policy_enum.default_value(),
+            Expression::BoolLiteral(false) => {
+                close_policy_enum.try_value_from_string("no-auto-close").unwrap()
+            }
+            _ => {
                 diag.push_error(
                     "The close-on-click property only supports constants at the moment".into(),
-                    &location,
+                    &binding.borrow().span,
+                );
+                return;
+            }
+        },
+        (None, Some(binding)) => match &binding.borrow().expression {
+            Expression::EnumerationValue(value) => value.clone(),
+            _ => {
+                diag.push_error(
+                    "The close-policy property only supports constants at the moment".into(),
+                    &binding.borrow().span,
                 );
                 return;
             }
-        };
+        },
+        (None, None) => close_policy_enum.default_value(),
+    };
 
     let popup_comp = Rc::new(Component {
         root_element: popup_window_element.clone(),
@@ -123,7 +142,7 @@ fn lower_popup_window(
         component: popup_comp,
         x: coord_x,
         y: coord_y,
-        close_on_click,
+        close_policy,
         parent_element: parent_element.clone(),
     });
 }
@@ -11,8 +11,8 @@
 use crate::typeregister::TypeRegister;
 use std::rc::Rc;
 
-/// If any element in `component` declares a binding to `property_name`, then a new
-/// element of type `element_name` is created, injected as a parent to the element and bindings
+/// If any element in `component` declares a binding to `property_name` or to one of the `extra_properties`,
+/// then a new element of type `element_name` is created, injected as a parent to the element and bindings
 /// to property_name and all properties in  extra_properties are mapped.
 /// Default value for the property extra_properties is queried with the `default_value_for_extra_properties`
 pub(crate) fn lower_property_to_element(
@@ -24,14 +24,16 @@ pub(crate) fn lower_property_to_element(
     type_register: &TypeRegister,
     diag: &mut BuildDiagnostics,
 ) {
-    if let Some(b) = component.root_element.borrow().bindings.get(property_name) {
-        diag.push_warning(
-            format!(
-                "The {} property cannot be used on the root element, it will not be applied",
-                property_name
-            ),
-            &*b.borrow(),
-        );
+    for property_name in core::iter::once(property_name).chain(extra_properties.clone()) {
+        if let Some(b) = component.root_element.borrow().bindings.get(property_name) {
+            diag.push_warning(
+                format!(
+                    "The {} property cannot be used on the root element, it will not be applied",
+                    property_name
+                ),
+                &*b.borrow(),
+            );
+        }
     }
 
     object_tree::recurse_elem_including_sub_components_no_borrow(component, &(), &mut |elem, _| {
@@ -46,13 +48,15 @@ pub(crate) fn lower_property_to_element(
         };
 
         let has_property_binding = |e: &ElementRc| {
-            e.borrow().base_type.lookup_property(property_name).property_type != Type::Invalid
-                && (e.borrow().bindings.contains_key(property_name)
-                    || e.borrow()
-                        .property_analysis
-                        .borrow()
-                        .get(property_name)
-                        .map_or(false, |a| a.is_set || a.is_linked))
+            core::iter::once(property_name).chain(extra_properties.clone()).any(|property_name| {
+                e.borrow().base_type.lookup_property(property_name).property_type != Type::Invalid
+                    && (e.borrow().bindings.contains_key(property_name)
+                        || e.borrow()
+                            .property_analysis
+                            .borrow()
+                            .get(property_name)
+                            .map_or(false, |a| a.is_set || a.is_linked))
+            })
         };
 
         for mut child in old_children {
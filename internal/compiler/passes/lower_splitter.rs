@@ -0,0 +1,329 @@
+// Copyright © SixtyFPS GmbH <info@slint.dev>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-Royalty-free-1.1 OR LicenseRef-Slint-commercial
+
+//! Pass that lowers the Splitter.
+//!
+//! The Splitter becomes a SplitterImpl from the style. Its children are the panes, they get
+//! their geometry from the SplitterImpl, and a SplitterHandleImpl is inserted between each
+//! pair of panes.
+//!
+//! Must be done before inlining and many other passes because the lowered code must
+//! be further inlined, just like for the TabWidget.
+
+use crate::diagnostics::BuildDiagnostics;
+use crate::expression_tree::{Expression, MinMaxOp, NamedReference, Unit};
+use crate::langtype::{ElementType, Type};
+use crate::object_tree::*;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+pub async fn lower_splitter(
+    component: &Rc<Component>,
+    type_loader: &mut crate::typeloader::TypeLoader,
+    diag: &mut BuildDiagnostics,
+) {
+    let mut has_splitter = false;
+    recurse_elem_including_sub_components_no_borrow(component, &(), &mut |elem, _| {
+        has_splitter |= is_splitter(elem);
+    });
+    if !has_splitter {
+        return;
+    }
+
+    // Ignore import errors
+    let mut build_diags_to_ignore = BuildDiagnostics::default();
+    let splitter_impl = type_loader
+        .import_component("std-widgets.slint", "SplitterImpl", &mut build_diags_to_ignore)
+        .await
+        .expect("can't load SplitterImpl from std-widgets.slint");
+    let handle_impl = type_loader
+        .import_component("std-widgets.slint", "SplitterHandleImpl", &mut build_diags_to_ignore)
+        .await
+        .expect("can't load SplitterHandleImpl from std-widgets.slint");
+
+    recurse_elem_including_sub_components_no_borrow(component, &(), &mut |elem, _| {
+        if is_splitter(elem) {
+            process_splitter(
+                elem,
+                ElementType::Component(splitter_impl.clone()),
+                ElementType::Component(handle_impl.clone()),
+                diag,
+            );
+        }
+    })
+}
+
+/// Returns true if the element is a Splitter, but not the Splitter re-exported by the style
+fn is_splitter(elem: &ElementRc) -> bool {
+    let elem = elem.borrow();
+    !matches!(elem.base_type, ElementType::Builtin(_))
+        && matches!(elem.builtin_type(), Some(b) if b.name == "Splitter")
+}
+
+fn process_splitter(
+    elem: &ElementRc,
+    splitter_impl: ElementType,
+    handle_impl: ElementType,
+    diag: &mut BuildDiagnostics,
+) {
+    elem.borrow_mut().base_type = splitter_impl;
+    let mut panes = Vec::new();
+    for child in std::mem::take(&mut elem.borrow_mut().children) {
+        if child.borrow().repeated.is_some() {
+            diag.push_error(
+                "dynamic panes ('if' or 'for') are currently not supported".into(),
+                &*child.borrow(),
+            );
+            continue;
+        }
+        panes.push(child);
+    }
+    let pane_count = panes.len();
+
+    let splitter_call = |function: &str, arguments: Vec<Expression>| Expression::FunctionCall {
+        function: Expression::FunctionReference(NamedReference::new(elem, function), None).into(),
+        arguments,
+        source_location: None,
+    };
+    let index_literal = |index: usize| Expression::NumberLiteral(index as _, Unit::None);
+
+    for (index, pane) in panes.iter().enumerate() {
+        for prop in ["offset", "position", "size"] {
+            elem.borrow_mut()
+                .property_declarations
+                .insert(format!("pane-{index}-{prop}"), Type::LogicalLength.into());
+        }
+
+        // The panes before this one take `offset`, the handles are added by the position
+        let offset = if index == 0 {
+            Expression::NumberLiteral(0., Unit::Px)
+        } else {
+            Expression::BinaryExpression {
+                lhs: pane_property(elem, index - 1, "offset").into(),
+                rhs: pane_property(elem, index - 1, "size").into(),
+                op: '+',
+            }
+        };
+        set_binding(elem, &format!("pane-{index}-offset"), offset);
+        set_binding(
+            elem,
+            &format!("pane-{index}-position"),
+            splitter_call(
+                "pane-position",
+                vec![index_literal(index), pane_property(elem, index, "offset")],
+            ),
+        );
+        // The sum of the limits of the panes after this one
+        let remaining = |function: &str| {
+            (index + 1..pane_count)
+                .map(|next| splitter_call(function, vec![index_literal(next)]))
+                .reduce(|lhs, rhs| Expression::BinaryExpression {
+                    lhs: lhs.into(),
+                    rhs: rhs.into(),
+                    op: '+',
+                })
+                .unwrap_or(Expression::NumberLiteral(0., Unit::Px))
+        };
+        set_binding(
+            elem,
+            &format!("pane-{index}-size"),
+            splitter_call(
+                "pane-size",
+                vec![
+                    index_literal(index),
+                    pane_property(elem, index, "offset"),
+                    remaining("effective-min-size"),
+                    remaining("effective-max-size"),
+                ],
+            ),
+        );
+
+        let position = pane_property(elem, index, "position");
+        let size = pane_property(elem, index, "size");
+        let zero = Expression::NumberLiteral(0., Unit::Px);
+        set_pane_geometry(pane, "x", along(elem, position.clone(), zero.clone()), diag);
+        set_pane_geometry(pane, "y", along(elem, zero, position), diag);
+        set_pane_geometry(
+            pane,
+            "width",
+            along(elem, size.clone(), property_reference(elem, "width")),
+            diag,
+        );
+        set_pane_geometry(
+            pane,
+            "height",
+            along(elem, property_reference(elem, "height"), size.clone()),
+            diag,
+        );
+        // A collapsed pane is hidden, unless its visibility is already bound
+        if !pane.borrow().is_binding_set("visible", true) {
+            set_binding(
+                pane,
+                "visible",
+                Expression::BinaryExpression {
+                    lhs: size.into(),
+                    rhs: Expression::NumberLiteral(0., Unit::Px).into(),
+                    op: '>',
+                },
+            );
+        }
+    }
+
+    let pane_lengths = |f: &dyn Fn(&ElementRc) -> Expression| Expression::Array {
+        element_ty: Type::LogicalLength,
+        values: panes.iter().map(f).collect(),
+    };
+    set_binding(elem, "pane-count", index_literal(pane_count));
+    set_binding(
+        elem,
+        "min-sizes",
+        pane_lengths(&|pane| {
+            along(
+                elem,
+                property_reference(pane, "min-width"),
+                property_reference(pane, "min-height"),
+            )
+        }),
+    );
+    set_binding(
+        elem,
+        "max-sizes",
+        pane_lengths(&|pane| {
+            along(
+                elem,
+                property_reference(pane, "max-width"),
+                property_reference(pane, "max-height"),
+            )
+        }),
+    );
+    let pane_sizes = Expression::Array {
+        element_ty: Type::LogicalLength,
+        values: (0..pane_count).map(|index| pane_property(elem, index, "size")).collect(),
+    };
+    set_binding(elem, "pane-sizes", pane_sizes.clone());
+    if let Some(expr) = (0..pane_count)
+        .map(|index| splitter_call("effective-min-size", vec![index_literal(index)]))
+        .reduce(|lhs, rhs| Expression::BinaryExpression {
+            lhs: lhs.into(),
+            rhs: rhs.into(),
+            op: '+',
+        })
+    {
+        set_binding(elem, "content-min-size", expr);
+    }
+    if let Some(expr) = panes
+        .iter()
+        .map(|pane| {
+            along(
+                elem,
+                property_reference(pane, "min-height"),
+                property_reference(pane, "min-width"),
+            )
+        })
+        .reduce(|lhs, rhs| crate::builtin_macros::min_max_expression(lhs, rhs, MinMaxOp::Max))
+    {
+        set_binding(elem, "cross-min-size", expr);
+    }
+
+    let sizes = NamedReference::new(elem, "sizes");
+    sizes.mark_as_set();
+    set_binding(
+        elem,
+        "store-sizes",
+        Expression::SelfAssignment {
+            lhs: Expression::PropertyReference(sizes).into(),
+            rhs: pane_sizes.into(),
+            op: '=',
+            node: None,
+        },
+    );
+
+    let mut children = Vec::with_capacity(pane_count * 2);
+    for (index, pane) in panes.into_iter().enumerate() {
+        if index > 0 {
+            children.push(new_handle(elem, index - 1, handle_impl.clone()));
+        }
+        children.push(pane);
+    }
+    elem.borrow_mut().children = children;
+}
+
+/// Creates the handle between the pane at `index` and the next one
+fn new_handle(elem: &ElementRc, index: usize, handle_impl: ElementType) -> ElementRc {
+    let handle = Element::make_rc(Element {
+        id: format!("{}-handle{}", elem.borrow().id, index),
+        base_type: handle_impl,
+        enclosing_component: elem.borrow().enclosing_component.clone(),
+        ..Default::default()
+    });
+    let position = Expression::BinaryExpression {
+        lhs: pane_property(elem, index, "position").into(),
+        rhs: pane_property(elem, index, "size").into(),
+        op: '+',
+    };
+    let zero = Expression::NumberLiteral(0., Unit::Px);
+    let handle_size = property_reference(elem, "handle-size");
+    set_binding(&handle, "horizontal", property_reference(elem, "horizontal"));
+    set_binding(&handle, "x", along(elem, position.clone(), zero.clone()));
+    set_binding(&handle, "y", along(elem, zero, position));
+    set_binding(
+        &handle,
+        "width",
+        along(elem, handle_size.clone(), property_reference(elem, "width")),
+    );
+    set_binding(&handle, "height", along(elem, property_reference(elem, "height"), handle_size));
+    set_binding(
+        &handle,
+        "moved",
+        Expression::FunctionCall {
+            function: Expression::FunctionReference(NamedReference::new(elem, "move-handle"), None)
+                .into(),
+            arguments: vec![
+                Expression::NumberLiteral(index as _, Unit::None),
+                Expression::FunctionParameterReference { index: 0, ty: Type::LogicalLength },
+            ],
+            source_location: None,
+        },
+    );
+    handle
+}
+
+fn set_pane_geometry(
+    pane: &ElementRc,
+    prop: &str,
+    expression: Expression,
+    diag: &mut BuildDiagnostics,
+) {
+    let old = pane.borrow_mut().bindings.insert(prop.into(), RefCell::new(expression.into()));
+    if let Some(old) = old.map(RefCell::into_inner) {
+        diag.push_error(
+            format!("The property '{}' cannot be set for panes inside a Splitter", prop),
+            &old,
+        );
+    }
+}
+
+/// Returns `horizontal_value` if the splitter is horizontal, `vertical_value` otherwise
+fn along(
+    splitter: &ElementRc,
+    horizontal_value: Expression,
+    vertical_value: Expression,
+) -> Expression {
+    Expression::Condition {
+        condition: property_reference(splitter, "horizontal").into(),
+        true_expr: horizontal_value.into(),
+        false_expr: vertical_value.into(),
+    }
+}
+
+fn set_binding(elem: &ElementRc, prop: &str, expression: Expression) {
+    elem.borrow_mut().bindings.insert(prop.into(), RefCell::new(expression.into()));
+}
+
+fn property_reference(elem: &ElementRc, prop: &str) -> Expression {
+    Expression::PropertyReference(NamedReference::new(elem, prop))
+}
+
+fn pane_property(splitter: &ElementRc, index: usize, prop: &str) -> Expression {
+    property_reference(splitter, &format!("pane-{index}-{prop}"))
+}
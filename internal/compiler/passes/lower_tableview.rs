@@ -0,0 +1,208 @@
+// Copyright © SixtyFPS GmbH <info@slint.dev>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-Royalty-free-1.1 OR LicenseRef-Slint-commercial
+
+//! Pass that moves the children of a StandardTableView into the cells of its `custom` columns.
+//!
+//! The StandardTableView is inlined, and its children are moved into the TableViewCustomCell
+//! of the style, which is repeated for each cell. Their `row-index`, `column-index` and `item` properties
+//! are bound to the ones of the cell.
+//!
+//! Must be done right before inlining, once the other passes have processed the style.
+
+use crate::diagnostics::BuildDiagnostics;
+use crate::expression_tree::{Expression, NamedReference};
+use crate::langtype::ElementType;
+use crate::object_tree::*;
+use by_address::ByAddress;
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::rc::Rc;
+
+pub async fn lower_tableview(
+    component: &Rc<Component>,
+    type_loader: &mut crate::typeloader::TypeLoader,
+    diag: &mut BuildDiagnostics,
+) {
+    let mut has_table_with_children = false;
+    recurse_elem_including_sub_components_no_borrow(component, &(), &mut |elem, _| {
+        let elem = elem.borrow();
+        has_table_with_children |= !elem.children.is_empty()
+            && matches!(&elem.base_type, ElementType::Component(c) if c.id == "StandardTableView");
+    });
+    if !has_table_with_children {
+        return;
+    }
+
+    // Ignore import errors
+    let mut build_diags_to_ignore = BuildDiagnostics::default();
+    let table_view = type_loader
+        .import_component("std-widgets.slint", "StandardTableView", &mut build_diags_to_ignore)
+        .await
+        .expect("can't load StandardTableView from std-widgets.slint");
+    let custom_cell = type_loader
+        .import_component("std-widgets.slint", "TableViewCustomCell", &mut build_diags_to_ignore)
+        .await
+        .expect("can't load TableViewCustomCell from std-widgets.slint");
+
+    let mut tables = Vec::new();
+    recurse_elem_including_sub_components_no_borrow(component, &(), &mut |elem, _| {
+        let e = elem.borrow();
+        if !e.children.is_empty()
+            && matches!(&e.base_type, ElementType::Component(c) if Rc::ptr_eq(c, &table_view))
+        {
+            tables.push(elem.clone());
+        }
+    });
+    for table in tables {
+        process_tableview(&table, &table_view, &custom_cell, component, diag);
+    }
+}
+
+fn process_tableview(
+    elem: &ElementRc,
+    table_view: &Rc<Component>,
+    custom_cell: &Rc<Component>,
+    component: &Rc<Component>,
+    diag: &mut BuildDiagnostics,
+) {
+    let mut cell_children = Vec::new();
+    for child in std::mem::take(&mut elem.borrow_mut().children) {
+        if child.borrow().repeated.is_some() {
+            diag.push_error(
+                "The children of a StandardTableView can't be created with 'if' or 'for'".into(),
+                &*child.borrow(),
+            );
+            continue;
+        }
+        cell_children.push(child);
+    }
+
+    let enclosing_component = elem.borrow().enclosing_component.upgrade().unwrap();
+    super::inlining::inline_element(elem, table_view, &enclosing_component);
+
+    let cell = find_custom_cell(elem, custom_cell)
+        .expect("the StandardTableView of the style must have a TableViewCustomCell");
+
+    let mut moved = HashSet::new();
+    for child in &cell_children {
+        bind_cell_property(child, &cell, "row-index", diag);
+        bind_cell_property(child, &cell, "column-index", diag);
+        bind_cell_property(child, &cell, "item", diag);
+        recurse_elem(child, &(), &mut |e, _| {
+            e.borrow_mut().enclosing_component = cell.borrow().enclosing_component.clone();
+        });
+        collect_elements(child, &mut moved);
+    }
+    cell.borrow_mut().children.extend(cell_children);
+
+    // The elements now exist once per cell, so nothing outside of the cell can refer to them
+    let error = "The children of a StandardTableView are repeated in its custom cells and can't be accessed from outside";
+    let refers_to_cell = |expr: &mut Expression| {
+        let mut result = false;
+        visit_named_references_in_expression(expr, &mut |nr| {
+            result |= moved.contains(&ByAddress(nr.element()));
+        });
+        expr.visit_recursive(&mut |expr| {
+            if let Expression::ElementReference(r) = expr {
+                result |= r.upgrade().is_some_and(|r| moved.contains(&ByAddress(r)));
+            }
+        });
+        result
+    };
+    recurse_elem_including_sub_components_no_borrow(component, &(), &mut |e, _| {
+        if moved.contains(&ByAddress(e.clone())) {
+            return;
+        }
+        let mut reported = false;
+        for binding in e.borrow().bindings.values() {
+            let mut binding = binding.borrow_mut();
+            if refers_to_cell(&mut binding.expression)
+                || binding
+                    .two_way_bindings
+                    .iter()
+                    .any(|nr| moved.contains(&ByAddress(nr.element())))
+            {
+                diag.push_error(error.into(), &*binding);
+                reported = true;
+            }
+        }
+        if reported {
+            return;
+        }
+        // States, transitions and change callbacks
+        let mut found = false;
+        visit_all_named_references_in_element(e, |nr| {
+            found |= moved.contains(&ByAddress(nr.element()));
+        });
+        visit_element_expressions(e, |expr, _, _| found |= refers_to_cell(expr));
+        if found {
+            diag.push_error(error.into(), &*e.borrow());
+        }
+    });
+}
+
+/// Collects the element, its children and the elements of the repeated sub-components in them
+fn collect_elements(elem: &ElementRc, elements: &mut HashSet<ByAddress<ElementRc>>) {
+    elements.insert(ByAddress(elem.clone()));
+    if let ElementType::Component(c) = &elem.borrow().base_type {
+        if c.parent_element.upgrade().is_some() {
+            collect_elements(&c.root_element, elements);
+        }
+    }
+    for child in &elem.borrow().children {
+        collect_elements(child, elements);
+    }
+}
+
+/// Finds the TableViewCustomCell in the inlined StandardTableView, which is inside the repeated rows and cells
+fn find_custom_cell(elem: &ElementRc, custom_cell: &Rc<Component>) -> Option<ElementRc> {
+    let base = elem.borrow().base_type.clone();
+    if let ElementType::Component(c) = base {
+        if Rc::ptr_eq(&c, custom_cell) {
+            return Some(elem.clone());
+        }
+        if c.parent_element.upgrade().is_some() {
+            if let Some(cell) = find_custom_cell(&c.root_element, custom_cell) {
+                return Some(cell);
+            }
+        }
+    }
+    let children = elem.borrow().children.clone();
+    children.iter().find_map(|child| find_custom_cell(child, custom_cell))
+}
+
+/// Binds the property of the child to the one of the cell, if the child declares it
+fn bind_cell_property(
+    child: &ElementRc,
+    cell: &ElementRc,
+    name: &str,
+    diag: &mut BuildDiagnostics,
+) {
+    let lookup = child.borrow().lookup_property(name);
+    if !lookup.is_valid() {
+        return;
+    }
+    let expected_type = cell.borrow().lookup_property(name).property_type;
+    let can_be_set = match lookup.property_visibility {
+        PropertyVisibility::Input | PropertyVisibility::InOut => true,
+        PropertyVisibility::Private => lookup.is_local_to_component,
+        _ => false,
+    };
+    if lookup.property_type != expected_type || !can_be_set {
+        diag.push_error(
+            format!("The '{name}' property of a child of a StandardTableView must be an input property of type {expected_type}"),
+            &*child.borrow(),
+        );
+        return;
+    }
+    let old = child.borrow_mut().bindings.insert(
+        name.into(),
+        RefCell::new(Expression::PropertyReference(NamedReference::new(cell, name)).into()),
+    );
+    if let Some(old) = old {
+        diag.push_error(
+            format!("The '{name}' property of a child of a StandardTableView is set by the table"),
+            &old.into_inner(),
+        );
+    }
+}
@@ -0,0 +1,83 @@
+// Copyright © SixtyFPS GmbH <info@slint.dev>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-Royalty-free-1.1 OR LicenseRef-Slint-commercial
+
+//! Pass that removes the Timer elements from the tree and registers them in the component
+
+use crate::diagnostics::BuildDiagnostics;
+use crate::expression_tree::{BuiltinFunction, Expression, NamedReference};
+use crate::langtype::ElementType;
+use crate::object_tree::*;
+use std::rc::Rc;
+
+pub fn lower_timers(component: &Rc<Component>, diag: &mut BuildDiagnostics) {
+    recurse_elem_including_sub_components_no_borrow(
+        component,
+        &None,
+        &mut |elem, parent_element: &Option<ElementRc>| {
+            let is_timer = matches!(&elem.borrow().base_type, ElementType::Builtin(base_type) if base_type.name == "Timer");
+            if is_timer {
+                lower_timer(elem, parent_element.as_ref(), diag);
+            }
+            Some(elem.clone())
+        },
+    )
+}
+
+fn lower_timer(
+    timer_element: &ElementRc,
+    parent_element: Option<&ElementRc>,
+    diag: &mut BuildDiagnostics,
+) {
+    let Some(parent_element) = parent_element else {
+        diag.push_error("Timer cannot be the top level".into(), &*timer_element.borrow());
+        return;
+    };
+
+    let parent_component = timer_element.borrow().enclosing_component.upgrade().unwrap();
+    if Rc::ptr_eq(&parent_component.root_element, timer_element) {
+        diag.push_error(
+            "Timer cannot be directly repeated or conditional".into(),
+            &*timer_element.borrow(),
+        );
+        return;
+    }
+
+    if !timer_element.borrow().children.is_empty() {
+        diag.push_error("Timer cannot have children".into(), &*timer_element.borrow());
+        return;
+    }
+
+    if !timer_element.borrow().is_binding_set("interval", true) {
+        diag.push_error(
+            "Timer must have a binding set for its 'interval' property".into(),
+            &*timer_element.borrow(),
+        );
+        return;
+    }
+
+    // Remove the timer_element from its parent. Its properties may still be used so it
+    // becomes an optimized element
+    parent_element.borrow_mut().children.retain(|child| !Rc::ptr_eq(child, timer_element));
+    parent_component.optimized_elements.borrow_mut().push(timer_element.clone());
+
+    let running = NamedReference::new(timer_element, "running");
+    let interval = NamedReference::new(timer_element, "interval");
+    parent_component.timers.borrow_mut().push(Timer {
+        interval: interval.clone(),
+        running: running.clone(),
+        triggered: NamedReference::new(timer_element, "triggered"),
+    });
+
+    // Start, stop or restart the timers when one of these properties changes
+    let update_timers = Expression::FunctionCall {
+        function: Box::new(Expression::BuiltinFunctionReference(
+            BuiltinFunction::UpdateTimers,
+            None,
+        )),
+        arguments: vec![],
+        source_location: None,
+    };
+    let mut parent = parent_element.borrow_mut();
+    parent.change_callbacks.push((running, update_timers.clone()));
+    parent.change_callbacks.push((interval, update_timers));
+}
@@ -15,6 +15,8 @@ pub fn purity_check(doc: &crate::object_tree::Document, diag: &mut BuildDiagnost
                     true => crate::diagnostics::DiagnosticLevel::Warning,
                     false => crate::diagnostics::DiagnosticLevel::Error,
                 };
+                // The change callbacks are allowed to have side effects
+                let change_callbacks = std::mem::take(&mut elem.borrow_mut().change_callbacks);
                 crate::object_tree::visit_element_expressions(elem, |expr, name, _| {
                     if let Some(name) = name {
                         let lookup = elem.borrow().lookup_property(name);
@@ -27,7 +29,8 @@ pub fn purity_check(doc: &crate::object_tree::Document, diag: &mut BuildDiagnost
                         // model expression must be pure
                         ensure_pure(expr, Some(diag), level);
                     };
-                })
+                });
+                elem.borrow_mut().change_callbacks = change_callbacks;
             },
         )
     }
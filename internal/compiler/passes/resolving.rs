@@ -50,6 +50,11 @@ fn resolve_expression(
                 //FIXME: proper callback support (node is a codeblock)
                 Expression::from_callback_connection(node.clone().into(), &mut lookup_ctx)
             }
+            SyntaxKind::PropertyChangedCallback => Expression::from_codeblock_node(
+                syntax_nodes::PropertyChangedCallback::from(node.clone()).CodeBlock(),
+                &mut lookup_ctx,
+            )
+            .maybe_convert_to(Type::Void, node, diag),
             SyntaxKind::Function => Expression::from_function(node.clone().into(), &mut lookup_ctx),
             SyntaxKind::Expression => {
                 //FIXME again: this happen for non-binding expression (i.e: model)
@@ -1175,13 +1180,27 @@ fn from_object_literal_node(
         node: syntax_nodes::ObjectLiteral,
         ctx: &mut LookupCtx,
     ) -> Expression {
+        // Use the type of the fields as the expected type, for the lookup of enums or easing curves.
+        // (Not in functions, where the property type is needed to look up the arguments)
+        let fields = match &ctx.property_type {
+            Type::Struct { fields, .. } => fields.clone(),
+            _ => Default::default(),
+        };
         let values: HashMap<String, Expression> = node
             .ObjectMember()
             .map(|n| {
-                (
-                    identifier_text(&n).unwrap_or_default(),
-                    Expression::from_expression_node(n.Expression(), ctx),
-                )
+                let name = identifier_text(&n).unwrap_or_default();
+                let e = match fields.get(&name) {
+                    Some(ty) => {
+                        let old_property_type =
+                            std::mem::replace(&mut ctx.property_type, ty.clone());
+                        let e = Expression::from_expression_node(n.Expression(), ctx);
+                        ctx.property_type = old_property_type;
+                        e
+                    }
+                    None => Expression::from_expression_node(n.Expression(), ctx),
+                };
+                (name, e)
             })
             .collect();
         let ty = Type::Struct {
@@ -1194,8 +1213,19 @@ fn from_object_literal_node(
     }
 
     fn from_array_node(node: syntax_nodes::Array, ctx: &mut LookupCtx) -> Expression {
+        // Use the type of the elements as the expected type, like for the fields of an object literal
+        let old_property_type = match &ctx.property_type {
+            Type::Array(ty) => {
+                let ty = (**ty).clone();
+                Some(std::mem::replace(&mut ctx.property_type, ty))
+            }
+            _ => None,
+        };
         let mut values: Vec<Expression> =
             node.Expression().map(|e| Expression::from_expression_node(e, ctx)).collect();
+        if let Some(old_property_type) = old_property_type {
+            ctx.property_type = old_property_type;
+        }
 
         let element_ty = if values.is_empty() {
             Type::Void
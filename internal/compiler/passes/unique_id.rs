@@ -22,17 +22,28 @@ pub fn assign_unique_id(doc: &Document) {
 
 fn assign_unique_id_in_component(component: &Rc<Component>, count: &mut u32) {
     recurse_elem_including_sub_components(component, &(), &mut |elem, _| {
-        *count += 1;
-        let mut elem_mut = elem.borrow_mut();
-        let old_id = if !elem_mut.id.is_empty() {
-            elem_mut.id.clone()
-        } else {
-            elem_mut.base_type.to_string().to_ascii_lowercase()
-        };
-        elem_mut.id = format!("{}-{}", old_id, count);
+        assign_unique_id_in_element(elem, count);
+        // The optimized elements (such as timers) may still have properties in use
+        let enclosing = elem.borrow().enclosing_component.upgrade().unwrap();
+        if Rc::ptr_eq(&enclosing.root_element, elem) {
+            for e in enclosing.optimized_elements.borrow().iter() {
+                recurse_elem(e, &(), &mut |e, _| assign_unique_id_in_element(e, count));
+            }
+        }
     });
 }
 
+fn assign_unique_id_in_element(elem: &ElementRc, count: &mut u32) {
+    *count += 1;
+    let mut elem_mut = elem.borrow_mut();
+    let old_id = if !elem_mut.id.is_empty() {
+        elem_mut.id.clone()
+    } else {
+        elem_mut.base_type.to_string().to_ascii_lowercase()
+    };
+    elem_mut.id = format!("{}-{}", old_id, count);
+}
+
 /// Give globals unique name
 fn rename_globals(component: &Rc<Component>, mut count: u32) {
     for g in &component.used_types.borrow().globals {
@@ -147,8 +147,17 @@ fn compare_styles(base: &Style, mut other: Style, style_name: &str) -> bool {
     let mut ok = true;
     for (compo_name, c1) in base.components.iter() {
         // These more or less internals component can have different properties
-        let ignore_extra =
-            matches!(compo_name.as_str(), "TabImpl" | "TabWidgetImpl" | "StyleMetrics");
+        let ignore_extra = matches!(
+            compo_name.as_str(),
+            "TabImpl"
+                | "TabWidgetImpl"
+                | "StyleMetrics"
+                | "MenuBarImpl"
+                | "MenuBarItemImpl"
+                | "MenuFrameImpl"
+                | "MenuItemImpl"
+                | "MenuSeparatorImpl"
+        );
         if let Some(mut c2) = other.components.remove(compo_name) {
             for (prop_name, p1) in c1.properties.iter() {
                 if let Some(p2) = c2.properties.remove(prop_name) {
@@ -92,24 +92,37 @@ fn fill_register(&self, register: &mut TypeRegister) {
     ("drop-shadow-color", Type::Color),
 ];
 
-pub const RESERVED_ROTATION_PROPERTIES: &[(&str, Type)] = &[
+pub const RESERVED_TRANSFORM_PROPERTIES: &[(&str, Type)] = &[
     ("rotation-angle", Type::Angle),
     ("rotation-origin-x", Type::LogicalLength),
     ("rotation-origin-y", Type::LogicalLength),
+    ("scale-x", Type::Float32),
+    ("scale-y", Type::Float32),
 ];
 
-pub const RESERVED_ACCESSIBILITY_PROPERTIES: &[(&str, Type)] = &[
-    //("accessible-role", ...)
-    ("accessible-checkable", Type::Bool),
-    ("accessible-checked", Type::Bool),
-    ("accessible-delegate-focus", Type::Int32),
-    ("accessible-description", Type::String),
-    ("accessible-label", Type::String),
-    ("accessible-value", Type::String),
-    ("accessible-value-maximum", Type::Float32),
-    ("accessible-value-minimum", Type::Float32),
-    ("accessible-value-step", Type::Float32),
-];
+pub fn reserved_accessibility_properties() -> impl Iterator<Item = (&'static str, Type)> {
+    let action = || Type::Callback { return_type: None, args: vec![] };
+    [
+        //("accessible-role", ...)
+        ("accessible-checkable", Type::Bool),
+        ("accessible-checked", Type::Bool),
+        ("accessible-delegate-focus", Type::Int32),
+        ("accessible-description", Type::String),
+        ("accessible-label", Type::String),
+        ("accessible-value", Type::String),
+        ("accessible-value-maximum", Type::Float32),
+        ("accessible-value-minimum", Type::Float32),
+        ("accessible-value-step", Type::Float32),
+        ("accessible-action-default", action()),
+        ("accessible-action-increment", action()),
+        ("accessible-action-decrement", action()),
+        (
+            "accessible-action-set-value",
+            Type::Callback { return_type: None, args: vec![Type::String] },
+        ),
+    ]
+    .into_iter()
+}
 
 /// list of reserved property injected in every item
 pub fn reserved_properties() -> impl Iterator<Item = (&'static str, Type, PropertyVisibility)> {
@@ -118,9 +131,10 @@ pub fn reserved_properties() -> impl Iterator<Item = (&'static str, Type, Proper
         .chain(RESERVED_LAYOUT_PROPERTIES.iter())
         .chain(RESERVED_OTHER_PROPERTIES.iter())
         .chain(RESERVED_DROP_SHADOW_PROPERTIES.iter())
-        .chain(RESERVED_ROTATION_PROPERTIES.iter())
-        .chain(RESERVED_ACCESSIBILITY_PROPERTIES.iter())
-        .map(|(k, v)| (*k, v.clone(), PropertyVisibility::InOut))
+        .chain(RESERVED_TRANSFORM_PROPERTIES.iter())
+        .map(|(k, v)| (*k, v.clone()))
+        .chain(reserved_accessibility_properties())
+        .map(|(k, v)| (k, v, PropertyVisibility::InOut))
         .chain(
             RESERVED_GRIDLAYOUT_PROPERTIES
                 .iter()
@@ -303,6 +317,7 @@ struct $Name:ident {
             )* };
         }
         i_slint_common::for_each_builtin_structs!(register_builtin_structs);
+        register.insert_type_with_name(animation_keyframe_type(), "AnimationKeyframe".into());
 
         crate::load_builtins::load_builtins(&mut register);
 
@@ -330,6 +345,8 @@ struct $Name:ident {
 
                 popup.properties.get_mut("close-on-click").unwrap().property_visibility =
                     PropertyVisibility::Constexpr;
+                popup.properties.get_mut("close-policy").unwrap().property_visibility =
+                    PropertyVisibility::Constexpr;
             }
 
             _ => unreachable!(),
@@ -491,6 +508,21 @@ pub fn empty_type(&self) -> ElementType {
     }
 }
 
+/// The type of the elements of the `keyframes` of a `PropertyAnimation`
+pub fn animation_keyframe_type() -> Type {
+    Type::Struct {
+        fields: IntoIterator::into_iter([
+            ("position".to_owned(), Type::Float32),
+            ("progress".to_owned(), Type::Float32),
+            ("easing".to_owned(), Type::Easing),
+        ])
+        .collect(),
+        name: Some("slint::private_api::AnimationKeyframe".into()),
+        node: None,
+        rust_attributes: None,
+    }
+}
+
 pub fn logical_point_type() -> Type {
     Type::Struct {
         fields: IntoIterator::into_iter([
@@ -7,6 +7,7 @@
 use alloc::vec::Vec;
 
 use crate::items::ItemRc;
+use crate::SharedString;
 
 // The property names of the accessible-properties
 #[repr(u32)]
@@ -24,6 +25,54 @@ pub enum AccessibleStringProperty {
     ValueStep,
 }
 
+/// The argument of an accessibility action.
+#[repr(u32)]
+#[derive(PartialEq, Clone, Debug)]
+pub enum AccessibilityAction {
+    Default,
+    Decrement,
+    Increment,
+    SetValue(SharedString),
+}
+
+/// The set of accessibility actions that an item supports.
+///
+/// This is a bit set of the `SupportedAccessibilityAction::*` constants.
+#[repr(C)]
+#[derive(PartialEq, Eq, Copy, Clone, Default, Debug)]
+pub struct SupportedAccessibilityAction(pub u32);
+
+#[allow(non_upper_case_globals)]
+impl SupportedAccessibilityAction {
+    pub const Default: Self = Self(1 << 0);
+    pub const Decrement: Self = Self(1 << 1);
+    pub const Increment: Self = Self(1 << 2);
+    pub const SetValue: Self = Self(1 << 3);
+
+    /// Returns true if all the actions of `other` are also in `self`
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Returns true if no action is supported
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+}
+
+impl core::ops::BitOr for SupportedAccessibilityAction {
+    type Output = Self;
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl core::ops::BitOrAssign for SupportedAccessibilityAction {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0;
+    }
+}
+
 /// Find accessible descendents of `root_item`.
 ///
 /// This will recurse through all children of `root_item`, but will not recurse
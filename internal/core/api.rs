@@ -516,6 +516,21 @@ pub fn dispatch_event(&self, event: crate::platform::WindowEvent) {
             crate::platform::WindowEvent::PointerExited => {
                 self.0.process_mouse_input(MouseEvent::Exit)
             }
+            crate::platform::WindowEvent::DragMoved { position, mime_type, data } => {
+                self.0.start_drag(crate::items::DropEvent { mime_type, data });
+                self.0.process_mouse_input(MouseEvent::DragMove {
+                    position: position.to_euclid().cast(),
+                });
+            }
+            crate::platform::WindowEvent::DragExited => {
+                self.0.process_mouse_input(MouseEvent::Exit)
+            }
+            crate::platform::WindowEvent::Dropped { position, mime_type, data } => {
+                self.0.start_drag(crate::items::DropEvent { mime_type, data });
+                self.0.process_mouse_input(MouseEvent::Drop {
+                    position: position.to_euclid().cast(),
+                });
+            }
 
             crate::platform::WindowEvent::KeyPressed { text } => {
                 self.0.process_key_input(crate::input::KeyEvent {
@@ -0,0 +1,66 @@
+// Copyright © SixtyFPS GmbH <info@slint.dev>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-Royalty-free-1.1 OR LicenseRef-Slint-commercial
+
+//! Locale dependent information used by the date and time widgets.
+
+/// Returns the first day of the week in the current locale, where 0 is Sunday, 1 is Monday, and so on.
+///
+/// The locale is taken from the `LC_ALL`, `LC_TIME` or `LANG` environment variables.
+/// When no region can be found there, the week starts on Monday, as in ISO 8601.
+pub fn first_day_of_week() -> i32 {
+    #[cfg(feature = "std")]
+    {
+        let locale = ["LC_ALL", "LC_TIME", "LANG"]
+            .iter()
+            .find_map(|var| std::env::var(var).ok().filter(|value| !value.is_empty()));
+        if let Some(locale) = locale {
+            return first_day_of_week_for_locale(&locale);
+        }
+    }
+    1
+}
+
+/// Returns the first day of the week for a locale name such as `en_US.UTF-8` or `de-CH`.
+#[cfg(feature = "std")]
+fn first_day_of_week_for_locale(locale: &str) -> i32 {
+    // Strip the encoding and the modifier, e.g. "sr_RS.UTF-8@latin"
+    let locale = locale.split(['.', '@']).next().unwrap_or_default();
+    let Some(region) = locale.split(['_', '-']).nth(1) else { return 1 };
+    // From the "firstDay" data of the Unicode CLDR
+    match region {
+        "AG" | "AS" | "BD" | "BR" | "BS" | "BT" | "BW" | "BZ" | "CA" | "CN" | "CO" | "DM"
+        | "DO" | "ET" | "GT" | "GU" | "HK" | "HN" | "ID" | "IL" | "IN" | "JM" | "JP" | "KE"
+        | "KH" | "KR" | "LA" | "MH" | "MM" | "MO" | "MT" | "MX" | "MZ" | "NI" | "NP" | "PA"
+        | "PE" | "PH" | "PK" | "PR" | "PT" | "PY" | "SA" | "SG" | "SV" | "TH" | "TT" | "TW"
+        | "UM" | "US" | "VE" | "VI" | "WS" | "YE" | "ZA" | "ZW" => 0,
+        "AE" | "AF" | "BH" | "DJ" | "DZ" | "EG" | "IQ" | "IR" | "JO" | "KW" | "LY" | "OM"
+        | "QA" | "SD" | "SY" => 6,
+        "MV" => 5,
+        _ => 1,
+    }
+}
+
+#[cfg(feature = "ffi")]
+mod ffi {
+    #![allow(unsafe_code)]
+    use super::*;
+
+    #[no_mangle]
+    /// Returns the first day of the week in the current locale, where 0 is Sunday.
+    pub extern "C" fn slint_date_time_first_day_of_week() -> i32 {
+        first_day_of_week()
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_first_day_of_week_for_locale() {
+    assert_eq!(first_day_of_week_for_locale("en_US.UTF-8"), 0);
+    assert_eq!(first_day_of_week_for_locale("en_GB.UTF-8"), 1);
+    assert_eq!(first_day_of_week_for_locale("de-CH"), 1);
+    assert_eq!(first_day_of_week_for_locale("ar_EG"), 6);
+    assert_eq!(first_day_of_week_for_locale("sr_RS.UTF-8@latin"), 1);
+    assert_eq!(first_day_of_week_for_locale("pt_BR@euro"), 0);
+    assert_eq!(first_day_of_week_for_locale("C"), 1);
+    assert_eq!(first_day_of_week_for_locale("fr"), 1);
+}
@@ -233,16 +233,60 @@ pub enum PixelFormat {
     RgbaPremultiplied,
     /// Alpha map. 8bits. Each pixel is an alpha value. The color is specified separately.
     AlphaMap,
+    /// Grayscale. 8bits. Each pixel is the luminance of an opaque gray.
+    Gray8,
+    /// Grayscale. 4bits. Two pixels per byte, the first one in the most significant bits.
+    /// Each line starts on a new byte.
+    Gray4,
+    /// Black and white. 1bit. Eight pixels per byte, the first one in the most significant bit.
+    /// Each line starts on a new byte.
+    Gray1,
 }
 
 impl PixelFormat {
     /// The number of bytes in a pixel
+    ///
+    /// For the formats packing several pixels in a byte, this is 1 and [`Self::pixels_per_byte()`]
+    /// is the number of pixels in that byte.
     pub fn bpp(self) -> usize {
         match self {
             PixelFormat::Rgb => 3,
             PixelFormat::Rgba => 4,
             PixelFormat::RgbaPremultiplied => 4,
             PixelFormat::AlphaMap => 1,
+            PixelFormat::Gray8 => 1,
+            PixelFormat::Gray4 => 1,
+            PixelFormat::Gray1 => 1,
+        }
+    }
+
+    /// The number of pixels packed in [`Self::bpp()`] bytes
+    pub fn pixels_per_byte(self) -> usize {
+        match self {
+            PixelFormat::Gray4 => 2,
+            PixelFormat::Gray1 => 8,
+            _ => 1,
+        }
+    }
+
+    /// The number of bytes of a line of `width` pixels
+    pub(crate) fn line_size(self, width: usize) -> usize {
+        let ppb = self.pixels_per_byte();
+        (width + ppb - 1) / ppb * self.bpp()
+    }
+
+    /// Returns the luminance of the pixel at `index` in `data`, for the grayscale formats
+    pub(crate) fn gray_at(self, data: &[u8], index: usize) -> u8 {
+        match self {
+            PixelFormat::Gray4 => ((data[index / 2] >> (4 - index % 2 * 4)) & 0xf) * 0x11,
+            PixelFormat::Gray1 => {
+                if data[index / 8] & (0x80 >> (index % 8)) != 0 {
+                    0xff
+                } else {
+                    0
+                }
+            }
+            _ => data[index],
         }
     }
 }
@@ -403,7 +447,7 @@ pub fn render_to_buffer(
                     let rect = t.rect.to_usize();
                     for y in 0..rect.height() {
                         let slice = &mut slice[(rect.min_y() + y) * stride..][rect.x_range()];
-                        let source = &ts.data[t.index + y * rect.width() * t.format.bpp()..];
+                        let source = &ts.data[t.index + y * t.format.line_size(rect.width())..];
                         match t.format {
                             PixelFormat::Rgb => {
                                 let mut iter = source.chunks_exact(3).map(|p| Rgba8Pixel {
@@ -448,6 +492,13 @@ pub fn render_to_buffer(
                                 });
                                 slice.fill_with(|| iter.next().unwrap());
                             }
+                            PixelFormat::Gray8 | PixelFormat::Gray4 | PixelFormat::Gray1 => {
+                                let mut iter = (0..).map(|i| {
+                                    let p = t.format.gray_at(source, i);
+                                    Rgba8Pixel { r: p, g: p, b: p, a: 255 }
+                                });
+                                slice.fill_with(|| iter.next().unwrap());
+                            }
                         };
                     }
                 }
@@ -852,6 +903,38 @@ fn test_image_size_from_buffer_without_backend() {
     }
 }
 
+#[test]
+fn test_render_packed_grayscale_textures() {
+    // Three pixels per line: each line starts on a new byte
+    let data: &'static [u8] = &[0b1010_0000, 0b0110_0000, 0xf0, 0x80, 0x0f, 0x00];
+    let textures = Box::leak(Box::new([
+        StaticTexture {
+            rect: euclid::rect(0, 0, 3, 2),
+            format: PixelFormat::Gray1,
+            color: Default::default(),
+            index: 0,
+        },
+        StaticTexture {
+            rect: euclid::rect(0, 2, 3, 2),
+            format: PixelFormat::Gray4,
+            color: Default::default(),
+            index: 2,
+        },
+    ]));
+    let image = ImageInner::StaticTextures(Box::leak(Box::new(StaticTextures {
+        size: [3, 4].into(),
+        original_size: [3, 4].into(),
+        data: Slice::from_slice(data),
+        textures: Slice::from_slice(textures),
+    })));
+    let Some(SharedImageBuffer::RGBA8Premultiplied(buffer)) = image.render_to_buffer(None) else {
+        panic!("static textures are rendered to a premultiplied buffer")
+    };
+    let gray = buffer.as_slice().iter().map(|p| p.r).collect::<Vec<_>>();
+    assert_eq!(gray, [0xff, 0, 0xff, 0, 0xff, 0xff, 0xff, 0, 0x88, 0, 0xff, 0]);
+    assert!(buffer.as_slice().iter().all(|p| p.a == 0xff && p.r == p.g && p.g == p.b));
+}
+
 #[cfg(feature = "svg")]
 #[test]
 fn test_image_size_from_svg() {
@@ -8,7 +8,7 @@
 use crate::item_tree::ItemTreeRc;
 use crate::item_tree::{ItemRc, ItemWeak, VisitChildrenResult};
 pub use crate::items::PointerEventButton;
-use crate::items::{ItemRef, TextCursorDirection};
+use crate::items::{ItemRef, Rotate, TextCursorDirection};
 pub use crate::items::{KeyEvent, KeyboardModifiers};
 use crate::lengths::{LogicalPoint, LogicalVector};
 use crate::timers::Timer;
@@ -49,6 +49,12 @@ pub enum MouseEvent {
     Wheel { position: LogicalPoint, delta_x: Coord, delta_y: Coord },
     /// The mouse exited the item or component
     Exit,
+    /// A drag and drop operation moved over the item.
+    /// The dragged data is available through [`WindowInner::drag_data`].
+    DragMove { position: LogicalPoint },
+    /// The data of a drag and drop operation was dropped on the item.
+    /// The dropped data is available through [`WindowInner::drag_data`].
+    Drop { position: LogicalPoint },
 }
 
 impl MouseEvent {
@@ -60,23 +66,37 @@ pub fn position(&self) -> Option<LogicalPoint> {
             MouseEvent::Moved { position } => Some(*position),
             MouseEvent::Wheel { position, .. } => Some(*position),
             MouseEvent::Exit => None,
+            MouseEvent::DragMove { position } => Some(*position),
+            MouseEvent::Drop { position } => Some(*position),
         }
     }
 
-    /// Translate the position by the given value
-    pub fn translate(&mut self, vec: LogicalVector) {
-        let pos = match self {
+    fn position_mut(&mut self) -> Option<&mut LogicalPoint> {
+        match self {
             MouseEvent::Pressed { position, .. } => Some(position),
             MouseEvent::Released { position, .. } => Some(position),
             MouseEvent::Moved { position } => Some(position),
             MouseEvent::Wheel { position, .. } => Some(position),
             MouseEvent::Exit => None,
-        };
-        if let Some(pos) = pos {
+            MouseEvent::DragMove { position } => Some(position),
+            MouseEvent::Drop { position } => Some(position),
+        }
+    }
+
+    /// Translate the position by the given value
+    pub fn translate(&mut self, vec: LogicalVector) {
+        if let Some(pos) = self.position_mut() {
             *pos += vec;
         }
     }
 
+    /// Map the position from the coordinates of the item to the coordinates of its children
+    fn map_to_children(&mut self, item: Pin<ItemRef>) {
+        if let Some(pos) = self.position_mut() {
+            *pos = map_to_children(item, *pos);
+        }
+    }
+
     /// Set the click count of the pressed or released event
     fn set_click_count(&mut self, count: u8) {
         match self {
@@ -395,6 +415,7 @@ pub fn text_shortcut(&self) -> Option<TextShortcut> {
 }
 
 /// Represents a non context specific shortcut.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum StandardShortcut {
     /// Copy Something
     Copy,
@@ -533,6 +554,18 @@ pub fn check_repeat(&self, mouse_event: MouseEvent, click_interval: Duration) ->
     }
 }
 
+/// If the item transforms its children (such as the `Rotate` element), map the point from the
+/// coordinates of the item to the coordinates of its children.
+fn map_to_children(item: Pin<ItemRef>, point: LogicalPoint) -> LogicalPoint {
+    match ItemRef::downcast_pin::<Rotate>(item) {
+        // When the children are scaled to nothing, they can't be under the mouse
+        Some(rotate) => {
+            rotate.map_to_children(point).unwrap_or(LogicalPoint::new(Coord::MAX, Coord::MAX))
+        }
+        None => point,
+    }
+}
+
 /// The state which a window should hold for the mouse input
 #[derive(Default)]
 pub struct MouseInputState {
@@ -603,6 +636,7 @@ pub(crate) fn handle_mouse_grab(
         {
             intercept = true;
         }
+        event.map_to_children(item.borrow());
         true
     });
     if invalid {
@@ -641,7 +675,7 @@ pub(crate) fn send_exit_events(
         let g = item.geometry();
         let contains = pos.map_or(false, |p| g.contains(p));
         if let Some(p) = pos.as_mut() {
-            *p -= g.origin.to_vector();
+            *p = map_to_children(item.borrow(), *p - g.origin.to_vector());
         }
         if !contains || clipped {
             if crate::item_rendering::is_clipping_item(item.borrow()) {
@@ -792,6 +826,8 @@ fn send_mouse_event_to_item(
 
     result.item_stack.push((item_rc.downgrade(), filter_result));
     if forward_to_children {
+        let mut event_for_children = event_for_children;
+        event_for_children.map_to_children(item);
         let mut actual_visitor =
             |component: &ItemTreeRc, index: u32, _: Pin<ItemRef>| -> VisitChildrenResult {
                 send_mouse_event_to_item(
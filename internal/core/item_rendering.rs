@@ -277,6 +277,55 @@ pub fn item_children_bounding_rect(
     bounding_rect
 }
 
+/// Compute the area in which the children draw, relative to the item and intersected with
+/// `clip_rect`. Unlike [`item_children_bounding_rect`], this includes what the children draw
+/// outside of their geometry, such as shadows or the transformed geometry of rotated children.
+/// Remember to run this via `evaluate_no_tracking`.
+pub(crate) fn item_children_drawing_rect(
+    component: &ItemTreeRc,
+    index: isize,
+    clip_rect: &LogicalRect,
+) -> LogicalRect {
+    let mut drawing_rect = LogicalRect::zero();
+
+    let mut actual_visitor = |component: &ItemTreeRc,
+                              index: u32,
+                              item: Pin<ItemRef>|
+     -> VisitChildrenResult {
+        let item_rc = ItemRc::new(component.clone(), index);
+        let CachedItemBoundingBox { geometry, mut bounding_rect } =
+            CachedItemBoundingBox::new(&item_rc);
+        if !is_clipping_item(item) && !is_cached_layer(item) {
+            let children = item_children_drawing_rect(component, index as isize, &unbounded_rect());
+            let children = match ItemRef::downcast_pin::<Rotate>(item) {
+                Some(rotate) => rotate.transform().outer_transformed_rect(&children),
+                None => children,
+            };
+            bounding_rect = bounding_rect.union(&children.translate(geometry.origin.to_vector()));
+        }
+        if let Some(rect) = bounding_rect.intersection(clip_rect) {
+            drawing_rect = drawing_rect.union(&rect);
+        }
+        VisitChildrenResult::CONTINUE
+    };
+    vtable::new_vref!(let mut actual_visitor : VRefMut<ItemVisitorVTable> for ItemVisitor = &mut actual_visitor);
+    VRc::borrow_pin(component).as_ref().visit_children_item(
+        index,
+        crate::item_tree::TraversalOrder::BackToFront,
+        actual_visitor,
+    );
+
+    drawing_rect
+}
+
+/// A rectangle that contains everything
+fn unbounded_rect() -> LogicalRect {
+    LogicalRect::new(
+        LogicalPoint::new(Coord::MIN / (2 as Coord), Coord::MIN / (2 as Coord)),
+        LogicalSize::new(Coord::MAX, Coord::MAX),
+    )
+}
+
 /// Trait for an item that represent a Rectangle to the Renderer
 #[allow(missing_docs)]
 pub trait RenderBorderRectangle {
@@ -321,6 +370,7 @@ fn draw_image(
         _cache: &CachedRenderingData,
     );
     fn draw_text(&mut self, text: Pin<&Text>, _self_rc: &ItemRc, _size: LogicalSize);
+    fn draw_styled_text(&mut self, text: Pin<&StyledText>, _self_rc: &ItemRc, _size: LogicalSize);
     fn draw_text_input(
         &mut self,
         text_input: Pin<&TextInput>,
@@ -354,6 +404,25 @@ fn visit_layer(
         RenderingResult::ContinueRenderingChildren
     }
 
+    /// Apply the rotation and scale of the Rotate element to its children. The default implementation
+    /// transforms the coordinate system, but the renderer may choose an alternate way of implementing it.
+    fn visit_rotate(
+        &mut self,
+        rotate_item: Pin<&Rotate>,
+        _self_rc: &ItemRc,
+        _size: LogicalSize,
+    ) -> RenderingResult {
+        let origin = LogicalVector::from_lengths(
+            rotate_item.rotation_origin_x(),
+            rotate_item.rotation_origin_y(),
+        );
+        self.translate(origin);
+        self.rotate(rotate_item.rotation_angle());
+        self.scale(rotate_item.scale_x(), rotate_item.scale_y());
+        self.translate(-origin);
+        RenderingResult::ContinueRenderingChildren
+    }
+
     // Apply the bounds of the Clip element, if enabled. The default implementation calls
     // combine_clip, but the render may choose an alternate way of implementing the clip.
     // For example the GL backend uses a layered rendering approach.
@@ -397,6 +466,8 @@ fn combine_clip(
 
     fn translate(&mut self, distance: LogicalVector);
     fn rotate(&mut self, angle_in_degrees: f32);
+    /// Scale the coordinate system by the given factors on each axis
+    fn scale(&mut self, x_factor: f32, y_factor: f32);
     /// Apply the opacity (between 0 and 1) for all following items until the next call to restore_state.
     fn apply_opacity(&mut self, opacity: f32);
 
@@ -443,11 +514,140 @@ fn metrics(&self) -> crate::graphics::rendering_metrics_collector::RenderingMetr
     }
 }
 
+/// The data cached for each item by the [`PartialRenderer`]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct CachedItemBoundingBox {
+    /// The geometry of the item, relative to its parent
+    pub geometry: LogicalRect,
+    /// The area in which the item draws, relative to its parent.
+    /// This is usually the same as the geometry, except for items such as the `BoxShadow`
+    /// that draw outside of their geometry, or the `Rotate` for which it is the bounding box of
+    /// its transformed geometry.
+    pub bounding_rect: LogicalRect,
+}
+
+impl CachedItemBoundingBox {
+    fn new(item_rc: &ItemRc) -> Self {
+        let geometry = item_rc.geometry();
+        let item = item_rc.borrow();
+        let bounding_rect = if let Some(box_shadow) = ItemRef::downcast_pin::<BoxShadow>(item) {
+            box_shadow.shadow_rect(geometry.size).translate(geometry.origin.to_vector())
+        } else if let Some(rotate) = ItemRef::downcast_pin::<Rotate>(item) {
+            rotate
+                .transform()
+                .outer_transformed_rect(&LogicalRect::from_size(geometry.size))
+                .translate(geometry.origin.to_vector())
+        } else if is_cached_layer(item) {
+            // The children are rendered together with the layer
+            LogicalRect::from_size(geometry.size)
+                .union(&item_children_bounding_rect(
+                    item_rc.item_tree(),
+                    item_rc.index() as isize,
+                    &unbounded_rect(),
+                ))
+                .translate(geometry.origin.to_vector())
+        } else {
+            geometry
+        };
+        Self { geometry, bounding_rect }
+    }
+}
+
+/// Return true if the item is a `Layer` whose children are rendered and cached by the renderer
+/// in one go. These children are not in the partial rendering cache, and their changes are tracked
+/// by the layer itself.
+fn is_cached_layer(item: Pin<ItemRef>) -> bool {
+    cfg!(feature = "std")
+        && ItemRef::downcast_pin::<Layer>(item).map_or(false, |layer| layer.cache_rendering_hint())
+}
+
 /// The cache that needs to be held by the Window for the partial rendering
-pub type PartialRenderingCache = RenderingCache<LogicalRect>;
+pub type PartialRenderingCache = RenderingCache<CachedItemBoundingBox>;
 
-/// FIXME: Should actually be a region and not just a rectangle
-pub type DirtyRegion = euclid::Box2D<Coord, LogicalPx>;
+/// A region of the screen that needs to be repainted, made of a few rectangles.
+///
+/// The rectangles may overlap. When more than [`DirtyRegion::MAX_COUNT`] rectangles are added,
+/// the new rectangle is merged with the one whose area grows the least.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct DirtyRegion {
+    rectangles: [euclid::Box2D<Coord, LogicalPx>; Self::MAX_COUNT],
+    count: usize,
+}
+
+impl DirtyRegion {
+    /// The maximum number of rectangles in the region
+    pub const MAX_COUNT: usize = 3;
+
+    /// Iterate over the rectangles of this region
+    pub fn iter(&self) -> impl Iterator<Item = euclid::Box2D<Coord, LogicalPx>> + '_ {
+        self.rectangles[..self.count].iter().copied()
+    }
+
+    /// Add a rectangle to the region
+    pub fn add_rect(&mut self, rect: LogicalRect) {
+        self.add_box(rect.to_box2d());
+    }
+
+    /// Add a box to the region
+    pub fn add_box(&mut self, b: euclid::Box2D<Coord, LogicalPx>) {
+        if b.is_empty() {
+            return;
+        }
+        let mut i = 0;
+        while i < self.count {
+            let r = &self.rectangles[i];
+            if r.contains_box(&b) {
+                return;
+            } else if b.contains_box(r) {
+                self.count -= 1;
+                self.rectangles.swap(i, self.count);
+                continue;
+            }
+            i += 1;
+        }
+
+        if self.count < Self::MAX_COUNT {
+            self.rectangles[self.count] = b;
+            self.count += 1;
+        } else {
+            let area = |b: euclid::Box2D<Coord, LogicalPx>| b.width() as f32 * b.height() as f32;
+            let best_merge = (0..self.count)
+                .map(|i| (i, area(self.rectangles[i].union(&b)) - area(self.rectangles[i])))
+                .min_by(|a, b| a.1.total_cmp(&b.1))
+                .map(|(i, _)| i)
+                .unwrap_or_default();
+            self.rectangles[best_merge] = self.rectangles[best_merge].union(&b);
+        }
+    }
+
+    /// Returns the union of this region with another one
+    #[must_use]
+    pub fn union(&self, other: &Self) -> Self {
+        let mut result = *self;
+        for b in other.iter() {
+            result.add_box(b);
+        }
+        result
+    }
+
+    /// Returns the bounding rectangle of this region
+    pub fn bounding_rect(&self) -> LogicalRect {
+        self.iter().fold(euclid::Box2D::zero(), |acc, b| acc.union(&b)).to_rect()
+    }
+
+    /// Returns true if the region is empty
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+}
+
+impl From<LogicalRect> for DirtyRegion {
+    fn from(rect: LogicalRect) -> Self {
+        let mut region = Self::default();
+        region.add_rect(rect);
+        region
+    }
+}
 
 /// Put this structure in the renderer to help with partial rendering
 pub struct PartialRenderer<'a, T> {
@@ -476,13 +676,53 @@ struct ComputeDirtyRegionState {
             old_offset: euclid::Vector2D<Coord, LogicalPx>,
             clipped: LogicalRect,
             must_refresh_children: bool,
+            /// When inside the children of a `Rotate`, the area covered by it in which any change is reported
+            transformed_area: Option<LogicalRect>,
+            /// When inside the children of a cached `Layer`, changes are reported by the layer
+            in_cached_layer: bool,
+        }
+
+        /// Return the area in which the children of the item are rendered if they are transformed,
+        /// either by this item or by one of its ancestors.
+        fn transformed_area(
+            item: Pin<ItemRef>,
+            state: &ComputeDirtyRegionState,
+            old_bounding_rect: LogicalRect,
+            bounding_rect: LogicalRect,
+        ) -> Option<LogicalRect> {
+            if state.transformed_area.is_some() {
+                return state.transformed_area;
+            }
+            let rotate = ItemRef::downcast_pin::<Rotate>(item)?;
+            if crate::properties::evaluate_no_tracking(|| rotate.is_identity()) {
+                return None;
+            }
+            Some(
+                old_bounding_rect
+                    .translate(state.old_offset)
+                    .union(&bounding_rect.translate(state.offset))
+                    .intersection(&state.clipped)
+                    .unwrap_or_default(),
+            )
         }
 
         crate::item_tree::visit_items(
             component,
             crate::item_tree::TraversalOrder::BackToFront,
             |component, item, index, state| {
+                let mark_dirty_rect =
+                    |this: &mut Self, rect: LogicalRect, offset, clip_rect: &LogicalRect| {
+                        match state.transformed_area {
+                            Some(area) => this.mark_dirty_rect(area, Default::default(), &area),
+                            None => this.mark_dirty_rect(rect, offset, clip_rect),
+                        }
+                    };
+                if state.in_cached_layer {
+                    return ItemVisitorResult::Continue(*state);
+                }
                 let mut new_state = *state;
+                new_state.in_cached_layer =
+                    crate::properties::evaluate_no_tracking(|| is_cached_layer(item));
                 let mut borrowed = self.cache.borrow_mut();
                 let item_rc = ItemRc::new(component.clone(), index);
 
@@ -494,14 +734,26 @@ struct ComputeDirtyRegionState {
                         if tr.is_dirty() {
                             let old_geom = *cached_geom;
                             drop(borrowed);
-                            let geom =
-                                crate::properties::evaluate_no_tracking(|| item_rc.geometry());
-
-                            self.mark_dirty_rect(old_geom, state.old_offset, &state.clipped);
-                            self.mark_dirty_rect(geom, state.offset, &state.clipped);
-
-                            new_state.offset += geom.origin.to_vector();
-                            new_state.old_offset += old_geom.origin.to_vector();
+                            let geom = crate::properties::evaluate_no_tracking(|| {
+                                CachedItemBoundingBox::new(&item_rc)
+                            });
+
+                            mark_dirty_rect(
+                                self,
+                                old_geom.bounding_rect,
+                                state.old_offset,
+                                &state.clipped,
+                            );
+                            mark_dirty_rect(self, geom.bounding_rect, state.offset, &state.clipped);
+
+                            new_state.offset += geom.geometry.origin.to_vector();
+                            new_state.old_offset += old_geom.geometry.origin.to_vector();
+                            new_state.transformed_area = transformed_area(
+                                item,
+                                state,
+                                old_geom.bounding_rect,
+                                geom.bounding_rect,
+                            );
                             if ItemRef::downcast_pin::<Clip>(item).is_some()
                                 || ItemRef::downcast_pin::<Opacity>(item).is_some()
                             {
@@ -517,23 +769,36 @@ struct ComputeDirtyRegionState {
                             if state.must_refresh_children
                                 || new_state.offset != new_state.old_offset
                             {
-                                self.mark_dirty_rect(
-                                    *cached_geom,
+                                mark_dirty_rect(
+                                    self,
+                                    cached_geom.bounding_rect,
                                     state.old_offset,
                                     &state.clipped,
                                 );
-                                self.mark_dirty_rect(*cached_geom, state.offset, &state.clipped);
+                                mark_dirty_rect(
+                                    self,
+                                    cached_geom.bounding_rect,
+                                    state.offset,
+                                    &state.clipped,
+                                );
                             }
 
-                            new_state.offset += cached_geom.origin.to_vector();
-                            new_state.old_offset += cached_geom.origin.to_vector();
+                            let geometry = cached_geom.geometry;
+                            new_state.offset += geometry.origin.to_vector();
+                            new_state.old_offset += geometry.origin.to_vector();
+                            new_state.transformed_area = transformed_area(
+                                item,
+                                state,
+                                cached_geom.bounding_rect,
+                                cached_geom.bounding_rect,
+                            );
                             if crate::properties::evaluate_no_tracking(|| is_clipping_item(item)) {
                                 new_state.clipped = new_state
                                     .clipped
                                     .intersection(
-                                        &cached_geom
+                                        &geometry
                                             .translate(state.offset)
-                                            .union(&cached_geom.translate(state.old_offset)),
+                                            .union(&geometry.translate(state.old_offset)),
                                     )
                                     .unwrap_or_default();
                             }
@@ -543,18 +808,20 @@ struct ComputeDirtyRegionState {
                     _ => {
                         drop(borrowed);
                         let geom = crate::properties::evaluate_no_tracking(|| {
-                            let geom = item_rc.geometry();
-                            new_state.offset += geom.origin.to_vector();
-                            new_state.old_offset += geom.origin.to_vector();
+                            let geom = CachedItemBoundingBox::new(&item_rc);
+                            new_state.offset += geom.geometry.origin.to_vector();
+                            new_state.old_offset += geom.geometry.origin.to_vector();
                             if is_clipping_item(item) {
                                 new_state.clipped = new_state
                                     .clipped
-                                    .intersection(&geom.translate(state.offset))
+                                    .intersection(&geom.geometry.translate(state.offset))
                                     .unwrap_or_default();
                             }
                             geom
                         });
-                        self.mark_dirty_rect(geom, state.offset, &state.clipped);
+                        new_state.transformed_area =
+                            transformed_area(item, state, geom.bounding_rect, geom.bounding_rect);
+                        mark_dirty_rect(self, geom.bounding_rect, state.offset, &state.clipped);
                         ItemVisitorResult::Continue(new_state)
                     }
                 }
@@ -564,6 +831,8 @@ struct ComputeDirtyRegionState {
                 old_offset: origin.to_vector(),
                 clipped: euclid::rect(0 as Coord, 0 as Coord, Coord::MAX, Coord::MAX),
                 must_refresh_children: false,
+                transformed_area: None,
+                in_cached_layer: false,
             },
         );
     }
@@ -576,7 +845,7 @@ fn mark_dirty_rect(
     ) {
         if !rect.is_empty() {
             if let Some(rect) = rect.translate(offset).intersection(clip_rect) {
-                self.dirty_region = self.dirty_region.union(&rect.to_box2d());
+                self.dirty_region.add_rect(rect);
             }
         }
     }
@@ -584,7 +853,7 @@ fn mark_dirty_rect(
     fn do_rendering(
         cache: &RefCell<PartialRenderingCache>,
         rendering_data: &CachedRenderingData,
-        render_fn: impl FnOnce() -> LogicalRect,
+        render_fn: impl FnOnce() -> CachedItemBoundingBox,
     ) {
         let mut cache = cache.borrow_mut();
         if let Some(entry) = rendering_data.get_entry(&mut cache) {
@@ -612,7 +881,7 @@ fn $fn(&mut self, obj: Pin<&$Ty>, item_rc: &ItemRc, size: LogicalSize) $(-> $Ret
             let mut ret = None;
             Self::do_rendering(&self.cache, &obj.cached_rendering_data, || {
                 ret = Some(self.actual_renderer.$fn(obj, item_rc, size));
-                item_rc.geometry()
+                CachedItemBoundingBox::new(item_rc)
             });
             ret.unwrap_or_default()
         }
@@ -625,7 +894,7 @@ fn $fn(&mut self, obj: Pin<&$Ty>, item_rc: &ItemRc, size: LogicalSize, cache: &C
             let mut ret = None;
             Self::do_rendering(&self.cache, &cache, || {
                 ret = Some(self.actual_renderer.$fn(obj, item_rc, size, &cache));
-                item_rc.geometry()
+                CachedItemBoundingBox::new(item_rc)
             });
             ret.unwrap_or_default()
         }
@@ -640,12 +909,12 @@ fn filter_item(&mut self, item_rc: &ItemRc) -> (bool, LogicalRect) {
                 // Make sure we register a dependency on the clip
                 clip.clip();
             }
-            item_rc.geometry()
+            CachedItemBoundingBox::new(item_rc)
         };
 
         let rendering_data = item.cached_rendering_data_offset();
         let mut cache = self.cache.borrow_mut();
-        let item_geometry = match rendering_data.get_entry(&mut cache) {
+        let item_bounding_box = match rendering_data.get_entry(&mut cache) {
             Some(CachedGraphicsData { data, dependency_tracker }) => {
                 dependency_tracker
                     .get_or_insert_with(|| Box::pin(PropertyTracker::default()))
@@ -665,14 +934,15 @@ fn filter_item(&mut self, item_rc: &ItemRc) -> (bool, LogicalRect) {
         //let clip = self.get_current_clip().intersection(&self.dirty_region.to_rect());
         //let draw = clip.map_or(false, |r| r.intersects(&item_geometry));
         //FIXME: the dirty_region is in global coordinate but item_geometry and current_clip is not
-        let draw = self.get_current_clip().intersects(&item_geometry);
-        (draw, item_geometry)
+        let draw = self.get_current_clip().intersects(&item_bounding_box.bounding_rect);
+        (draw, item_bounding_box.geometry)
     }
 
     forward_rendering_call!(fn draw_rectangle(Rectangle));
     forward_rendering_call2!(fn draw_border_rectangle(dyn RenderBorderRectangle));
     forward_rendering_call2!(fn draw_image(dyn RenderImage));
     forward_rendering_call!(fn draw_text(Text));
+    forward_rendering_call!(fn draw_styled_text(StyledText));
     forward_rendering_call!(fn draw_text_input(TextInput));
     #[cfg(feature = "std")]
     forward_rendering_call!(fn draw_path(Path));
@@ -680,6 +950,8 @@ fn filter_item(&mut self, item_rc: &ItemRc) -> (bool, LogicalRect) {
 
     forward_rendering_call!(fn visit_clip(Clip) -> RenderingResult);
     forward_rendering_call!(fn visit_opacity(Opacity) -> RenderingResult);
+    forward_rendering_call!(fn visit_layer(Layer) -> RenderingResult);
+    forward_rendering_call!(fn visit_rotate(Rotate) -> RenderingResult);
 
     fn combine_clip(
         &mut self,
@@ -702,6 +974,10 @@ fn rotate(&mut self, angle_in_degrees: f32) {
         self.actual_renderer.rotate(angle_in_degrees)
     }
 
+    fn scale(&mut self, x_factor: f32, y_factor: f32) {
+        self.actual_renderer.scale(x_factor, y_factor)
+    }
+
     fn apply_opacity(&mut self, opacity: f32) {
         self.actual_renderer.apply_opacity(opacity)
     }
@@ -29,8 +29,8 @@
 pub use crate::item_tree::ItemRc;
 use crate::layout::LayoutInfo;
 use crate::lengths::{
-    LogicalBorderRadius, LogicalLength, LogicalPoint, LogicalRect, LogicalSize, LogicalVector,
-    PointLengths, RectLengths,
+    LogicalBorderRadius, LogicalLength, LogicalPoint, LogicalPx, LogicalRect, LogicalSize,
+    LogicalVector, PointLengths, RectLengths,
 };
 #[cfg(feature = "rtti")]
 use crate::rtti::*;
@@ -45,8 +45,14 @@
 
 mod component_container;
 pub use self::component_container::*;
+mod context_menu;
+pub use context_menu::*;
+mod drag_n_drop;
+pub use drag_n_drop::*;
 mod flickable;
 pub use flickable::*;
+mod shortcut;
+pub use shortcut::*;
 mod text;
 pub use text::*;
 mod image;
@@ -65,6 +71,8 @@
 pub type KeyEventArg = (KeyEvent,);
 type PointerEventArg = (PointerEvent,);
 type PointerScrollEventArg = (PointerScrollEvent,);
+type DropEventArg = (DropEvent,);
+type StringArg = (SharedString,);
 type PointArg = (Point,);
 
 #[cfg(all(feature = "ffi", windows))]
@@ -572,7 +580,8 @@ fn input_event_filter_before_children(
         window_adapter: &Rc<dyn WindowAdapter>,
         _self_rc: &ItemRc,
     ) -> InputEventFilterResult {
-        if !self.enabled() {
+        if !self.enabled() || matches!(event, MouseEvent::DragMove { .. } | MouseEvent::Drop { .. })
+        {
             return InputEventFilterResult::ForwardAndIgnore;
         }
         if let Some(pos) = event.position() {
@@ -692,6 +701,7 @@ fn input_event(
                     }
                 }
             }
+            MouseEvent::DragMove { .. } | MouseEvent::Drop { .. } => InputEventResult::EventIgnored,
         }
     }
 
@@ -1142,9 +1152,34 @@ pub struct Rotate {
     pub rotation_angle: Property<f32>,
     pub rotation_origin_x: Property<LogicalLength>,
     pub rotation_origin_y: Property<LogicalLength>,
+    pub scale_x: Property<f32>,
+    pub scale_y: Property<f32>,
     pub cached_rendering_data: CachedRenderingData,
 }
 
+impl Rotate {
+    /// Returns true if this element doesn't move, rotate, or scale its children
+    pub fn is_identity(self: Pin<&Self>) -> bool {
+        self.rotation_angle() % 360. == 0. && self.scale_x() == 1. && self.scale_y() == 1.
+    }
+
+    /// The transformation that maps the coordinates of the children to the coordinates of this element
+    pub fn transform(self: Pin<&Self>) -> euclid::Transform2D<Coord, LogicalPx, LogicalPx> {
+        let origin =
+            LogicalVector::from_lengths(self.rotation_origin_x(), self.rotation_origin_y());
+        euclid::Transform2D::translation(-origin.x, -origin.y)
+            .then_scale(self.scale_x(), self.scale_y())
+            .then_rotate(euclid::Angle::degrees(self.rotation_angle()))
+            .then_translate(origin)
+    }
+
+    /// Maps a point from the coordinates of this element to the coordinates of its children.
+    /// Returns None if the children are scaled down to nothing.
+    pub fn map_to_children(self: Pin<&Self>, point: LogicalPoint) -> Option<LogicalPoint> {
+        Some(self.transform().inverse()?.transform_point(point))
+    }
+}
+
 impl Item for Rotate {
     fn init(self: Pin<&Self>, _self_rc: &ItemRc) {}
 
@@ -1195,15 +1230,10 @@ fn focus_event(
     fn render(
         self: Pin<&Self>,
         backend: &mut ItemRendererRef,
-        _self_rc: &ItemRc,
-        _size: LogicalSize,
+        self_rc: &ItemRc,
+        size: LogicalSize,
     ) -> RenderingResult {
-        let origin =
-            LogicalVector::from_lengths(self.rotation_origin_x(), self.rotation_origin_y());
-        (*backend).translate(origin);
-        (*backend).rotate(self.rotation_angle());
-        (*backend).translate(-origin);
-        RenderingResult::ContinueRenderingChildren
+        backend.visit_rotate(self, self_rc, size)
     }
 }
 
@@ -1222,6 +1252,18 @@ fn slint_get_RotateVTable() -> RotateVTable for Rotate
     fn slint_get_FlickableVTable() -> FlickableVTable for Flickable
 }
 
+/// A keyframe of a `PropertyAnimation`
+#[repr(C)]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct AnimationKeyframe {
+    /// The time of the keyframe within an iteration, between 0 and 1
+    pub position: f32,
+    /// The progress of the animated property at that time: 0 for the start value and 1 for the end value
+    pub progress: f32,
+    /// The easing curve of the segment that leads to this keyframe
+    pub easing: crate::animations::EasingCurve,
+}
+
 /// The implementation of the `PropertyAnimation` element
 #[repr(C)]
 #[derive(FieldOffsets, SlintElement, Clone, Debug)]
@@ -1235,13 +1277,27 @@ pub struct PropertyAnimation {
     pub iteration_count: f32,
     #[rtti_field]
     pub easing: crate::animations::EasingCurve,
+    #[rtti_field]
+    pub direction: AnimationDirection,
+    #[rtti_field]
+    pub fill_mode: AnimationFillMode,
+    #[rtti_field]
+    pub keyframes: crate::SharedVector<AnimationKeyframe>,
 }
 
 impl Default for PropertyAnimation {
     fn default() -> Self {
         // Defaults for PropertyAnimation are defined here (for internal Rust code doing programmatic animations)
         // as well as in `builtins.slint` (for generated C++ and Rust code)
-        Self { delay: 0, duration: 0, iteration_count: 1., easing: Default::default() }
+        Self {
+            delay: 0,
+            duration: 0,
+            iteration_count: 1.,
+            easing: Default::default(),
+            direction: Default::default(),
+            fill_mode: Default::default(),
+            keyframes: Default::default(),
+        }
     }
 }
 
@@ -1430,6 +1486,20 @@ fn render(
     }
 }
 
+impl BoxShadow {
+    /// Returns the area covered by the shadow, relative to the item's position.
+    ///
+    /// Unlike other items, the shadow is not drawn within the item's geometry but moved
+    /// by the offset and enlarged by the blur.
+    pub fn shadow_rect(self: Pin<&Self>, size: LogicalSize) -> LogicalRect {
+        let blur = self.blur();
+        LogicalRect::new(
+            LogicalPoint::from_lengths(self.offset_x() - blur, self.offset_y() - blur),
+            size + LogicalSize::from_lengths(blur * 2 as Coord, blur * 2 as Coord),
+        )
+    }
+}
+
 impl ItemConsts for BoxShadow {
     const cached_rendering_data_offset: const_field_offset::FieldOffset<Self, CachedRenderingData> =
         Self::FIELD_OFFSETS.cached_rendering_data.as_unpinned_projection();
@@ -1447,6 +1517,10 @@ fn slint_get_ComponentContainerVTable() -> ComponentContainerVTable for Componen
     fn slint_get_TextVTable() -> TextVTable for Text
 }
 
+declare_item_vtable! {
+    fn slint_get_StyledTextVTable() -> StyledTextVTable for StyledText
+}
+
 declare_item_vtable! {
     fn slint_get_TextInputVTable() -> TextInputVTable for TextInput
 }
@@ -1459,6 +1533,22 @@ fn slint_get_ImageItemVTable() -> ImageItemVTable for ImageItem
     fn slint_get_ClippedImageVTable() -> ClippedImageVTable for ClippedImage
 }
 
+declare_item_vtable! {
+    fn slint_get_DragAreaVTable() -> DragAreaVTable for DragArea
+}
+
+declare_item_vtable! {
+    fn slint_get_DropAreaVTable() -> DropAreaVTable for DropArea
+}
+
+declare_item_vtable! {
+    fn slint_get_ShortcutVTable() -> ShortcutVTable for Shortcut
+}
+
+declare_item_vtable! {
+    fn slint_get_ContextMenuAreaVTable() -> ContextMenuAreaVTable for ContextMenuArea
+}
+
 #[cfg(feature = "std")]
 declare_item_vtable! {
     fn slint_get_PathVTable() -> PathVTable for Path
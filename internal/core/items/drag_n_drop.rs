@@ -0,0 +1,296 @@
+// Copyright © SixtyFPS GmbH <info@slint.dev>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-Royalty-free-1.1 OR LicenseRef-Slint-commercial
+
+//! The `DragArea` and `DropArea` items
+
+use super::{
+    DropEvent, DropEventArg, Item, ItemConsts, ItemRc, ItemRendererRef, KeyEventResult,
+    MouseCursor, PointerEventButton, RenderingResult, VoidArg,
+};
+use crate::input::{
+    FocusEvent, FocusEventResult, InputEventFilterResult, InputEventResult, KeyEvent, MouseEvent,
+};
+use crate::item_rendering::CachedRenderingData;
+use crate::layout::{LayoutInfo, Orientation};
+use crate::lengths::{LogicalLength, LogicalPoint, LogicalSize};
+#[cfg(feature = "rtti")]
+use crate::rtti::*;
+use crate::window::{WindowAdapter, WindowInner};
+use crate::{Callback, Property, SharedString};
+use alloc::rc::Rc;
+use const_field_offset::FieldOffsets;
+use core::cell::Cell;
+use core::pin::Pin;
+use i_slint_core_macros::*;
+
+/// The distance the mouse needs to move while pressed before a drag operation starts.
+const DISTANCE_THRESHOLD: LogicalLength = LogicalLength::new(8 as _);
+
+/// The implementation of the `DragArea` element
+#[repr(C)]
+#[derive(FieldOffsets, Default, SlintElement)]
+#[pin]
+pub struct DragArea {
+    pub enabled: Property<bool>,
+    pub mime_type: Property<SharedString>,
+    pub data: Property<SharedString>,
+    /// FIXME: remove this
+    pub cached_rendering_data: CachedRenderingData,
+    /// The position of the last left button press, if the button is still pressed
+    pressed_position: Cell<LogicalPoint>,
+    pressed: Cell<bool>,
+}
+
+impl Item for DragArea {
+    fn init(self: Pin<&Self>, _self_rc: &ItemRc) {}
+
+    fn layout_info(
+        self: Pin<&Self>,
+        _orientation: Orientation,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+    ) -> LayoutInfo {
+        LayoutInfo { stretch: 1., ..LayoutInfo::default() }
+    }
+
+    fn input_event_filter_before_children(
+        self: Pin<&Self>,
+        event: MouseEvent,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+        _self_rc: &ItemRc,
+    ) -> InputEventFilterResult {
+        if !self.enabled() {
+            self.pressed.set(false);
+            return InputEventFilterResult::ForwardAndIgnore;
+        }
+
+        match event {
+            MouseEvent::Pressed { position, button: PointerEventButton::Left, .. } => {
+                self.pressed_position.set(position);
+                self.pressed.set(true);
+                InputEventFilterResult::ForwardAndInterceptGrab
+            }
+            MouseEvent::Exit | MouseEvent::Released { button: PointerEventButton::Left, .. } => {
+                self.pressed.set(false);
+                InputEventFilterResult::ForwardAndIgnore
+            }
+            MouseEvent::Moved { position } if self.pressed.get() => {
+                if self.exceeds_threshold(position) {
+                    // Take the grab from the children, the drag starts in input_event
+                    InputEventFilterResult::Intercept
+                } else {
+                    InputEventFilterResult::ForwardAndInterceptGrab
+                }
+            }
+            _ => InputEventFilterResult::ForwardAndIgnore,
+        }
+    }
+
+    fn input_event(
+        self: Pin<&Self>,
+        event: MouseEvent,
+        window_adapter: &Rc<dyn WindowAdapter>,
+        _self_rc: &ItemRc,
+    ) -> InputEventResult {
+        if !self.enabled() {
+            return InputEventResult::EventIgnored;
+        }
+
+        match event {
+            MouseEvent::Pressed { button: PointerEventButton::Left, .. } => {
+                InputEventResult::GrabMouse
+            }
+            MouseEvent::Moved { position } if self.pressed.get() => {
+                if self.exceeds_threshold(position) {
+                    self.pressed.set(false);
+                    WindowInner::from_pub(window_adapter.window())
+                        .start_drag(DropEvent { mime_type: self.mime_type(), data: self.data() });
+                    // Release the grab so that the drag events reach the DropArea under the mouse
+                    InputEventResult::EventAccepted
+                } else {
+                    InputEventResult::GrabMouse
+                }
+            }
+            MouseEvent::Exit | MouseEvent::Released { .. } => {
+                self.pressed.set(false);
+                InputEventResult::EventIgnored
+            }
+            _ => InputEventResult::EventIgnored,
+        }
+    }
+
+    fn key_event(
+        self: Pin<&Self>,
+        _: &KeyEvent,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+        _self_rc: &ItemRc,
+    ) -> KeyEventResult {
+        KeyEventResult::EventIgnored
+    }
+
+    fn focus_event(
+        self: Pin<&Self>,
+        _: &FocusEvent,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+        _self_rc: &ItemRc,
+    ) -> FocusEventResult {
+        FocusEventResult::FocusIgnored
+    }
+
+    fn render(
+        self: Pin<&Self>,
+        _backend: &mut ItemRendererRef,
+        _self_rc: &ItemRc,
+        _size: LogicalSize,
+    ) -> RenderingResult {
+        RenderingResult::ContinueRenderingChildren
+    }
+}
+
+impl DragArea {
+    fn exceeds_threshold(&self, position: LogicalPoint) -> bool {
+        let threshold = DISTANCE_THRESHOLD.get();
+        (position - self.pressed_position.get()).square_length() > threshold * threshold
+    }
+}
+
+impl ItemConsts for DragArea {
+    const cached_rendering_data_offset: const_field_offset::FieldOffset<
+        DragArea,
+        CachedRenderingData,
+    > = DragArea::FIELD_OFFSETS.cached_rendering_data.as_unpinned_projection();
+}
+
+/// The implementation of the `DropArea` element
+#[repr(C)]
+#[derive(FieldOffsets, Default, SlintElement)]
+#[pin]
+pub struct DropArea {
+    pub enabled: Property<bool>,
+    pub mime_type: Property<SharedString>,
+    pub contains_drag: Property<bool>,
+    pub drag_enter: Callback<DropEventArg>,
+    pub drag_leave: Callback<VoidArg>,
+    pub dropped: Callback<DropEventArg>,
+    /// FIXME: remove this
+    pub cached_rendering_data: CachedRenderingData,
+}
+
+impl Item for DropArea {
+    fn init(self: Pin<&Self>, _self_rc: &ItemRc) {}
+
+    fn layout_info(
+        self: Pin<&Self>,
+        _orientation: Orientation,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+    ) -> LayoutInfo {
+        LayoutInfo { stretch: 1., ..LayoutInfo::default() }
+    }
+
+    fn input_event_filter_before_children(
+        self: Pin<&Self>,
+        event: MouseEvent,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+        _self_rc: &ItemRc,
+    ) -> InputEventFilterResult {
+        if self.enabled() && matches!(event, MouseEvent::DragMove { .. } | MouseEvent::Drop { .. })
+        {
+            InputEventFilterResult::ForwardEvent
+        } else {
+            InputEventFilterResult::ForwardAndIgnore
+        }
+    }
+
+    fn input_event(
+        self: Pin<&Self>,
+        event: MouseEvent,
+        window_adapter: &Rc<dyn WindowAdapter>,
+        _self_rc: &ItemRc,
+    ) -> InputEventResult {
+        match event {
+            MouseEvent::DragMove { .. } => {
+                let Some(data) = self.accepted_data(window_adapter) else {
+                    self.leave();
+                    return InputEventResult::EventIgnored;
+                };
+                if !self.contains_drag() {
+                    Self::FIELD_OFFSETS.contains_drag.apply_pin(self).set(true);
+                    Self::FIELD_OFFSETS.drag_enter.apply_pin(self).call(&(data,));
+                }
+                if let Some(x) = window_adapter.internal(crate::InternalToken) {
+                    x.set_mouse_cursor(MouseCursor::Copy);
+                }
+                InputEventResult::EventAccepted
+            }
+            MouseEvent::Drop { .. } => {
+                let Some(data) = self.accepted_data(window_adapter) else {
+                    self.leave();
+                    return InputEventResult::EventIgnored;
+                };
+                Self::FIELD_OFFSETS.contains_drag.apply_pin(self).set(false);
+                Self::FIELD_OFFSETS.dropped.apply_pin(self).call(&(data,));
+                InputEventResult::EventAccepted
+            }
+            MouseEvent::Exit => {
+                self.leave();
+                InputEventResult::EventIgnored
+            }
+            _ => InputEventResult::EventIgnored,
+        }
+    }
+
+    fn key_event(
+        self: Pin<&Self>,
+        _: &KeyEvent,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+        _self_rc: &ItemRc,
+    ) -> KeyEventResult {
+        KeyEventResult::EventIgnored
+    }
+
+    fn focus_event(
+        self: Pin<&Self>,
+        _: &FocusEvent,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+        _self_rc: &ItemRc,
+    ) -> FocusEventResult {
+        FocusEventResult::FocusIgnored
+    }
+
+    fn render(
+        self: Pin<&Self>,
+        _backend: &mut ItemRendererRef,
+        _self_rc: &ItemRc,
+        _size: LogicalSize,
+    ) -> RenderingResult {
+        RenderingResult::ContinueRenderingChildren
+    }
+}
+
+impl DropArea {
+    /// Returns the data of the current drag operation if this area accepts it
+    fn accepted_data(
+        self: Pin<&Self>,
+        window_adapter: &Rc<dyn WindowAdapter>,
+    ) -> Option<DropEvent> {
+        if !self.enabled() {
+            return None;
+        }
+        let data = WindowInner::from_pub(window_adapter.window()).drag_data()?;
+        let mime_type = self.mime_type();
+        (mime_type.is_empty() || mime_type == data.mime_type).then_some(data)
+    }
+
+    fn leave(self: Pin<&Self>) {
+        if self.contains_drag() {
+            Self::FIELD_OFFSETS.contains_drag.apply_pin(self).set(false);
+            Self::FIELD_OFFSETS.drag_leave.apply_pin(self).call(&());
+        }
+    }
+}
+
+impl ItemConsts for DropArea {
+    const cached_rendering_data_offset: const_field_offset::FieldOffset<
+        DropArea,
+        CachedRenderingData,
+    > = DropArea::FIELD_OFFSETS.cached_rendering_data.as_unpinned_projection();
+}
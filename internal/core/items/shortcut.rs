@@ -0,0 +1,226 @@
+// Copyright © SixtyFPS GmbH <info@slint.dev>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-Royalty-free-1.1 OR LicenseRef-Slint-commercial
+
+//! The `Shortcut` item
+
+use super::{
+    Item, ItemConsts, ItemRc, ItemRendererRef, KeyEventResult, KeyboardModifiers, RenderingResult,
+    VoidArg,
+};
+use crate::input::{
+    FocusEvent, FocusEventResult, InputEventFilterResult, InputEventResult, KeyEvent, MouseEvent,
+    StandardShortcut,
+};
+use crate::item_rendering::CachedRenderingData;
+use crate::layout::{LayoutInfo, Orientation};
+use crate::lengths::LogicalSize;
+#[cfg(feature = "rtti")]
+use crate::rtti::*;
+use crate::window::WindowAdapter;
+use crate::{Callback, Property, SharedString};
+use alloc::rc::Rc;
+use const_field_offset::FieldOffsets;
+use core::pin::Pin;
+use i_slint_core_macros::*;
+
+/// The implementation of the `Shortcut` element
+#[repr(C)]
+#[derive(FieldOffsets, Default, SlintElement)]
+#[pin]
+pub struct Shortcut {
+    pub keys: Property<SharedString>,
+    pub enabled: Property<bool>,
+    pub activated: Callback<VoidArg>,
+    /// FIXME: remove this
+    pub cached_rendering_data: CachedRenderingData,
+}
+
+impl Item for Shortcut {
+    fn init(self: Pin<&Self>, _self_rc: &ItemRc) {}
+
+    fn layout_info(
+        self: Pin<&Self>,
+        _orientation: Orientation,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+    ) -> LayoutInfo {
+        LayoutInfo::default()
+    }
+
+    fn input_event_filter_before_children(
+        self: Pin<&Self>,
+        _: MouseEvent,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+        _self_rc: &ItemRc,
+    ) -> InputEventFilterResult {
+        InputEventFilterResult::ForwardAndIgnore
+    }
+
+    fn input_event(
+        self: Pin<&Self>,
+        _: MouseEvent,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+        _self_rc: &ItemRc,
+    ) -> InputEventResult {
+        InputEventResult::EventIgnored
+    }
+
+    fn key_event(
+        self: Pin<&Self>,
+        _: &KeyEvent,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+        _self_rc: &ItemRc,
+    ) -> KeyEventResult {
+        KeyEventResult::EventIgnored
+    }
+
+    fn focus_event(
+        self: Pin<&Self>,
+        _: &FocusEvent,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+        _self_rc: &ItemRc,
+    ) -> FocusEventResult {
+        FocusEventResult::FocusIgnored
+    }
+
+    fn render(
+        self: Pin<&Self>,
+        _backend: &mut ItemRendererRef,
+        _self_rc: &ItemRc,
+        _size: LogicalSize,
+    ) -> RenderingResult {
+        RenderingResult::ContinueRenderingChildren
+    }
+}
+
+impl Shortcut {
+    /// Returns true if this shortcut is enabled and the event is the key combination of its `keys`
+    pub fn matches(self: Pin<&Self>, event: &KeyEvent) -> bool {
+        self.enabled() && keys_match(&self.keys(), event)
+    }
+
+    /// Invoke the `activated` callback
+    pub fn activate(self: Pin<&Self>) {
+        Self::FIELD_OFFSETS.activated.apply_pin(self).call(&());
+    }
+}
+
+impl ItemConsts for Shortcut {
+    const cached_rendering_data_offset: const_field_offset::FieldOffset<
+        Shortcut,
+        CachedRenderingData,
+    > = Shortcut::FIELD_OFFSETS.cached_rendering_data.as_unpinned_projection();
+}
+
+fn standard_shortcut_from_name(name: &str) -> Option<StandardShortcut> {
+    Some(match name {
+        "Copy" => StandardShortcut::Copy,
+        "Cut" => StandardShortcut::Cut,
+        "Paste" => StandardShortcut::Paste,
+        "SelectAll" => StandardShortcut::SelectAll,
+        "Find" => StandardShortcut::Find,
+        "Save" => StandardShortcut::Save,
+        "Print" => StandardShortcut::Print,
+        "Undo" => StandardShortcut::Undo,
+        "Redo" => StandardShortcut::Redo,
+        "Refresh" => StandardShortcut::Refresh,
+        _ => return None,
+    })
+}
+
+fn special_key_from_name(name: &str) -> Option<char> {
+    macro_rules! lookup_special_key {
+        ($($char:literal # $name:ident # $($_qt:ident)|* # $($_winit:ident $(($_pos:ident))?)|*    # $($_xkb:ident)|*;)*) => {
+            match name {
+                $(stringify!($name) => Some($char),)*
+                _ => None,
+            }
+        };
+    }
+    i_slint_common::for_each_special_keys!(lookup_special_key)
+}
+
+/// Returns true if the event matches a key combination such as `"Ctrl+Shift+Z"`, `"F5"`,
+/// or the name of a platform standard shortcut such as `"Copy"`.
+fn keys_match(keys: &str, event: &KeyEvent) -> bool {
+    let keys = keys.trim();
+    if let Some(standard) = standard_shortcut_from_name(keys) {
+        return event.shortcut() == Some(standard);
+    }
+
+    // The last component is the key. A trailing "++" means the `+` key.
+    let (modifier_names, key) = match keys.strip_suffix("++") {
+        Some(modifier_names) => (modifier_names, "+"),
+        None if keys == "+" => ("", keys),
+        None => keys.rsplit_once('+').unwrap_or(("", keys)),
+    };
+    let mut modifiers = KeyboardModifiers::default();
+    for name in modifier_names.split('+').map(str::trim).filter(|name| !name.is_empty()) {
+        match name {
+            "Ctrl" | "Control" => modifiers.control = true,
+            "Shift" => modifiers.shift = true,
+            "Alt" => modifiers.alt = true,
+            "Meta" => modifiers.meta = true,
+            _ => return false,
+        }
+    }
+
+    let key = key.trim();
+    let key = match special_key_from_name(key) {
+        Some(key) => key,
+        None => {
+            let mut chars = key.chars();
+            match (chars.next(), chars.next()) {
+                (Some(key), None) => key,
+                _ => return false,
+            }
+        }
+    };
+
+    let mut event_chars = event.text.chars();
+    let Some(event_key) = event_chars.next().filter(|_| event_chars.next().is_none()) else {
+        return false;
+    };
+
+    let mut event_modifiers = event.modifiers;
+    if key.is_alphabetic() {
+        if !event_key.to_lowercase().eq(key.to_lowercase()) {
+            return false;
+        }
+    } else {
+        if event_key != key {
+            return false;
+        }
+        if key.is_ascii_punctuation() {
+            // The Shift key may be needed to type a symbol such as `?`, it doesn't matter
+            event_modifiers.shift = modifiers.shift;
+        }
+    }
+    event_modifiers == modifiers
+}
+
+#[test]
+fn test_keys_match() {
+    use crate::input::key_codes;
+    let event = |text: &str, control: bool, shift: bool| KeyEvent {
+        text: text.into(),
+        modifiers: KeyboardModifiers { control, shift, ..Default::default() },
+        ..Default::default()
+    };
+
+    assert!(keys_match("Ctrl+S", &event("s", true, false)));
+    assert!(keys_match("Control + s", &event("s", true, false)));
+    assert!(!keys_match("Ctrl+S", &event("s", false, false)));
+    assert!(!keys_match("Ctrl+S", &event("s", true, true)));
+    assert!(keys_match("Ctrl+Shift+S", &event("S", true, true)));
+    assert!(keys_match("F5", &event(&key_codes::F5.to_string(), false, false)));
+    assert!(!keys_match("Shift+F5", &event(&key_codes::F5.to_string(), false, false)));
+    assert!(keys_match("Escape", &event(&key_codes::Escape.to_string(), false, false)));
+    assert!(keys_match("?", &event("?", false, true)));
+    assert!(keys_match("Ctrl++", &event("+", true, false)));
+    assert!(keys_match("+", &event("+", false, false)));
+    assert!(keys_match("Save", &event("s", true, false)));
+    assert!(!keys_match("Save", &event("s", false, false)));
+    assert!(!keys_match("Ctrl+Foo", &event("f", true, false)));
+    assert!(!keys_match("Hyper+S", &event("s", false, false)));
+    assert!(!keys_match("", &event("s", false, false)));
+}
@@ -9,8 +9,8 @@
 */
 use super::{
     InputType, Item, ItemConsts, ItemRc, KeyEventResult, KeyEventType, PointArg,
-    PointerEventButton, RenderingResult, TextHorizontalAlignment, TextOverflow,
-    TextVerticalAlignment, TextWrap, VoidArg,
+    PointerEventButton, RenderingResult, StringArg, TextDirection, TextHorizontalAlignment,
+    TextOverflow, TextVerticalAlignment, TextWrap, VoidArg,
 };
 use crate::graphics::{Brush, Color, FontRequest};
 use crate::input::{
@@ -23,11 +23,14 @@
 use crate::platform::Clipboard;
 #[cfg(feature = "rtti")]
 use crate::rtti::*;
+use crate::textlayout::{text_decorations, StyledString, StyledTextLayout, TextStyle};
 use crate::window::{InputMethodProperties, InputMethodRequest, WindowAdapter, WindowInner};
 use crate::{Callback, Coord, Property, SharedString, SharedVector};
 use alloc::rc::Rc;
 #[cfg(not(feature = "std"))]
 use alloc::string::String;
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
 use const_field_offset::FieldOffsets;
 use core::cell::Cell;
 use core::pin::Pin;
@@ -50,6 +53,7 @@ pub struct Text {
     pub vertical_alignment: Property<TextVerticalAlignment>,
     pub wrap: Property<TextWrap>,
     pub overflow: Property<TextOverflow>,
+    pub text_direction: Property<TextDirection>,
     pub letter_spacing: Property<LogicalLength>,
     pub width: Property<LogicalLength>,
     pub height: Property<LogicalLength>,
@@ -201,6 +205,319 @@ pub fn font_request(self: Pin<&Self>, window: &WindowInner) -> FontRequest {
     }
 }
 
+/// The implementation of the `StyledText` element
+#[repr(C)]
+#[derive(FieldOffsets, Default, SlintElement)]
+#[pin]
+pub struct StyledText {
+    pub text: Property<SharedString>,
+    pub font_family: Property<SharedString>,
+    pub font_size: Property<LogicalLength>,
+    pub font_weight: Property<i32>,
+    pub font_italic: Property<bool>,
+    pub color: Property<Brush>,
+    pub link_color: Property<Brush>,
+    pub horizontal_alignment: Property<TextHorizontalAlignment>,
+    pub vertical_alignment: Property<TextVerticalAlignment>,
+    pub wrap: Property<TextWrap>,
+    pub width: Property<LogicalLength>,
+    pub height: Property<LogicalLength>,
+    pub link_clicked: Callback<StringArg>,
+    pub cached_rendering_data: CachedRenderingData,
+    /// The target of the link that was pressed, or an empty string
+    pressed_link: Cell<SharedString>,
+    /// true when the mouse cursor was set because the mouse is over a link
+    has_link_hover: Cell<bool>,
+}
+
+impl Item for StyledText {
+    fn init(self: Pin<&Self>, _self_rc: &ItemRc) {}
+
+    fn layout_info(
+        self: Pin<&Self>,
+        orientation: Orientation,
+        window_adapter: &Rc<dyn WindowAdapter>,
+    ) -> LayoutInfo {
+        let styled = StyledString::from_markdown(self.text().as_str());
+        match orientation {
+            Orientation::Horizontal => {
+                let width = self.layout(&styled, None, window_adapter).size.width.ceil();
+                let min = match self.wrap() {
+                    TextWrap::NoWrap => width,
+                    TextWrap::WordWrap => 0 as Coord,
+                };
+                LayoutInfo { min, preferred: width, ..LayoutInfo::default() }
+            }
+            Orientation::Vertical => {
+                let max_width = match self.wrap() {
+                    TextWrap::NoWrap => None,
+                    TextWrap::WordWrap => Some(self.width().get()),
+                };
+                let h = self.layout(&styled, max_width, window_adapter).size.height.ceil();
+                LayoutInfo { min: h, preferred: h, ..LayoutInfo::default() }
+            }
+        }
+    }
+
+    fn input_event_filter_before_children(
+        self: Pin<&Self>,
+        _: MouseEvent,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+        _self_rc: &ItemRc,
+    ) -> InputEventFilterResult {
+        InputEventFilterResult::ForwardEvent
+    }
+
+    fn input_event(
+        self: Pin<&Self>,
+        event: MouseEvent,
+        window_adapter: &Rc<dyn WindowAdapter>,
+        self_rc: &ItemRc,
+    ) -> InputEventResult {
+        let link_at = |position: LogicalPoint| {
+            let styled = StyledString::from_markdown(self.text().as_str());
+            let layout = self.aligned_layout(&styled, self_rc.geometry().size, window_adapter);
+            layout
+                .run_at(position)
+                .and_then(|run| styled.runs[run.run_index].style.link.clone())
+                .filter(|link| !link.is_empty())
+        };
+        let set_link_hover = |hover: bool| {
+            if self.has_link_hover.replace(hover) != hover {
+                if let Some(x) = window_adapter.internal(crate::InternalToken) {
+                    x.set_mouse_cursor(if hover {
+                        super::MouseCursor::Pointer
+                    } else {
+                        super::MouseCursor::Default
+                    });
+                }
+            }
+        };
+        match event {
+            MouseEvent::Pressed { position, button: PointerEventButton::Left, .. } => {
+                match link_at(position) {
+                    Some(link) => {
+                        self.pressed_link.set(link);
+                        InputEventResult::GrabMouse
+                    }
+                    None => InputEventResult::EventIgnored,
+                }
+            }
+            MouseEvent::Released { position, button: PointerEventButton::Left, .. } => {
+                let pressed_link = self.pressed_link.take();
+                if pressed_link.is_empty() {
+                    return InputEventResult::EventIgnored;
+                }
+                if link_at(position).as_ref() == Some(&pressed_link) {
+                    Self::FIELD_OFFSETS.link_clicked.apply_pin(self).call(&(pressed_link,));
+                }
+                InputEventResult::EventAccepted
+            }
+            MouseEvent::Moved { position } => {
+                let hover = link_at(position).is_some();
+                set_link_hover(hover);
+                let pressed_link = self.pressed_link.take();
+                if !pressed_link.is_empty() {
+                    self.pressed_link.set(pressed_link);
+                    InputEventResult::GrabMouse
+                } else if hover {
+                    InputEventResult::EventAccepted
+                } else {
+                    InputEventResult::EventIgnored
+                }
+            }
+            MouseEvent::Exit => {
+                set_link_hover(false);
+                self.pressed_link.take();
+                InputEventResult::EventIgnored
+            }
+            _ => InputEventResult::EventIgnored,
+        }
+    }
+
+    fn key_event(
+        self: Pin<&Self>,
+        _: &KeyEvent,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+        _self_rc: &ItemRc,
+    ) -> KeyEventResult {
+        KeyEventResult::EventIgnored
+    }
+
+    fn focus_event(
+        self: Pin<&Self>,
+        _: &FocusEvent,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+        _self_rc: &ItemRc,
+    ) -> FocusEventResult {
+        FocusEventResult::FocusIgnored
+    }
+
+    fn render(
+        self: Pin<&Self>,
+        backend: &mut &mut dyn ItemRenderer,
+        self_rc: &ItemRc,
+        size: LogicalSize,
+    ) -> RenderingResult {
+        (*backend).draw_styled_text(self, self_rc, size);
+        RenderingResult::ContinueRenderingChildren
+    }
+}
+
+impl ItemConsts for StyledText {
+    const cached_rendering_data_offset: const_field_offset::FieldOffset<
+        StyledText,
+        CachedRenderingData,
+    > = StyledText::FIELD_OFFSETS.cached_rendering_data.as_unpinned_projection();
+}
+
+/// A run of a [`StyledText`] to draw as a single line of text, see [`StyledText::render_runs`]
+pub struct StyledTextRenderRun {
+    pub text: SharedString,
+    pub font_request: FontRequest,
+    pub brush: Brush,
+    /// The geometry of the text, relative to the element
+    pub rect: LogicalRect,
+    /// The underline and strikethrough lines, to be filled with the brush
+    pub decorations: SharedVector<LogicalRect>,
+}
+
+impl StyledText {
+    pub fn font_request(self: Pin<&Self>, window: &WindowInner) -> FontRequest {
+        let window_item = window.window_item();
+
+        FontRequest {
+            family: {
+                let maybe_family = self.font_family();
+                if !maybe_family.is_empty() {
+                    Some(maybe_family)
+                } else {
+                    window_item.as_ref().and_then(|item| item.as_pin_ref().font_family())
+                }
+            },
+            weight: {
+                let weight = self.font_weight();
+                if weight == 0 {
+                    window_item.as_ref().and_then(|item| item.as_pin_ref().font_weight())
+                } else {
+                    Some(weight)
+                }
+            },
+            pixel_size: {
+                let font_size = self.font_size();
+                if font_size.get() == 0 as Coord {
+                    window_item.as_ref().and_then(|item| item.as_pin_ref().font_size())
+                } else {
+                    Some(font_size)
+                }
+            },
+            letter_spacing: None,
+            italic: self.font_italic(),
+        }
+    }
+
+    /// Returns the font request for a run of text with the given style
+    fn font_request_for_style(font_request: &FontRequest, style: &TextStyle) -> FontRequest {
+        FontRequest {
+            weight: if style.bold {
+                Some(font_request.weight.unwrap_or(400).max(700))
+            } else {
+                font_request.weight
+            },
+            italic: font_request.italic || style.italic,
+            ..font_request.clone()
+        }
+    }
+
+    fn layout(
+        self: Pin<&Self>,
+        styled: &StyledString,
+        max_width: Option<Coord>,
+        window_adapter: &Rc<dyn WindowAdapter>,
+    ) -> StyledTextLayout {
+        let font_request = self.font_request(WindowInner::from_pub(window_adapter.window()));
+        let scale_factor = ScaleFactor::new(window_adapter.window().scale_factor());
+        let renderer = window_adapter.renderer();
+        StyledTextLayout::new(
+            styled,
+            |text, style| {
+                let font_request = Self::font_request_for_style(&font_request, style);
+                let measure =
+                    |text| renderer.text_size(font_request.clone(), text, None, scale_factor);
+                // Not all renderers include the trailing whitespace in the width, so measure it separately
+                let trimmed = text.trim_end();
+                let mut size = measure(if trimmed.is_empty() { " " } else { trimmed });
+                if trimmed.is_empty() {
+                    size.width = 0 as Coord;
+                }
+                let trailing_whitespace = text[trimmed.len()..].chars().count();
+                if trailing_whitespace > 0 {
+                    let space_width = measure("x x").width - measure("xx").width;
+                    size.width += space_width * trailing_whitespace as Coord;
+                }
+                size
+            },
+            max_width,
+        )
+    }
+
+    fn aligned_layout(
+        self: Pin<&Self>,
+        styled: &StyledString,
+        size: LogicalSize,
+        window_adapter: &Rc<dyn WindowAdapter>,
+    ) -> StyledTextLayout {
+        let max_width = match self.wrap() {
+            TextWrap::NoWrap => None,
+            TextWrap::WordWrap => Some(size.width),
+        };
+        let mut layout = self.layout(styled, max_width, window_adapter);
+        layout.align(size, self.horizontal_alignment(), self.vertical_alignment());
+        layout
+    }
+
+    /// Lays out the text within the given size, and returns the runs that the renderer needs
+    /// to draw, each as a single line of text.
+    pub fn render_runs(
+        self: Pin<&Self>,
+        size: LogicalSize,
+        window_adapter: &Rc<dyn WindowAdapter>,
+    ) -> Vec<StyledTextRenderRun> {
+        let styled = StyledString::from_markdown(self.text().as_str());
+        let layout = self.aligned_layout(&styled, size, window_adapter);
+        let font_request = self.font_request(WindowInner::from_pub(window_adapter.window()));
+        let font_size = font_request
+            .pixel_size
+            .unwrap_or_else(|| window_adapter.renderer().default_font_size())
+            .get();
+        layout
+            .runs
+            .iter()
+            .filter_map(|run| {
+                let style = &styled.runs[run.run_index].style;
+                let text = styled.text[run.range.clone()].trim_end();
+                let decorations: SharedVector<_> =
+                    text_decorations(&run.rect, style, font_size).collect();
+                if text.is_empty() && decorations.is_empty() {
+                    return None;
+                }
+                let brush = match (style.color, &style.link) {
+                    (Some(color), _) => Brush::SolidColor(color),
+                    (None, Some(_)) => self.link_color(),
+                    (None, None) => self.color(),
+                };
+                Some(StyledTextRenderRun {
+                    text: text.into(),
+                    font_request: Self::font_request_for_style(&font_request, style),
+                    brush,
+                    rect: run.rect,
+                    decorations,
+                })
+            })
+            .collect()
+    }
+}
+
 #[repr(C)]
 #[derive(Default, Clone, Copy, PartialEq)]
 /// Similar as `Option<core::ops::Range<i32>>` but `repr(C)`
@@ -257,6 +574,7 @@ pub struct TextInput {
     pub horizontal_alignment: Property<TextHorizontalAlignment>,
     pub vertical_alignment: Property<TextVerticalAlignment>,
     pub wrap: Property<TextWrap>,
+    pub text_direction: Property<TextDirection>,
     pub input_type: Property<InputType>,
     pub letter_spacing: Property<LogicalLength>,
     pub width: Property<LogicalLength>,
@@ -456,10 +774,17 @@ fn key_event(
             KeyEventType::KeyPressed => {
                 match event.text_shortcut() {
                     Some(text_shortcut) if !self.read_only() => match text_shortcut {
+                        TextShortcut::Move(
+                            TextCursorDirection::PreviousLine | TextCursorDirection::NextLine,
+                        ) if self.single_line() => {
+                            // There is no other line: let the parent handle the key, for example
+                            // to move in a list of suggestions
+                            return KeyEventResult::EventIgnored;
+                        }
                         TextShortcut::Move(direction) => {
                             TextInput::move_cursor(
                                 self,
-                                direction,
+                                self.visual_cursor_direction(direction),
                                 event.modifiers.into(),
                                 TextChangeNotify::TriggerCallbacks,
                                 window_adapter,
@@ -956,19 +1281,21 @@ fn move_cursor(
             TextCursorDirection::BackwardByWord => {
                 prev_word_boundary(&text, last_cursor_pos.saturating_sub(1))
             }
-            TextCursorDirection::StartOfLine => {
+            TextCursorDirection::StartOfLine | TextCursorDirection::EndOfLine => {
                 let cursor_rect = self.cursor_rect_for_byte_offset(last_cursor_pos, window_adapter);
                 let mut cursor_xy_pos = cursor_rect.center();
 
+                // The logical start and end of the line are its two visual ends, in either order
+                // depending on the direction of the paragraph
                 cursor_xy_pos.x = 0 as Coord;
-                self.byte_offset_for_position(cursor_xy_pos, window_adapter)
-            }
-            TextCursorDirection::EndOfLine => {
-                let cursor_rect = self.cursor_rect_for_byte_offset(last_cursor_pos, window_adapter);
-                let mut cursor_xy_pos = cursor_rect.center();
-
+                let left = self.byte_offset_for_position(cursor_xy_pos, window_adapter);
                 cursor_xy_pos.x = Coord::MAX;
-                self.byte_offset_for_position(cursor_xy_pos, window_adapter)
+                let right = self.byte_offset_for_position(cursor_xy_pos, window_adapter);
+                if matches!(direction, TextCursorDirection::StartOfLine) {
+                    left.min(right)
+                } else {
+                    left.max(right)
+                }
             }
             TextCursorDirection::StartOfParagraph => {
                 prev_paragraph_boundary(&text, last_cursor_pos.saturating_sub(1))
@@ -1001,6 +1328,30 @@ fn move_cursor(
         new_cursor_pos != last_cursor_pos
     }
 
+    /// The arrow keys move the cursor visually: in a right-to-left paragraph the left arrow moves the
+    /// cursor forward in the text. Home and end are not affected, they move to the logical start and end
+    /// of the line.
+    fn visual_cursor_direction(
+        self: Pin<&Self>,
+        direction: TextCursorDirection,
+    ) -> TextCursorDirection {
+        let text = self.text();
+        if !crate::textlayout::is_rtl_paragraph(
+            &text,
+            self.cursor_position(&text),
+            self.text_direction(),
+        ) {
+            return direction;
+        }
+        match direction {
+            TextCursorDirection::Forward => TextCursorDirection::Backward,
+            TextCursorDirection::Backward => TextCursorDirection::Forward,
+            TextCursorDirection::ForwardByWord => TextCursorDirection::BackwardByWord,
+            TextCursorDirection::BackwardByWord => TextCursorDirection::ForwardByWord,
+            direction => direction,
+        }
+    }
+
     fn set_cursor_position(
         self: Pin<&Self>,
         new_position: i32,
@@ -5,7 +5,7 @@
 
 // cspell:ignore coord
 
-use crate::items::{DialogButtonRole, LayoutAlignment};
+use crate::items::{DialogButtonRole, FlexAlignment, FlexDirection, FlexWrap, LayoutAlignment};
 use crate::{slice::Slice, Coord, SharedVector};
 use alloc::vec::Vec;
 
@@ -505,11 +505,13 @@ pub struct BoxLayoutCellData {
 
 /// Solve a BoxLayout
 pub fn solve_box_layout(data: &BoxLayoutData, repeater_indexes: Slice<u32>) -> SharedVector<Coord> {
-    let mut result = SharedVector::<Coord>::default();
-    result.resize(data.cells.len() * 2 + repeater_indexes.len(), 0 as _);
+    layout_cache_with_repeaters(&box_layout_items(data), repeater_indexes)
+}
 
+/// Compute the position and size of the cells of a BoxLayout
+fn box_layout_items(data: &BoxLayoutData) -> Vec<grid_internal::LayoutData> {
     if data.cells.is_empty() {
-        return result;
+        return Vec::new();
     }
 
     let mut layout_data: Vec<_> = data
@@ -575,7 +577,17 @@ pub fn solve_box_layout(data: &BoxLayoutData, repeater_indexes: Slice<u32>) -> S
             pos += spacing + it.size;
         }
     }
+    layout_data
+}
 
+/// Return the layout cache for the given cells, with the indirection for the repeated items
+/// described by `repeater_indexes`
+fn layout_cache_with_repeaters(
+    layout_data: &[grid_internal::LayoutData],
+    repeater_indexes: Slice<u32>,
+) -> SharedVector<Coord> {
+    let mut result = SharedVector::<Coord>::default();
+    result.resize(layout_data.len() * 2 + repeater_indexes.len(), 0 as _);
     let res = result.make_mut_slice();
 
     // The index/2 in result in which we should add the next repeated item
@@ -656,6 +668,253 @@ pub fn box_layout_info_ortho(cells: Slice<BoxLayoutCellData>, padding: &Padding)
     fold
 }
 
+#[repr(C)]
+#[derive(Debug)]
+/// The FlexboxLayoutData is used to represent a FlexboxLayout.
+/// `cells_horizontal` and `cells_vertical` contain the constraints of the same items, in both orientations.
+/// The cells that are not needed for a computation can be left empty: the cells across the main axis
+/// are only needed for the orientation across the main axis.
+pub struct FlexboxLayoutData<'a> {
+    pub width: Coord,
+    pub height: Coord,
+    pub spacing_horizontal: Coord,
+    pub spacing_vertical: Coord,
+    pub padding_horizontal: Padding,
+    pub padding_vertical: Padding,
+    pub direction: FlexDirection,
+    pub wrap: FlexWrap,
+    pub justify_content: LayoutAlignment,
+    pub align_items: FlexAlignment,
+    pub cells_horizontal: Slice<'a, BoxLayoutCellData>,
+    pub cells_vertical: Slice<'a, BoxLayoutCellData>,
+}
+
+impl FlexboxLayoutData<'_> {
+    fn main_orientation(&self) -> Orientation {
+        match self.direction {
+            FlexDirection::Row => Orientation::Horizontal,
+            FlexDirection::Column => Orientation::Vertical,
+        }
+    }
+
+    /// Return the size, the spacing, the padding and the cells along the given orientation
+    fn axis(&self, orientation: Orientation) -> (Coord, Coord, Padding, &[BoxLayoutCellData]) {
+        match orientation {
+            Orientation::Horizontal => (
+                self.width,
+                self.spacing_horizontal,
+                self.padding_horizontal,
+                self.cells_horizontal.as_slice(),
+            ),
+            Orientation::Vertical => (
+                self.height,
+                self.spacing_vertical,
+                self.padding_vertical,
+                self.cells_vertical.as_slice(),
+            ),
+        }
+    }
+
+    /// Split the items into lines along the main axis, each line takes as many items as fit with
+    /// their preferred size.
+    fn lines(&self) -> Vec<core::ops::Range<usize>> {
+        let (size, spacing, padding, cells) = self.axis(self.main_orientation());
+        if cells.is_empty() {
+            return Vec::new();
+        }
+        if self.wrap == FlexWrap::NoWrap {
+            return alloc::vec![0..cells.len()];
+        }
+        let available = size - padding.begin - padding.end;
+        let mut lines = Vec::new();
+        let mut start = 0;
+        let mut used = 0 as Coord;
+        for (idx, cell) in cells.iter().enumerate() {
+            let (_, _, pref) = bounded_constraint(&cell.constraint, size);
+            if idx == start {
+                used = pref;
+            } else if used + spacing + pref > available {
+                lines.push(start..idx);
+                start = idx;
+                used = pref;
+            } else {
+                used += spacing + pref;
+            }
+        }
+        lines.push(start..cells.len());
+        lines
+    }
+}
+
+/// Return the minimum, maximum and preferred size of a constraint, when the size of the layout is `size`
+fn bounded_constraint(constraint: &LayoutInfo, size: Coord) -> (Coord, Coord, Coord) {
+    let min = constraint.min.max(constraint.min_percent * size / 100 as Coord);
+    let max = constraint.max.min(constraint.max_percent * size / 100 as Coord);
+    (min, max, constraint.preferred.min(max).max(min))
+}
+
+/// Solve a FlexboxLayout in the given orientation.
+///
+/// Along the main axis, each line is laid out like a BoxLayout aligned with `justify_content`.
+/// Across the main axis, the remaining space is shared between the lines, and the items are aligned
+/// within their line with `align_items`.
+pub fn solve_flexbox_layout(
+    data: &FlexboxLayoutData,
+    orientation: Orientation,
+    repeater_indexes: Slice<u32>,
+) -> SharedVector<Coord> {
+    let (size, spacing, padding, cells) = data.axis(orientation);
+    let lines = data.lines();
+    let mut layout_data = alloc::vec![grid_internal::LayoutData::default(); cells.len()];
+
+    if orientation == data.main_orientation() {
+        for line in lines {
+            let line_data = BoxLayoutData {
+                size,
+                spacing,
+                padding,
+                alignment: data.justify_content,
+                cells: Slice::from_slice(&cells[line.clone()]),
+            };
+            layout_data[line].clone_from_slice(&box_layout_items(&line_data));
+        }
+    } else {
+        let mut line_data: Vec<_> = lines
+            .iter()
+            .map(|line| {
+                let (min, pref) =
+                    cells[line.clone()].iter().fold((0 as Coord, 0 as Coord), |(min, pref), c| {
+                        let (c_min, _, c_pref) = bounded_constraint(&c.constraint, size);
+                        (min.max(c_min), pref.max(c_pref))
+                    });
+                grid_internal::LayoutData { min, pref, stretch: 1., ..Default::default() }
+            })
+            .collect();
+        if !line_data.is_empty() {
+            grid_internal::layout_items(
+                &mut line_data,
+                padding.begin,
+                size - padding.begin - padding.end,
+                spacing,
+            );
+        }
+        for (line, line_data) in lines.into_iter().zip(line_data) {
+            for idx in line {
+                let (min, max, pref) = bounded_constraint(&cells[idx].constraint, size);
+                let it = &mut layout_data[idx];
+                it.size = match data.align_items {
+                    FlexAlignment::Stretch => line_data.size.min(max).max(min),
+                    _ => pref,
+                };
+                it.pos = match data.align_items {
+                    FlexAlignment::Stretch | FlexAlignment::Start => line_data.pos,
+                    FlexAlignment::Center => {
+                        line_data.pos + (line_data.size - it.size) / 2 as Coord
+                    }
+                    FlexAlignment::End => line_data.pos + line_data.size - it.size,
+                };
+            }
+        }
+    }
+    layout_cache_with_repeaters(&layout_data, repeater_indexes)
+}
+
+/// Return the LayoutInfo for a FlexboxLayout in the given orientation.
+///
+/// Across the main axis, this depends on the size of the layout along the main axis, because it
+/// determines how the items wrap.
+pub fn flexbox_layout_info(data: &FlexboxLayoutData, orientation: Orientation) -> LayoutInfo {
+    let (_, spacing, padding, cells) = data.axis(orientation);
+    let cells = Slice::from_slice(cells);
+    if data.wrap == FlexWrap::NoWrap {
+        return if orientation == data.main_orientation() {
+            box_layout_info(cells, spacing, &padding, data.justify_content)
+        } else {
+            box_layout_info_ortho(cells, &padding)
+        };
+    }
+    if cells.is_empty() {
+        return LayoutInfo { max: 0 as _, ..LayoutInfo::default() };
+    }
+    let extra_w = padding.begin + padding.end;
+    if orientation == data.main_orientation() {
+        // When wrapping, the layout can be as narrow as its largest item
+        let min =
+            cells.iter().map(|c| c.constraint.min).fold(0 as Coord, |a, b| a.max(b)) + extra_w;
+        LayoutInfo {
+            min,
+            max: Coord::MAX,
+            ..box_layout_info(cells, spacing, &padding, data.justify_content)
+        }
+    } else {
+        let lines = data.lines();
+        let extra_w = extra_w + spacing * (lines.len() - 1) as Coord;
+        let (min, preferred) =
+            lines.into_iter().fold((extra_w, extra_w), |(min, preferred), line| {
+                let line_cells = &cells[line];
+                let line_min =
+                    line_cells.iter().map(|c| c.constraint.min).fold(0 as Coord, |a, b| a.max(b));
+                let line_preferred = line_cells
+                    .iter()
+                    .map(|c| c.constraint.preferred_bounded())
+                    .fold(0 as Coord, |a, b| a.max(b));
+                (min + line_min, preferred + line_preferred)
+            });
+        let stretch = cells.iter().map(|c| c.constraint.stretch).fold(f32::MAX, f32::min);
+        LayoutInfo {
+            min,
+            max: Coord::MAX,
+            min_percent: 0 as _,
+            max_percent: 100 as _,
+            preferred,
+            stretch,
+        }
+    }
+}
+
+#[test]
+#[allow(clippy::float_cmp)] // We want bit-wise equality here
+fn test_flexbox_layout_wrap() {
+    let cell = |min, preferred| BoxLayoutCellData {
+        constraint: LayoutInfo { min, preferred, stretch: 1., ..Default::default() },
+    };
+    let cells_horizontal = [cell(10., 40.), cell(10., 40.), cell(10., 40.)];
+    let cells_vertical = [cell(5., 20.), cell(5., 20.), cell(5., 20.)];
+    let data = FlexboxLayoutData {
+        width: 100.,
+        height: 100.,
+        spacing_horizontal: 10.,
+        spacing_vertical: 10.,
+        padding_horizontal: Padding::default(),
+        padding_vertical: Padding::default(),
+        direction: FlexDirection::Row,
+        wrap: FlexWrap::Wrap,
+        justify_content: LayoutAlignment::Start,
+        align_items: FlexAlignment::Start,
+        cells_horizontal: Slice::from_slice(&cells_horizontal),
+        cells_vertical: Slice::from_slice(&cells_vertical),
+    };
+
+    let horizontal = solve_flexbox_layout(&data, Orientation::Horizontal, Slice::default());
+    assert_eq!(horizontal.as_slice(), &[0., 40., 50., 40., 0., 40.]);
+    // The two lines share the remaining height
+    let vertical = solve_flexbox_layout(&data, Orientation::Vertical, Slice::default());
+    assert_eq!(vertical.as_slice(), &[0., 20., 0., 20., 55., 20.]);
+
+    let info = flexbox_layout_info(&data, Orientation::Horizontal);
+    assert_eq!((info.min, info.preferred), (10., 140.));
+    let info = flexbox_layout_info(&data, Orientation::Vertical);
+    assert_eq!((info.min, info.preferred), (20., 50.));
+
+    // Everything fits on one line when the layout is wide enough
+    let data = FlexboxLayoutData { width: 200., ..data };
+    let info = flexbox_layout_info(&data, Orientation::Vertical);
+    assert_eq!((info.min, info.preferred), (5., 20.));
+    let data = FlexboxLayoutData { align_items: FlexAlignment::Stretch, ..data };
+    let vertical = solve_flexbox_layout(&data, Orientation::Vertical, Slice::default());
+    assert_eq!(vertical.as_slice(), &[0., 100., 0., 100., 0., 100.]);
+}
+
 /// Given the cells of a layout of a Dialog, re-order the button according to the platform
 ///
 /// This function assume that the `roles` contains the roles of the button which are the first `cells`
@@ -777,6 +1036,25 @@ pub extern "C" fn slint_box_layout_info_ortho(
         super::box_layout_info_ortho(cells, padding)
     }
 
+    #[no_mangle]
+    pub extern "C" fn slint_solve_flexbox_layout(
+        data: &FlexboxLayoutData,
+        orientation: Orientation,
+        repeater_indexes: Slice<u32>,
+        result: &mut SharedVector<Coord>,
+    ) {
+        *result = super::solve_flexbox_layout(data, orientation, repeater_indexes)
+    }
+
+    #[no_mangle]
+    /// Return the LayoutInfo for a FlexboxLayout in the given orientation.
+    pub extern "C" fn slint_flexbox_layout_info(
+        data: &FlexboxLayoutData,
+        orientation: Orientation,
+    ) -> LayoutInfo {
+        super::flexbox_layout_info(data, orientation)
+    }
+
     /// Calls [`reorder_dialog_button_layout`].
     ///
     /// Safety: `cells` must be a pointer to a mutable array of cell data, the array must have at
@@ -7,7 +7,7 @@
 
 use crate::item_tree::ItemTreeVTable;
 use crate::item_tree::TraversalOrder;
-pub use crate::items::{StandardListViewItem, TableColumn};
+pub use crate::items::{StandardListViewItem, StandardTreeViewItem, TableColumn};
 use crate::layout::Orientation;
 use crate::lengths::{LogicalLength, RectLengths};
 use crate::{Coord, Property, SharedString, SharedVector};
@@ -23,9 +23,14 @@
 pub use model_peer::*;
 use once_cell::unsync::OnceCell;
 use pin_project::pin_project;
+pub use tree::{
+    FlattenedTreeModel, TreeIndex, TreeModel, TreeModelChangeListener, TreeModelNotify,
+    TreeModelTracker, TreeNode, VecTreeModel,
+};
 
 mod adapters;
 mod model_peer;
+mod tree;
 
 type ItemTreeRc<C> = vtable::VRc<crate::item_tree::ItemTreeVTable, C>;
 
@@ -1200,16 +1205,47 @@ pub fn instances_vec(&self) -> Vec<ItemTreeRc<C>> {
 
 impl From<SharedString> for StandardListViewItem {
     fn from(value: SharedString) -> Self {
-        StandardListViewItem { text: value }
+        StandardListViewItem { text: value, checked: false }
     }
 }
 
 impl From<&str> for StandardListViewItem {
     fn from(value: &str) -> Self {
-        StandardListViewItem { text: value.into() }
+        StandardListViewItem { text: value.into(), checked: false }
     }
 }
 
+/// Returns a model with the indexes of the rows of `model` that contain `filter`, ignoring the case.
+/// All the rows match an empty filter.
+///
+/// This is used by the `EditableComboBox` widget to show the values matching the text typed by the user.
+#[doc(hidden)]
+pub fn filter_strings<T: TryInto<SharedString>>(model: &ModelRc<T>, filter: &str) -> ModelRc<i32> {
+    model.model_tracker().track_row_count_changes();
+    let filter = filter.to_lowercase();
+    let rows = (0..model.row_count())
+        .filter(|row| {
+            model
+                .row_data_tracked(*row)
+                .and_then(|data| data.try_into().ok())
+                .is_some_and(|text| text.to_lowercase().contains(filter.as_str()))
+        })
+        .map(|row| row as i32)
+        .collect::<Vec<_>>();
+    ModelRc::new(VecModel::from(rows))
+}
+
+#[test]
+fn test_filter_strings() {
+    let model: ModelRc<SharedString> =
+        VecModel::from_slice(&["Apple".into(), "Banana".into(), "Pineapple".into()]);
+    let rows = |filter| filter_strings(&model, filter).iter().collect::<Vec<_>>();
+    assert_eq!(rows("apple"), vec![0, 2]);
+    assert_eq!(rows("AN"), vec![1]);
+    assert_eq!(rows(""), vec![0, 1, 2]);
+    assert_eq!(rows("cherry"), Vec::<i32>::new());
+}
+
 #[test]
 fn test_tracking_model_handle() {
     let model: Rc<VecModel<u8>> = Rc::new(Default::default());
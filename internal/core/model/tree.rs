@@ -0,0 +1,658 @@
+// Copyright © SixtyFPS GmbH <info@slint.dev>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-Royalty-free-1.1 OR LicenseRef-Slint-commercial
+
+//! This module contains the hierarchical models used by the `StandardTreeView`.
+
+use super::*;
+use crate::items::StandardTreeViewItem;
+use alloc::rc::Weak;
+
+/// A node in a hierarchical model is addressed by the path of row indices leading to it:
+/// `&[2]` is the third top level row, `&[2, 0]` is the first child of that row.
+/// The empty path addresses the invisible root, whose children are the top level rows.
+pub type TreeIndex<'a> = &'a [usize];
+
+/// This trait defines the interface that views of a [`TreeModel`] use to be notified of changes.
+/// It is supplied via [`TreeModel::model_tracker`] and implementation usually return a reference
+/// to its field of [`TreeModelNotify`].
+pub trait TreeModelTracker {
+    /// Attach one listener. The listener will be notified when the model changes,
+    /// until it is dropped.
+    fn attach_listener(&self, listener: Weak<dyn TreeModelChangeListener>);
+}
+
+impl TreeModelTracker for () {
+    fn attach_listener(&self, _listener: Weak<dyn TreeModelChangeListener>) {}
+}
+
+/// The counterpart of [`ModelChangeListener`] for hierarchical models.
+pub trait TreeModelChangeListener {
+    /// The data of the row at `index` has changed.
+    fn row_changed(&self, index: TreeIndex);
+    /// `count` rows were inserted at position `index` among the children of `parent`.
+    fn row_added(&self, parent: TreeIndex, index: usize, count: usize);
+    /// `count` rows were removed at position `index` among the children of `parent`.
+    fn row_removed(&self, parent: TreeIndex, index: usize, count: usize);
+    /// The model has been changed in some way and everything needs to be reloaded.
+    fn reset(&self);
+}
+
+/// Dispatch notifications from a [`TreeModel`] to its listeners.
+/// Typically, you would want to put this in the implementation of the TreeModel
+#[derive(Default)]
+pub struct TreeModelNotify {
+    listeners: RefCell<Vec<Weak<dyn TreeModelChangeListener>>>,
+}
+
+impl TreeModelNotify {
+    fn for_each_listener(&self, f: impl Fn(&dyn TreeModelChangeListener)) {
+        self.listeners.borrow_mut().retain(|l| l.strong_count() > 0);
+        // Clone the list, as a listener may attach more listeners while being notified
+        let listeners = self.listeners.borrow().clone();
+        for listener in listeners.iter().filter_map(|l| l.upgrade()) {
+            f(&*listener)
+        }
+    }
+
+    /// Notify the listeners that a specific row was changed
+    pub fn row_changed(&self, index: TreeIndex) {
+        self.for_each_listener(|l| l.row_changed(index))
+    }
+    /// Notify the listeners that rows were added to `parent`
+    pub fn row_added(&self, parent: TreeIndex, index: usize, count: usize) {
+        self.for_each_listener(|l| l.row_added(parent, index, count))
+    }
+    /// Notify the listeners that rows were removed from `parent`
+    pub fn row_removed(&self, parent: TreeIndex, index: usize, count: usize) {
+        self.for_each_listener(|l| l.row_removed(parent, index, count))
+    }
+    /// Notify the listeners that the model has been changed in some way and
+    /// everything needs to be reloaded
+    pub fn reset(&self) {
+        self.for_each_listener(|l| l.reset())
+    }
+}
+
+impl TreeModelTracker for TreeModelNotify {
+    fn attach_listener(&self, listener: Weak<dyn TreeModelChangeListener>) {
+        self.listeners.borrow_mut().push(listener)
+    }
+}
+
+/// A TreeModel provides hierarchical data, where each row can have child rows.
+///
+/// Rows are addressed by their [`TreeIndex`]. The children of a row are only queried when
+/// a view needs them, typically when the row is expanded in a `StandardTreeView`. So a model
+/// can load them lazily in [`Self::row_count`], and implement [`Self::has_children`] to tell
+/// whether a row can be expanded without loading its children.
+///
+/// A TreeModel is shown in a `StandardTreeView` by wrapping it in a [`FlattenedTreeModel`].
+pub trait TreeModel {
+    /// The model data: A model is a set of rows and each row has this data
+    type Data;
+    /// The number of children of the row at `parent`. The empty index returns the number of
+    /// top level rows.
+    fn row_count(&self, parent: TreeIndex) -> usize;
+    /// Returns the data for the row at `index`, or None if it doesn't exist.
+    fn row_data(&self, index: TreeIndex) -> Option<Self::Data>;
+    /// Returns true if the row at `index` has children.
+    ///
+    /// The default implementation calls [`Self::row_count`]. Models that load their children
+    /// lazily should re-implement it.
+    fn has_children(&self, index: TreeIndex) -> bool {
+        self.row_count(index) > 0
+    }
+    /// Sets the data for the row at `index`.
+    ///
+    /// The default implementation does nothing and prints a warning in debug builds.
+    fn set_row_data(&self, _index: TreeIndex, _data: Self::Data) {
+        #[cfg(feature = "std")]
+        eprintln!(
+            "Model::set_row_data called on a model of type {} which does not re-implement this method. \
+            This happens when trying to modify a read-only model",
+            core::any::type_name::<Self>(),
+        );
+    }
+    /// The implementation should return a reference to its [`TreeModelNotify`] field.
+    fn model_tracker(&self) -> &dyn TreeModelTracker;
+}
+
+impl<M: TreeModel> TreeModel for Rc<M> {
+    type Data = M::Data;
+
+    fn row_count(&self, parent: TreeIndex) -> usize {
+        (**self).row_count(parent)
+    }
+
+    fn row_data(&self, index: TreeIndex) -> Option<Self::Data> {
+        (**self).row_data(index)
+    }
+
+    fn has_children(&self, index: TreeIndex) -> bool {
+        (**self).has_children(index)
+    }
+
+    fn set_row_data(&self, index: TreeIndex, data: Self::Data) {
+        (**self).set_row_data(index, data)
+    }
+
+    fn model_tracker(&self) -> &dyn TreeModelTracker {
+        (**self).model_tracker()
+    }
+}
+
+/// A node of a [`VecTreeModel`]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TreeNode<T> {
+    /// The data of this row
+    pub data: T,
+    /// The child rows
+    pub children: Vec<TreeNode<T>>,
+}
+
+impl<T> TreeNode<T> {
+    /// Creates a node without children
+    pub fn new(data: T) -> Self {
+        Self { data, children: Vec::new() }
+    }
+
+    /// Creates a node with the given children
+    pub fn with_children(data: T, children: impl Into<Vec<TreeNode<T>>>) -> Self {
+        Self { data, children: children.into() }
+    }
+}
+
+/// A [`TreeModel`] backed by a `Vec<TreeNode<T>>`
+#[derive(Default)]
+pub struct VecTreeModel<T> {
+    nodes: RefCell<Vec<TreeNode<T>>>,
+    notify: TreeModelNotify,
+}
+
+impl<T> From<Vec<TreeNode<T>>> for VecTreeModel<T> {
+    fn from(nodes: Vec<TreeNode<T>>) -> Self {
+        VecTreeModel { nodes: RefCell::new(nodes), notify: Default::default() }
+    }
+}
+
+impl<T> VecTreeModel<T> {
+    fn with_children<R>(
+        &self,
+        parent: TreeIndex,
+        f: impl FnOnce(&mut Vec<TreeNode<T>>) -> R,
+    ) -> Option<R> {
+        let mut nodes = self.nodes.borrow_mut();
+        let mut children = &mut *nodes;
+        for row in parent {
+            children = &mut children.get_mut(*row)?.children;
+        }
+        Some(f(children))
+    }
+
+    /// Inserts a node at position `index` among the children of `parent`.
+    /// This function panics if `parent` doesn't exist or if index is greater than its number of children.
+    pub fn insert(&self, parent: TreeIndex, index: usize, node: TreeNode<T>) {
+        self.with_children(parent, |children| children.insert(index, node))
+            .expect("invalid parent index");
+        self.notify.row_added(parent, index, 1)
+    }
+
+    /// Adds a node after the last child of `parent`.
+    /// This function panics if `parent` doesn't exist.
+    pub fn push(&self, parent: TreeIndex, node: TreeNode<T>) {
+        let index = self
+            .with_children(parent, |children| {
+                children.push(node);
+                children.len() - 1
+            })
+            .expect("invalid parent index");
+        self.notify.row_added(parent, index, 1)
+    }
+
+    /// Removes the node at `index` together with its children, and returns it.
+    /// This function panics if `index` is empty or doesn't exist.
+    pub fn remove(&self, index: TreeIndex) -> TreeNode<T> {
+        let (row, parent) = index.split_last().expect("can't remove the root");
+        let node = self
+            .with_children(parent, |children| children.remove(*row))
+            .expect("invalid parent index");
+        self.notify.row_removed(parent, *row, 1);
+        node
+    }
+
+    /// Replaces all the nodes
+    pub fn set_vec(&self, nodes: impl Into<Vec<TreeNode<T>>>) {
+        *self.nodes.borrow_mut() = nodes.into();
+        self.notify.reset();
+    }
+}
+
+impl<T: Clone> TreeModel for VecTreeModel<T> {
+    type Data = T;
+
+    fn row_count(&self, parent: TreeIndex) -> usize {
+        self.with_children(parent, |children| children.len()).unwrap_or_default()
+    }
+
+    fn row_data(&self, index: TreeIndex) -> Option<Self::Data> {
+        let (row, parent) = index.split_last()?;
+        self.with_children(parent, |children| children.get(*row).map(|n| n.data.clone()))?
+    }
+
+    fn set_row_data(&self, index: TreeIndex, data: Self::Data) {
+        let Some((row, parent)) = index.split_last() else { return };
+        let changed = self.with_children(parent, |children| {
+            children.get_mut(*row).map(|node| node.data = data).is_some()
+        });
+        if changed == Some(true) {
+            self.notify.row_changed(index);
+        }
+    }
+
+    fn model_tracker(&self) -> &dyn TreeModelTracker {
+        &self.notify
+    }
+}
+
+#[derive(Debug)]
+struct FlattenedRow {
+    index: Vec<usize>,
+    expanded: bool,
+}
+
+struct FlattenedTreeModelInner {
+    /// The visible rows, or None when they need to be reloaded from the top level rows of the tree
+    rows: RefCell<Option<Vec<FlattenedRow>>>,
+    notify: ModelNotify,
+}
+
+impl FlattenedTreeModelInner {
+    /// Returns the position of the row with the given index, if it is visible
+    fn position(rows: &[FlattenedRow], index: TreeIndex) -> Option<usize> {
+        rows.iter().position(|r| r.index == index)
+    }
+
+    /// Returns whether the children of `parent` are visible, and the position of `parent`
+    fn children_visible(rows: &[FlattenedRow], parent: TreeIndex) -> (bool, Option<usize>) {
+        if parent.is_empty() {
+            return (true, None);
+        }
+        match Self::position(rows, parent) {
+            Some(pos) => (rows[pos].expanded, Some(pos)),
+            None => (false, None),
+        }
+    }
+
+    /// Adds `delta` to the row number at the depth of the children of `parent`, for
+    /// all the visible descendants of `parent` that come at or after `from`.
+    fn shift(rows: &mut [FlattenedRow], parent: TreeIndex, from: usize, delta: isize) {
+        let depth = parent.len();
+        for row in rows.iter_mut() {
+            if row.index.len() > depth && row.index.starts_with(parent) && row.index[depth] >= from
+            {
+                row.index[depth] = (row.index[depth] as isize + delta) as usize;
+            }
+        }
+    }
+}
+
+impl TreeModelChangeListener for FlattenedTreeModelInner {
+    fn row_changed(&self, index: TreeIndex) {
+        let pos = self.rows.borrow().as_ref().and_then(|rows| Self::position(rows, index));
+        if let Some(pos) = pos {
+            self.notify.row_changed(pos);
+        }
+    }
+
+    fn row_added(&self, parent: TreeIndex, index: usize, count: usize) {
+        let mut guard = self.rows.borrow_mut();
+        let Some(rows) = guard.as_mut() else { return };
+        let (visible, parent_pos) = Self::children_visible(rows, parent);
+        let mut inserted = None;
+        if visible {
+            let depth = parent.len();
+            let start = parent_pos.map_or(0, |p| p + 1);
+            let insert_pos = rows[start..]
+                .iter()
+                .position(|r| !r.index.starts_with(parent) || r.index[depth] >= index)
+                .map_or(rows.len(), |p| p + start);
+            Self::shift(rows, parent, index, count as isize);
+            rows.splice(
+                insert_pos..insert_pos,
+                (index..index + count).map(|row| FlattenedRow {
+                    index: parent.iter().copied().chain(core::iter::once(row)).collect(),
+                    expanded: false,
+                }),
+            );
+            inserted = Some(insert_pos);
+        }
+        drop(guard);
+        if let Some(pos) = parent_pos {
+            // The parent may have got its first children
+            self.notify.row_changed(pos);
+        }
+        if let Some(insert_pos) = inserted {
+            self.notify.row_added(insert_pos, count);
+        }
+    }
+
+    fn row_removed(&self, parent: TreeIndex, index: usize, count: usize) {
+        let mut guard = self.rows.borrow_mut();
+        let Some(rows) = guard.as_mut() else { return };
+        let (visible, parent_pos) = Self::children_visible(rows, parent);
+        let mut removed = None;
+        if visible {
+            let depth = parent.len();
+            let is_removed = |r: &FlattenedRow| {
+                r.index.len() > depth
+                    && r.index.starts_with(parent)
+                    && (index..index + count).contains(&r.index[depth])
+            };
+            if let Some(first) = rows.iter().position(is_removed) {
+                let len = rows[first..].iter().take_while(|r| is_removed(r)).count();
+                rows.drain(first..first + len);
+                removed = Some((first, len));
+            }
+            Self::shift(rows, parent, index + count, -(count as isize));
+        }
+        drop(guard);
+        if let Some((first, len)) = removed {
+            self.notify.row_removed(first, len);
+        }
+        if let Some(pos) = parent_pos {
+            self.notify.row_changed(pos);
+        }
+    }
+
+    fn reset(&self) {
+        *self.rows.borrow_mut() = None;
+        self.notify.reset();
+    }
+}
+
+/// Provides the rows of a [`TreeModel`] as a flat [`Model`] of [`StandardTreeViewItem`], as
+/// used by the `model` property of the `StandardTreeView` widget.
+///
+/// Only the top level rows and the children of expanded rows are part of the model. Rows are
+/// expanded and collapsed with [`Self::expand`] and [`Self::collapse`], or when the `expanded`
+/// field of a row is changed with [`Model::set_row_data`], which is what the `StandardTreeView`
+/// does when the user clicks on the arrow of a row.
+///
+/// ## Example
+///
+/// ```
+/// # use i_slint_core::model::{Model, ModelRc, FlattenedTreeModel, VecTreeModel, TreeNode, StandardListViewItem};
+/// let tree = VecTreeModel::from(vec![
+///     TreeNode::with_children(StandardListViewItem::from("Fruits"), [
+///         TreeNode::new("Apple".into()),
+///         TreeNode::new("Banana".into()),
+///     ]),
+///     TreeNode::new("Vegetables".into()),
+/// ]);
+/// let model = std::rc::Rc::new(FlattenedTreeModel::new(tree));
+/// assert_eq!(model.row_count(), 2);
+/// model.expand(0);
+/// assert_eq!(model.row_count(), 4);
+/// assert_eq!(model.row_data(2).unwrap().text, "Banana");
+/// assert_eq!(model.tree_index(2), Some(vec![0, 1]));
+///
+/// // The model can be set to a property of type `[StandardTreeViewItem]`
+/// let model_rc = ModelRc::from(model);
+/// ```
+pub struct FlattenedTreeModel<M> {
+    tree: M,
+    inner: Rc<FlattenedTreeModelInner>,
+}
+
+impl<M: TreeModel<Data = StandardListViewItem> + 'static> FlattenedTreeModel<M> {
+    /// Creates a new FlattenedTreeModel showing the top level rows of `tree`
+    pub fn new(tree: M) -> Self {
+        let inner = Rc::new(FlattenedTreeModelInner {
+            rows: Default::default(),
+            notify: Default::default(),
+        });
+        tree.model_tracker()
+            .attach_listener(Rc::downgrade(&inner) as Weak<dyn TreeModelChangeListener>);
+        Self { tree, inner }
+    }
+
+    /// Returns a reference to the tree model
+    pub fn source_model(&self) -> &M {
+        &self.tree
+    }
+
+    fn rows(&self) -> core::cell::RefMut<'_, Vec<FlattenedRow>> {
+        if self.inner.rows.borrow().is_none() {
+            // The tree is queried while the rows are not borrowed, as a model that loads its rows
+            // lazily may send notifications from `row_count`
+            let count = self.tree.row_count(&[]);
+            let mut rows = self.inner.rows.borrow_mut();
+            if rows.is_none() {
+                *rows = Some(
+                    (0..count)
+                        .map(|row| FlattenedRow { index: alloc::vec![row], expanded: false })
+                        .collect(),
+                );
+            }
+        }
+        core::cell::RefMut::map(self.inner.rows.borrow_mut(), |rows| {
+            rows.get_or_insert_with(Default::default)
+        })
+    }
+
+    /// Returns the index in the tree of the given row, or None if `row` is out of bounds
+    pub fn tree_index(&self, row: usize) -> Option<Vec<usize>> {
+        self.rows().get(row).map(|r| r.index.clone())
+    }
+
+    /// Returns the row of the given tree index, or None if it's not visible
+    pub fn row_for_tree_index(&self, index: TreeIndex) -> Option<usize> {
+        FlattenedTreeModelInner::position(&self.rows(), index)
+    }
+
+    /// Shows the children of the given row
+    pub fn expand(&self, row: usize) {
+        let Some(index) = self.rows().get(row).filter(|r| !r.expanded).map(|r| r.index.clone())
+        else {
+            return;
+        };
+        // Not called while the rows are borrowed, as the model may load the children lazily
+        // and notify about them
+        let count = self.tree.row_count(&index);
+        let mut rows = self.rows();
+        // The notifications may have moved the row
+        let Some(row) = FlattenedTreeModelInner::position(&rows, &index) else { return };
+        if rows[row].expanded {
+            return;
+        }
+        rows[row].expanded = true;
+        rows.splice(
+            row + 1..row + 1,
+            (0..count).map(|child| FlattenedRow {
+                index: index.iter().copied().chain(core::iter::once(child)).collect(),
+                expanded: false,
+            }),
+        );
+        drop(rows);
+        self.inner.notify.row_changed(row);
+        if count > 0 {
+            self.inner.notify.row_added(row + 1, count);
+        }
+    }
+
+    /// Hides the children of the given row, and all their descendants
+    pub fn collapse(&self, row: usize) {
+        let mut rows = self.rows();
+        let Some(r) = rows.get_mut(row) else { return };
+        if !r.expanded {
+            return;
+        }
+        r.expanded = false;
+        let index = r.index.clone();
+        let count = rows[row + 1..]
+            .iter()
+            .take_while(|r| r.index.len() > index.len() && r.index.starts_with(&index))
+            .count();
+        rows.drain(row + 1..row + 1 + count);
+        drop(rows);
+        self.inner.notify.row_changed(row);
+        if count > 0 {
+            self.inner.notify.row_removed(row + 1, count);
+        }
+    }
+}
+
+impl<M: TreeModel<Data = StandardListViewItem> + 'static> Model for FlattenedTreeModel<M> {
+    type Data = StandardTreeViewItem;
+
+    fn row_count(&self) -> usize {
+        self.rows().len()
+    }
+
+    fn row_data(&self, row: usize) -> Option<Self::Data> {
+        let (index, expanded) = self.rows().get(row).map(|r| (r.index.clone(), r.expanded))?;
+        let item = self.tree.row_data(&index)?;
+        Some(StandardTreeViewItem {
+            text: item.text,
+            level: index.len() as i32 - 1,
+            has_children: self.tree.has_children(&index),
+            expanded,
+        })
+    }
+
+    fn set_row_data(&self, row: usize, data: Self::Data) {
+        let Some(index) = self.tree_index(row) else { return };
+        if self.tree.row_data(&index).is_some_and(|item| item.text != data.text) {
+            self.tree.set_row_data(&index, data.text.clone().into());
+        }
+        if data.expanded {
+            self.expand(row);
+        } else {
+            self.collapse(row);
+        }
+    }
+
+    fn model_tracker(&self) -> &dyn ModelTracker {
+        &self.inner.notify
+    }
+
+    fn as_any(&self) -> &dyn core::any::Any {
+        self
+    }
+}
+
+#[test]
+fn test_flattened_tree_model() {
+    let tree = Rc::new(VecTreeModel::from(alloc::vec![
+        TreeNode::with_children(
+            StandardListViewItem::from("a"),
+            [
+                TreeNode::with_children("a0".into(), [TreeNode::new("a00".into())]),
+                TreeNode::new("a1".into()),
+            ]
+        ),
+        TreeNode::new("b".into()),
+    ]));
+    let model = Rc::new(FlattenedTreeModel::new(tree.clone()));
+    let texts = || model.iter().map(|i| (i.text.to_string(), i.level)).collect::<Vec<_>>();
+    let checker = adapters::ModelChecker::new(model.clone());
+
+    assert_eq!(texts(), [("a".into(), 0), ("b".into(), 0)]);
+    assert!(model.row_data(0).unwrap().has_children);
+    assert!(!model.row_data(1).unwrap().has_children);
+
+    let mut item = model.row_data(0).unwrap();
+    item.expanded = true;
+    model.set_row_data(0, item);
+    model.expand(1);
+    model.expand(1);
+    assert_eq!(
+        texts(),
+        [("a".into(), 0), ("a0".into(), 1), ("a00".into(), 2), ("a1".into(), 1), ("b".into(), 0)]
+    );
+    checker.check();
+    assert_eq!(model.tree_index(3), Some(alloc::vec![0, 1]));
+    assert_eq!(model.row_for_tree_index(&[0, 0, 0]), Some(2));
+
+    assert!(model.row_data(0).unwrap().expanded);
+    assert!(model.row_data(1).unwrap().expanded);
+    tree.insert(&[0], 1, TreeNode::new("a01".into()));
+    tree.push(&[1], TreeNode::new("b0".into()));
+    tree.insert(&[], 0, TreeNode::new("c".into()));
+    assert_eq!(
+        texts(),
+        [
+            ("c".into(), 0),
+            ("a".into(), 0),
+            ("a0".into(), 1),
+            ("a00".into(), 2),
+            ("a01".into(), 1),
+            ("a1".into(), 1),
+            ("b".into(), 0)
+        ]
+    );
+    assert!(model.row_data(6).unwrap().has_children);
+    checker.check();
+
+    tree.remove(&[1, 0]);
+    tree.set_row_data(&[1, 1], "a1'".into());
+    assert_eq!(
+        texts(),
+        [("c".into(), 0), ("a".into(), 0), ("a01".into(), 1), ("a1'".into(), 1), ("b".into(), 0)]
+    );
+    checker.check();
+
+    model.collapse(1);
+    assert_eq!(texts(), [("c".into(), 0), ("a".into(), 0), ("b".into(), 0)]);
+    checker.check();
+
+    tree.set_vec([TreeNode::new("d".into())]);
+    assert_eq!(texts(), [("d".into(), 0)]);
+    checker.check();
+}
+
+#[test]
+fn test_flattened_tree_model_lazy_loading() {
+    /// A tree with two rows at each of its two levels, that are loaded when first counted
+    #[derive(Default)]
+    struct LazyTree {
+        loaded: RefCell<Vec<Vec<usize>>>,
+        notify: TreeModelNotify,
+    }
+    impl TreeModel for LazyTree {
+        type Data = StandardListViewItem;
+        fn row_count(&self, parent: TreeIndex) -> usize {
+            if parent.len() >= 2 {
+                return 0;
+            }
+            if !self.loaded.borrow().iter().any(|l| l == parent) {
+                self.loaded.borrow_mut().push(parent.to_vec());
+                self.notify.row_added(parent, 0, 2);
+                self.notify.row_changed(parent);
+            }
+            2
+        }
+        fn row_data(&self, index: TreeIndex) -> Option<Self::Data> {
+            Some(alloc::format!("{index:?}").as_str().into())
+        }
+        fn has_children(&self, index: TreeIndex) -> bool {
+            index.len() < 2
+        }
+        fn model_tracker(&self) -> &dyn TreeModelTracker {
+            &self.notify
+        }
+    }
+
+    let model = Rc::new(FlattenedTreeModel::new(LazyTree::default()));
+    let texts = || model.iter().map(|i| i.text.to_string()).collect::<Vec<_>>();
+    assert_eq!(texts(), ["[0]", "[1]"]);
+    let checker = adapters::ModelChecker::new(model.clone());
+
+    model.expand(1);
+    assert_eq!(texts(), ["[0]", "[1]", "[1, 0]", "[1, 1]"]);
+    checker.check();
+    model.expand(0);
+    assert_eq!(texts(), ["[0]", "[0, 0]", "[0, 1]", "[1]", "[1, 0]", "[1, 1]"]);
+    checker.check();
+    assert_eq!(model.source_model().loaded.borrow().len(), 3);
+}
@@ -285,6 +285,32 @@ pub enum WindowEvent {
     },
     /// The pointer exited the window.
     PointerExited,
+    /// Data from outside the application is being dragged over the window.
+    ///
+    /// The backend should send this event when, for example, files are dragged over the window, and
+    /// follow up with [`WindowEvent::Dropped`] or [`WindowEvent::DragExited`].
+    /// When several files are dragged at once, send a single event with all of them in the data.
+    DragMoved {
+        /// The position of the drag in the window. Some windowing systems, winit among them, don't
+        /// report the position during a drag from another application, in which case this is the
+        /// last known pointer position, which may be stale.
+        position: LogicalPosition,
+        /// The mime type of the data, for example `text/uri-list` for files.
+        mime_type: SharedString,
+        /// The data being dragged. For `text/uri-list`, one URI per line.
+        data: SharedString,
+    },
+    /// Data dragged from outside the application left the window, or the drag was cancelled.
+    DragExited,
+    /// Data from outside the application was dropped on the window.
+    Dropped {
+        /// The position of the drop in the window. See [`WindowEvent::DragMoved`] for the caveats.
+        position: LogicalPosition,
+        /// The mime type of the data, for example `text/uri-list` for files.
+        mime_type: SharedString,
+        /// The dropped data.
+        data: SharedString,
+    },
     /// A key was pressed.
     KeyPressed {
         /// The unicode representation of the key pressed.
@@ -358,6 +384,8 @@ pub fn position(&self) -> Option<LogicalPosition> {
             WindowEvent::PointerReleased { position, .. } => Some(*position),
             WindowEvent::PointerMoved { position } => Some(*position),
             WindowEvent::PointerScrolled { position, .. } => Some(*position),
+            WindowEvent::DragMoved { position, .. } => Some(*position),
+            WindowEvent::Dropped { position, .. } => Some(*position),
             _ => None,
         }
     }
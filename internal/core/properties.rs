@@ -1281,6 +1281,9 @@ fn property_two_ways_binding_of_two_two_way_bindings() {
 pub use crate::items::StateInfo;
 pub use properties_animations::*;
 
+mod change_tracker;
+pub use change_tracker::ChangeTracker;
+
 struct StateInfoBinding<F> {
     dirty_time: Cell<Option<crate::animations::Instant>>,
     binding: F,
@@ -0,0 +1,220 @@
+// Copyright © SixtyFPS GmbH <info@slint.dev>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-Royalty-free-1.1 OR LicenseRef-Slint-commercial
+
+//! The implementation of the `changed <property> => { ... }` callbacks.
+//!
+//! A [`ChangeTracker`] evaluates a function with dependency tracking and remembers its value.
+//! When one of the dependencies changes, the tracker is only queued: the handlers are called
+//! later from [`ChangeTracker::run_change_handlers()`], once all the bindings have settled.
+//! That function is run from a timer started when the first tracker is queued, and before
+//! the window contents are drawn.
+
+use super::*;
+use alloc::rc::Weak;
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+#[cfg(all(not(feature = "std"), feature = "unsafe-single-threaded"))]
+use crate::unsafe_single_threaded::thread_local;
+
+/// Guard against handlers that keep changing the properties they track
+const MAX_ITERATIONS: usize = 100;
+
+thread_local!(
+    /// The trackers whose dependencies changed since the last call to `run_change_handlers`
+    static DIRTY_TRACKERS: RefCell<Vec<Weak<dyn ErasedChangeTracker>>> = RefCell::default()
+);
+
+thread_local!(static RUNNING_HANDLERS: Cell<bool> = Cell::new(false));
+
+trait ErasedChangeTracker {
+    /// Re-evaluate the value, and call the handler if it is different from the previous one
+    fn run(&self);
+}
+
+/// The dirty handler of the PropertyTracker: it queues the tracker.
+struct QueueTracker(Weak<dyn ErasedChangeTracker>);
+
+impl PropertyDirtyHandler for QueueTracker {
+    fn notify(&self) {
+        DIRTY_TRACKERS.with(|dirty| {
+            let mut dirty = dirty.borrow_mut();
+            if dirty.is_empty() && !RUNNING_HANDLERS.with(Cell::get) {
+                // Make sure the event loop wakes up to run the handlers
+                crate::timers::Timer::single_shot(
+                    core::time::Duration::ZERO,
+                    ChangeTracker::run_change_handlers,
+                );
+            }
+            dirty.push(self.0.clone());
+        });
+    }
+}
+
+struct ChangeTrackerInner<T, Data, EvalFn, NotifyFn> {
+    tracker: PropertyTracker<QueueTracker>,
+    value: RefCell<Option<T>>,
+    data: Data,
+    eval_fn: EvalFn,
+    notify_fn: NotifyFn,
+}
+
+impl<T, Data, EvalFn, NotifyFn> ChangeTrackerInner<T, Data, EvalFn, NotifyFn>
+where
+    EvalFn: Fn(&Data) -> T,
+{
+    fn evaluate(&self) -> T {
+        // Safety: the inner is allocated in a Rc and is never moved
+        let tracker = unsafe { Pin::new_unchecked(&self.tracker) };
+        tracker.evaluate_as_dependency_root(|| (self.eval_fn)(&self.data))
+    }
+}
+
+impl<T, Data, EvalFn, NotifyFn> ErasedChangeTracker
+    for ChangeTrackerInner<T, Data, EvalFn, NotifyFn>
+where
+    T: PartialEq + Clone,
+    EvalFn: Fn(&Data) -> T,
+    NotifyFn: Fn(&Data, &T),
+{
+    fn run(&self) {
+        let value = self.evaluate();
+        let previous = self.value.replace(Some(value.clone()));
+        if previous.as_ref() != Some(&value) {
+            evaluate_no_tracking(|| (self.notify_fn)(&self.data, &value));
+        }
+    }
+}
+
+/// Calls a handler when the value returned by a function changes.
+///
+/// This is used to implement the `changed <property> => { ... }` callbacks.
+/// The handler is not called from within the property change, but later by
+/// [`Self::run_change_handlers()`], and only if the value is then different.
+#[derive(Default)]
+pub struct ChangeTracker {
+    inner: Cell<Option<Rc<dyn ErasedChangeTracker>>>,
+}
+
+impl ChangeTracker {
+    /// Start tracking the value returned by `eval_fn`. `notify_fn` will be called with the
+    /// new value every time it changed.
+    ///
+    /// `eval_fn` is called right away to get the initial value, which does not call `notify_fn`.
+    /// Any previous tracking done by this tracker is cancelled.
+    pub fn init<T: PartialEq + Clone + 'static, Data: 'static>(
+        &self,
+        data: Data,
+        eval_fn: impl Fn(&Data) -> T + 'static,
+        notify_fn: impl Fn(&Data, &T) + 'static,
+    ) {
+        let inner =
+            Rc::new_cyclic(|weak: &Weak<ChangeTrackerInner<T, Data, _, _>>| ChangeTrackerInner {
+                tracker: PropertyTracker::new_with_dirty_handler(QueueTracker(weak.clone())),
+                value: RefCell::new(None),
+                data,
+                eval_fn,
+                notify_fn,
+            });
+        let value = inner.evaluate();
+        *inner.value.borrow_mut() = Some(value);
+        self.inner.set(Some(inner));
+    }
+
+    /// Stop tracking. The handler will no longer be called.
+    pub fn clear(&self) {
+        self.inner.set(None);
+    }
+
+    /// Call the handlers of all the trackers whose value changed.
+    ///
+    /// Handlers that change properties can cause more handlers to be queued: these are run
+    /// as well before this function returns. This function is not re-entrant: calling it
+    /// from a handler does nothing.
+    pub fn run_change_handlers() {
+        struct ResetOnDrop;
+        impl Drop for ResetOnDrop {
+            fn drop(&mut self) {
+                RUNNING_HANDLERS.with(|running| running.set(false));
+            }
+        }
+
+        if RUNNING_HANDLERS.with(|running| running.replace(true)) {
+            return;
+        }
+        let _reset = ResetOnDrop;
+
+        for _ in 0..MAX_ITERATIONS {
+            let dirty = DIRTY_TRACKERS.with(|dirty| core::mem::take(&mut *dirty.borrow_mut()));
+            if dirty.is_empty() {
+                return;
+            }
+            for tracker in dirty {
+                if let Some(tracker) = tracker.upgrade() {
+                    tracker.run();
+                }
+            }
+        }
+        crate::debug_log!(
+            "Slint: the changed callbacks keep changing the properties they track. Giving up."
+        );
+    }
+}
+
+#[test]
+fn change_tracker_batching() {
+    let prop = Rc::pin(Property::new(1));
+    let log = Rc::new(RefCell::new(Vec::new()));
+    let tracker = ChangeTracker::default();
+    tracker.init(
+        (prop.clone(), log.clone()),
+        |(prop, _)| prop.as_ref().get(),
+        |(_, log), value| log.borrow_mut().push(*value),
+    );
+    ChangeTracker::run_change_handlers();
+    assert!(log.borrow().is_empty());
+
+    // Only called once the bindings have settled, with the final value
+    prop.set(2);
+    prop.set(3);
+    assert!(log.borrow().is_empty());
+    ChangeTracker::run_change_handlers();
+    assert_eq!(*log.borrow(), vec![3]);
+
+    // Not called if the value is back to what it was
+    prop.set(4);
+    prop.set(3);
+    ChangeTracker::run_change_handlers();
+    assert_eq!(*log.borrow(), vec![3]);
+
+    tracker.clear();
+    prop.set(5);
+    ChangeTracker::run_change_handlers();
+    assert_eq!(*log.borrow(), vec![3]);
+}
+
+#[test]
+fn change_tracker_chain() {
+    let prop1 = Rc::pin(Property::new(1));
+    let prop2 = Rc::pin(Property::new(1));
+    let log = Rc::new(RefCell::new(Vec::new()));
+    let tracker1 = ChangeTracker::default();
+    tracker1.init(
+        (prop1.clone(), prop2.clone()),
+        |(prop1, _)| prop1.as_ref().get(),
+        |(_, prop2), value| {
+            // Does nothing, we are already running the handlers
+            ChangeTracker::run_change_handlers();
+            prop2.set(value * 10)
+        },
+    );
+    let tracker2 = ChangeTracker::default();
+    tracker2.init(
+        (prop2.clone(), log.clone()),
+        |(prop2, _)| prop2.as_ref().get(),
+        |(_, log), value| log.borrow_mut().push(*value),
+    );
+    prop1.set(2);
+    ChangeTracker::run_change_handlers();
+    assert_eq!(*log.borrow(), vec![20]);
+}
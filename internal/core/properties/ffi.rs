@@ -450,6 +450,74 @@ pub struct PropertyTrackerOpaque {
     core::ptr::drop_in_place(handle as *mut PropertyTracker);
 }
 
+#[repr(C)]
+/// Opaque type representing the ChangeTracker
+pub struct ChangeTrackerOpaque {
+    inner: [usize; 2],
+}
+
+static_assertions::assert_eq_align!(ChangeTrackerOpaque, ChangeTracker);
+static_assertions::assert_eq_size!(ChangeTrackerOpaque, ChangeTracker);
+
+/// Initialize the ChangeTracker.
+/// `out` is assumed to be uninitialized
+/// slint_change_tracker_drop need to be called after that
+#[no_mangle]
+pub unsafe extern "C" fn slint_change_tracker_construct(out: *mut ChangeTrackerOpaque) {
+    core::ptr::write(out as *mut ChangeTracker, ChangeTracker::default());
+}
+
+/// Destroy the ChangeTracker
+#[no_mangle]
+pub unsafe extern "C" fn slint_change_tracker_drop(handle: *mut ChangeTrackerOpaque) {
+    core::ptr::drop_in_place(handle as *mut ChangeTracker);
+}
+
+/// Start tracking. `eval_fn` evaluates the tracked value, stores it in the user data and
+/// returns true if it is different from the previously stored value.
+/// `notify_fn` is called when the value changed.
+#[no_mangle]
+pub unsafe extern "C" fn slint_change_tracker_init(
+    handle: &ChangeTrackerOpaque,
+    user_data: *mut c_void,
+    drop_user_data: extern "C" fn(user_data: *mut c_void),
+    eval_fn: extern "C" fn(user_data: *mut c_void) -> bool,
+    notify_fn: extern "C" fn(user_data: *mut c_void),
+) {
+    struct CChangeTracker {
+        user_data: *mut c_void,
+        drop_user_data: extern "C" fn(*mut c_void),
+        eval_fn: extern "C" fn(*mut c_void) -> bool,
+        notify_fn: extern "C" fn(*mut c_void),
+        /// Incremented every time eval_fn reports a change
+        generation: Cell<u32>,
+    }
+    impl Drop for CChangeTracker {
+        fn drop(&mut self) {
+            (self.drop_user_data)(self.user_data)
+        }
+    }
+
+    let data =
+        CChangeTracker { user_data, drop_user_data, eval_fn, notify_fn, generation: Cell::new(0) };
+    (*(handle as *const ChangeTrackerOpaque as *const ChangeTracker)).init(
+        data,
+        |data| {
+            if (data.eval_fn)(data.user_data) {
+                data.generation.set(data.generation.get().wrapping_add(1));
+            }
+            data.generation.get()
+        },
+        |data, _| (data.notify_fn)(data.user_data),
+    );
+}
+
+/// Call the handlers of the ChangeTracker whose value changed
+#[no_mangle]
+pub extern "C" fn slint_change_tracker_run_change_handlers() {
+    ChangeTracker::run_change_handlers();
+}
+
 /// return the current animation tick for the `animation-tick` function
 #[no_mangle]
 pub extern "C" fn slint_animation_tick() -> u64 {
@@ -2,7 +2,8 @@
 // SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-Royalty-free-1.1 OR LicenseRef-Slint-commercial
 
 use super::*;
-use crate::{items::PropertyAnimation, lengths::LogicalLength};
+use crate::items::{AnimationDirection, AnimationFillMode, AnimationKeyframe, PropertyAnimation};
+use crate::lengths::LogicalLength;
 #[cfg(not(feature = "std"))]
 use num_traits::Float;
 
@@ -41,7 +42,7 @@ pub fn compute_interpolated_value(&mut self) -> (T, bool) {
                 let delay = self.details.delay as u64;
 
                 if time_progress < delay {
-                    (self.from_value.clone(), false)
+                    (self.value_before_start(), false)
                 } else {
                     self.start_time =
                         new_tick - core::time::Duration::from_millis(time_progress - delay);
@@ -73,25 +74,92 @@ pub fn compute_interpolated_value(&mut self) -> (T, bool) {
 
                     let progress =
                         (time_progress as f32 / self.details.duration as f32).clamp(0., 1.);
-                    let t = crate::animations::easing_curve(&self.details.easing, progress);
-                    let val = self.from_value.interpolate(&self.to_value, t);
 
-                    (val, false)
+                    (self.value_at(current_iteration, progress), false)
                 } else {
                     self.state = AnimationState::Done;
                     self.compute_interpolated_value()
                 }
             }
-            AnimationState::Done => (self.to_value.clone(), true),
+            AnimationState::Done => (self.value_after_end(), true),
         }
     }
 
+    /// Returns the value at the given progress (between 0 and 1) of the given iteration
+    fn value_at(&self, iteration: u64, progress: f32) -> T {
+        let reversed = match self.details.direction {
+            AnimationDirection::Normal => false,
+            AnimationDirection::Reverse => true,
+            AnimationDirection::Alternate => iteration % 2 == 1,
+            AnimationDirection::AlternateReverse => iteration % 2 == 0,
+        };
+        let progress = if reversed { 1. - progress } else { progress };
+        let t = crate::animations::easing_curve(&self.details.easing, progress);
+        let t = keyframes_progress(&self.details.keyframes, t);
+        self.from_value.interpolate(&self.to_value, t)
+    }
+
+    /// The value during the delay
+    fn value_before_start(&self) -> T {
+        match self.details.fill_mode {
+            AnimationFillMode::Backwards | AnimationFillMode::Both => self.value_at(0, 0.),
+            AnimationFillMode::None | AnimationFillMode::Forwards => self.from_value.clone(),
+        }
+    }
+
+    /// The value once the animation is finished
+    fn value_after_end(&self) -> T {
+        if !matches!(self.details.fill_mode, AnimationFillMode::Forwards | AnimationFillMode::Both)
+        {
+            return self.to_value.clone();
+        }
+        // The last frame is at the end of the last, possibly partial, iteration
+        let count = self.details.iteration_count;
+        let (iteration, progress) = if count < 0. {
+            (0, 1.)
+        } else if count == 0. {
+            (0, 0.)
+        } else {
+            let last_iteration = count.ceil() - 1.;
+            (last_iteration as u64, count - last_iteration)
+        };
+        self.value_at(iteration, progress)
+    }
+
     fn reset(&mut self) {
         self.state = AnimationState::Delaying;
         self.start_time = crate::animations::current_tick();
     }
 }
 
+/// Returns the interpolation progress at the time `t` of an iteration, according to the keyframes.
+///
+/// The keyframes are sorted by position. There is an implicit keyframe with a progress of 0 at the
+/// start, and one with a progress of 1 at the end if the last keyframe is before it.
+fn keyframes_progress(keyframes: &[AnimationKeyframe], t: f32) -> f32 {
+    if keyframes.is_empty() {
+        return t;
+    }
+    let (mut position, mut progress) = (0., 0.);
+    for keyframe in keyframes {
+        if t < keyframe.position {
+            if keyframe.position <= position {
+                // Before a keyframe at the start
+                return keyframe.progress;
+            }
+            let segment = (t - position) / (keyframe.position - position);
+            let segment = crate::animations::easing_curve(&keyframe.easing, segment);
+            return progress + (keyframe.progress - progress) * segment;
+        }
+        (position, progress) = (keyframe.position, keyframe.progress);
+    }
+    if position < 1. {
+        progress + (1. - progress) * (t - position) / (1. - position)
+    } else {
+        progress
+    }
+}
+
 #[derive(Clone, Copy, Eq, PartialEq, Debug)]
 pub(super) enum AnimatedBindingState {
     Animating,
@@ -965,4 +1033,120 @@ fn test_loop_via_binding() {
 
         assert_eq!(get_prop_value(&compo.width), 300);
     }
+
+    #[test]
+    fn properties_test_animation_keyframes() {
+        let compo = Component::new_test_component();
+
+        let animation_details = PropertyAnimation {
+            duration: DURATION.as_millis() as _,
+            fill_mode: AnimationFillMode::Forwards,
+            keyframes: [
+                AnimationKeyframe { position: 0.5, progress: 1., ..Default::default() },
+                AnimationKeyframe { position: 1., progress: 0., ..Default::default() },
+            ]
+            .into_iter()
+            .collect(),
+            ..PropertyAnimation::default()
+        };
+
+        compo.width.set(100);
+        let start_time = crate::animations::current_tick();
+        compo.width.set_animated_value(200, animation_details);
+        assert_eq!(get_prop_value(&compo.width), 100);
+
+        crate::animations::CURRENT_ANIMATION_DRIVER
+            .with(|driver| driver.update_animations(start_time + DURATION / 4));
+        assert_eq!(get_prop_value(&compo.width), 150);
+
+        crate::animations::CURRENT_ANIMATION_DRIVER
+            .with(|driver| driver.update_animations(start_time + DURATION / 2));
+        assert_eq!(get_prop_value(&compo.width), 200);
+        assert_eq!(get_prop_value(&compo.width_times_two), 400);
+
+        crate::animations::CURRENT_ANIMATION_DRIVER
+            .with(|driver| driver.update_animations(start_time + DURATION * 3 / 4));
+        assert_eq!(get_prop_value(&compo.width), 150);
+
+        // The last keyframe goes back to the start value, and the fill mode keeps it
+        crate::animations::CURRENT_ANIMATION_DRIVER
+            .with(|driver| driver.update_animations(start_time + DURATION + DURATION / 2));
+        assert_eq!(get_prop_value(&compo.width), 100);
+        assert_eq!(get_prop_value(&compo.width_times_two), 200);
+
+        compo.width.handle.access(|binding| assert!(binding.is_none()));
+    }
+
+    #[test]
+    fn properties_test_animation_implicit_last_keyframe() {
+        let keyframes = [AnimationKeyframe { position: 0.5, progress: 0.75, ..Default::default() }];
+        assert_eq!(keyframes_progress(&keyframes, 0.25), 0.375);
+        assert_eq!(keyframes_progress(&keyframes, 0.75), 0.875);
+        assert_eq!(keyframes_progress(&keyframes, 1.), 1.);
+        assert_eq!(keyframes_progress(&[], 0.3), 0.3);
+    }
+
+    #[test]
+    fn properties_test_animation_alternate_direction() {
+        let compo = Component::new_test_component();
+
+        let animation_details = PropertyAnimation {
+            duration: DURATION.as_millis() as _,
+            iteration_count: 2.,
+            direction: AnimationDirection::Alternate,
+            ..PropertyAnimation::default()
+        };
+
+        compo.width.set(100);
+        let start_time = crate::animations::current_tick();
+        compo.width.set_animated_value(200, animation_details);
+
+        crate::animations::CURRENT_ANIMATION_DRIVER
+            .with(|driver| driver.update_animations(start_time + DURATION / 4));
+        assert_eq!(get_prop_value(&compo.width), 125);
+
+        // The second iteration goes backwards
+        crate::animations::CURRENT_ANIMATION_DRIVER
+            .with(|driver| driver.update_animations(start_time + DURATION + DURATION / 4));
+        assert_eq!(get_prop_value(&compo.width), 175);
+
+        // Without fill mode, the property gets its new value at the end
+        crate::animations::CURRENT_ANIMATION_DRIVER
+            .with(|driver| driver.update_animations(start_time + 2 * DURATION));
+        assert_eq!(get_prop_value(&compo.width), 200);
+
+        compo.width.handle.access(|binding| assert!(binding.is_none()));
+    }
+
+    #[test]
+    fn properties_test_delayed_animation_reverse_fill_both() {
+        let compo = Component::new_test_component();
+
+        let animation_details = PropertyAnimation {
+            delay: DELAY.as_millis() as _,
+            duration: DURATION.as_millis() as _,
+            direction: AnimationDirection::Reverse,
+            fill_mode: AnimationFillMode::Both,
+            ..PropertyAnimation::default()
+        };
+
+        compo.width.set(100);
+        let start_time = crate::animations::current_tick();
+        compo.width.set_animated_value(200, animation_details);
+
+        // The first frame of a reversed animation is the end value
+        crate::animations::CURRENT_ANIMATION_DRIVER
+            .with(|driver| driver.update_animations(start_time + DELAY / 2));
+        assert_eq!(get_prop_value(&compo.width), 200);
+
+        crate::animations::CURRENT_ANIMATION_DRIVER
+            .with(|driver| driver.update_animations(start_time + DELAY + DURATION / 4));
+        assert_eq!(get_prop_value(&compo.width), 175);
+
+        crate::animations::CURRENT_ANIMATION_DRIVER
+            .with(|driver| driver.update_animations(start_time + DELAY + DURATION * 2));
+        assert_eq!(get_prop_value(&compo.width), 100);
+
+        compo.width.handle.access(|binding| assert!(binding.is_none()));
+    }
 }
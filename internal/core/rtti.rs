@@ -44,8 +44,10 @@ macro_rules! declare_ValueType_2 {
             crate::graphics::Point,
             crate::items::PointerEvent,
             crate::items::PointerScrollEvent,
+            crate::items::DropEvent,
             crate::lengths::LogicalLength,
             crate::component_factory::ComponentFactory,
+            crate::SharedVector<crate::items::AnimationKeyframe>,
             $(crate::items::$Name,)*
         ];
     };
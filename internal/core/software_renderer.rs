@@ -7,9 +7,13 @@
 
 #![warn(missing_docs)]
 
+mod box_shadow;
+mod dithering;
 mod draw_functions;
 mod fixed;
 mod fonts;
+#[cfg(feature = "std")]
+mod path;
 
 use self::fonts::GlyphRenderer;
 use crate::api::Window;
@@ -18,10 +22,13 @@
 use crate::item_rendering::{
     CachedRenderingData, ItemRenderer, RenderBorderRectangle, RenderImage,
 };
-use crate::items::{ItemRc, TextOverflow};
+use crate::items::{
+    ItemRc, RenderingResult, TextDirection, TextHorizontalAlignment, TextOverflow,
+    TextVerticalAlignment, TextWrap,
+};
 use crate::lengths::{
-    LogicalBorderRadius, LogicalLength, LogicalPoint, LogicalRect, LogicalSize, LogicalVector,
-    PhysicalPx, PointLengths, RectLengths, ScaleFactor, SizeLengths,
+    LogicalBorderRadius, LogicalLength, LogicalPoint, LogicalPx, LogicalRect, LogicalSize,
+    LogicalVector, PhysicalPx, PointLengths, RectLengths, ScaleFactor, SizeLengths,
 };
 use crate::renderer::{Renderer, RendererSealed};
 use crate::textlayout::{AbstractFont, FontMetrics, TextParagraphLayout};
@@ -39,7 +46,10 @@
 use num_traits::Float;
 use num_traits::NumCast;
 
-pub use draw_functions::{PremultipliedRgbaColor, Rgb565Pixel, TargetPixel};
+pub use dithering::{Dithering, GrayscalePacker, PackedGrayscaleFormat};
+pub use draw_functions::{
+    Argb8888PremultipliedPixel, Gray8Pixel, PremultipliedRgbaColor, Rgb565Pixel, TargetPixel,
+};
 
 type PhysicalLength = euclid::Length<i16, PhysicalPx>;
 type PhysicalRect = euclid::Rect<i16, PhysicalPx>;
@@ -47,8 +57,6 @@
 type PhysicalPoint = euclid::Point2D<i16, PhysicalPx>;
 type PhysicalBorderRadius = BorderRadius<i16, PhysicalPx>;
 
-type DirtyRegion = PhysicalRect;
-
 /// This enum describes which parts of the buffer passed to the [`SoftwareRenderer`] may be re-used to speed up painting.
 // FIXME: #[non_exhaustive] #3023
 #[derive(PartialEq, Eq, Debug, Clone, Default, Copy)]
@@ -212,20 +220,148 @@ fn process_line(
     );
 }
 
-/// Represents a rectangular region on the screen, used for partial rendering.
+/// Represents a region on the screen, used for partial rendering.
 ///
-/// The region may be composed of multiple sub-regions.
-#[derive(Clone, Debug, Default)]
-pub struct PhysicalRegion(PhysicalRect);
+/// The region may be composed of multiple rectangles, which don't overlap.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct PhysicalRegion {
+    rectangles: [PhysicalRect; crate::item_rendering::DirtyRegion::MAX_COUNT],
+    count: usize,
+}
 
 impl PhysicalRegion {
+    fn from_rect(rect: PhysicalRect) -> Self {
+        let mut region = Self::default();
+        region.add_rect(rect);
+        region
+    }
+
+    /// Convert the dirty region of the partial renderer to physical pixels
+    fn from_dirty_region(
+        dirty_region: &crate::item_rendering::DirtyRegion,
+        scale_factor: ScaleFactor,
+    ) -> Self {
+        let mut region = Self::default();
+        for b in dirty_region.iter() {
+            region.add_rect(
+                (b.to_rect().cast() * scale_factor)
+                    .round_out()
+                    .intersection(&euclid::rect(0., 0., i16::MAX as f32, i16::MAX as f32))
+                    .unwrap_or_default()
+                    .cast(),
+            );
+        }
+        region
+    }
+
+    fn iter_rect(&self) -> impl Iterator<Item = PhysicalRect> + '_ {
+        self.rectangles[..self.count].iter().copied()
+    }
+
+    /// Add a rectangle to the region. It is merged with the rectangles it overlaps with so that
+    /// the rectangles stay disjoint, and with the closest one if there are too many rectangles.
+    fn add_rect(&mut self, mut rect: PhysicalRect) {
+        if rect.is_empty() {
+            return;
+        }
+        loop {
+            let Some(i) = self.iter_rect().position(|r| r.intersects(&rect)) else { break };
+            rect = rect.union(&self.remove(i));
+        }
+        if self.count < self.rectangles.len() {
+            self.rectangles[self.count] = rect;
+            self.count += 1;
+        } else {
+            let area = |r: PhysicalRect| r.width() as i32 * r.height() as i32;
+            let best_merge = (0..self.count)
+                .min_by_key(|i| area(self.rectangles[*i].union(&rect)) - area(self.rectangles[*i]))
+                .unwrap_or_default();
+            let merged = self.remove(best_merge).union(&rect);
+            self.add_rect(merged);
+        }
+    }
+
+    fn remove(&mut self, index: usize) -> PhysicalRect {
+        self.count -= 1;
+        self.rectangles.swap(index, self.count);
+        self.rectangles[self.count]
+    }
+
+    #[must_use]
+    fn union(&self, other: &Self) -> Self {
+        let mut result = *self;
+        for r in other.iter_rect() {
+            result.add_rect(r);
+        }
+        result
+    }
+
+    #[must_use]
+    fn intersection(&self, rect: &PhysicalRect) -> Self {
+        let mut result = Self::default();
+        for r in self.iter_rect().filter_map(|r| r.intersection(rect)) {
+            result.add_rect(r);
+        }
+        result
+    }
+
+    fn bounding_rect(&self) -> PhysicalRect {
+        self.iter_rect().fold(PhysicalRect::default(), |acc, r| acc.union(&r))
+    }
+
     /// Returns the size of the bounding box of this region.
     pub fn bounding_box_size(&self) -> crate::api::PhysicalSize {
-        crate::api::PhysicalSize { width: self.0.width() as _, height: self.0.height() as _ }
+        let bounding_rect = self.bounding_rect();
+        crate::api::PhysicalSize {
+            width: bounding_rect.width() as _,
+            height: bounding_rect.height() as _,
+        }
     }
     /// Returns the origin of the bounding box of this region.
     pub fn bounding_box_origin(&self) -> crate::api::PhysicalPosition {
-        crate::api::PhysicalPosition { x: self.0.origin.x as _, y: self.0.origin.y as _ }
+        let bounding_rect = self.bounding_rect();
+        crate::api::PhysicalPosition {
+            x: bounding_rect.origin.x as _,
+            y: bounding_rect.origin.y as _,
+        }
+    }
+
+    /// Returns an iterator over the rectangles of this region, as their origin and size.
+    ///
+    /// The rectangles don't overlap. This can be used to only send the parts of the buffer
+    /// that were rendered to the display:
+    ///
+    /// ```rust
+    /// # use i_slint_core::software_renderer::{SoftwareRenderer, Rgb565Pixel};
+    /// # fn send_line_to_display(x: i32, y: i32, pixels: &[Rgb565Pixel]) {}
+    /// # fn xxx(renderer: &SoftwareRenderer, buffer: &mut [Rgb565Pixel], stride: usize) {
+    /// let region = renderer.render(buffer, stride);
+    /// for (origin, size) in region.iter() {
+    ///     for y in origin.y..origin.y + size.height as i32 {
+    ///         let begin = y as usize * stride + origin.x as usize;
+    ///         send_line_to_display(origin.x, y, &buffer[begin..begin + size.width as usize]);
+    ///     }
+    /// }
+    /// # }
+    /// ```
+    pub fn iter(
+        &self,
+    ) -> impl Iterator<Item = (crate::api::PhysicalPosition, crate::api::PhysicalSize)> + '_ {
+        self.iter_rect().map(|r| {
+            (
+                crate::api::PhysicalPosition { x: r.origin.x as _, y: r.origin.y as _ },
+                crate::api::PhysicalSize { width: r.width() as _, height: r.height() as _ },
+            )
+        })
+    }
+}
+
+impl Transform for PhysicalRegion {
+    fn transformed(mut self, info: RotationInfo) -> Self {
+        for r in &mut self.rectangles[..self.count] {
+            *r = r.transformed(info);
+        }
+        self
     }
 }
 
@@ -240,6 +376,8 @@ pub fn bounding_box_origin(&self) -> crate::api::PhysicalPosition {
 ///     in one single buffer
 pub struct SoftwareRenderer {
     partial_cache: RefCell<crate::item_rendering::PartialRenderingCache>,
+    box_shadow_cache: box_shadow::BoxShadowCache,
+    layer_cache: LayerCache,
     repaint_buffer_type: Cell<RepaintBufferType>,
     /// This is the area which we are going to redraw in the next frame, no matter if the items are dirty or not
     force_dirty: Cell<crate::item_rendering::DirtyRegion>,
@@ -247,7 +385,7 @@ pub struct SoftwareRenderer {
     force_screen_refresh: Cell<bool>,
     /// This is the area which was dirty on the previous frame.
     /// Only used if repaint_buffer_type == RepaintBufferType::SwappedBuffers
-    prev_frame_dirty: Cell<DirtyRegion>,
+    prev_frame_dirty: Cell<PhysicalRegion>,
     maybe_window_adapter: RefCell<Option<Weak<dyn crate::window::WindowAdapter>>>,
     rotation: Cell<RenderingRotation>,
     rendering_metrics_collector: Option<Rc<RenderingMetricsCollector>>,
@@ -257,6 +395,8 @@ impl Default for SoftwareRenderer {
     fn default() -> Self {
         Self {
             partial_cache: Default::default(),
+            box_shadow_cache: Default::default(),
+            layer_cache: Default::default(),
             repaint_buffer_type: Default::default(),
             force_dirty: Default::default(),
             force_screen_refresh: Default::default(),
@@ -315,26 +455,23 @@ pub fn rendering_rotation(&self) -> RenderingRotation {
     /// Returns the region to actually draw.
     fn apply_dirty_region(
         &self,
-        mut dirty_region: DirtyRegion,
+        mut dirty_region: PhysicalRegion,
         screen_size: PhysicalSize,
-    ) -> DirtyRegion {
+    ) -> PhysicalRegion {
         let screen_region = PhysicalRect { origin: euclid::point2(0, 0), size: screen_size };
 
         if self.force_screen_refresh.take() {
-            dirty_region = screen_region;
+            dirty_region = PhysicalRegion::from_rect(screen_region);
         }
 
         match self.repaint_buffer_type() {
-            RepaintBufferType::NewBuffer => {
-                PhysicalRect { origin: euclid::point2(0, 0), size: screen_size }
-            }
+            RepaintBufferType::NewBuffer => PhysicalRegion::from_rect(screen_region),
             RepaintBufferType::ReusedBuffer => dirty_region,
             RepaintBufferType::SwappedBuffers => {
                 dirty_region.union(&self.prev_frame_dirty.replace(dirty_region))
             }
         }
         .intersection(&screen_region)
-        .unwrap_or_default()
     }
 
     /// Render the window to the given frame buffer.
@@ -383,12 +520,16 @@ pub fn render(&self, buffer: &mut [impl TargetPixel], pixel_stride: usize) -> Ph
             },
             "buffer of size {} with stride {pixel_stride} is too small to handle a window of size {size:?}", buffer.len()
         );
+        #[cfg(feature = "std")]
+        self.layer_cache.clear_cache_if_scale_factor_changed(window.window());
         let buffer_renderer = SceneBuilder::new(
             size,
             factor,
             window_inner,
             RenderToBuffer { buffer, stride: pixel_stride },
             rotation,
+            &self.box_shadow_cache,
+            &self.layer_cache,
         );
         let mut renderer = crate::item_rendering::PartialRenderer::new(
             &self.partial_cache,
@@ -396,43 +537,47 @@ pub fn render(&self, buffer: &mut [impl TargetPixel], pixel_stride: usize) -> Ph
             buffer_renderer,
         );
 
-        window_inner
+        let region = window_inner
             .draw_contents(|components| {
                 for (component, origin) in components {
                     renderer.compute_dirty_regions(component, *origin);
                 }
 
-                let dirty_region = (renderer.dirty_region.to_rect().cast() * factor)
-                    .round_out()
-                    .intersection(&euclid::rect(0., 0., i16::MAX as f32, i16::MAX as f32))
-                    .unwrap_or_default()
-                    .cast();
-
+                let dirty_region =
+                    PhysicalRegion::from_dirty_region(&renderer.dirty_region, factor);
                 let to_draw = self.apply_dirty_region(dirty_region, size);
-
-                renderer.combine_clip(
-                    (to_draw.cast() / factor).cast(),
-                    LogicalBorderRadius::zero(),
-                    LogicalLength::zero(),
-                );
+                let to_draw_tr = to_draw.transformed(renderer.actual_renderer.rotation);
 
                 let mut bg = TargetPixel::background();
-                // TODO: gradient background
-                TargetPixel::blend(&mut bg, background.color().into());
-                let to_draw_tr = to_draw.transformed(renderer.actual_renderer.rotation);
-                for line in to_draw_tr.min_y()..to_draw_tr.max_y() {
-                    let begin = line as usize * pixel_stride + to_draw_tr.origin.x as usize;
-                    renderer.actual_renderer.processor.buffer[begin..]
-                        [..to_draw_tr.width() as usize]
-                        .fill(bg);
+                if !matches!(background, Brush::LinearGradient(_)) {
+                    TargetPixel::blend(&mut bg, background.color().into());
+                }
+                for rect in to_draw_tr.iter_rect() {
+                    for line in rect.min_y()..rect.max_y() {
+                        let begin = line as usize * pixel_stride + rect.origin.x as usize;
+                        renderer.actual_renderer.processor.base.buffer[begin..]
+                            [..rect.width() as usize]
+                            .fill(bg);
+                    }
                 }
 
-                for (component, origin) in components {
-                    crate::item_rendering::render_component_items(
-                        component,
-                        &mut renderer,
-                        *origin,
+                // Each rectangle of the region is rendered separately, so that the parts in between are left untouched
+                for rect in to_draw.iter_rect() {
+                    renderer.save_state();
+                    renderer.combine_clip(
+                        (rect.cast() / factor).cast(),
+                        LogicalBorderRadius::zero(),
+                        LogicalLength::zero(),
                     );
+                    renderer.actual_renderer.draw_window_background_gradient(&background);
+                    for (component, origin) in components {
+                        crate::item_rendering::render_component_items(
+                            component,
+                            &mut renderer,
+                            *origin,
+                        );
+                    }
+                    renderer.restore_state();
                 }
 
                 if let Some(metrics) = &self.rendering_metrics_collector {
@@ -442,9 +587,11 @@ pub fn render(&self, buffer: &mut [impl TargetPixel], pixel_stride: usize) -> Ph
                     }
                 }
 
-                PhysicalRegion(to_draw_tr)
+                to_draw_tr
             })
-            .unwrap_or_default()
+            .unwrap_or_default();
+        self.box_shadow_cache.collect_garbage();
+        region
     }
 
     /// Render the window, line by line, into the line buffer provided by the [`LineBufferProvider`].
@@ -503,8 +650,64 @@ pub fn render_by_line(&self, line_buffer: impl LineBufferProvider) -> PhysicalRe
                 line_buffer,
             )
         } else {
-            PhysicalRegion(Default::default())
+            Default::default()
+        }
+    }
+
+    /// Render the window to the given frame buffer, in parallel on the threads of the
+    /// [rayon](https://docs.rs/rayon) global thread pool.
+    ///
+    /// This is an alternative to [`Self::render()`] for devices with several cores. The scene is
+    /// prepared once like with [`Self::render_by_line()`], then the dirty region is split into
+    /// horizontal bands that are rendered concurrently. Every line is rendered from the same scene,
+    /// so the result is identical to the one of [`Self::render_by_line()`], no matter how many
+    /// threads are used.
+    ///
+    /// The `pixel_stride` and the size of the buffer have the same meaning as for [`Self::render()`].
+    /// Returns the physical dirty region for this frame, affected by the screen rotation.
+    #[cfg(feature = "software-renderer-parallel")]
+    pub fn render_parallel(
+        &self,
+        buffer: &mut [impl TargetPixel + Send + Sync],
+        pixel_stride: usize,
+    ) -> PhysicalRegion {
+        let Some(window) = self.maybe_window_adapter.borrow().as_ref().and_then(|w| w.upgrade())
+        else {
+            return Default::default();
+        };
+        let window_inner = WindowInner::from_pub(window.window());
+        let component_rc = window_inner.component();
+        let component = crate::item_tree::ItemTreeRc::borrow_pin(&component_rc);
+        let Some(window_item) = crate::items::ItemRef::downcast_pin::<crate::items::WindowItem>(
+            component.as_ref().get_item_ref(0),
+        ) else {
+            return Default::default();
+        };
+        let factor = ScaleFactor::new(window_inner.scale_factor());
+        let size: PhysicalSize =
+            (LogicalSize::from_lengths(window_item.width(), window_item.height()).cast() * factor)
+                .cast();
+        if size.is_empty() {
+            return Default::default();
         }
+        let (width, height) = if self.rotation.get().is_transpose() {
+            (size.height as usize, size.width as usize)
+        } else {
+            (size.width as usize, size.height as usize)
+        };
+        assert!(
+            pixel_stride >= width && buffer.len() >= (height * pixel_stride + width) - pixel_stride,
+            "buffer of size {} with stride {pixel_stride} is too small to handle a window of size {size:?}",
+            buffer.len()
+        );
+        render_window_frame_in_bands(
+            window_inner,
+            window_item.background(),
+            size,
+            self,
+            buffer,
+            pixel_stride,
+        )
     }
 }
 
@@ -551,6 +754,7 @@ fn text_input_byte_offset_for_position(
                     vertical_alignment: text_input.vertical_alignment(),
                     wrap: text_input.wrap(),
                     overflow: TextOverflow::Clip,
+                    text_direction: text_input.text_direction(),
                     single_line: false,
                 };
 
@@ -571,6 +775,7 @@ fn text_input_byte_offset_for_position(
                     vertical_alignment: text_input.vertical_alignment(),
                     wrap: text_input.wrap(),
                     overflow: TextOverflow::Clip,
+                    text_direction: text_input.text_direction(),
                     single_line: false,
                 };
 
@@ -608,6 +813,7 @@ fn text_input_cursor_rect_for_byte_offset(
                     vertical_alignment: text_input.vertical_alignment(),
                     wrap: text_input.wrap(),
                     overflow: TextOverflow::Clip,
+                    text_direction: text_input.text_direction(),
                     single_line: false,
                 };
 
@@ -626,6 +832,7 @@ fn text_input_cursor_rect_for_byte_offset(
                     vertical_alignment: text_input.vertical_alignment(),
                     wrap: text_input.wrap(),
                     overflow: TextOverflow::Clip,
+                    text_direction: text_input.text_direction(),
                     single_line: false,
                 };
 
@@ -653,6 +860,8 @@ fn free_graphics_resources(
         for item in items {
             item.cached_rendering_data_offset().release(&mut self.partial_cache.borrow_mut());
         }
+        #[cfg(feature = "std")]
+        self.layer_cache.component_destroyed(_component);
         // We don't have a way to determine the screen region of the delete items, what's in the cache is relative. So
         // as a last resort, refresh everything.
         self.force_screen_refresh.set(true);
@@ -690,6 +899,8 @@ fn default_font_size(&self) -> LogicalLength {
     fn set_window_adapter(&self, window_adapter: &Rc<dyn WindowAdapter>) {
         *self.maybe_window_adapter.borrow_mut() = Some(Rc::downgrade(window_adapter));
         self.partial_cache.borrow_mut().clear();
+        #[cfg(feature = "std")]
+        self.layer_cache.clear_all();
     }
 }
 
@@ -698,104 +909,219 @@ fn render_window_frame_by_line(
     background: Brush,
     size: PhysicalSize,
     renderer: &SoftwareRenderer,
-    mut line_buffer: impl LineBufferProvider,
+    line_buffer: impl LineBufferProvider,
 ) -> PhysicalRegion {
     let rotation = RotationInfo { orientation: renderer.rotation.get(), screen_size: size };
-    let mut scene = prepare_scene(window, size, renderer);
+    let (mut scene, vectors) = prepare_scene(window, size, &background, renderer);
+
+    let dirty_region = scene.dirty_region.transformed(rotation);
+    scene.current_line = dirty_region.bounding_rect().origin.y_length();
 
-    let dirty_region = scene.dirty_region;
-    let to_draw_tr = dirty_region.transformed(rotation);
+    render_scene_by_line(
+        &mut scene,
+        &dirty_region,
+        &SceneData::new(&vectors),
+        background_color(&background),
+        line_buffer,
+    );
+    dirty_region
+}
+
+/// Renders the window in horizontal bands of the buffer, in parallel on the rayon thread pool.
+#[cfg(feature = "software-renderer-parallel")]
+fn render_window_frame_in_bands<T: TargetPixel + Send + Sync>(
+    window: &WindowInner,
+    background: Brush,
+    size: PhysicalSize,
+    renderer: &SoftwareRenderer,
+    buffer: &mut [T],
+    pixel_stride: usize,
+) -> PhysicalRegion {
+    use rayon::prelude::*;
+
+    /// A LineBufferProvider for the lines of a band
+    struct BandBuffer<'a, T> {
+        buffer: &'a mut [T],
+        first_line: usize,
+        stride: usize,
+    }
+    impl<'a, T: TargetPixel> LineBufferProvider for BandBuffer<'a, T> {
+        type TargetPixel = T;
+        fn process_line(
+            &mut self,
+            line: usize,
+            range: core::ops::Range<usize>,
+            render_fn: impl FnOnce(&mut [Self::TargetPixel]),
+        ) {
+            render_fn(&mut self.buffer[(line - self.first_line) * self.stride..][range])
+        }
+    }
 
-    scene.current_line = to_draw_tr.origin.y_length();
+    let rotation = RotationInfo { orientation: renderer.rotation.get(), screen_size: size };
+    let (scene, vectors) = prepare_scene(window, size, &background, renderer);
+
+    let dirty_region = scene.dirty_region.transformed(rotation);
+    let bounding_rect = dirty_region.bounding_rect();
+    if bounding_rect.is_empty() {
+        return dirty_region;
+    }
+
+    // Use more bands than threads, so that the threads that rendered simple bands can help with the others
+    let band_count = rayon::current_num_threads() * 2;
+    let band_height = (bounding_rect.height() as usize + band_count - 1) / band_count;
+    let first_line = bounding_rect.min_y() as usize;
+    let end = (bounding_rect.max_y() as usize * pixel_stride).min(buffer.len());
+
+    let items = &scene.items;
+    let data = SceneData::new(&vectors);
+    let background_color = background_color(&background);
+    buffer[first_line * pixel_stride..end]
+        .par_chunks_mut(band_height * pixel_stride)
+        .enumerate()
+        .for_each(|(band, band_buffer)| {
+            let band_first_line = first_line + band * band_height;
+            let band_rect = PhysicalRect::new(
+                euclid::point2(bounding_rect.min_x(), band_first_line as i16),
+                euclid::size2(bounding_rect.width(), band_height as i16),
+            );
+            let band_region = dirty_region.intersection(&band_rect);
+            // Each band has its own scene, that starts at the first line of the band's region
+            let mut band_scene = Scene::new(items.clone(), band_region);
+            render_scene_by_line(
+                &mut band_scene,
+                &band_region,
+                &data,
+                background_color,
+                BandBuffer {
+                    buffer: band_buffer,
+                    first_line: band_first_line,
+                    stride: pixel_stride,
+                },
+            );
+        });
+    dirty_region
+}
 
+/// Returns the color used to fill the background, which doesn't include gradients as they are
+/// part of the scene.
+fn background_color<T: TargetPixel>(background: &Brush) -> T {
     let mut background_color = TargetPixel::background();
-    // FIXME gradient
-    TargetPixel::blend(&mut background_color, background.color().into());
-
-    while scene.current_line < to_draw_tr.origin.y_length() + to_draw_tr.size.height_length() {
-        line_buffer.process_line(
-            scene.current_line.get() as usize,
-            to_draw_tr.min_x() as usize..to_draw_tr.max_x() as usize,
-            |line_buffer| {
-                let offset = to_draw_tr.min_x() as usize;
-
-                line_buffer.fill(background_color);
-                for span in scene.items[0..scene.current_items_index].iter().rev() {
-                    debug_assert!(scene.current_line >= span.pos.y_length());
-                    debug_assert!(
-                        scene.current_line < span.pos.y_length() + span.size.height_length(),
-                    );
-                    match span.command {
-                        SceneCommand::Rectangle { color } => {
-                            TargetPixel::blend_slice(
-                                &mut line_buffer[span.pos.x as usize - offset
-                                    ..(span.pos.x_length() + span.size.width_length()).get()
-                                        as usize
-                                        - offset],
-                                color,
-                            );
-                        }
-                        SceneCommand::Texture { texture_index } => {
-                            let texture = &scene.vectors.textures[texture_index as usize];
-                            draw_functions::draw_texture_line(
-                                &PhysicalRect {
-                                    origin: span.pos - euclid::vec2(offset as i16, 0),
-                                    size: span.size,
-                                },
-                                scene.current_line,
-                                texture,
-                                line_buffer,
-                            );
-                        }
-                        SceneCommand::SharedBuffer { shared_buffer_index } => {
-                            let texture = scene.vectors.shared_buffers
-                                [shared_buffer_index as usize]
-                                .as_texture();
-                            draw_functions::draw_texture_line(
-                                &PhysicalRect {
-                                    origin: span.pos - euclid::vec2(offset as i16, 0),
-                                    size: span.size,
-                                },
-                                scene.current_line,
-                                &texture,
-                                line_buffer,
-                            );
-                        }
-                        SceneCommand::RoundedRectangle { rectangle_index } => {
-                            let rr = &scene.vectors.rounded_rectangles[rectangle_index as usize];
-                            draw_functions::draw_rounded_rectangle_line(
-                                &PhysicalRect {
-                                    origin: span.pos - euclid::vec2(offset as i16, 0),
-                                    size: span.size,
-                                },
-                                scene.current_line,
-                                rr,
-                                line_buffer,
-                            );
+    if !matches!(background, Brush::LinearGradient(_)) {
+        TargetPixel::blend(&mut background_color, background.color().into());
+    }
+    background_color
+}
+
+/// Renders the lines of the scene within the region, starting at `scene.current_line`.
+fn render_scene_by_line<B: LineBufferProvider>(
+    scene: &mut Scene,
+    dirty_region: &PhysicalRegion,
+    data: &SceneData,
+    background_color: B::TargetPixel,
+    mut line_buffer: B,
+) {
+    let bounding_rect = dirty_region.bounding_rect();
+    // The rectangles of the region are disjoint, so the ones that cross a line are processed from left to right
+    let mut rectangles = dirty_region.iter_rect().collect::<Vec<_>>();
+    rectangles.sort_unstable_by_key(|r| r.min_x());
+
+    while scene.current_line < bounding_rect.origin.y_length() + bounding_rect.size.height_length()
+    {
+        for rect in
+            rectangles.iter().filter(|r| (r.min_y()..r.max_y()).contains(&scene.current_line.get()))
+        {
+            line_buffer.process_line(
+                scene.current_line.get() as usize,
+                rect.min_x() as usize..rect.max_x() as usize,
+                |line_buffer| {
+                    let offset = rect.min_x() as usize;
+
+                    line_buffer.fill(background_color);
+                    for span in scene.items[0..scene.current_items_index].iter().rev() {
+                        debug_assert!(scene.current_line >= span.pos.y_length());
+                        debug_assert!(
+                            scene.current_line < span.pos.y_length() + span.size.height_length(),
+                        );
+                        if span.pos.x >= rect.max_x()
+                            || span.pos.x + span.size.width <= rect.min_x()
+                        {
+                            // This span belongs to another rectangle of the region
+                            continue;
                         }
-                        SceneCommand::Gradient { gradient_index } => {
-                            let g = &scene.vectors.gradients[gradient_index as usize];
-
-                            draw_functions::draw_gradient_line(
-                                &PhysicalRect {
-                                    origin: span.pos - euclid::vec2(offset as i16, 0),
-                                    size: span.size,
-                                },
-                                scene.current_line,
-                                g,
-                                line_buffer,
-                            );
+                        debug_assert!(
+                            span.pos.x >= rect.min_x()
+                                && span.pos.x + span.size.width <= rect.max_x()
+                        );
+                        match span.command {
+                            SceneCommand::Rectangle { color } => {
+                                TargetPixel::blend_slice(
+                                    &mut line_buffer[span.pos.x as usize - offset
+                                        ..(span.pos.x_length() + span.size.width_length()).get()
+                                            as usize
+                                            - offset],
+                                    color,
+                                );
+                            }
+                            SceneCommand::Texture { texture_index } => {
+                                let texture = &data.textures[texture_index as usize];
+                                draw_functions::draw_texture_line(
+                                    &PhysicalRect {
+                                        origin: span.pos - euclid::vec2(offset as i16, 0),
+                                        size: span.size,
+                                    },
+                                    scene.current_line,
+                                    texture,
+                                    line_buffer,
+                                );
+                            }
+                            SceneCommand::SharedBuffer { shared_buffer_index } => {
+                                let texture = &data.shared_buffers[shared_buffer_index as usize];
+                                draw_functions::draw_texture_line(
+                                    &PhysicalRect {
+                                        origin: span.pos - euclid::vec2(offset as i16, 0),
+                                        size: span.size,
+                                    },
+                                    scene.current_line,
+                                    texture,
+                                    line_buffer,
+                                );
+                            }
+                            SceneCommand::RoundedRectangle { rectangle_index } => {
+                                let rr = &data.rounded_rectangles[rectangle_index as usize];
+                                draw_functions::draw_rounded_rectangle_line(
+                                    &PhysicalRect {
+                                        origin: span.pos - euclid::vec2(offset as i16, 0),
+                                        size: span.size,
+                                    },
+                                    scene.current_line,
+                                    rr,
+                                    line_buffer,
+                                );
+                            }
+                            SceneCommand::Gradient { gradient_index } => {
+                                let g = &data.gradients[gradient_index as usize];
+
+                                draw_functions::draw_gradient_line(
+                                    &PhysicalRect {
+                                        origin: span.pos - euclid::vec2(offset as i16, 0),
+                                        size: span.size,
+                                    },
+                                    scene.current_line,
+                                    g,
+                                    line_buffer,
+                                );
+                            }
                         }
                     }
-                }
-            },
-        );
+                },
+            );
+        }
 
-        if scene.current_line < to_draw_tr.origin.y_length() + to_draw_tr.size.height_length() {
+        if scene.current_line < bounding_rect.origin.y_length() + bounding_rect.size.height_length()
+        {
             scene.next_line();
         }
     }
-    PhysicalRegion(to_draw_tr)
 }
 
 #[derive(Default)]
@@ -806,6 +1132,28 @@ struct SceneVectors {
     gradients: Vec<GradientCommand>,
 }
 
+/// The data referenced by the commands of the scene, borrowed from the [`SceneVectors`].
+///
+/// Unlike the [`SceneVectors`], this doesn't hold any reference count, so it can be shared
+/// between threads.
+struct SceneData<'a> {
+    textures: &'a [SceneTexture<'static>],
+    rounded_rectangles: &'a [RoundedRectangle],
+    shared_buffers: Vec<SceneTexture<'a>>,
+    gradients: &'a [GradientCommand],
+}
+
+impl<'a> SceneData<'a> {
+    fn new(vectors: &'a SceneVectors) -> Self {
+        Self {
+            textures: &vectors.textures,
+            rounded_rectangles: &vectors.rounded_rectangles,
+            shared_buffers: vectors.shared_buffers.iter().map(|b| b.as_texture()).collect(),
+            gradients: &vectors.gradients,
+        }
+    }
+}
+
 struct Scene {
     /// the next line to be processed
     current_line: PhysicalLength,
@@ -817,21 +1165,15 @@ struct Scene {
     ///   sorted by z (front to back)
     items: Vec<SceneItem>,
 
-    vectors: SceneVectors,
-
     future_items_index: usize,
     current_items_index: usize,
 
-    dirty_region: DirtyRegion,
+    dirty_region: PhysicalRegion,
 }
 
 impl Scene {
-    pub fn new(
-        mut items: Vec<SceneItem>,
-        vectors: SceneVectors,
-        dirty_region: DirtyRegion,
-    ) -> Self {
-        let current_line = dirty_region.origin.y_length();
+    pub fn new(mut items: Vec<SceneItem>, dirty_region: PhysicalRegion) -> Self {
+        let current_line = dirty_region.bounding_rect().origin.y_length();
         items.retain(|i| i.pos.y_length() + i.size.height_length() > current_line);
         items.sort_unstable_by(compare_scene_item);
         let current_items_index = items.partition_point(|i| i.pos.y_length() <= current_line);
@@ -841,7 +1183,6 @@ pub fn new(
             current_line,
             current_items_index,
             future_items_index: current_items_index,
-            vectors,
             dirty_region,
         }
     }
@@ -1003,7 +1344,7 @@ enum SceneCommand {
 }
 
 struct SceneTexture<'a> {
-    /// This should have a size so that the entire slice is ((height - 1) * pixel_stride + width) * bpp
+    /// This should have a size so that the entire slice is ((height - 1) * pixel_stride + width) * bpp / pixels_per_byte
     data: &'a [u8],
     format: PixelFormat,
     /// number of pixels between two lines in the source
@@ -1014,7 +1355,7 @@ struct SceneTexture<'a> {
 
 impl<'a> SceneTexture<'a> {
     fn source_size(&self) -> PhysicalSize {
-        let len = self.data.len() / self.format.bpp();
+        let len = self.data.len() * self.format.pixels_per_byte() / self.format.bpp();
         let stride = self.pixel_stride as usize;
         let h = len / stride;
         let w = len % stride;
@@ -1098,6 +1439,34 @@ fn as_texture(&self) -> SceneTexture<'_> {
     }
 }
 
+/// Returns true if the part `clipped` of the rounded rectangle `geom` overlaps its corners or
+/// its border, that is the area that is not fully covered by the rounded rectangle
+fn reaches_rounded_border(
+    geom: LogicalRect,
+    clipped: LogicalRect,
+    radius: LogicalBorderRadius,
+    border_width: LogicalLength,
+) -> bool {
+    let radius = radius
+        .min(LogicalBorderRadius::from_length(geom.width_length() / 2 as Coord))
+        .min(LogicalBorderRadius::from_length(geom.height_length() / 2 as Coord));
+    let corner = |x: Coord, y: Coord, r: Coord| {
+        LogicalRect::new(LogicalPoint::new(x, y), LogicalSize::new(r, r))
+    };
+    let corners = [
+        corner(geom.min_x(), geom.min_y(), radius.top_left),
+        corner(geom.max_x() - radius.top_right, geom.min_y(), radius.top_right),
+        corner(
+            geom.max_x() - radius.bottom_right,
+            geom.max_y() - radius.bottom_right,
+            radius.bottom_right,
+        ),
+        corner(geom.min_x(), geom.max_y() - radius.bottom_left, radius.bottom_left),
+    ];
+    let inner = geom.inflate(-border_width.get(), -border_width.get());
+    !inner.contains_rect(&clipped) || corners.iter().any(|c| c.intersects(&clipped))
+}
+
 // Given a rectangle of coordinate in a buffer and a stride, compute the range, in pixel
 fn compute_range_in_buffer(
     source_rect: &PhysicalRect,
@@ -1149,15 +1518,22 @@ struct GradientCommand {
 fn prepare_scene(
     window: &WindowInner,
     size: PhysicalSize,
+    background: &Brush,
     software_renderer: &SoftwareRenderer,
-) -> Scene {
+) -> (Scene, SceneVectors) {
     let factor = ScaleFactor::new(window.scale_factor());
+    #[cfg(feature = "std")]
+    software_renderer
+        .layer_cache
+        .clear_cache_if_scale_factor_changed(window.window_adapter().window());
     let prepare_scene = SceneBuilder::new(
         size,
         factor,
         window,
         PrepareScene::default(),
         software_renderer.rotation.get(),
+        &software_renderer.box_shadow_cache,
+        &software_renderer.layer_cache,
     );
     let mut renderer = crate::item_rendering::PartialRenderer::new(
         &software_renderer.partial_cache,
@@ -1165,22 +1541,27 @@ fn prepare_scene(
         prepare_scene,
     );
 
-    let mut dirty_region = PhysicalRect::default();
+    let mut dirty_region = PhysicalRegion::default();
     window.draw_contents(|components| {
         for (component, origin) in components {
             renderer.compute_dirty_regions(component, *origin);
         }
 
-        dirty_region = (renderer.dirty_region.to_rect().cast() * factor).round_out().cast();
+        dirty_region = PhysicalRegion::from_dirty_region(&renderer.dirty_region, factor);
         dirty_region = software_renderer.apply_dirty_region(dirty_region, size);
 
-        renderer.combine_clip(
-            (dirty_region.cast() / factor).cast(),
-            LogicalBorderRadius::zero(),
-            LogicalLength::zero(),
-        );
-        for (component, origin) in components {
-            crate::item_rendering::render_component_items(component, &mut renderer, *origin);
+        for rect in dirty_region.iter_rect() {
+            renderer.save_state();
+            renderer.combine_clip(
+                (rect.cast() / factor).cast(),
+                LogicalBorderRadius::zero(),
+                LogicalLength::zero(),
+            );
+            renderer.actual_renderer.draw_window_background_gradient(background);
+            for (component, origin) in components {
+                crate::item_rendering::render_component_items(component, &mut renderer, *origin);
+            }
+            renderer.restore_state();
         }
     });
 
@@ -1192,8 +1573,10 @@ fn prepare_scene(
     }
 
     let prepare_scene = renderer.into_inner();
+    software_renderer.box_shadow_cache.collect_garbage();
 
-    Scene::new(prepare_scene.processor.items, prepare_scene.processor.vectors, dirty_region)
+    let processor = prepare_scene.processor.base;
+    (Scene::new(processor.items, dirty_region), processor.vectors)
 }
 
 trait ProcessScene {
@@ -1335,13 +1718,285 @@ fn process_gradient(&mut self, geometry: PhysicalRect, gradient: GradientCommand
     }
 }
 
+/// An offscreen buffer in which the children of some elements are rendered, before being composed
+/// into the parent layer or the scene.
+struct Layer {
+    /// The area covered by the layer, in (rotated) physical screen coordinates
+    geometry: PhysicalRect,
+    buffer: SharedPixelBuffer<crate::graphics::Rgba8Pixel>,
+    /// How the layer is composed
+    composition: LayerComposition,
+    /// The depth of the state stack when the layer was created.
+    /// The layer is composed when that state is restored
+    state_depth: usize,
+}
+
+enum LayerComposition {
+    /// The children of a `Clip` with a border radius, that are masked by the rounded rectangle
+    /// (relative to the layer)
+    RoundedClip(RoundedRectangle),
+    /// The children of an `Opacity`, that are blended as a group with the given alpha
+    Opacity(u8),
+    /// The children of a `Rotate`, rendered untransformed in the layer's own coordinates
+    Transform(LayerTransform),
+    /// A gradient that is masked by the coverage of a path (relative to the layer)
+    #[cfg(feature = "std")]
+    AlphaMask(Vec<u8>),
+}
+
+struct LayerTransform {
+    /// Maps the physical coordinates of the children to the physical coordinates of the screen
+    /// (before the screen rotation)
+    transform: euclid::default::Transform2D<f32>,
+    /// The position of the layer in the physical coordinates of the children
+    origin: euclid::default::Vector2D<f32>,
+    /// The part of the screen (before the screen rotation) in which the layer can be drawn
+    clip: PhysicalRect,
+    /// The rotation of the screen, which was replaced by the layer's coordinates while rendering the children
+    rotation: RotationInfo,
+}
+
+impl Layer {
+    fn render_to_buffer(&mut self) -> RenderToBuffer<'_, PremultipliedRgbaColor> {
+        let stride = self.buffer.width() as usize;
+        RenderToBuffer { buffer: bytemuck::cast_slice_mut(self.buffer.make_mut_bytes()), stride }
+    }
+
+    /// Map the geometry to the layer's coordinates, or return None if it doesn't fit in the layer.
+    /// (The commands are already clipped, so they should fit, unless of rounding errors)
+    fn local(&self, geometry: PhysicalRect) -> Option<PhysicalRect> {
+        self.geometry
+            .contains_rect(&geometry)
+            .then(|| geometry.translate(-self.geometry.origin.to_vector()))
+    }
+
+    /// Return the geometry and the command to draw the layer
+    fn into_command(self) -> Option<(PhysicalRect, SharedBufferCommand)> {
+        let Layer { geometry, buffer, composition, .. } = self;
+        match composition {
+            LayerComposition::RoundedClip(mask) => Some(Self::masked(geometry, buffer, mask)),
+            LayerComposition::Opacity(alpha) => {
+                // The content of the layer is already rotated
+                let mut command = Self::buffer_command(buffer, RenderingRotation::NoRotation);
+                command.extra.alpha = alpha;
+                Some((geometry, command))
+            }
+            LayerComposition::Transform(transform) => {
+                Self::transformed(geometry, &buffer, transform)
+            }
+            #[cfg(feature = "std")]
+            LayerComposition::AlphaMask(mask) => {
+                Some((geometry, Self::apply_mask(buffer, mask.into_iter())))
+            }
+        }
+    }
+
+    /// Apply the mask and return the command to draw the layer
+    fn masked(
+        geometry: PhysicalRect,
+        buffer: SharedPixelBuffer<crate::graphics::Rgba8Pixel>,
+        mask: RoundedRectangle,
+    ) -> (PhysicalRect, SharedBufferCommand) {
+        let size = geometry.size;
+        let mut mask_pixels = vec![PremultipliedRgbaColor::default(); size.area() as usize];
+        RenderToBuffer { buffer: &mut mask_pixels, stride: size.width as usize }
+            .process_rounded_rectangle(PhysicalRect::from_size(size), mask);
+        (geometry, Self::apply_mask(buffer, mask_pixels.into_iter().map(|p| p.alpha)))
+    }
+
+    /// Multiply the pixels of the layer with the coverage of the mask and return the command to draw it
+    fn apply_mask(
+        mut buffer: SharedPixelBuffer<crate::graphics::Rgba8Pixel>,
+        mask: impl Iterator<Item = u8>,
+    ) -> SharedBufferCommand {
+        let pixels: &mut [PremultipliedRgbaColor] =
+            bytemuck::cast_slice_mut(buffer.make_mut_bytes());
+        for (pixel, a) in pixels.iter_mut().zip(mask) {
+            let a = a as u16;
+            if a != 255 {
+                pixel.red = (pixel.red as u16 * a / 255) as u8;
+                pixel.green = (pixel.green as u16 * a / 255) as u8;
+                pixel.blue = (pixel.blue as u16 * a / 255) as u8;
+                pixel.alpha = (pixel.alpha as u16 * a / 255) as u8;
+            }
+        }
+        // The content of the layer is already rotated
+        Self::buffer_command(buffer, RenderingRotation::NoRotation)
+    }
+
+    /// Resample the layer with the transformation and return the command to draw it
+    fn transformed(
+        geometry: PhysicalRect,
+        buffer: &SharedPixelBuffer<crate::graphics::Rgba8Pixel>,
+        t: LayerTransform,
+    ) -> Option<(PhysicalRect, SharedBufferCommand)> {
+        let inverse = t.transform.inverse()?;
+        let bounds =
+            t.transform.outer_transformed_rect(&geometry.to_untyped().cast().translate(t.origin));
+        let target =
+            PhysicalRect::from_untyped(&bounds.round_out().try_cast()?).intersection(&t.clip)?;
+
+        let (width, height) = (geometry.width() as i32, geometry.height() as i32);
+        let source: &[PremultipliedRgbaColor] = bytemuck::cast_slice(buffer.as_bytes());
+        let fetch = |x: i32, y: i32| -> [f32; 4] {
+            if x < 0 || y < 0 || x >= width || y >= height {
+                return [0.; 4];
+            }
+            let p = source[(y * width + x) as usize];
+            [p.red as f32, p.green as f32, p.blue as f32, p.alpha as f32]
+        };
+
+        let mut result = SharedPixelBuffer::new(target.width() as u32, target.height() as u32);
+        let pixels: &mut [PremultipliedRgbaColor] =
+            bytemuck::cast_slice_mut(result.make_mut_bytes());
+        for (y, line) in pixels.chunks_mut(target.width() as usize).enumerate() {
+            let y = target.min_y() as f32 + y as f32 + 0.5;
+            for (x, pixel) in line.iter_mut().enumerate() {
+                // Sample the layer at the center of the pixel, with a bilinear interpolation
+                let x = target.min_x() as f32 + x as f32 + 0.5;
+                let p = inverse.transform_point(euclid::point2(x, y))
+                    - t.origin
+                    - euclid::vec2(0.5, 0.5);
+                let (x0, y0) = (p.x.floor(), p.y.floor());
+                let (fx, fy) = (p.x - x0, p.y - y0);
+                let (x0, y0) = (x0 as i32, y0 as i32);
+                let (c00, c10, c01, c11) =
+                    (fetch(x0, y0), fetch(x0 + 1, y0), fetch(x0, y0 + 1), fetch(x0 + 1, y0 + 1));
+                let c = |i: usize| {
+                    let top = c00[i] + (c10[i] - c00[i]) * fx;
+                    let bottom = c01[i] + (c11[i] - c01[i]) * fx;
+                    (top + (bottom - top) * fy + 0.5) as u8
+                };
+                *pixel = PremultipliedRgbaColor { red: c(0), green: c(1), blue: c(2), alpha: c(3) };
+            }
+        }
+        Some((target.transformed(t.rotation), Self::buffer_command(result, t.rotation.orientation)))
+    }
+
+    fn buffer_command(
+        buffer: SharedPixelBuffer<crate::graphics::Rgba8Pixel>,
+        rotation: RenderingRotation,
+    ) -> SharedBufferCommand {
+        SharedBufferCommand {
+            source_rect: PhysicalRect::from_size(euclid::size2(
+                buffer.width() as i16,
+                buffer.height() as i16,
+            )),
+            buffer: SharedBufferData::SharedImage(SharedImageBuffer::RGBA8Premultiplied(buffer)),
+            extra: SceneTextureExtra {
+                colorize: Default::default(),
+                alpha: 255,
+                rotation,
+                dx: Fixed::from_integer(1),
+                dy: Fixed::from_integer(1),
+                off_x: Fixed::from_integer(0),
+                off_y: Fixed::from_integer(0),
+            },
+        }
+    }
+}
+
+impl ProcessScene for Layer {
+    fn process_texture(&mut self, geometry: PhysicalRect, texture: SceneTexture<'static>) {
+        if let Some(geometry) = self.local(geometry) {
+            self.render_to_buffer().process_texture(geometry, texture)
+        }
+    }
+
+    fn process_shared_image_buffer(&mut self, geometry: PhysicalRect, buffer: SharedBufferCommand) {
+        if let Some(geometry) = self.local(geometry) {
+            self.render_to_buffer().process_shared_image_buffer(geometry, buffer)
+        }
+    }
+
+    fn process_rectangle(&mut self, geometry: PhysicalRect, color: PremultipliedRgbaColor) {
+        if let Some(geometry) = geometry.intersection(&self.geometry) {
+            let geometry = geometry.translate(-self.geometry.origin.to_vector());
+            self.render_to_buffer().process_rectangle(geometry, color)
+        }
+    }
+
+    fn process_rounded_rectangle(&mut self, geometry: PhysicalRect, data: RoundedRectangle) {
+        if let Some(geometry) = self.local(geometry) {
+            self.render_to_buffer().process_rounded_rectangle(geometry, data)
+        }
+    }
+
+    fn process_gradient(&mut self, geometry: PhysicalRect, gradient: GradientCommand) {
+        if let Some(geometry) = self.local(geometry) {
+            self.render_to_buffer().process_gradient(geometry, gradient)
+        }
+    }
+}
+
+/// The content of a `Layer` element with a `cache-rendering-hint`, rendered without the screen rotation
+#[cfg(feature = "std")]
+#[derive(Clone)]
+struct CachedLayer {
+    buffer: SharedPixelBuffer<crate::graphics::Rgba8Pixel>,
+    /// The position of the buffer relative to the layer item, in physical pixels
+    origin: euclid::Vector2D<i16, PhysicalPx>,
+}
+
+/// The cached content of the `Layer` elements, invalidated when their children change
+#[cfg(feature = "std")]
+type LayerCache = crate::item_rendering::ItemCache<Option<CachedLayer>>;
+#[cfg(not(feature = "std"))]
+type LayerCache = ();
+
+/// Forwards the commands to the innermost [`Layer`], or to the base processor if there is no layer
+struct LayeredProcessor<T> {
+    base: T,
+    layers: Vec<Layer>,
+}
+
+impl<T: ProcessScene> ProcessScene for LayeredProcessor<T> {
+    fn process_texture(&mut self, geometry: PhysicalRect, texture: SceneTexture<'static>) {
+        match self.layers.last_mut() {
+            Some(layer) => layer.process_texture(geometry, texture),
+            None => self.base.process_texture(geometry, texture),
+        }
+    }
+
+    fn process_shared_image_buffer(&mut self, geometry: PhysicalRect, buffer: SharedBufferCommand) {
+        match self.layers.last_mut() {
+            Some(layer) => layer.process_shared_image_buffer(geometry, buffer),
+            None => self.base.process_shared_image_buffer(geometry, buffer),
+        }
+    }
+
+    fn process_rectangle(&mut self, geometry: PhysicalRect, color: PremultipliedRgbaColor) {
+        match self.layers.last_mut() {
+            Some(layer) => layer.process_rectangle(geometry, color),
+            None => self.base.process_rectangle(geometry, color),
+        }
+    }
+
+    fn process_rounded_rectangle(&mut self, geometry: PhysicalRect, data: RoundedRectangle) {
+        match self.layers.last_mut() {
+            Some(layer) => layer.process_rounded_rectangle(geometry, data),
+            None => self.base.process_rounded_rectangle(geometry, data),
+        }
+    }
+
+    fn process_gradient(&mut self, geometry: PhysicalRect, gradient: GradientCommand) {
+        match self.layers.last_mut() {
+            Some(layer) => layer.process_gradient(geometry, gradient),
+            None => self.base.process_gradient(geometry, gradient),
+        }
+    }
+}
+
 struct SceneBuilder<'a, T> {
-    processor: T,
+    processor: LayeredProcessor<T>,
     state_stack: Vec<RenderState>,
     current_state: RenderState,
     scale_factor: ScaleFactor,
     window: &'a WindowInner,
     rotation: RotationInfo,
+    box_shadow_cache: &'a box_shadow::BoxShadowCache,
+    #[cfg_attr(not(feature = "std"), allow(dead_code))]
+    layer_cache: &'a LayerCache,
 }
 
 impl<'a, T: ProcessScene> SceneBuilder<'a, T> {
@@ -1351,9 +2006,11 @@ fn new(
         window: &'a WindowInner,
         processor: T,
         orientation: RenderingRotation,
+        box_shadow_cache: &'a box_shadow::BoxShadowCache,
+        layer_cache: &'a LayerCache,
     ) -> Self {
         Self {
-            processor,
+            processor: LayeredProcessor { base: processor, layers: vec![] },
             state_stack: vec![],
             current_state: RenderState {
                 alpha: 1.,
@@ -1366,6 +2023,8 @@ fn new(
             scale_factor,
             window,
             rotation: RotationInfo { orientation, screen_size },
+            box_shadow_cache,
+            layer_cache,
         }
     }
 
@@ -1428,20 +2087,28 @@ fn draw_image_impl(
                         continue;
                     };
 
-                    let off_x = Fixed::from_integer(tiled_off.x as i32)
+                    // The lines of the formats packing several pixels in a byte start on a new byte,
+                    // and the part of the texture to draw must also start and end on a byte boundary.
+                    // (When tiling an image clipped in the middle of a byte, the tile is then a bit larger)
+                    let ppb = t.format.pixels_per_byte() as i16;
+                    let pixel_stride = ((t.rect.width() as i16 + ppb - 1) / ppb * ppb) as u16;
+                    let mut texture_rect = PhysicalRect::from_untyped(
+                        &src_rect.translate(-t.rect.origin.to_vector()).cast(),
+                    );
+                    let align_x = texture_rect.origin.x % ppb;
+                    texture_rect.origin.x -= align_x;
+                    texture_rect.size.width =
+                        (texture_rect.size.width + align_x + ppb - 1) / ppb * ppb;
+
+                    let off_x = Fixed::from_integer(tiled_off.x as i32 + align_x as i32)
                         + (Fixed::<i32, 8>::from_fixed(dx))
                             * (clipped_target.origin.x - target_rect.origin.x) as i32;
                     let off_y = Fixed::from_integer(tiled_off.y as i32)
                         + (Fixed::<i32, 8>::from_fixed(dy))
                             * (clipped_target.origin.y - target_rect.origin.y) as i32;
 
-                    let pixel_stride = t.rect.width() as u16;
-                    let core::ops::Range { start, end } = compute_range_in_buffer(
-                        &PhysicalRect::from_untyped(
-                            &src_rect.translate(-t.rect.origin.to_vector()).cast(),
-                        ),
-                        pixel_stride as usize,
-                    );
+                    let core::ops::Range { start, end } =
+                        compute_range_in_buffer(&texture_rect, pixel_stride as usize);
                     let bpp = t.format.bpp();
 
                     let color = if colorize.alpha() > 0 { colorize } else { t.color };
@@ -1454,7 +2121,8 @@ fn draw_image_impl(
                     self.processor.process_texture(
                         clipped_target.cast().transformed(self.rotation),
                         SceneTexture {
-                            data: &data.as_slice()[t.index..][start * bpp..end * bpp],
+                            data: &data.as_slice()[t.index..]
+                                [start * bpp / ppb as usize..end * bpp / ppb as usize],
                             pixel_stride,
                             format: t.format,
                             extra: SceneTextureExtra {
@@ -1655,25 +2323,410 @@ fn draw_text_paragraph<Font: AbstractFont>(
             .ok();
     }
 
-    /// Returns the color, mixed with the current_state's alpha
-    fn alpha_color(&self, color: Color) -> Color {
-        if self.current_state.alpha < 1.0 {
-            Color::from_argb_u8(
-                (color.alpha() as f32 * self.current_state.alpha) as u8,
-                color.red(),
-                color.green(),
-                color.blue(),
-            )
-        } else {
-            color
+    /// Draw the window background if it is a gradient.
+    /// (Plain colors are used to clear the buffer instead)
+    fn draw_window_background_gradient(&mut self, background: &Brush) {
+        if let Brush::LinearGradient(g) = background {
+            let geom = LogicalRect::from_size(
+                (self.rotation.screen_size.cast() / self.scale_factor).cast(),
+            );
+            if let Some(clipped) = geom.intersection(&self.current_state.clip) {
+                self.draw_linear_gradient(geom, clipped, g);
+            }
         }
     }
-}
 
-struct SelectionInfo {
-    selection_color: Color,
-    selection_background: Color,
-    selection: core::ops::Range<usize>,
+    /// Draw the part of the rectangle `geom` filled with the gradient that is within `clipped`
+    /// (both in the current coordinates)
+    #[allow(clippy::unnecessary_cast)] // Coord!
+    fn draw_linear_gradient(
+        &mut self,
+        geom: LogicalRect,
+        clipped: LogicalRect,
+        g: &crate::graphics::LinearGradientBrush,
+    ) {
+        let geom2 = (geom.cast() * self.scale_factor).transformed(self.rotation);
+        let clipped2 = (clipped.cast() * self.scale_factor).transformed(self.rotation);
+        let act_rect = (clipped.translate(self.current_state.offset.to_vector()).cast()
+            * self.scale_factor)
+            .round()
+            .cast()
+            .transformed(self.rotation);
+        let axis_angle = (360. - self.rotation.orientation.angle()) % 360.;
+        let angle = g.angle() - axis_angle;
+        let tan = angle.to_radians().tan().abs();
+        let start = if !tan.is_finite() {
+            255.
+        } else {
+            let h = tan * geom2.width() as f32;
+            255. * h / (h + geom2.height() as f32)
+        } as u8;
+        let mut angle = angle as i32 % 360;
+        if angle < 0 {
+            angle += 360;
+        }
+        let mut stops = g.stops().copied().peekable();
+        let mut idx = 0;
+        let stop_count = g.stops().count();
+        while let (Some(mut s1), Some(mut s2)) = (stops.next(), stops.peek().copied()) {
+            let mut flags = 0;
+            if (angle % 180) > 90 {
+                flags |= 0b1;
+            }
+            if angle <= 90 || angle > 270 {
+                core::mem::swap(&mut s1, &mut s2);
+                s1.position = 1. - s1.position;
+                s2.position = 1. - s2.position;
+                if idx == 0 {
+                    flags |= 0b100;
+                }
+                if idx == stop_count - 2 {
+                    flags |= 0b010;
+                }
+            } else {
+                if idx == 0 {
+                    flags |= 0b010;
+                }
+                if idx == stop_count - 2 {
+                    flags |= 0b100;
+                }
+            }
+
+            idx += 1;
+
+            let (adjust_left, adjust_right) = if (angle % 180) > 90 {
+                (
+                    (geom2.width() * s1.position).floor() as i16,
+                    (geom2.width() * (1. - s2.position)).ceil() as i16,
+                )
+            } else {
+                (
+                    (geom2.width() * (1. - s2.position)).ceil() as i16,
+                    (geom2.width() * s1.position).floor() as i16,
+                )
+            };
+
+            let gr = GradientCommand {
+                color1: self.alpha_color(s1.color).into(),
+                color2: self.alpha_color(s2.color).into(),
+                start,
+                flags,
+                top_clip: Length::new(
+                    (clipped2.min_y() - geom2.min_y()) as i16
+                        - (geom2.height() * s1.position).floor() as i16,
+                ),
+                bottom_clip: Length::new(
+                    (geom2.max_y() - clipped2.max_y()) as i16
+                        - (geom2.height() * (1. - s2.position)).ceil() as i16,
+                ),
+                left_clip: Length::new((clipped2.min_x() - geom2.min_x()) as i16 - adjust_left),
+                right_clip: Length::new((geom2.max_x() - clipped2.max_x()) as i16 - adjust_right),
+            };
+
+            let size_y = act_rect.height_length() + gr.top_clip + gr.bottom_clip;
+            let size_x = act_rect.width_length() + gr.left_clip + gr.right_clip;
+            if size_x.get() == 0 || size_y.get() == 0 {
+                // the position are too close to each other
+                // FIXME: For the first or the last, we should draw a plain color to the end
+                continue;
+            }
+
+            self.processor.process_gradient(act_rect, gr);
+        }
+    }
+
+    /// Draw the part of the rectangle `geom` filled with the gradient that is within `clipped`
+    /// (both in the current coordinates), masked by the coverage `mask` of the physical `target_rect`
+    #[cfg(feature = "std")]
+    fn draw_masked_linear_gradient(
+        &mut self,
+        geom: LogicalRect,
+        clipped: LogicalRect,
+        target_rect: PhysicalRect,
+        mask: Vec<u8>,
+        g: &crate::graphics::LinearGradientBrush,
+    ) {
+        let geometry = target_rect.transformed(self.rotation);
+        // The gradient is drawn in a layer in screen coordinates, so the mask needs to be rotated too
+        let mask = if self.rotation.orientation == RenderingRotation::NoRotation {
+            mask
+        } else {
+            let info = RotationInfo {
+                orientation: self.rotation.orientation,
+                screen_size: target_rect.size,
+            };
+            let (width, stride) = (target_rect.width() as usize, geometry.width() as usize);
+            let mut rotated = vec![0; mask.len()];
+            for (i, a) in mask.into_iter().enumerate() {
+                let p =
+                    PhysicalPoint::new((i % width) as i16, (i / width) as i16).transformed(info);
+                rotated[p.y as usize * stride + p.x as usize] = a;
+            }
+            rotated
+        };
+        self.processor.layers.push(Layer {
+            geometry,
+            buffer: SharedPixelBuffer::new(geometry.width() as u32, geometry.height() as u32),
+            composition: LayerComposition::AlphaMask(mask),
+            state_depth: self.state_stack.len(),
+        });
+        self.draw_linear_gradient(geom, clipped, g);
+        let layer = self.processor.layers.pop().unwrap();
+        if let Some((geometry, command)) = layer.into_command() {
+            self.processor.process_shared_image_buffer(geometry, command);
+        }
+    }
+
+    /// Returns the command to draw the part of the rounded rectangle `geom` that is within `clipped`
+    /// (both in the current coordinates), and the physical geometry in which to draw it.
+    #[allow(clippy::unnecessary_cast)] // Coord
+    fn rounded_rectangle(
+        &self,
+        geom: LogicalRect,
+        clipped: LogicalRect,
+        radius: LogicalBorderRadius,
+        border: LogicalLength,
+        inner_color: PremultipliedRgbaColor,
+        border_color: PremultipliedRgbaColor,
+    ) -> (PhysicalRect, RoundedRectangle) {
+        let radius = radius
+            .min(LogicalBorderRadius::from_length(geom.width_length() / 2 as Coord))
+            .min(LogicalBorderRadius::from_length(geom.height_length() / 2 as Coord));
+        let geom2 = (geom.cast() * self.scale_factor).transformed(self.rotation);
+        let clipped2 = (clipped.cast() * self.scale_factor).transformed(self.rotation);
+        let geometry = (clipped.translate(self.current_state.offset.to_vector()).cast()
+            * self.scale_factor)
+            .round()
+            .cast()
+            .transformed(self.rotation);
+        let radius = (radius.cast() * self.scale_factor).cast().transformed(self.rotation);
+        // Add a small value to make sure that the clip is always positive despite floating point shenanigans
+        const E: f32 = 0.00001;
+
+        (
+            geometry,
+            RoundedRectangle {
+                radius,
+                width: (border.cast() * self.scale_factor).cast(),
+                border_color,
+                inner_color,
+                top_clip: PhysicalLength::new((clipped2.min_y() - geom2.min_y() + E) as _),
+                bottom_clip: PhysicalLength::new((geom2.max_y() - clipped2.max_y() + E) as _),
+                left_clip: PhysicalLength::new((clipped2.min_x() - geom2.min_x() + E) as _),
+                right_clip: PhysicalLength::new((geom2.max_x() - clipped2.max_x() + E) as _),
+            },
+        )
+    }
+
+    /// Clip what follows to `other` with rounded corners and a border that are masked out.
+    /// `drawn` is the area in which what follows draws, if known: when it doesn't reach the rounded
+    /// corners or the border, there is nothing to mask. Otherwise, only that area is rendered in a
+    /// layer that is masked by the rounded rectangle when the current state is restored.
+    fn combine_rounded_clip(
+        &mut self,
+        other: LogicalRect,
+        radius: LogicalBorderRadius,
+        border_width: LogicalLength,
+        drawn: Option<LogicalRect>,
+    ) -> bool {
+        // Snap to whole physical pixels, like the rest of the clip, so that the mask stays aligned
+        let drawn =
+            drawn.map(|d| ((d.cast() * self.scale_factor).round_out() / self.scale_factor).cast());
+        let Some(clipped) = self
+            .current_state
+            .clip
+            .intersection(&other)
+            .and_then(|clipped| drawn.map_or(Some(clipped), |d| clipped.intersection(&d)))
+        else {
+            self.current_state.clip = LogicalRect::default();
+            return false;
+        };
+        self.current_state.clip = clipped;
+        if !radius.is_zero() && reaches_rounded_border(other, clipped, radius, border_width) {
+            let (geometry, mask) = self.rounded_rectangle(
+                other,
+                clipped,
+                radius,
+                border_width,
+                Color::from_rgb_u8(0, 0, 0).into(),
+                Color::default().into(),
+            );
+            if !geometry.is_empty() {
+                self.processor.layers.push(Layer {
+                    geometry,
+                    buffer: SharedPixelBuffer::new(
+                        geometry.width() as u32,
+                        geometry.height() as u32,
+                    ),
+                    composition: LayerComposition::RoundedClip(mask),
+                    state_depth: self.state_stack.len(),
+                });
+            }
+        }
+        true
+    }
+
+    /// Returns the color, mixed with the current_state's alpha
+    fn alpha_color(&self, color: Color) -> Color {
+        if self.current_state.alpha < 1.0 {
+            Color::from_argb_u8(
+                (color.alpha() as f32 * self.current_state.alpha) as u8,
+                color.red(),
+                color.green(),
+                color.blue(),
+            )
+        } else {
+            color
+        }
+    }
+
+    /// Render what follows in a layer, in the coordinates mapped by `transform` to the current ones.
+    /// The layer is transformed and composed when the current state is restored.
+    /// `bounds` is the area of the children that can be rendered, if known.
+    /// Returns false if nothing of the children is visible.
+    fn push_transform_layer(
+        &mut self,
+        transform: euclid::Transform2D<Coord, LogicalPx, LogicalPx>,
+        bounds: Option<LogicalRect>,
+    ) -> bool {
+        // Maps the coordinates of the children to the absolute logical coordinates
+        let transform = transform.then_translate(self.current_state.offset.to_vector());
+        let Some(inverse) = transform.inverse() else {
+            return false;
+        };
+        let clip = self.current_state.clip.translate(self.current_state.offset.to_vector());
+        let visible = inverse.outer_transformed_rect(&clip);
+        let Some(visible) = bounds.map_or(Some(visible), |b| visible.intersection(&b)) else {
+            return false;
+        };
+        let bounds = bounds.unwrap_or(visible);
+
+        // Render the visible part of the children in a layer, in their own coordinates and
+        // without the screen rotation.
+        // One more pixel is rendered on each side for the interpolation at the edge of the visible part.
+        let item_rect = (bounds.cast() * self.scale_factor).round_out();
+        let Some(layer_rect) = (visible.cast() * self.scale_factor)
+            .round_out()
+            .inflate(1., 1.)
+            .intersection(&item_rect)
+            .and_then(|r| r.try_cast::<i16>())
+            .filter(|r| !r.is_empty())
+        else {
+            return false;
+        };
+        let scale_factor = self.scale_factor.get();
+        let layer_transform =
+            euclid::default::Transform2D::scale(1. / scale_factor, 1. / scale_factor)
+                .then(&transform.to_untyped().cast())
+                .then_scale(scale_factor, scale_factor);
+        self.processor.layers.push(Layer {
+            geometry: PhysicalRect::from_size(layer_rect.size),
+            buffer: SharedPixelBuffer::new(layer_rect.width() as u32, layer_rect.height() as u32),
+            composition: LayerComposition::Transform(LayerTransform {
+                transform: layer_transform,
+                origin: layer_rect.origin.to_vector().to_untyped().cast(),
+                clip: (clip.cast() * self.scale_factor).round().cast(),
+                rotation: self.rotation,
+            }),
+            state_depth: self.state_stack.len(),
+        });
+        self.rotation = RotationInfo {
+            orientation: RenderingRotation::NoRotation,
+            screen_size: layer_rect.size,
+        };
+        self.current_state.offset =
+            (-layer_rect.origin.cast::<f32>().to_vector() / self.scale_factor).cast().to_point();
+        self.current_state.clip = (layer_rect.cast() / self.scale_factor)
+            .cast()
+            .intersection(&bounds)
+            .unwrap_or_default();
+        true
+    }
+
+    /// Draw the children of a `Layer` from a buffer that is only rendered again when they change
+    #[cfg(feature = "std")]
+    fn draw_cached_layer(&mut self, item_rc: &ItemRc, size: LogicalSize) {
+        let scale_factor = self.scale_factor;
+        let cached = self.layer_cache.get_or_update_cache_entry(item_rc, || {
+            // The whole content of the layer is rendered, not only the visible part, so that it can
+            // be reused when the layer is moved or partially redrawn
+            let unbounded = LogicalRect::new(
+                LogicalPoint::new(Coord::MIN / (2 as Coord), Coord::MIN / (2 as Coord)),
+                LogicalSize::new(Coord::MAX, Coord::MAX),
+            );
+            let bounding_rect = LogicalRect::from_size(size).union(
+                &crate::item_rendering::item_children_bounding_rect(
+                    item_rc.item_tree(),
+                    item_rc.index() as isize,
+                    &unbounded,
+                ),
+            );
+            let rect = (bounding_rect.cast() * scale_factor)
+                .round_out()
+                .try_cast::<i16>()
+                .filter(|r| !r.is_empty())?;
+            let mut buffer = SharedPixelBuffer::<crate::graphics::Rgba8Pixel>::new(
+                rect.width() as u32,
+                rect.height() as u32,
+            );
+            let pixels: &mut [PremultipliedRgbaColor] =
+                bytemuck::cast_slice_mut(buffer.make_mut_bytes());
+            let mut builder = SceneBuilder::new(
+                rect.size,
+                scale_factor,
+                self.window,
+                RenderToBuffer { buffer: pixels, stride: rect.width() as usize },
+                RenderingRotation::NoRotation,
+                self.box_shadow_cache,
+                self.layer_cache,
+            );
+            builder.current_state.offset =
+                (-rect.origin.cast::<f32>().to_vector() / scale_factor).cast().to_point();
+            builder.current_state.clip = (rect.cast() / scale_factor).cast();
+            crate::item_rendering::render_item_children(
+                &mut builder,
+                item_rc.item_tree(),
+                item_rc.index() as isize,
+            );
+            Some(CachedLayer { buffer, origin: rect.origin.to_vector() })
+        });
+        let Some(CachedLayer { buffer, origin }) = cached else { return };
+
+        let size = PhysicalSize::new(buffer.width() as i16, buffer.height() as i16);
+        let item_origin =
+            (self.current_state.offset.cast() * self.scale_factor).round().cast::<i16>();
+        let geometry = PhysicalRect::new(item_origin + origin, size);
+        let physical_clip =
+            (self.current_state.clip.translate(self.current_state.offset.to_vector()).cast()
+                * self.scale_factor)
+                .round()
+                .cast();
+        let Some(target) = geometry.intersection(&physical_clip) else { return };
+        self.processor.process_shared_image_buffer(
+            target.transformed(self.rotation),
+            SharedBufferCommand {
+                buffer: SharedBufferData::SharedImage(SharedImageBuffer::RGBA8Premultiplied(
+                    buffer,
+                )),
+                source_rect: PhysicalRect::from_size(size),
+                extra: SceneTextureExtra {
+                    colorize: Default::default(),
+                    alpha: (self.current_state.alpha * 255.) as u8,
+                    rotation: self.rotation.orientation,
+                    dx: Fixed::from_integer(1),
+                    dy: Fixed::from_integer(1),
+                    off_x: Fixed::from_integer((target.min_x() - geometry.min_x()) as _),
+                    off_y: Fixed::from_integer((target.min_y() - geometry.min_y()) as _),
+                },
+            },
+        );
+    }
+}
+
+struct SelectionInfo {
+    selection_color: Color,
+    selection_background: Color,
+    selection: core::ops::Range<usize>,
 }
 
 #[derive(Clone, Copy)]
@@ -1700,98 +2753,7 @@ fn draw_rectangle(
 
             let background = rect.background();
             if let Brush::LinearGradient(g) = background {
-                let geom2 = (geom.cast() * self.scale_factor).transformed(self.rotation);
-                let clipped2 = (clipped.cast() * self.scale_factor).transformed(self.rotation);
-                let act_rect = (clipped.translate(self.current_state.offset.to_vector()).cast()
-                    * self.scale_factor)
-                    .round()
-                    .cast()
-                    .transformed(self.rotation);
-                let axis_angle = (360. - self.rotation.orientation.angle()) % 360.;
-                let angle = g.angle() - axis_angle;
-                let tan = angle.to_radians().tan().abs();
-                let start = if !tan.is_finite() {
-                    255.
-                } else {
-                    let h = tan * geom2.width() as f32;
-                    255. * h / (h + geom2.height() as f32)
-                } as u8;
-                let mut angle = angle as i32 % 360;
-                if angle < 0 {
-                    angle += 360;
-                }
-                let mut stops = g.stops().copied().peekable();
-                let mut idx = 0;
-                let stop_count = g.stops().count();
-                while let (Some(mut s1), Some(mut s2)) = (stops.next(), stops.peek().copied()) {
-                    let mut flags = 0;
-                    if (angle % 180) > 90 {
-                        flags |= 0b1;
-                    }
-                    if angle <= 90 || angle > 270 {
-                        core::mem::swap(&mut s1, &mut s2);
-                        s1.position = 1. - s1.position;
-                        s2.position = 1. - s2.position;
-                        if idx == 0 {
-                            flags |= 0b100;
-                        }
-                        if idx == stop_count - 2 {
-                            flags |= 0b010;
-                        }
-                    } else {
-                        if idx == 0 {
-                            flags |= 0b010;
-                        }
-                        if idx == stop_count - 2 {
-                            flags |= 0b100;
-                        }
-                    }
-
-                    idx += 1;
-
-                    let (adjust_left, adjust_right) = if (angle % 180) > 90 {
-                        (
-                            (geom2.width() * s1.position).floor() as i16,
-                            (geom2.width() * (1. - s2.position)).ceil() as i16,
-                        )
-                    } else {
-                        (
-                            (geom2.width() * (1. - s2.position)).ceil() as i16,
-                            (geom2.width() * s1.position).floor() as i16,
-                        )
-                    };
-
-                    let gr = GradientCommand {
-                        color1: self.alpha_color(s1.color).into(),
-                        color2: self.alpha_color(s2.color).into(),
-                        start,
-                        flags,
-                        top_clip: Length::new(
-                            (clipped2.min_y() - geom2.min_y()) as i16
-                                - (geom2.height() * s1.position).floor() as i16,
-                        ),
-                        bottom_clip: Length::new(
-                            (geom2.max_y() - clipped2.max_y()) as i16
-                                - (geom2.height() * (1. - s2.position)).ceil() as i16,
-                        ),
-                        left_clip: Length::new(
-                            (clipped2.min_x() - geom2.min_x()) as i16 - adjust_left,
-                        ),
-                        right_clip: Length::new(
-                            (geom2.max_x() - clipped2.max_x()) as i16 - adjust_right,
-                        ),
-                    };
-
-                    let size_y = act_rect.height_length() + gr.top_clip + gr.bottom_clip;
-                    let size_x = act_rect.width_length() + gr.left_clip + gr.right_clip;
-                    if size_x.get() == 0 || size_y.get() == 0 {
-                        // the position are too close to each other
-                        // FIXME: For the first or the last, we should draw a plain color to the end
-                        continue;
-                    }
-
-                    self.processor.process_gradient(act_rect, gr);
-                }
+                self.draw_linear_gradient(geom, clipped, &g);
                 return;
             }
 
@@ -1855,44 +2817,10 @@ fn draw_border_rectangle(
             }
 
             if !radius.is_zero() {
-                let radius = radius
-                    .min(LogicalBorderRadius::from_length(geom.width_length() / 2 as Coord))
-                    .min(LogicalBorderRadius::from_length(geom.height_length() / 2 as Coord));
                 if let Some(clipped) = geom.intersection(&self.current_state.clip) {
-                    let geom2 = (geom.cast() * self.scale_factor).transformed(self.rotation);
-                    let clipped2 = (clipped.cast() * self.scale_factor).transformed(self.rotation);
-                    let geometry =
-                        (clipped.translate(self.current_state.offset.to_vector()).cast()
-                            * self.scale_factor)
-                            .round()
-                            .cast()
-                            .transformed(self.rotation);
-                    let radius =
-                        (radius.cast() * self.scale_factor).cast().transformed(self.rotation);
-                    // Add a small value to make sure that the clip is always positive despite floating point shenanigans
-                    const E: f32 = 0.00001;
-
-                    self.processor.process_rounded_rectangle(
-                        geometry,
-                        RoundedRectangle {
-                            radius,
-                            width: (border.cast() * self.scale_factor).cast(),
-                            border_color,
-                            inner_color: color,
-                            top_clip: PhysicalLength::new(
-                                (clipped2.min_y() - geom2.min_y() + E) as _,
-                            ),
-                            bottom_clip: PhysicalLength::new(
-                                (geom2.max_y() - clipped2.max_y() + E) as _,
-                            ),
-                            left_clip: PhysicalLength::new(
-                                (clipped2.min_x() - geom2.min_x() + E) as _,
-                            ),
-                            right_clip: PhysicalLength::new(
-                                (geom2.max_x() - clipped2.max_x() + E) as _,
-                            ),
-                        },
-                    );
+                    let (geometry, rounded_rectangle) =
+                        self.rounded_rectangle(geom, clipped, radius, border, color, border_color);
+                    self.processor.process_rounded_rectangle(geometry, rounded_rectangle);
                 }
                 return;
             }
@@ -2022,6 +2950,7 @@ fn draw_text(&mut self, text: Pin<&crate::items::Text>, _: &ItemRc, size: Logica
                     vertical_alignment: text.vertical_alignment(),
                     wrap: text.wrap(),
                     overflow: text.overflow(),
+                    text_direction: text.text_direction(),
                     single_line: false,
                 };
 
@@ -2040,6 +2969,7 @@ fn draw_text(&mut self, text: Pin<&crate::items::Text>, _: &ItemRc, size: Logica
                     vertical_alignment: text.vertical_alignment(),
                     wrap: text.wrap(),
                     overflow: text.overflow(),
+                    text_direction: text.text_direction(),
                     single_line: false,
                 };
 
@@ -2048,6 +2978,87 @@ fn draw_text(&mut self, text: Pin<&crate::items::Text>, _: &ItemRc, size: Logica
         }
     }
 
+    fn draw_styled_text(
+        &mut self,
+        text: Pin<&crate::items::StyledText>,
+        _: &ItemRc,
+        size: LogicalSize,
+    ) {
+        let geom = LogicalRect::from(size);
+        if !self.should_draw(&geom) {
+            return;
+        }
+        let Some(logical_clip) = self.current_state.clip.intersection(&geom) else {
+            return;
+        };
+        let physical_clip: euclid::Rect<f32, PhysicalPx> = logical_clip.cast() * self.scale_factor;
+        let offset = self.current_state.offset.to_vector().cast() * self.scale_factor;
+
+        for run in text.render_runs(size, &self.window.window_adapter()) {
+            let color = self.alpha_color(run.brush.color());
+            if color.alpha() == 0 {
+                continue;
+            }
+            for decoration in run.decorations.iter() {
+                if let Some(clipped) = decoration.intersection(&logical_clip) {
+                    let geometry =
+                        (clipped.translate(self.current_state.offset.to_vector()).cast()
+                            * self.scale_factor)
+                            .round()
+                            .cast()
+                            .transformed(self.rotation);
+                    self.processor.process_rectangle(geometry, color.into());
+                }
+            }
+
+            // Lay out the run as a single line of text at the origin of its rectangle
+            let origin: euclid::Vector2D<f32, PhysicalPx> =
+                run.rect.origin.to_vector().cast() * self.scale_factor;
+            let physical_clip = physical_clip.translate(-origin);
+            let offset = offset + origin;
+            let max_size = (run.rect.size.cast() * self.scale_factor).cast();
+            let font = fonts::match_font(&run.font_request, self.scale_factor);
+
+            match font {
+                fonts::Font::PixelFont(pf) => {
+                    let layout =
+                        fonts::text_layout_for_font(&pf, &run.font_request, self.scale_factor);
+                    let paragraph = TextParagraphLayout {
+                        string: &run.text,
+                        layout,
+                        max_width: max_size.width_length(),
+                        max_height: max_size.height_length(),
+                        horizontal_alignment: TextHorizontalAlignment::Left,
+                        vertical_alignment: TextVerticalAlignment::Top,
+                        wrap: TextWrap::NoWrap,
+                        overflow: TextOverflow::Clip,
+                        text_direction: TextDirection::Auto,
+                        single_line: true,
+                    };
+                    self.draw_text_paragraph(&paragraph, physical_clip, offset, color, None);
+                }
+                #[cfg(all(feature = "software-renderer-systemfonts", not(target_arch = "wasm32")))]
+                fonts::Font::VectorFont(vf) => {
+                    let layout =
+                        fonts::text_layout_for_font(&vf, &run.font_request, self.scale_factor);
+                    let paragraph = TextParagraphLayout {
+                        string: &run.text,
+                        layout,
+                        max_width: max_size.width_length(),
+                        max_height: max_size.height_length(),
+                        horizontal_alignment: TextHorizontalAlignment::Left,
+                        vertical_alignment: TextVerticalAlignment::Top,
+                        wrap: TextWrap::NoWrap,
+                        overflow: TextOverflow::Clip,
+                        text_direction: TextDirection::Auto,
+                        single_line: true,
+                    };
+                    self.draw_text_paragraph(&paragraph, physical_clip, offset, color, None);
+                }
+            }
+        }
+    }
+
     fn draw_text_input(
         &mut self,
         text_input: Pin<&crate::items::TextInput>,
@@ -2097,6 +3108,7 @@ fn draw_text_input(
                     vertical_alignment: text_input.vertical_alignment(),
                     wrap: text_input.wrap(),
                     overflow: TextOverflow::Clip,
+                    text_direction: text_input.text_direction(),
                     single_line: text_input.single_line(),
                 };
 
@@ -2117,6 +3129,7 @@ fn draw_text_input(
                     vertical_alignment: text_input.vertical_alignment(),
                     wrap: text_input.wrap(),
                     overflow: TextOverflow::Clip,
+                    text_direction: text_input.text_direction(),
                     single_line: text_input.single_line(),
                 };
 
@@ -2145,36 +3158,319 @@ fn draw_text_input(
     }
 
     #[cfg(feature = "std")]
-    fn draw_path(&mut self, _path: Pin<&crate::items::Path>, _: &ItemRc, _size: LogicalSize) {
-        // TODO
+    fn draw_path(&mut self, path: Pin<&crate::items::Path>, item_rc: &ItemRc, size: LogicalSize) {
+        use lyon_path::iterator::PathIterator;
+
+        let geom = LogicalRect::from(size);
+        if !self.should_draw(&geom) {
+            return;
+        }
+
+        let fill = path.fill();
+        let stroke_width = (path.stroke_width().cast() * self.scale_factor).get();
+        let stroke = if stroke_width > 0.01 { path.stroke() } else { Brush::default() };
+        if (fill.is_transparent() && stroke.is_transparent()) || self.current_state.alpha <= 0. {
+            return;
+        }
+
+        // Like for the text, clip against the Path's geometry to avoid drawing outside of its boundaries
+        // because that would break partial rendering.
+        let Some(clipped) = geom.intersection(&self.current_state.clip) else { return };
+        let target_rect: PhysicalRect =
+            (clipped.translate(self.current_state.offset.to_vector()).cast() * self.scale_factor)
+                .round()
+                .cast();
+        if target_rect.is_empty() {
+            return;
+        }
+
+        let Some((offset, path_events)) = path.fitted_path_events(item_rc) else { return };
+        let origin = (self.current_state.offset + offset).cast() * self.scale_factor
+            - target_rect.origin.cast().to_vector();
+        let to_mask = |p: lyon_path::math::Point| {
+            (LogicalPoint::from_untyped(p.cast()).cast() * self.scale_factor + origin.to_vector())
+                .to_untyped()
+        };
+
+        // The polylines of the path, and whether they are closed
+        let mut contours: Vec<(Vec<euclid::default::Point2D<f32>>, bool)> = Vec::new();
+        let tolerance = 0.1 / self.scale_factor.get();
+        for event in path_events.iter().flattened(tolerance) {
+            match event {
+                lyon_path::Event::Begin { at } => contours.push((vec![to_mask(at)], false)),
+                lyon_path::Event::Line { to, .. } => {
+                    if let Some((points, _)) = contours.last_mut() {
+                        points.push(to_mask(to));
+                    }
+                }
+                lyon_path::Event::End { close, .. } => {
+                    if let Some((_, closed)) = contours.last_mut() {
+                        *closed = close;
+                    }
+                }
+                lyon_path::Event::Quadratic { .. } | lyon_path::Event::Cubic { .. } => {
+                    unreachable!("flattened paths only contain lines")
+                }
+            }
+        }
+
+        let mut draw_mask = |rasterizer: path::Rasterizer, fill_rule, brush: &Brush| {
+            if rasterizer.is_empty() {
+                return;
+            }
+            let mask = rasterizer.rasterize(
+                target_rect.width() as usize,
+                target_rect.height() as usize,
+                fill_rule,
+            );
+            if let Brush::LinearGradient(g) = brush {
+                self.draw_masked_linear_gradient(geom, clipped, target_rect, mask, g);
+                return;
+            }
+            let color = self.alpha_color(brush.color());
+            if color.alpha() == 0 {
+                return;
+            }
+            self.processor.process_shared_image_buffer(
+                target_rect.transformed(self.rotation),
+                SharedBufferCommand {
+                    buffer: SharedBufferData::AlphaMap {
+                        data: mask.into(),
+                        width: target_rect.width() as u16,
+                    },
+                    source_rect: PhysicalRect::from_size(target_rect.size),
+                    extra: SceneTextureExtra {
+                        colorize: color,
+                        // color already is mixed with global alpha
+                        alpha: color.alpha(),
+                        rotation: self.rotation.orientation,
+                        dx: Fixed::from_integer(1),
+                        dy: Fixed::from_integer(1),
+                        off_x: Fixed::from_integer(0),
+                        off_y: Fixed::from_integer(0),
+                    },
+                },
+            );
+        };
+
+        if !fill.is_transparent() {
+            let mut rasterizer = path::Rasterizer::default();
+            for (points, _) in &contours {
+                // Fills are always implicitly closed
+                rasterizer.add_polygon(points);
+            }
+            draw_mask(rasterizer, path.fill_rule(), &fill);
+        }
+
+        if !stroke.is_transparent() {
+            let mut rasterizer = path::Rasterizer::default();
+            for (points, closed) in &contours {
+                rasterizer.add_stroke(points, *closed, stroke_width);
+            }
+            draw_mask(rasterizer, crate::items::FillRule::Nonzero, &stroke);
+        }
     }
 
     fn draw_box_shadow(
         &mut self,
-        _box_shadow: Pin<&crate::items::BoxShadow>,
+        box_shadow: Pin<&crate::items::BoxShadow>,
         _: &ItemRc,
-        _size: LogicalSize,
+        size: LogicalSize,
     ) {
-        // TODO
+        let color = self.alpha_color(box_shadow.color());
+        let shadow_rect = box_shadow.shadow_rect(size);
+        if color.alpha() == 0 || !self.should_draw(&shadow_rect) {
+            return;
+        }
+
+        let to_physical = |l: LogicalLength| (l.cast() * self.scale_factor).get().round().max(0.);
+        let blur = to_physical(box_shadow.blur());
+        let key = box_shadow::BoxShadowKey {
+            width: to_physical(size.width_length()) as u16,
+            height: to_physical(size.height_length()) as u16,
+            blur: blur as u16,
+            radius: to_physical(box_shadow.border_radius()) as u16,
+        };
+        if key.width == 0 || key.height == 0 {
+            return;
+        }
+
+        let offset = self.current_state.offset.to_vector();
+        let origin = ((LogicalPoint::from_lengths(box_shadow.offset_x(), box_shadow.offset_y())
+            + offset)
+            .cast()
+            * self.scale_factor)
+            .round()
+            - euclid::vec2(blur, blur);
+        let shadow_geometry: PhysicalRect = euclid::Rect::new(
+            origin.cast(),
+            euclid::size2(key.map_width() as i16, key.map_height() as i16),
+        );
+        let physical_clip: PhysicalRect =
+            (self.current_state.clip.translate(offset).cast() * self.scale_factor).round().cast();
+        if !shadow_geometry.intersects(&physical_clip) {
+            return;
+        }
+
+        // The cached alpha map is the compact one, whose middle row and column are repeated
+        let compact = key.compact();
+        let data = self.box_shadow_cache.get(compact);
+        for x_span in key.spans(key.width, compact.width) {
+            for y_span in key.spans(key.height, compact.height) {
+                let span_geometry = PhysicalRect::new(
+                    shadow_geometry.origin + euclid::vec2(x_span.start as i16, y_span.start as i16),
+                    euclid::size2(x_span.len as i16, y_span.len as i16),
+                );
+                let Some(clipped) = span_geometry.intersection(&physical_clip) else { continue };
+                let source = |span: box_shadow::Span, clipped_start: i16, clipped_len: i16| {
+                    if span.repeat {
+                        (span.source as i16, 1)
+                    } else {
+                        (span.source as i16 + clipped_start, clipped_len)
+                    }
+                };
+                let (x, width) =
+                    source(x_span, clipped.min_x() - span_geometry.min_x(), clipped.width());
+                let (y, height) =
+                    source(y_span, clipped.min_y() - span_geometry.min_y(), clipped.height());
+                self.processor.process_shared_image_buffer(
+                    clipped.transformed(self.rotation),
+                    SharedBufferCommand {
+                        buffer: SharedBufferData::AlphaMap {
+                            data: data.clone(),
+                            width: compact.map_width() as u16,
+                        },
+                        source_rect: euclid::rect(x, y, width, height),
+                        extra: SceneTextureExtra {
+                            colorize: color,
+                            // color already is mixed with global alpha
+                            alpha: color.alpha(),
+                            rotation: self.rotation.orientation,
+                            dx: Fixed::from_integer(1),
+                            dy: Fixed::from_integer(1),
+                            off_x: Fixed::from_integer(0),
+                            off_y: Fixed::from_integer(0),
+                        },
+                    },
+                );
+            }
+        }
+    }
+
+    fn visit_opacity(
+        &mut self,
+        opacity_item: Pin<&crate::items::Opacity>,
+        item_rc: &ItemRc,
+        _size: LogicalSize,
+    ) -> RenderingResult {
+        let opacity = opacity_item.opacity();
+        if !crate::items::Opacity::need_layer(item_rc, opacity) {
+            self.apply_opacity(opacity);
+            return RenderingResult::ContinueRenderingChildren;
+        }
+        let alpha = self.current_state.alpha * opacity;
+        if alpha <= 0.01 {
+            return RenderingResult::ContinueRenderingWithoutChildren;
+        }
+        // Render the visible part of the children in a layer, which is blended as a whole
+        // when this state is restored. The layer only covers the area in which the children draw.
+        self.current_state.clip = crate::properties::evaluate_no_tracking(|| {
+            crate::item_rendering::item_children_drawing_rect(
+                item_rc.item_tree(),
+                item_rc.index() as isize,
+                &self.current_state.clip,
+            )
+        });
+        let Some(geometry) =
+            (self.current_state.clip.translate(self.current_state.offset.to_vector()).cast()
+                * self.scale_factor)
+                .round_out()
+                .try_cast::<i16>()
+                .filter(|r| !r.is_empty())
+        else {
+            return RenderingResult::ContinueRenderingWithoutChildren;
+        };
+        let geometry = geometry.transformed(self.rotation);
+        self.processor.layers.push(Layer {
+            geometry,
+            buffer: SharedPixelBuffer::new(geometry.width() as u32, geometry.height() as u32),
+            composition: LayerComposition::Opacity((alpha * 255.) as u8),
+            state_depth: self.state_stack.len(),
+        });
+        self.current_state.alpha = 1.;
+        RenderingResult::ContinueRenderingChildren
+    }
+
+    fn visit_layer(
+        &mut self,
+        layer_item: Pin<&crate::items::Layer>,
+        item_rc: &ItemRc,
+        size: LogicalSize,
+    ) -> RenderingResult {
+        #[cfg(feature = "std")]
+        {
+            if layer_item.cache_rendering_hint() {
+                self.draw_cached_layer(item_rc, size);
+                return RenderingResult::ContinueRenderingWithoutChildren;
+            }
+            self.layer_cache.release(item_rc);
+        }
+        #[cfg(not(feature = "std"))]
+        let _ = (layer_item, item_rc, size);
+        RenderingResult::ContinueRenderingChildren
+    }
+
+    fn visit_rotate(
+        &mut self,
+        rotate_item: Pin<&crate::items::Rotate>,
+        _: &ItemRc,
+        size: LogicalSize,
+    ) -> RenderingResult {
+        if rotate_item.is_identity() {
+            return RenderingResult::ContinueRenderingChildren;
+        }
+        if self.push_transform_layer(rotate_item.transform(), Some(LogicalRect::from_size(size))) {
+            RenderingResult::ContinueRenderingChildren
+        } else {
+            RenderingResult::ContinueRenderingWithoutChildren
+        }
+    }
+
+    fn visit_clip(
+        &mut self,
+        clip_item: Pin<&crate::items::Clip>,
+        item_rc: &ItemRc,
+        size: LogicalSize,
+    ) -> RenderingResult {
+        if !clip_item.clip() {
+            return RenderingResult::ContinueRenderingChildren;
+        }
+        let geometry = LogicalRect::from_size(size);
+        // Knowing where the children draw avoids masking them when they don't reach the rounded corners
+        let radius = clip_item.logical_border_radius();
+        let drawn = (!radius.is_zero()).then(|| {
+            crate::properties::evaluate_no_tracking(|| {
+                crate::item_rendering::item_children_drawing_rect(
+                    item_rc.item_tree(),
+                    item_rc.index() as isize,
+                    &geometry,
+                )
+            })
+        });
+        if self.combine_rounded_clip(geometry, radius, clip_item.border_width(), drawn) {
+            RenderingResult::ContinueRenderingChildren
+        } else {
+            RenderingResult::ContinueRenderingWithoutChildren
+        }
     }
 
     fn combine_clip(
         &mut self,
         other: LogicalRect,
-        _radius: LogicalBorderRadius,
-        _border_width: LogicalLength,
+        radius: LogicalBorderRadius,
+        border_width: LogicalLength,
     ) -> bool {
-        match self.current_state.clip.intersection(&other) {
-            Some(r) => {
-                self.current_state.clip = r;
-                true
-            }
-            None => {
-                self.current_state.clip = LogicalRect::default();
-                false
-            }
-        }
-        // TODO: handle radius and border
+        self.combine_rounded_clip(other, radius, border_width, None)
     }
 
     fn get_current_clip(&self) -> LogicalRect {
@@ -2190,6 +3486,19 @@ fn rotate(&mut self, _angle_in_degrees: f32) {
         todo!()
     }
 
+    #[allow(clippy::unnecessary_cast)] // Coord
+    fn scale(&mut self, x_factor: f32, y_factor: f32) {
+        // Like for `visit_rotate`, the children are rendered in a layer that is scaled when the
+        // state is restored. Nothing is visible if the children are scaled down to nothing.
+        let transform = euclid::Transform2D::scale(x_factor as Coord, y_factor as Coord);
+        if !x_factor.is_finite()
+            || !y_factor.is_finite()
+            || !self.push_transform_layer(transform, None)
+        {
+            self.current_state.clip = LogicalRect::default();
+        }
+    }
+
     fn apply_opacity(&mut self, opacity: f32) {
         self.current_state.alpha *= opacity;
     }
@@ -2199,6 +3508,21 @@ fn save_state(&mut self) {
     }
 
     fn restore_state(&mut self) {
+        // There can be several layers for this state, for example when it is both scaled and clipped
+        while self
+            .processor
+            .layers
+            .last()
+            .map_or(false, |layer| layer.state_depth == self.state_stack.len())
+        {
+            let layer = self.processor.layers.pop().unwrap();
+            if let LayerComposition::Transform(transform) = &layer.composition {
+                self.rotation = transform.rotation;
+            }
+            if let Some((geometry, command)) = layer.into_command() {
+                self.processor.process_shared_image_buffer(geometry, command);
+            }
+        }
         self.current_state = self.state_stack.pop().unwrap();
     }
 
@@ -2265,6 +3589,7 @@ fn draw_string(&mut self, string: &str, color: Color) {
                     vertical_alignment: Default::default(),
                     wrap: Default::default(),
                     overflow: Default::default(),
+                    text_direction: Default::default(),
                     single_line: false,
                 };
 
@@ -2283,6 +3608,7 @@ fn draw_string(&mut self, string: &str, color: Color) {
                     vertical_alignment: Default::default(),
                     wrap: Default::default(),
                     overflow: Default::default(),
+                    text_direction: Default::default(),
                     single_line: false,
                 };
 
@@ -2379,3 +3705,21 @@ fn deref(&self) -> &Self::Target {
         &self.window
     }
 }
+
+#[test]
+fn rounded_clip_mask_area() {
+    let geom = LogicalRect::new(LogicalPoint::new(10., 10.), LogicalSize::new(100., 50.));
+    let radius = LogicalBorderRadius::new(20., 0., 5., 0.);
+    let rect = |x, y, w, h| LogicalRect::new(LogicalPoint::new(x, y), LogicalSize::new(w, h));
+    let no_border = LogicalLength::zero();
+    // Away from the rounded corners, nothing needs to be masked
+    assert!(!reaches_rounded_border(geom, rect(30., 10., 70., 45.), radius, no_border));
+    // The corners without radius don't need to be masked either
+    assert!(!reaches_rounded_border(geom, rect(100., 10., 10., 10.), radius, no_border));
+    assert!(reaches_rounded_border(geom, rect(25., 25., 10., 10.), radius, no_border));
+    assert!(reaches_rounded_border(geom, rect(30., 30., 80., 30.), radius, no_border));
+    // With a border, everything that is over the border is masked
+    let border = LogicalLength::new(2.);
+    assert!(!reaches_rounded_border(geom, rect(30., 12., 70., 40.), radius, border));
+    assert!(reaches_rounded_border(geom, rect(30., 11., 70., 40.), radius, border));
+}
@@ -0,0 +1,245 @@
+// Copyright © SixtyFPS GmbH <info@slint.dev>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-Royalty-free-1.1 OR LicenseRef-Slint-commercial
+
+//! This module contains the computation of the alpha map of a [`BoxShadow`](crate::items::BoxShadow),
+//! and the cache that keeps these alpha maps from one frame to the next.
+
+use alloc::collections::BTreeMap;
+use alloc::rc::Rc;
+use alloc::vec::Vec;
+use core::cell::{Cell, RefCell};
+#[allow(unused_imports)]
+use num_traits::Float;
+
+/// The parameters of a shadow, in physical pixels
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub(super) struct BoxShadowKey {
+    /// Size of the shadowed rectangle, without the blur
+    pub width: u16,
+    pub height: u16,
+    pub blur: u16,
+    pub radius: u16,
+}
+
+impl BoxShadowKey {
+    /// The width of the alpha map, that is the size of the rectangle extended by the blur on each side
+    pub fn map_width(&self) -> usize {
+        self.width as usize + 2 * self.blur as usize
+    }
+
+    /// The height of the alpha map, that is the size of the rectangle extended by the blur on each side
+    pub fn map_height(&self) -> usize {
+        self.height as usize + 2 * self.blur as usize
+    }
+
+    /// Returns the key of the smallest alpha map this shadow can be drawn from.
+    ///
+    /// Far enough from the corners, all the columns (and rows) of the alpha map are the same, so
+    /// the rectangle is shrunk until only one of them remains, which is repeated by [`Self::spans`].
+    pub fn compact(&self) -> Self {
+        let radius = self.radius.min((self.width + 1) / 2).min((self.height + 1) / 2);
+        let extent = 2 * (radius as usize + blur_extent(self.blur)) + 1;
+        let shrink = |size: u16| if (size as usize) > extent { extent as u16 } else { size };
+        Self { width: shrink(self.width), height: shrink(self.height), ..*self }
+    }
+
+    /// How the alpha map of the `compact` key is drawn along one axis, for a shadow of size `size`
+    /// (the width or the height of this key, `compact_size` being the same size in the compact key).
+    pub fn spans(&self, size: u16, compact_size: u16) -> impl Iterator<Item = Span> {
+        let blur = self.blur as usize;
+        let (size, compact_size) = (size as usize + 2 * blur, compact_size as usize + 2 * blur);
+        let side = compact_size / 2;
+        let spans = if size == compact_size {
+            [Some(Span { start: 0, len: size, source: 0, repeat: false }), None, None]
+        } else {
+            [
+                Some(Span { start: 0, len: side, source: 0, repeat: false }),
+                Some(Span { start: side, len: size - 2 * side, source: side, repeat: true }),
+                Some(Span { start: size - side, len: side, source: side + 1, repeat: false }),
+            ]
+        };
+        spans.into_iter().flatten()
+    }
+}
+
+/// A part of the shadow along one axis: `len` pixels from `start`, drawn from the alpha map
+/// starting at `source`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(super) struct Span {
+    pub start: usize,
+    pub len: usize,
+    pub source: usize,
+    /// The single pixel at `source` is repeated over the whole span
+    pub repeat: bool,
+}
+
+/// The maximum number of bytes of alpha maps kept in the cache
+const CACHE_SIZE_LIMIT: usize = 64 * 1024;
+
+/// Cache of the shadows alpha maps.
+///
+/// The entries are kept from one frame to the next, and the least recently used ones are removed
+/// by [`Self::collect_garbage`] when the cache exceeds [`CACHE_SIZE_LIMIT`]
+#[derive(Default)]
+pub(super) struct BoxShadowCache {
+    entries: RefCell<BTreeMap<BoxShadowKey, (Rc<[u8]>, Cell<u64>)>>,
+    /// Incremented after each frame, to know when an entry was last used
+    frame: Cell<u64>,
+}
+
+impl BoxShadowCache {
+    /// Returns the alpha map for the given (compact) shadow, computing it if it is not in the cache
+    pub fn get(&self, key: BoxShadowKey) -> Rc<[u8]> {
+        let mut cache = self.entries.borrow_mut();
+        let (data, last_used) =
+            cache.entry(key).or_insert_with(|| (render_shadow(key).into(), Cell::default()));
+        last_used.set(self.frame.get());
+        data.clone()
+    }
+
+    /// To be called at the end of a frame: remove the least recently used entries until the cache
+    /// is within its size limit
+    pub fn collect_garbage(&self) {
+        let mut cache = self.entries.borrow_mut();
+        let mut size: usize = cache.values().map(|(data, _)| data.len()).sum();
+        while size > CACHE_SIZE_LIMIT {
+            let Some(oldest) =
+                cache.iter().min_by_key(|(_, (_, last_used))| last_used.get()).map(|(k, _)| *k)
+            else {
+                break;
+            };
+            size -= cache.remove(&oldest).map_or(0, |(data, _)| data.len());
+        }
+        self.frame.set(self.frame.get() + 1);
+    }
+}
+
+/// The distance over which the blur spreads the alpha map, which is the sum of the three box blurs
+fn blur_extent(blur: u16) -> usize {
+    if blur == 0 {
+        0
+    } else {
+        box_sizes_for_gauss(blur as f32 / 2.).iter().sum()
+    }
+}
+
+/// Compute the alpha map of a shadow: a rounded rectangle blurred by a gaussian blur
+/// of standard deviation `blur / 2`
+fn render_shadow(key: BoxShadowKey) -> Vec<u8> {
+    let (width, height) = (key.map_width(), key.map_height());
+    let blur = key.blur as f32;
+    let (half_width, half_height) = (key.width as f32 / 2., key.height as f32 / 2.);
+    let radius = (key.radius as f32).min(half_width).min(half_height);
+
+    let mut result = Vec::with_capacity(width * height);
+    for y in 0..height {
+        for x in 0..width {
+            // Signed distance from the center of the pixel to the rounded rectangle
+            let qx = (x as f32 + 0.5 - blur - half_width).abs() - half_width + radius;
+            let qy = (y as f32 + 0.5 - blur - half_height).abs() - half_height + radius;
+            let distance = qx.max(0.).hypot(qy.max(0.)) + qx.max(qy).min(0.) - radius;
+            result.push(((0.5 - distance).clamp(0., 1.) * 255. + 0.5) as u8);
+        }
+    }
+
+    if key.blur > 0 {
+        // Three successive box blurs give a good approximation of a gaussian blur
+        let boxes = box_sizes_for_gauss(blur / 2.);
+        let mut line = Vec::new();
+        for radius in boxes {
+            for y in 0..height {
+                box_blur(&mut result[y * width..][..width], 1, radius, &mut line);
+            }
+            for x in 0..width {
+                box_blur(&mut result[x..], width, radius, &mut line);
+            }
+        }
+    }
+    result
+}
+
+/// Returns the radius of three box blurs which combined approximate a gaussian blur of standard deviation `sigma`
+fn box_sizes_for_gauss(sigma: f32) -> [usize; 3] {
+    const N: f32 = 3.;
+    let w_ideal = (12. * sigma * sigma / N + 1.).sqrt();
+    let mut wl = w_ideal.floor() as i32;
+    if wl % 2 == 0 {
+        wl -= 1;
+    }
+    let wl = wl.max(1);
+    let wu = wl + 2;
+    let wlf = wl as f32;
+    let m_ideal = (12. * sigma * sigma - N * wlf * wlf - 4. * N * wlf - 3. * N) / (-4. * wlf - 4.);
+    let m = m_ideal.round() as i32;
+    core::array::from_fn(|i| (if (i as i32) < m { wl - 1 } else { wu - 1 }) as usize / 2)
+}
+
+/// Blur in place the values `data[0]`, `data[stride]`, `data[2*stride]`, ... by averaging each of them with
+/// the `radius` values on each side. Values outside of the data are considered to be zero.
+fn box_blur(data: &mut [u8], stride: usize, radius: usize, line: &mut Vec<u8>) {
+    if radius == 0 {
+        return;
+    }
+    line.clear();
+    line.extend(data.iter().step_by(stride).copied());
+    let len = line.len();
+    let window = 2 * radius as u32 + 1;
+    let mut sum: u32 = line[..radius.min(len)].iter().map(|v| *v as u32).sum();
+    for (i, out) in data.iter_mut().step_by(stride).enumerate() {
+        if i + radius < len {
+            sum += line[i + radius] as u32;
+        }
+        *out = ((sum + window / 2) / window) as u8;
+        if i >= radius {
+            sum -= line[i - radius] as u32;
+        }
+    }
+}
+
+#[test]
+fn shadow_alpha_map() {
+    // Without blur, the shadow is just the rectangle
+    let key = BoxShadowKey { width: 3, height: 2, blur: 0, radius: 0 };
+    assert_eq!(render_shadow(key), [255; 6]);
+
+    let key = BoxShadowKey { width: 10, height: 10, blur: 4, radius: 2 };
+    let map = render_shadow(key);
+    assert_eq!(map.len(), 18 * 18);
+    // The center remains mostly opaque, the edges are half transparent, and the outside fades away
+    assert!(map[9 * 18 + 9] > 240, "{}", map[9 * 18 + 9]);
+    assert!((128..180).contains(&map[9 * 18 + 4]), "{}", map[9 * 18 + 4]);
+    assert!(map[9 * 18] < 10, "{}", map[9 * 18]);
+    // Symmetrical
+    assert_eq!(map[9 * 18 + 1], map[9 * 18 + 16]);
+    assert_eq!(map[18 + 9], map[16 * 18 + 9]);
+}
+
+#[test]
+fn compact_shadow_alpha_map() {
+    for key in [
+        BoxShadowKey { width: 60, height: 30, blur: 6, radius: 4 },
+        BoxShadowKey { width: 45, height: 90, blur: 0, radius: 10 },
+        BoxShadowKey { width: 50, height: 7, blur: 3, radius: 20 },
+    ] {
+        let compact = key.compact();
+        assert!(compact.map_width() * compact.map_height() < key.map_width() * key.map_height());
+        let (full, compact_map) = (render_shadow(key), render_shadow(compact));
+        // Drawing the compact map with the spans gives back the full alpha map
+        let mut result = alloc::vec![0; full.len()];
+        for x in key.spans(key.width, compact.width) {
+            for y in key.spans(key.height, compact.height) {
+                for j in 0..y.len {
+                    for i in 0..x.len {
+                        let (sx, sy) = (
+                            x.source + if x.repeat { 0 } else { i },
+                            y.source + if y.repeat { 0 } else { j },
+                        );
+                        result[(y.start + j) * key.map_width() + x.start + i] =
+                            compact_map[sy * compact.map_width() + sx];
+                    }
+                }
+            }
+        }
+        assert_eq!(result, full, "{key:?}");
+    }
+}
@@ -0,0 +1,200 @@
+// Copyright © SixtyFPS GmbH <info@slint.dev>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-Royalty-free-1.1 OR LicenseRef-Slint-commercial
+
+//! Conversion of lines of [`Gray8Pixel`] to grayscale formats with less than 8 bits per pixel,
+//! as used by e-paper and monochrome displays.
+
+use super::Gray8Pixel;
+use alloc::vec::Vec;
+
+/// A grayscale format with less than 8 bits per pixel, where several pixels are packed in a byte.
+///
+/// The leftmost pixel is stored in the most significant bits of the byte, and the highest
+/// value is white.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum PackedGrayscaleFormat {
+    /// 4 bits per pixel, two pixels per byte.
+    Gray4,
+    /// 1 bit per pixel, eight pixels per byte.
+    Gray1,
+}
+
+impl PackedGrayscaleFormat {
+    fn bits_per_pixel(self) -> usize {
+        match self {
+            PackedGrayscaleFormat::Gray4 => 4,
+            PackedGrayscaleFormat::Gray1 => 1,
+        }
+    }
+
+    /// The highest level of a pixel (white)
+    fn max_level(self) -> i16 {
+        (1 << self.bits_per_pixel()) - 1
+    }
+}
+
+/// The dithering used to reduce a [`Gray8Pixel`] to a [`PackedGrayscaleFormat`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Dithering {
+    /// Each pixel is rounded to the closest level.
+    #[default]
+    None,
+    /// Ordered dithering with a 4x4 Bayer matrix. The result of a pixel only depends on its
+    /// value and position, so it is stable with partial rendering.
+    Ordered,
+    /// Floyd–Steinberg error diffusion. The error is diffused to the pixels on the right and
+    /// on the next line, so the lines need to be converted from top to bottom.
+    FloydSteinberg,
+}
+
+/// Converts lines rendered with [`Gray8Pixel`] to a [`PackedGrayscaleFormat`].
+///
+/// This is meant to be used from a [`LineBufferProvider`](super::LineBufferProvider): render
+/// the line in a buffer of [`Gray8Pixel`] and then call [`Self::pack_line()`] to write it to
+/// the frame buffer of the display.
+///
+/// ```rust
+/// # use i_slint_core::software_renderer::*;
+/// struct EpaperLineBuffer<'a> {
+///     frame_buffer: &'a mut [u8],
+///     line_buffer: &'a mut [Gray8Pixel],
+///     packer: &'a mut GrayscalePacker,
+/// }
+/// impl LineBufferProvider for EpaperLineBuffer<'_> {
+///     type TargetPixel = Gray8Pixel;
+///     fn process_line(
+///         &mut self,
+///         line: usize,
+///         range: core::ops::Range<usize>,
+///         render_fn: impl FnOnce(&mut [Self::TargetPixel]),
+///     ) {
+///         // A line of 1bit pixels of a display that is 200 pixels wide
+///         const STRIDE: usize = 200 / 8;
+///         let line_buffer = &mut self.line_buffer[range.clone()];
+///         render_fn(line_buffer);
+///         let dest = &mut self.frame_buffer[line * STRIDE..(line + 1) * STRIDE];
+///         self.packer.pack_line(line, range.start, line_buffer, dest);
+///     }
+/// }
+/// let packer = GrayscalePacker::new(PackedGrayscaleFormat::Gray1, Dithering::FloydSteinberg);
+/// ```
+///
+/// With [`Dithering::FloydSteinberg`], a packer keeps the error of the previous line. Lines
+/// that are not rendered right after the previous one start without error, so the dithering
+/// of a partially rendered frame may differ slightly from the one of a full frame.
+#[derive(Clone, Debug)]
+pub struct GrayscalePacker {
+    format: PackedGrayscaleFormat,
+    dithering: Dithering,
+    /// The error diffused to the current and to the next line, indexed by x + 1
+    errors: [Vec<i16>; 2],
+    last_line: Option<usize>,
+}
+
+impl GrayscalePacker {
+    /// The 4x4 Bayer matrix used for ordered dithering
+    const BAYER: [[i16; 4]; 4] = [[0, 8, 2, 10], [12, 4, 14, 6], [3, 11, 1, 9], [15, 7, 13, 5]];
+
+    /// Creates a new packer that converts to the given format with the given dithering.
+    pub fn new(format: PackedGrayscaleFormat, dithering: Dithering) -> Self {
+        Self { format, dithering, errors: Default::default(), last_line: None }
+    }
+
+    /// Converts the `pixels` of the given line, starting at the column `x`, and writes them
+    /// in `dest`.
+    ///
+    /// `dest` is the whole line of the packed frame buffer, so the first pixel is written in
+    /// the bits of `dest` that correspond to the column `x`. The other pixels of `dest` are
+    /// left untouched.
+    pub fn pack_line(&mut self, line: usize, x: usize, pixels: &[Gray8Pixel], dest: &mut [u8]) {
+        if self.dithering == Dithering::FloydSteinberg {
+            self.start_line(line, x + pixels.len() + 2);
+        }
+        let max_level = self.format.max_level();
+        let step = 255 / max_level;
+        let bpp = self.format.bits_per_pixel();
+        let mut right_error = 0;
+        for (i, pix) in pixels.iter().enumerate() {
+            let col = x + i;
+            let value = pix.0 as i16;
+            let level = match self.dithering {
+                Dithering::None => Self::quantize(value, max_level),
+                Dithering::Ordered => {
+                    // Offset the value by a threshold within (-step/2, step/2)
+                    let threshold = Self::BAYER[line % 4][col % 4] * 2 - 15;
+                    Self::quantize(value + threshold * step / 32, max_level)
+                }
+                Dithering::FloydSteinberg => {
+                    let value = value + self.errors[0][col + 1] + right_error;
+                    let level = Self::quantize(value, max_level);
+                    let error = value - level * step;
+                    right_error = error * 7 / 16;
+                    let next = &mut self.errors[1];
+                    next[col] += error * 3 / 16;
+                    next[col + 1] += error * 5 / 16;
+                    next[col + 2] += error / 16;
+                    level
+                }
+            };
+            let shift = 8 - bpp - (col * bpp) % 8;
+            let byte = &mut dest[col * bpp / 8];
+            *byte = (*byte & !((max_level as u8) << shift)) | ((level as u8) << shift);
+        }
+    }
+
+    /// Returns the closest level of the value, clamped within the range of the format
+    fn quantize(value: i16, max_level: i16) -> i16 {
+        ((value.clamp(0, 255) * max_level + 127) / 255).clamp(0, max_level)
+    }
+
+    /// Prepare the error buffers to convert the given line, with at least `len` entries
+    fn start_line(&mut self, line: usize, len: usize) {
+        match self.last_line {
+            Some(last) if last == line => {}
+            Some(last) if last + 1 == line => {
+                self.errors.swap(0, 1);
+                self.errors[1].fill(0);
+            }
+            _ => self.errors.iter_mut().for_each(|e| e.fill(0)),
+        }
+        self.last_line = Some(line);
+        for e in self.errors.iter_mut() {
+            if e.len() < len {
+                e.resize(len, 0);
+            }
+        }
+    }
+}
+
+#[test]
+fn pack_without_dithering() {
+    let pixels = [0, 17, 0x80, 0xff].map(Gray8Pixel);
+
+    let mut dest = [0u8; 2];
+    GrayscalePacker::new(PackedGrayscaleFormat::Gray4, Dithering::None)
+        .pack_line(0, 0, &pixels, &mut dest);
+    assert_eq!(dest, [0x01, 0x8f]);
+
+    let mut dest = [0xffu8; 1];
+    GrayscalePacker::new(PackedGrayscaleFormat::Gray1, Dithering::None)
+        .pack_line(0, 2, &pixels, &mut dest);
+    assert_eq!(dest, [0b1100_1111]);
+}
+
+#[test]
+fn dithering_preserves_average() {
+    for dithering in [Dithering::Ordered, Dithering::FloydSteinberg] {
+        let mut packer = GrayscalePacker::new(PackedGrayscaleFormat::Gray1, dithering);
+        let pixels = [Gray8Pixel(0x40); 32];
+        let mut dest = [0u8; 4];
+        let mut count = 0;
+        for line in 0..32 {
+            packer.pack_line(line, 0, &pixels, &mut dest);
+            count += dest.iter().map(|b| b.count_ones()).sum::<u32>();
+        }
+        // A quarter of the pixels are white
+        assert!((224..=288).contains(&count), "{dithering:?}: {count}");
+    }
+}
@@ -260,6 +260,18 @@ fn fetch_blend_pixel(
                     }
                 }
             }
+            PixelFormat::Gray8 | PixelFormat::Gray4 | PixelFormat::Gray1 => {
+                for pix in line_buffer {
+                    let p = format.gray_at(data, pos(1));
+                    if alpha == 0xff {
+                        *pix = TargetPixel::from_rgb(p, p, p);
+                    } else {
+                        pix.blend(PremultipliedRgbaColor::premultiply(Color::from_argb_u8(
+                            alpha, p, p, p,
+                        )))
+                    }
+                }
+            }
             PixelFormat::AlphaMap => {
                 for pix in line_buffer {
                     let pos = pos(1);
@@ -322,6 +334,8 @@ fn mul(self, rhs: Self) -> Self::Output {
     let border = Shifted::new(rr.width.get());
     const ONE: Shifted = Shifted::ONE;
     const ZERO: Shifted = Shifted(0);
+    // All the x coordinates are relative to the left of the unclipped rectangle
+    let left_clip = rr.left_clip.get() as u32;
     let anti_alias = |x1: Shifted, x2: Shifted, process_pixel: &mut dyn FnMut(usize, u32)| {
         // x1 and x2 are the coordinate on the top and bottom of the intersection of the pixel
         // line and the curve.
@@ -330,12 +344,19 @@ fn mul(self, rhs: Self) -> Self::Output {
         for x in x1.floor()..x2.ceil() {
             // the coverage is basically how much of the pixel should be used
             let cov = ((ONE + Shifted::new(x) - x1).0 << 8) / (ONE + x2 - x1).0;
-            process_pixel(x as usize, cov);
+            if let Some(x) = x.checked_sub(left_clip) {
+                process_pixel(x as usize, cov);
+            }
         }
     };
     let rev = |x: Shifted| {
-        (Shifted::new(span.size.width) + Shifted::new(rr.right_clip.get())).saturating_sub(x)
+        (Shifted::new(rr.left_clip.get())
+            + Shifted::new(span.size.width)
+            + Shifted::new(rr.right_clip.get()))
+        .saturating_sub(x)
     };
+    // Convert a coordinate to an index in the span
+    let to_span = |x: u32| x.saturating_sub(left_clip).min(span.size.width as u32) as usize;
     let calculate_xxxx = |r: i16, y: i16| {
         let r = Shifted::new(r);
         // `y` is how far away from the center of the circle the current line is.
@@ -373,28 +394,23 @@ fn mul(self, rhs: Self) -> Self::Output {
         };
         (x1, x2, x3, x4, rev(x5), rev(x6), rev(x7), rev(x8))
     };
-    anti_alias(
-        x1.saturating_sub(Shifted::new(rr.left_clip.get())),
-        x2.saturating_sub(Shifted::new(rr.left_clip.get())),
-        &mut |x, cov| {
-            if x >= span.size.width as usize {
-                return;
-            }
-            let c = if border == ZERO { rr.inner_color } else { rr.border_color };
-            let col = PremultipliedRgbaColor {
-                alpha: (((c.alpha as u32) * cov as u32) / 255) as u8,
-                red: (((c.red as u32) * cov as u32) / 255) as u8,
-                green: (((c.green as u32) * cov as u32) / 255) as u8,
-                blue: (((c.blue as u32) * cov as u32) / 255) as u8,
-            };
-            line_buffer[pos_x + x].blend(col);
-        },
-    );
+    anti_alias(x1, x2, &mut |x, cov| {
+        if x >= span.size.width as usize {
+            return;
+        }
+        let c = if border == ZERO { rr.inner_color } else { rr.border_color };
+        let col = PremultipliedRgbaColor {
+            alpha: (((c.alpha as u32) * cov as u32) / 255) as u8,
+            red: (((c.red as u32) * cov as u32) / 255) as u8,
+            green: (((c.green as u32) * cov as u32) / 255) as u8,
+            blue: (((c.blue as u32) * cov as u32) / 255) as u8,
+        };
+        line_buffer[pos_x + x].blend(col);
+    });
     if y < rr.width {
         // up or down border (x2 .. x7)
-        let l = x2.ceil().saturating_sub(rr.left_clip.get() as u32).min(span.size.width as u32)
-            as usize;
-        let r = x7.floor().min(span.size.width as u32) as usize;
+        let l = to_span(x2.ceil());
+        let r = to_span(x7.floor());
         if l < r {
             TargetPixel::blend_slice(&mut line_buffer[pos_x + l..pos_x + r], rr.border_color)
         }
@@ -403,39 +419,26 @@ fn mul(self, rhs: Self) -> Self::Output {
             // 3. draw the border (between x2 and x3)
             if ONE + x2 <= x3 {
                 TargetPixel::blend_slice(
-                    &mut line_buffer[pos_x
-                        + x2.ceil()
-                            .saturating_sub(rr.left_clip.get() as u32)
-                            .min(span.size.width as u32) as usize
-                        ..pos_x
-                            + x3.floor()
-                                .saturating_sub(rr.left_clip.get() as u32)
-                                .min(span.size.width as u32)
-                                as usize],
+                    &mut line_buffer[pos_x + to_span(x2.ceil())..pos_x + to_span(x3.floor())],
                     rr.border_color,
                 )
             }
             // 4. anti-aliasing for the contents (x3 .. x4)
-            anti_alias(
-                x3.saturating_sub(Shifted::new(rr.left_clip.get())),
-                x4.saturating_sub(Shifted::new(rr.left_clip.get())),
-                &mut |x, cov| {
-                    if x >= span.size.width as usize {
-                        return;
-                    }
-                    let col = interpolate_color(cov, rr.border_color, rr.inner_color);
-                    line_buffer[pos_x + x].blend(col);
-                },
-            );
+            anti_alias(x3, x4, &mut |x, cov| {
+                if x >= span.size.width as usize {
+                    return;
+                }
+                let col = interpolate_color(cov, rr.border_color, rr.inner_color);
+                line_buffer[pos_x + x].blend(col);
+            });
         }
         if rr.inner_color.alpha > 0 {
             // 5. inside (x4 .. x5)
-            let begin =
-                x4.ceil().saturating_sub(rr.left_clip.get() as u32).min(span.size.width as u32);
-            let end = x5.floor().min(span.size.width as u32);
+            let begin = to_span(x4.ceil());
+            let end = to_span(x5.floor());
             if begin < end {
                 TargetPixel::blend_slice(
-                    &mut line_buffer[pos_x + begin as usize..pos_x + end as usize],
+                    &mut line_buffer[pos_x + begin..pos_x + end],
                     rr.inner_color,
                 )
             }
@@ -452,8 +455,7 @@ fn mul(self, rhs: Self) -> Self::Output {
             // 7. border x6 .. x7
             if ONE + x6 <= x7 {
                 TargetPixel::blend_slice(
-                    &mut line_buffer[pos_x + x6.ceil().min(span.size.width as u32) as usize
-                        ..pos_x + x7.floor().min(span.size.width as u32) as usize],
+                    &mut line_buffer[pos_x + to_span(x6.ceil())..pos_x + to_span(x7.floor())],
                     rr.border_color,
                 )
             }
@@ -772,6 +774,87 @@ fn from(p: Rgb565Pixel) -> Self {
     }
 }
 
+/// An 8bit grayscale pixel, where 0 is black and 255 is white
+#[repr(transparent)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct Gray8Pixel(pub u8);
+
+impl Gray8Pixel {
+    /// Compute the luminance of a color, using the Rec. 601 weights
+    fn luminance(r: u8, g: u8, b: u8) -> u8 {
+        ((r as u32 * 77 + g as u32 * 150 + b as u32 * 29) >> 8) as u8
+    }
+}
+
+impl TargetPixel for Gray8Pixel {
+    fn blend(&mut self, color: PremultipliedRgbaColor) {
+        let a = (u8::MAX - color.alpha) as u16;
+        // The luminance is linear, so it can be computed on the premultiplied components
+        let l = Self::luminance(color.red, color.green, color.blue);
+        self.0 = (self.0 as u16 * a / 255) as u8 + l;
+    }
+
+    fn from_rgb(r: u8, g: u8, b: u8) -> Self {
+        Self(Self::luminance(r, g, b))
+    }
+}
+
+impl From<Rgb8Pixel> for Gray8Pixel {
+    fn from(p: Rgb8Pixel) -> Self {
+        Self::from_rgb(p.r, p.g, p.b)
+    }
+}
+
+impl From<Gray8Pixel> for Rgb8Pixel {
+    fn from(p: Gray8Pixel) -> Self {
+        Rgb8Pixel { r: p.0, g: p.0, b: p.0 }
+    }
+}
+
+/// A 32bit pixel with 8 bits of alpha, red, green and blue, from the most significant to
+/// the least significant byte. The color components are premultiplied by alpha.
+#[repr(transparent)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct Argb8888PremultipliedPixel(pub u32);
+
+impl From<PremultipliedRgbaColor> for Argb8888PremultipliedPixel {
+    fn from(c: PremultipliedRgbaColor) -> Self {
+        Self(
+            ((c.alpha as u32) << 24)
+                | ((c.red as u32) << 16)
+                | ((c.green as u32) << 8)
+                | (c.blue as u32),
+        )
+    }
+}
+
+impl From<Argb8888PremultipliedPixel> for PremultipliedRgbaColor {
+    fn from(p: Argb8888PremultipliedPixel) -> Self {
+        Self {
+            alpha: (p.0 >> 24) as u8,
+            red: (p.0 >> 16) as u8,
+            green: (p.0 >> 8) as u8,
+            blue: p.0 as u8,
+        }
+    }
+}
+
+impl TargetPixel for Argb8888PremultipliedPixel {
+    fn blend(&mut self, color: PremultipliedRgbaColor) {
+        let mut c = PremultipliedRgbaColor::from(*self);
+        c.blend(color);
+        *self = c.into();
+    }
+
+    fn from_rgb(r: u8, g: u8, b: u8) -> Self {
+        PremultipliedRgbaColor::from_rgb(r, g, b).into()
+    }
+
+    fn background() -> Self {
+        Self(0)
+    }
+}
+
 #[test]
 fn rgb565() {
     let pix565 = Rgb565Pixel::from_rgb(0xff, 0x25, 0);
@@ -782,3 +865,24 @@ fn rgb565() {
     let pix888: Rgb8Pixel = pix565.into();
     assert_eq!(pix565, pix888.into());
 }
+
+#[test]
+fn gray8() {
+    assert_eq!(Gray8Pixel::from_rgb(0, 0, 0), Gray8Pixel(0));
+    assert_eq!(Gray8Pixel::from_rgb(0xff, 0xff, 0xff), Gray8Pixel(0xff));
+    assert_eq!(Gray8Pixel::from(Rgb8Pixel { r: 0x80, g: 0x80, b: 0x80 }), Gray8Pixel(0x80));
+
+    let mut pix = Gray8Pixel(0xff);
+    pix.blend(PremultipliedRgbaColor { red: 0, green: 0, blue: 0, alpha: 0x80 });
+    assert_eq!(pix, Gray8Pixel(0x7f));
+}
+
+#[test]
+fn argb8888_premultiplied() {
+    let pix = Argb8888PremultipliedPixel::from_rgb(0x12, 0x34, 0x56);
+    assert_eq!(pix, Argb8888PremultipliedPixel(0xff123456));
+
+    let mut pix = Argb8888PremultipliedPixel::background();
+    pix.blend(PremultipliedRgbaColor { red: 0x40, green: 0x20, blue: 0x10, alpha: 0x80 });
+    assert_eq!(pix, Argb8888PremultipliedPixel(0x80402010));
+}
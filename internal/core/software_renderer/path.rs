@@ -0,0 +1,326 @@
+// Copyright © SixtyFPS GmbH <info@slint.dev>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-Royalty-free-1.1 OR LicenseRef-Slint-commercial
+
+//! This module contains a small scanline rasterizer that turns the outline of a
+//! [`Path`](crate::items::Path) into an alpha map that can then be drawn like a glyph.
+
+use crate::items::FillRule;
+use alloc::vec::Vec;
+
+/// Number of sub-scanlines sampled per pixel row. The horizontal coverage is computed exactly.
+const SUB_SCANLINES: usize = 16;
+
+/// Stroke joins whose miter would be longer than this ratio of the stroke width are beveled.
+/// (Same default as in femtovg and skia)
+const MITER_LIMIT: f32 = 4.;
+
+type Point = euclid::default::Point2D<f32>;
+
+#[derive(Clone, Copy, Debug)]
+struct Edge {
+    /// The top of the edge
+    y0: f32,
+    /// The bottom of the edge
+    y1: f32,
+    /// The x coordinate at `y0`
+    x0: f32,
+    /// The amount x changes for every unit of y
+    dxdy: f32,
+    /// +1 if the edge goes down, -1 if it goes up
+    winding: i8,
+}
+
+/// Collects the edges of polygons and rasterizes them into an alpha map.
+///
+/// All coordinates are in physical pixels, relative to the top left corner of the alpha map.
+#[derive(Default)]
+pub(super) struct Rasterizer {
+    edges: Vec<Edge>,
+}
+
+impl Rasterizer {
+    /// Returns true if nothing was added to this rasterizer
+    pub fn is_empty(&self) -> bool {
+        self.edges.is_empty()
+    }
+
+    fn add_line(&mut self, from: Point, to: Point) {
+        if from.y == to.y || !from.y.is_finite() || !to.y.is_finite() {
+            return;
+        }
+        let (top, bottom, winding) = if from.y < to.y { (from, to, 1) } else { (to, from, -1) };
+        self.edges.push(Edge {
+            y0: top.y,
+            y1: bottom.y,
+            x0: top.x,
+            dxdy: (bottom.x - top.x) / (bottom.y - top.y),
+            winding,
+        });
+    }
+
+    /// Add a closed polygon, in the orientation given by the points.
+    pub fn add_polygon(&mut self, points: &[Point]) {
+        for (i, p) in points.iter().enumerate() {
+            self.add_line(*p, points[(i + 1) % points.len()]);
+        }
+    }
+
+    /// Add a closed polygon, but always in clockwise orientation (in screen coordinates), so that
+    /// several such polygons combined with the `Nonzero` fill rule result in their union.
+    fn add_positive_polygon(&mut self, points: &[Point]) {
+        let double_area: f32 = points
+            .iter()
+            .enumerate()
+            .map(|(i, p)| {
+                let q = points[(i + 1) % points.len()];
+                p.x * q.y - q.x * p.y
+            })
+            .sum();
+        if double_area >= 0. {
+            self.add_polygon(points);
+        } else {
+            for (i, p) in points.iter().enumerate().rev() {
+                self.add_line(*p, points[(i + points.len() - 1) % points.len()]);
+            }
+        }
+    }
+
+    /// Add the outline of a stroke of the given `width` along the polyline described by `points`.
+    ///
+    /// The stroke uses butt caps and miter joins. The result must be rasterized with the `Nonzero` fill rule.
+    pub fn add_stroke(&mut self, points: &[Point], closed: bool, width: f32) {
+        let half_width = width / 2.;
+        // Remove consecutive duplicated points, they do not have a direction
+        let mut polyline: Vec<Point> = Vec::with_capacity(points.len());
+        for p in points {
+            if polyline.last().map_or(true, |l| (*l - *p).square_length() > 1e-6) {
+                polyline.push(*p);
+            }
+        }
+        if closed
+            && polyline.len() > 2
+            && (polyline[0] - polyline[polyline.len() - 1]).square_length() <= 1e-6
+        {
+            polyline.pop();
+        }
+        if polyline.len() < 2 || half_width <= 0. {
+            return;
+        }
+
+        let normal = |a: Point, b: Point| {
+            let d = (b - a).normalize();
+            euclid::default::Vector2D::new(-d.y, d.x) * half_width
+        };
+
+        let segment_count = if closed { polyline.len() } else { polyline.len() - 1 };
+        for i in 0..segment_count {
+            let (a, b) = (polyline[i], polyline[(i + 1) % polyline.len()]);
+            let n = normal(a, b);
+            self.add_positive_polygon(&[a + n, b + n, b - n, a - n]);
+        }
+
+        let join_range = if closed { 0..polyline.len() } else { 1..polyline.len() - 1 };
+        for i in join_range {
+            let prev = polyline[(i + polyline.len() - 1) % polyline.len()];
+            let p = polyline[i];
+            let next = polyline[(i + 1) % polyline.len()];
+            let (n1, n2) = (normal(prev, p), normal(p, next));
+            // Pick the outer side of the turn
+            let cross = (p - prev).cross(next - p);
+            let (n1, n2) = if cross > 0. { (-n1, -n2) } else { (n1, n2) };
+            let bisector = n1 + n2;
+            let cos_half = bisector.length() / (2. * half_width);
+            if cos_half > 1. / MITER_LIMIT && bisector.square_length() > 1e-6 {
+                let miter = bisector.normalize() * (half_width / cos_half);
+                self.add_positive_polygon(&[p, p + n1, p + miter, p + n2]);
+            } else {
+                self.add_positive_polygon(&[p, p + n1, p + n2]);
+            }
+        }
+    }
+
+    /// Compute the coverage of the polygons for each pixel of an alpha map of the given size.
+    pub fn rasterize(mut self, width: usize, height: usize, fill_rule: FillRule) -> Vec<u8> {
+        let mut result = alloc::vec![0u8; width * height];
+        if width == 0 || self.edges.is_empty() {
+            return result;
+        }
+        self.edges.sort_unstable_by(|a, b| a.y0.total_cmp(&b.y0));
+
+        // Coverage of each pixel of the current row, plus one extra element for the end of spans.
+        let mut coverage = alloc::vec![0f32; width + 1];
+        // Full pixels spans are accumulated as differences, that is summed at the end of the row
+        let mut span_delta = alloc::vec![0f32; width + 1];
+        let mut active: Vec<usize> = Vec::new();
+        let mut crossings: Vec<(f32, i8)> = Vec::new();
+        let mut next_edge = 0;
+        let weight = 1. / SUB_SCANLINES as f32;
+
+        let first_row = self.edges[0].y0.floor().max(0.) as usize;
+        for row in first_row..height {
+            if next_edge >= self.edges.len() && active.is_empty() {
+                break;
+            }
+            let mut row_touched = false;
+            for sub in 0..SUB_SCANLINES {
+                let y = row as f32 + (sub as f32 + 0.5) * weight;
+                while next_edge < self.edges.len() && self.edges[next_edge].y0 <= y {
+                    active.push(next_edge);
+                    next_edge += 1;
+                }
+                active.retain(|e| self.edges[*e].y1 > y);
+
+                crossings.clear();
+                crossings.extend(
+                    active
+                        .iter()
+                        .map(|e| &self.edges[*e])
+                        .map(|e| (e.x0 + (y - e.y0) * e.dxdy, e.winding)),
+                );
+                if crossings.is_empty() {
+                    continue;
+                }
+                crossings.sort_unstable_by(|a, b| a.0.total_cmp(&b.0));
+                row_touched = true;
+
+                let mut winding = 0i32;
+                let mut span_start = 0.;
+                for (x, w) in crossings.iter().copied() {
+                    let was_inside = is_inside(winding, fill_rule);
+                    winding += w as i32;
+                    match (was_inside, is_inside(winding, fill_rule)) {
+                        (false, true) => span_start = x,
+                        (true, false) => {
+                            add_span(&mut coverage, &mut span_delta, span_start, x, width, weight)
+                        }
+                        _ => {}
+                    }
+                }
+            }
+
+            if row_touched {
+                let mut acc = 0.;
+                for (x, pixel) in result[row * width..][..width].iter_mut().enumerate() {
+                    acc += span_delta[x];
+                    *pixel = ((acc + coverage[x]).clamp(0., 1.) * 255. + 0.5) as u8;
+                }
+                coverage.fill(0.);
+                span_delta.fill(0.);
+            }
+        }
+        result
+    }
+}
+
+fn is_inside(winding: i32, fill_rule: FillRule) -> bool {
+    match fill_rule {
+        FillRule::Nonzero => winding != 0,
+        FillRule::Evenodd => winding % 2 != 0,
+    }
+}
+
+/// Add `weight` of coverage to the pixels covered by the horizontal span from `x1` to `x2`
+fn add_span(
+    coverage: &mut [f32],
+    span_delta: &mut [f32],
+    x1: f32,
+    x2: f32,
+    width: usize,
+    weight: f32,
+) {
+    let x1 = x1.clamp(0., width as f32);
+    let x2 = x2.clamp(0., width as f32);
+    if x2 <= x1 {
+        return;
+    }
+    let (i1, i2) = (x1 as usize, x2 as usize);
+    if i1 == i2 {
+        coverage[i1] += (x2 - x1) * weight;
+        return;
+    }
+    coverage[i1] += (i1 as f32 + 1. - x1) * weight;
+    span_delta[i1 + 1] += weight;
+    span_delta[i2] -= weight;
+    coverage[i2] += (x2 - i2 as f32) * weight;
+}
+
+#[test]
+fn rasterize_square() {
+    let mut r = Rasterizer::default();
+    r.add_polygon(&[
+        Point::new(1., 1.),
+        Point::new(3., 1.),
+        Point::new(3., 3.),
+        Point::new(1., 3.),
+    ]);
+    let mask = r.rasterize(4, 4, FillRule::Nonzero);
+    #[rustfmt::skip]
+    assert_eq!(mask, [
+        0, 0, 0, 0,
+        0, 255, 255, 0,
+        0, 255, 255, 0,
+        0, 0, 0, 0,
+    ]);
+
+    let mut r = Rasterizer::default();
+    r.add_polygon(&[
+        Point::new(0.5, 0.),
+        Point::new(2., 0.),
+        Point::new(2., 1.),
+        Point::new(0.5, 1.),
+    ]);
+    assert_eq!(r.rasterize(3, 1, FillRule::Nonzero), [128, 255, 0]);
+}
+
+#[test]
+fn rasterize_fill_rule() {
+    // Two nested squares in the same orientation
+    let square = |o: f32, s: f32| {
+        [Point::new(o, o), Point::new(o + s, o), Point::new(o + s, o + s), Point::new(o, o + s)]
+    };
+    let mut r = Rasterizer::default();
+    r.add_polygon(&square(0., 3.));
+    r.add_polygon(&square(1., 1.));
+    assert_eq!(r.rasterize(3, 3, FillRule::Nonzero), [255; 9]);
+
+    let mut r = Rasterizer::default();
+    r.add_polygon(&square(0., 3.));
+    r.add_polygon(&square(1., 1.));
+    #[rustfmt::skip]
+    assert_eq!(r.rasterize(3, 3, FillRule::Evenodd), [
+        255, 255, 255,
+        255, 0, 255,
+        255, 255, 255,
+    ]);
+}
+
+#[test]
+fn rasterize_stroke() {
+    let mut r = Rasterizer::default();
+    r.add_stroke(&[Point::new(0., 2.), Point::new(4., 2.)], false, 2.);
+    #[rustfmt::skip]
+    assert_eq!(r.rasterize(4, 4, FillRule::Nonzero), [
+        0, 0, 0, 0,
+        255, 255, 255, 255,
+        255, 255, 255, 255,
+        0, 0, 0, 0,
+    ]);
+
+    // A closed stroke with overlapping joins must not leave holes nor cancel out
+    let mut r = Rasterizer::default();
+    r.add_stroke(
+        &[Point::new(1., 1.), Point::new(5., 1.), Point::new(5., 5.), Point::new(1., 5.)],
+        true,
+        2.,
+    );
+    let mask = r.rasterize(6, 6, FillRule::Nonzero);
+    #[rustfmt::skip]
+    assert_eq!(mask, [
+        255, 255, 255, 255, 255, 255,
+        255, 255, 255, 255, 255, 255,
+        255, 255, 0, 0, 255, 255,
+        255, 255, 0, 0, 255, 255,
+        255, 255, 255, 255, 255, 255,
+        255, 255, 255, 255, 255, 255,
+    ]);
+}
@@ -409,6 +409,19 @@ fn test_slint_shared_string_from_number() {
         }
     }
 
+    /// Set `out` to the lowercase equivalent of `ss`.
+    #[no_mangle]
+    pub extern "C" fn slint_shared_string_to_lowercase(out: &mut SharedString, ss: &SharedString) {
+        *out = SharedString::from(ss.to_lowercase());
+    }
+
+    #[test]
+    fn test_slint_shared_string_to_lowercase() {
+        let mut s = SharedString::default();
+        slint_shared_string_to_lowercase(&mut s, &SharedString::from("Grüße, WELT"));
+        assert_eq!(s, "grüße, welt");
+    }
+
     /// Append some bytes to an existing shared string
     ///
     /// bytes must be a valid utf8 array of size `len`, without null bytes inside
@@ -15,6 +15,8 @@
 /// This function will add some milliseconds to the fake time
 #[no_mangle]
 pub extern "C" fn slint_mock_elapsed_time(time_in_ms: u64) {
+    // Apply the pending changes first, as the event loop would have done before the time elapsed
+    crate::properties::ChangeTracker::run_change_handlers();
     let tick = crate::animations::CURRENT_ANIMATION_DRIVER.with(|driver| {
         let mut tick = driver.current_tick();
         tick += core::time::Duration::from_millis(time_in_ms);
@@ -26,7 +26,9 @@
 
 use euclid::num::{One, Zero};
 
-use crate::items::{TextHorizontalAlignment, TextOverflow, TextVerticalAlignment, TextWrap};
+use crate::items::{
+    TextDirection, TextHorizontalAlignment, TextOverflow, TextVerticalAlignment, TextWrap,
+};
 
 #[cfg(feature = "unicode-linebreak")]
 mod linebreak_unicode;
@@ -38,6 +40,8 @@
 #[cfg(not(feature = "unicode-linebreak"))]
 use linebreak_simple::{BreakOpportunity, LineBreakIterator};
 
+mod bidi;
+pub use bidi::BidiParagraphs;
 mod fragments;
 mod glyphclusters;
 mod shaping;
@@ -49,6 +53,17 @@
 
 pub use linebreaker::TextLineBreaker;
 
+mod styled;
+pub use styled::{
+    text_decorations, StyledString, StyledTextLayout, StyledTextLayoutRun, StyledTextRun, TextStyle,
+};
+
+/// Returns true if the paragraph of `text` containing `byte_offset` is laid out from right to left, given the
+/// requested base direction.
+pub fn is_rtl_paragraph(text: &str, byte_offset: usize, direction: TextDirection) -> bool {
+    BidiParagraphs::new(text, direction).is_rtl_paragraph(byte_offset)
+}
+
 pub struct TextLayout<'a, Font: AbstractFont> {
     pub font: &'a Font,
     pub letter_spacing: Option<<Font as TextShaper>::Length>,
@@ -85,6 +100,8 @@ pub struct PositionedGlyph<Length> {
     pub advance: Length,
     pub glyph_id: core::num::NonZeroU16,
     pub text_byte_offset: usize,
+    /// True if the glyph is part of a right-to-left run, where the logical start of the glyph is its right edge.
+    pub is_rtl: bool,
 }
 
 pub struct TextParagraphLayout<'a, Font: AbstractFont> {
@@ -96,6 +113,7 @@ pub struct TextParagraphLayout<'a, Font: AbstractFont> {
     pub vertical_alignment: TextVerticalAlignment,
     pub wrap: TextWrap,
     pub overflow: TextOverflow,
+    pub text_direction: TextDirection,
     pub single_line: bool,
 }
 
@@ -125,6 +143,7 @@ pub fn layout_lines<R>(
         let max_width_without_elision = self.max_width - elide_width;
 
         let shape_buffer = ShapeBuffer::new(&self.layout, self.string);
+        let bidi = BidiParagraphs::new(self.string, self.text_direction);
 
         let new_line_break_iter = || {
             TextLineBreaker::<Font>::new(
@@ -184,29 +203,45 @@ pub fn layout_lines<R>(
 
             let mut elide_glyph = elide_glyph.as_ref();
 
+            let visual_order = bidi.visual_glyph_order(
+                line.byte_range.start..line.byte_range.end + line.trailing_whitespace_bytes,
+                glyphs,
+                line.glyph_range.clone(),
+            );
+            // Iterates over the indices of the glyphs of the line in visual order, and whether they are right-to-left.
+            let visual_order = &visual_order;
+            let glyph_order = move || {
+                line.glyph_range
+                    .clone()
+                    .filter(move |_| visual_order.is_none())
+                    .map(|index| (index, false))
+                    .chain(visual_order.iter().flatten().copied())
+            };
+
             let selection = selection
                 .as_ref()
                 .filter(|selection| {
                     line.byte_range.start < selection.end && selection.start < line.byte_range.end
                 })
                 .map(|selection| {
-                    let mut begin = Font::Length::zero();
-                    let mut end = Font::Length::zero();
-                    for glyph in glyphs[line.glyph_range.clone()].iter() {
-                        if glyph.text_byte_offset < selection.start {
-                            begin += glyph.advance;
+                    // With bidirectional text the selection may not be contiguous on screen, use the
+                    // span covering all selected glyphs.
+                    let mut x = Font::Length::zero();
+                    let mut selected_range: Option<core::ops::Range<Font::Length>> = None;
+                    for (index, _) in glyph_order() {
+                        let glyph = &glyphs[index];
+                        if selection.contains(&glyph.text_byte_offset) {
+                            selected_range.get_or_insert(x..x).end = x + glyph.advance;
                         }
-                        if glyph.text_byte_offset >= selection.end {
-                            break;
-                        }
-                        end += glyph.advance;
+                        x += glyph.advance;
                     }
-                    begin..end
+                    selected_range.unwrap_or(x..x)
                 });
 
-            let glyph_it = glyphs[line.glyph_range.clone()].iter();
+            let glyph_it = glyph_order().map(|(index, is_rtl)| (&glyphs[index], is_rtl));
             let mut glyph_x = Font::Length::zero();
             let mut positioned_glyph_it = glyph_it.enumerate().filter_map(|(index, glyph)| {
+                let (glyph, is_rtl) = glyph;
                 // TODO: cut off at grapheme boundaries
                 if glyph_x > self.max_width {
                     return None;
@@ -225,6 +260,7 @@ pub fn layout_lines<R>(
                             advance: elide_glyph.advance,
                             glyph_id: elide_glyph.glyph_id.unwrap(), // checked earlier when initializing elide_glyph
                             text_byte_offset: glyph.text_byte_offset,
+                            is_rtl,
                         });
                     } else {
                         return None;
@@ -239,6 +275,7 @@ pub fn layout_lines<R>(
                     advance: glyph.advance,
                     glyph_id: existing_glyph_id,
                     text_byte_offset: glyph.text_byte_offset,
+                    is_rtl,
                 })
             });
 
@@ -277,13 +314,19 @@ pub fn layout_lines<R>(
     pub fn cursor_pos_for_byte_offset(&self, byte_offset: usize) -> (Font::Length, Font::Length) {
         let mut last_glyph_right_edge = Font::Length::zero();
         let mut last_line_y = Font::Length::zero();
+        let bidi = BidiParagraphs::new(self.string, self.text_direction);
 
         match self.layout_lines(
             |glyphs, line_x, line_y, line, _| {
-                last_glyph_right_edge = euclid::approxord::min(
-                    self.max_width,
-                    line_x + line.width_including_trailing_whitespace(),
-                );
+                // The end of a line in a right-to-left paragraph is its left edge
+                last_glyph_right_edge = if bidi.is_rtl_paragraph(line.byte_range.start) {
+                    line_x
+                } else {
+                    euclid::approxord::min(
+                        self.max_width,
+                        line_x + line.width_including_trailing_whitespace(),
+                    )
+                };
                 last_line_y = line_y;
                 if byte_offset >= line.byte_range.end + line.trailing_whitespace_bytes {
                     return core::ops::ControlFlow::Continue(());
@@ -291,8 +334,13 @@ pub fn cursor_pos_for_byte_offset(&self, byte_offset: usize) -> (Font::Length, F
 
                 for positioned_glyph in glyphs {
                     if positioned_glyph.text_byte_offset == byte_offset {
+                        let leading_edge = if positioned_glyph.is_rtl {
+                            positioned_glyph.x + positioned_glyph.advance
+                        } else {
+                            positioned_glyph.x
+                        };
                         return core::ops::ControlFlow::Break((
-                            euclid::approxord::min(self.max_width, line_x + positioned_glyph.x),
+                            euclid::approxord::min(self.max_width, line_x + leading_edge),
                             last_line_y,
                         ));
                     }
@@ -311,6 +359,7 @@ pub fn cursor_pos_for_byte_offset(&self, byte_offset: usize) -> (Font::Length, F
     pub fn byte_offset_for_position(&self, (pos_x, pos_y): (Font::Length, Font::Length)) -> usize {
         let mut byte_offset = 0;
         let two = Font::LengthPrimitive::one() + Font::LengthPrimitive::one();
+        let bidi = BidiParagraphs::new(self.string, self.text_direction);
 
         match self.layout_lines(
             |glyphs, line_x, line_y, line, _| {
@@ -327,7 +376,22 @@ pub fn byte_offset_for_position(&self, (pos_x, pos_y): (Font::Length, Font::Leng
                     if pos_x >= line_x + positioned_glyph.x
                         && pos_x <= line_x + positioned_glyph.x + positioned_glyph.advance
                     {
-                        if pos_x < line_x + positioned_glyph.x + positioned_glyph.advance / two {
+                        let in_left_half =
+                            pos_x < line_x + positioned_glyph.x + positioned_glyph.advance / two;
+                        if positioned_glyph.is_rtl {
+                            // The logical start of a right-to-left glyph is its right edge
+                            let offset = positioned_glyph.text_byte_offset;
+                            return core::ops::ControlFlow::Break(if in_left_half {
+                                use unicode_segmentation::UnicodeSegmentation;
+                                self.string[offset..]
+                                    .graphemes(true)
+                                    .next()
+                                    .map_or(offset, |grapheme| offset + grapheme.len())
+                            } else {
+                                offset
+                            });
+                        }
+                        if in_left_half {
                             return core::ops::ControlFlow::Break(
                                 positioned_glyph.text_byte_offset,
                             );
@@ -337,6 +401,11 @@ pub fn byte_offset_for_position(&self, (pos_x, pos_y): (Font::Length, Font::Leng
                     }
                 }
 
+                // Right of a line in a right-to-left paragraph is its logical start
+                if bidi.is_rtl_paragraph(line.byte_range.start) && pos_x >= line_x {
+                    return core::ops::ControlFlow::Break(line.byte_range.start);
+                }
+
                 core::ops::ControlFlow::Break(line.byte_range.end)
             },
             None,
@@ -429,6 +498,7 @@ fn test_elision() {
         vertical_alignment: TextVerticalAlignment::Top,
         wrap: TextWrap::NoWrap,
         overflow: TextOverflow::Elide,
+        text_direction: TextDirection::Auto,
         single_line: true,
     };
     paragraph
@@ -473,6 +543,7 @@ fn test_exact_fit() {
         vertical_alignment: TextVerticalAlignment::Top,
         wrap: TextWrap::NoWrap,
         overflow: TextOverflow::Elide,
+        text_direction: TextDirection::Auto,
         single_line: true,
     };
     paragraph
@@ -517,6 +588,7 @@ fn test_no_line_separators_characters_rendered() {
         vertical_alignment: TextVerticalAlignment::Top,
         wrap: TextWrap::NoWrap,
         overflow: TextOverflow::Clip,
+        text_direction: TextDirection::Auto,
         single_line: true,
     };
     paragraph
@@ -564,6 +636,7 @@ fn test_cursor_position() {
         vertical_alignment: TextVerticalAlignment::Top,
         wrap: TextWrap::WordWrap,
         overflow: TextOverflow::Clip,
+        text_direction: TextDirection::Auto,
         single_line: false,
     };
 
@@ -604,12 +677,57 @@ fn test_cursor_position_with_newline() {
         vertical_alignment: TextVerticalAlignment::Top,
         wrap: TextWrap::WordWrap,
         overflow: TextOverflow::Clip,
+        text_direction: TextDirection::Auto,
         single_line: false,
     };
 
     assert_eq!(paragraph.cursor_pos_for_byte_offset(5), (5. * 10., 0.));
 }
 
+#[test]
+#[cfg_attr(
+    not(feature = "unicode-bidi"),
+    ignore = "Not supported without the unicode-bidi feature"
+)]
+fn test_rtl_cursor_position_and_byte_offset() {
+    let font = FixedTestFont;
+    // The hebrew letters alef, bet, gimel, each two bytes long
+    let text = "\u{5d0}\u{5d1}\u{5d2}";
+
+    let paragraph = TextParagraphLayout {
+        string: text,
+        layout: TextLayout { font: &font, letter_spacing: None },
+        max_width: 10. * 10.,
+        max_height: 10.,
+        horizontal_alignment: TextHorizontalAlignment::Left,
+        vertical_alignment: TextVerticalAlignment::Top,
+        wrap: TextWrap::NoWrap,
+        overflow: TextOverflow::Clip,
+        text_direction: TextDirection::Auto,
+        single_line: true,
+    };
+
+    let mut offsets = Vec::new();
+    paragraph
+        .layout_lines::<()>(
+            |glyphs, _, _, _, _| {
+                offsets.extend(glyphs.map(|glyph| (glyph.x, glyph.text_byte_offset)));
+                core::ops::ControlFlow::Continue(())
+            },
+            None,
+        )
+        .unwrap();
+    assert_eq!(offsets, vec![(0., 4), (10., 2), (20., 0)]);
+
+    assert_eq!(paragraph.cursor_pos_for_byte_offset(0), (30., 0.));
+    assert_eq!(paragraph.cursor_pos_for_byte_offset(2), (20., 0.));
+    assert_eq!(paragraph.cursor_pos_for_byte_offset(text.len()), (0., 0.));
+
+    assert_eq!(paragraph.byte_offset_for_position((25., 0.)), 0);
+    assert_eq!(paragraph.byte_offset_for_position((22., 0.)), 2);
+    assert_eq!(paragraph.byte_offset_for_position((35., 0.)), 0);
+}
+
 #[test]
 fn byte_offset_for_empty_line() {
     let font = FixedTestFont;
@@ -624,6 +742,7 @@ fn byte_offset_for_empty_line() {
         vertical_alignment: TextVerticalAlignment::Top,
         wrap: TextWrap::WordWrap,
         overflow: TextOverflow::Clip,
+        text_direction: TextDirection::Auto,
         single_line: false,
     };
 
@@ -646,6 +765,7 @@ fn test_byte_offset() {
         vertical_alignment: TextVerticalAlignment::Top,
         wrap: TextWrap::WordWrap,
         overflow: TextOverflow::Clip,
+        text_direction: TextDirection::Auto,
         single_line: false,
     };
 
@@ -0,0 +1,208 @@
+// Copyright © SixtyFPS GmbH <info@slint.dev>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-Royalty-free-1.1 OR LicenseRef-Slint-commercial
+
+//! Reordering of lines of text for display, following the Unicode Bidirectional Algorithm (UAX #9).
+//!
+//! Shaping and line breaking operate on the text in logical order. Once a line is known, this module
+//! determines the runs of the line in visual order, so that the glyphs of right-to-left runs can be
+//! placed from right to left.
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+use core::ops::Range;
+
+use super::{Glyph, TextDirection};
+
+/// A run of text within a line, in visual order.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VisualRun {
+    pub byte_range: Range<usize>,
+    pub is_rtl: bool,
+}
+
+/// The result of the bidi analysis of a whole text, used to reorder its individual lines.
+pub struct BidiParagraphs<'a> {
+    #[cfg(feature = "unicode-bidi")]
+    info: Option<unicode_bidi::BidiInfo<'a>>,
+    #[cfg(not(feature = "unicode-bidi"))]
+    marker: core::marker::PhantomData<&'a str>,
+    direction: TextDirection,
+}
+
+impl<'a> BidiParagraphs<'a> {
+    pub fn new(text: &'a str, direction: TextDirection) -> Self {
+        #[cfg(feature = "unicode-bidi")]
+        {
+            // All characters with a right-to-left bidi class as well as the explicit embedding controls
+            // are above U+0590, so text without such characters can skip the analysis.
+            let info = (direction == TextDirection::RightToLeft
+                || text.chars().any(|ch| ch >= '\u{0590}'))
+            .then(|| {
+                let default_level = match direction {
+                    TextDirection::Auto => None,
+                    TextDirection::LeftToRight => Some(unicode_bidi::Level::ltr()),
+                    TextDirection::RightToLeft => Some(unicode_bidi::Level::rtl()),
+                };
+                unicode_bidi::BidiInfo::new(text, default_level)
+            })
+            .filter(|info| info.has_rtl());
+            Self { info, direction }
+        }
+        #[cfg(not(feature = "unicode-bidi"))]
+        {
+            let _ = text;
+            Self { marker: Default::default(), direction }
+        }
+    }
+
+    /// Returns true if the paragraph that contains the given byte offset has a right-to-left base direction.
+    pub fn is_rtl_paragraph(&self, byte_offset: usize) -> bool {
+        #[cfg(feature = "unicode-bidi")]
+        if let Some(info) = &self.info {
+            return info
+                .paragraphs
+                .iter()
+                .find(|para| byte_offset < para.range.end)
+                .or(info.paragraphs.last())
+                .map_or(false, |para| para.level.is_rtl());
+        }
+        let _ = byte_offset;
+        self.direction == TextDirection::RightToLeft
+    }
+
+    /// Returns the runs of the given line in visual order, or None if the line is displayed in logical order.
+    pub fn visual_runs(&self, line: Range<usize>) -> Option<Vec<VisualRun>> {
+        #[cfg(feature = "unicode-bidi")]
+        if let Some(info) = &self.info {
+            let para = info.paragraphs.iter().find(|para| line.start < para.range.end)?;
+            let line = line.start..line.end.min(para.range.end);
+            if line.is_empty() {
+                return None;
+            }
+            let (levels, runs) = info.visual_runs(para, line);
+            if runs.len() == 1 && levels[runs[0].start].is_ltr() {
+                return None;
+            }
+            return Some(
+                runs.into_iter()
+                    .map(|run| VisualRun { is_rtl: levels[run.start].is_rtl(), byte_range: run })
+                    .collect(),
+            );
+        }
+        // Without the bidi tables, lines are displayed in logical order
+        let _ = line;
+        None
+    }
+
+    /// Returns the indices of the glyphs in `glyph_range` in visual order, together with whether the glyph
+    /// is part of a right-to-left run, or None if they are displayed in logical order.
+    ///
+    /// The glyphs of a cluster (sharing the same text byte offset) keep their relative order.
+    pub fn visual_glyph_order<Length>(
+        &self,
+        line: Range<usize>,
+        glyphs: &[Glyph<Length>],
+        glyph_range: Range<usize>,
+    ) -> Option<Vec<(usize, bool)>> {
+        let runs = self.visual_runs(line)?;
+        let mut order = Vec::with_capacity(glyph_range.len());
+        for run in runs {
+            let run_glyphs = glyph_range
+                .clone()
+                .filter(|index| run.byte_range.contains(&glyphs[*index].text_byte_offset))
+                .map(|index| (index, run.is_rtl));
+            if !run.is_rtl {
+                order.extend(run_glyphs);
+                continue;
+            }
+            let run_start = order.len();
+            order.extend(run_glyphs);
+            let run_order = &mut order[run_start..];
+            run_order.reverse();
+            // Restore the logical order of the glyphs within each cluster
+            let mut cluster_start = 0;
+            while cluster_start < run_order.len() {
+                let offset = glyphs[run_order[cluster_start].0].text_byte_offset;
+                let cluster_len = run_order[cluster_start..]
+                    .iter()
+                    .take_while(|(index, _)| glyphs[*index].text_byte_offset == offset)
+                    .count();
+                run_order[cluster_start..cluster_start + cluster_len].reverse();
+                cluster_start += cluster_len;
+            }
+        }
+        Some(order)
+    }
+}
+
+#[cfg(test)]
+fn glyphs_for(text: &str) -> Vec<Glyph<f32>> {
+    text.char_indices()
+        .map(|(text_byte_offset, _)| Glyph { text_byte_offset, advance: 10., ..Default::default() })
+        .collect()
+}
+
+#[test]
+fn test_ltr_text_is_not_reordered() {
+    let text = "Hello World";
+    let bidi = BidiParagraphs::new(text, TextDirection::Auto);
+    assert!(!bidi.is_rtl_paragraph(0));
+    assert_eq!(bidi.visual_glyph_order(0..text.len(), &glyphs_for(text), 0..11), None);
+}
+
+#[test]
+#[cfg_attr(
+    not(feature = "unicode-bidi"),
+    ignore = "Not supported without the unicode-bidi feature"
+)]
+fn test_mixed_text_visual_order() {
+    // "abc " followed by the hebrew letters alef, bet, gimel
+    let text = "abc \u{5d0}\u{5d1}\u{5d2}";
+    let glyphs = glyphs_for(text);
+    let bidi = BidiParagraphs::new(text, TextDirection::Auto);
+    assert!(!bidi.is_rtl_paragraph(0));
+    assert_eq!(
+        bidi.visual_runs(0..text.len()),
+        Some(alloc::vec![
+            VisualRun { byte_range: 0..4, is_rtl: false },
+            VisualRun { byte_range: 4..10, is_rtl: true }
+        ])
+    );
+    assert_eq!(
+        bidi.visual_glyph_order(0..text.len(), &glyphs, 0..glyphs.len()),
+        Some(alloc::vec![
+            (0, false),
+            (1, false),
+            (2, false),
+            (3, false),
+            (6, true),
+            (5, true),
+            (4, true)
+        ])
+    );
+}
+
+#[test]
+#[cfg_attr(
+    not(feature = "unicode-bidi"),
+    ignore = "Not supported without the unicode-bidi feature"
+)]
+fn test_rtl_paragraph_with_number() {
+    let text = "\u{5d0}\u{5d1} 42";
+    let glyphs = glyphs_for(text);
+    let bidi = BidiParagraphs::new(text, TextDirection::Auto);
+    assert!(bidi.is_rtl_paragraph(0));
+    // The number keeps its left-to-right order, but is placed left of the hebrew letters
+    assert_eq!(
+        bidi.visual_glyph_order(0..text.len(), &glyphs, 0..glyphs.len()),
+        Some(alloc::vec![(3, false), (4, false), (2, true), (1, true), (0, true)])
+    );
+}
+
+#[test]
+fn test_forced_rtl_direction() {
+    let text = "abc";
+    let bidi = BidiParagraphs::new(text, TextDirection::RightToLeft);
+    assert!(bidi.is_rtl_paragraph(0));
+    assert_eq!(bidi.visual_glyph_order(0..text.len(), &glyphs_for(text), 0..3), None);
+}
@@ -183,6 +183,20 @@ pub fn new<Font>(layout: &TextLayout<Font>, text: &str) -> Self
 
                 layout.font.shape_text(&text[*run_start..run_end], &mut glyphs);
 
+                let run_glyphs = &mut glyphs[glyphs_start..];
+                // Shapers emit the glyphs of right-to-left runs in visual order. Keep the buffer in
+                // logical order, the lines are reordered for display after line breaking.
+                if run_glyphs.len() > 1
+                    && run_glyphs[0].text_byte_offset
+                        > run_glyphs[run_glyphs.len() - 1].text_byte_offset
+                {
+                    run_glyphs.reverse();
+                }
+                // Shapers report offsets relative to the run, make them relative to the entire text.
+                for glyph in run_glyphs.iter_mut() {
+                    glyph.text_byte_offset += *run_start;
+                }
+
                 if let Some(letter_spacing) = layout.letter_spacing {
                     if glyphs.len() > glyphs_start {
                         let mut last_byte_offset = glyphs[glyphs_start].text_byte_offset;
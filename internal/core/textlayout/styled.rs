@@ -0,0 +1,599 @@
+// Copyright © SixtyFPS GmbH <info@slint.dev>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-Royalty-free-1.1 OR LicenseRef-Slint-commercial
+
+//! Layout of text made of runs with different styles, as used by the `StyledText` element.
+//!
+//! The text is parsed from a subset of Markdown into a [`StyledString`]: the plain text and the
+//! runs of text that share a [`TextStyle`]. [`StyledTextLayout`] breaks the text into lines at the
+//! line break opportunities of the whole text, measuring every piece of text with its own style.
+//! The renderers then only need to draw each laid out run as a single line of text.
+
+use super::{BreakOpportunity, LineBreakIterator};
+use crate::graphics::Color;
+use crate::items::{TextHorizontalAlignment, TextVerticalAlignment};
+use crate::lengths::{LogicalPoint, LogicalRect, LogicalSize};
+use crate::{Coord, SharedString};
+#[cfg(not(feature = "std"))]
+use alloc::string::String;
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+use core::ops::Range;
+
+/// The style of a run of text
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TextStyle {
+    pub bold: bool,
+    pub italic: bool,
+    pub underline: bool,
+    pub strikethrough: bool,
+    /// The color of the text, if it differs from the color of the element
+    pub color: Option<Color>,
+    /// The target of the link, if the text is a link
+    pub link: Option<SharedString>,
+}
+
+/// A range of the text of a [`StyledString`] that has the same style
+#[derive(Clone, Debug, PartialEq)]
+pub struct StyledTextRun {
+    pub range: Range<usize>,
+    pub style: TextStyle,
+}
+
+/// A text with the styles that apply to it.
+///
+/// The runs are sorted and cover the whole text.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct StyledString {
+    pub text: String,
+    pub runs: Vec<StyledTextRun>,
+}
+
+#[derive(Clone, Copy)]
+enum Emphasis {
+    Bold,
+    Italic,
+    Strikethrough,
+}
+
+const EMPHASIS_DELIMITERS: [(&str, Emphasis); 5] = [
+    ("**", Emphasis::Bold),
+    ("__", Emphasis::Bold),
+    ("~~", Emphasis::Strikethrough),
+    ("*", Emphasis::Italic),
+    ("_", Emphasis::Italic),
+];
+
+impl StyledString {
+    /// Parses a subset of Markdown:
+    ///  - `**bold**` or `__bold__`, `*italic*` or `_italic_`, and `~~strikethrough~~`
+    ///  - `[links](target)`
+    ///  - the `<u>underline</u>` and `<font color="#rrggbb">colored</font>` HTML tags
+    ///  - a backslash escapes the punctuation character that follows it
+    ///
+    /// Unlike in Markdown, new lines in the source are kept as line breaks.
+    pub fn from_markdown(markdown: &str) -> Self {
+        let mut result = Self::default();
+        let mut style = TextStyle::default();
+        // The delimiter that opened bold, italic and strikethrough, in that order
+        let mut open_emphasis: [Option<&str>; 3] = [None; 3];
+        let mut colors = Vec::new();
+        // The position of the `](` that ends the current link, and of the `)` after its target
+        let mut link_end = None;
+        let mut pos = 0;
+
+        while let Some(c) = markdown[pos..].chars().next() {
+            let rest = &markdown[pos..];
+            let prev = markdown[..pos].chars().next_back();
+
+            if let Some((text_end, target_end)) = link_end {
+                // An escaped `]` can make the position jump over the end of the text
+                if pos >= text_end {
+                    style.link = None;
+                    link_end = None;
+                    pos = target_end + 1;
+                    continue;
+                }
+            }
+
+            if c == '\\' {
+                match rest[1..].chars().next() {
+                    Some(escaped) if escaped.is_ascii_punctuation() => {
+                        result.push(&rest[1..2], &style);
+                        pos += 2;
+                    }
+                    _ => {
+                        result.push("\\", &style);
+                        pos += 1;
+                    }
+                }
+                continue;
+            }
+
+            if let Some(&(delimiter, emphasis)) =
+                EMPHASIS_DELIMITERS.iter().find(|(d, _)| rest.starts_with(d))
+            {
+                let after = &rest[delimiter.len()..];
+                let open = &mut open_emphasis[emphasis as usize];
+                if *open == Some(delimiter) {
+                    *open = None;
+                    set_emphasis(&mut style, emphasis, false);
+                } else if open.is_none()
+                    && !after.starts_with(char::is_whitespace)
+                    && (delimiter.starts_with('*') || !prev.is_some_and(char::is_alphanumeric))
+                    && find_closing_delimiter(after, delimiter).is_some()
+                {
+                    *open = Some(delimiter);
+                    set_emphasis(&mut style, emphasis, true);
+                } else {
+                    result.push(delimiter, &style);
+                }
+                pos += delimiter.len();
+                continue;
+            }
+
+            if c == '[' && link_end.is_none() {
+                if let Some(text_len) = rest.find("](") {
+                    if let Some(target_len) = rest[text_len + 2..].find(')') {
+                        let target = &rest[text_len + 2..text_len + 2 + target_len];
+                        if !rest[1..text_len].contains('\n')
+                            && !target.contains(char::is_whitespace)
+                        {
+                            style.link = Some(target.into());
+                            link_end = Some((pos + text_len, pos + text_len + 2 + target_len));
+                            pos += 1;
+                            continue;
+                        }
+                    }
+                }
+            }
+
+            if c == '<' {
+                if let Some(tag_len) = rest.find('>') {
+                    let tag = rest[1..tag_len].trim();
+                    let handled = match tag {
+                        "u" => {
+                            style.underline = true;
+                            true
+                        }
+                        "/u" => {
+                            style.underline = false;
+                            true
+                        }
+                        "/font" => {
+                            style.color = colors.pop().unwrap_or_default();
+                            true
+                        }
+                        _ => match font_color_attribute(tag) {
+                            Some(color) => {
+                                colors.push(style.color);
+                                style.color = Some(color);
+                                true
+                            }
+                            None => false,
+                        },
+                    };
+                    if handled {
+                        pos += tag_len + 1;
+                        continue;
+                    }
+                }
+            }
+
+            result.push(&rest[..c.len_utf8()], &style);
+            pos += c.len_utf8();
+        }
+        result
+    }
+
+    /// Appends `text` with the given style, extending the last run if it has the same style
+    fn push(&mut self, text: &str, style: &TextStyle) {
+        let start = self.text.len();
+        self.text.push_str(text);
+        match self.runs.last_mut() {
+            Some(run) if run.style == *style => run.range.end = self.text.len(),
+            _ => self
+                .runs
+                .push(StyledTextRun { range: start..self.text.len(), style: style.clone() }),
+        }
+    }
+}
+
+fn set_emphasis(style: &mut TextStyle, emphasis: Emphasis, value: bool) {
+    match emphasis {
+        Emphasis::Bold => style.bold = value,
+        Emphasis::Italic => style.italic = value,
+        Emphasis::Strikethrough => style.strikethrough = value,
+    }
+}
+
+/// Returns the position of the delimiter that closes an emphasis opened with `delimiter`
+fn find_closing_delimiter(text: &str, delimiter: &str) -> Option<usize> {
+    let mut search_from = 0;
+    while let Some(found) = text[search_from..].find(delimiter) {
+        let found = search_from + found;
+        let before = text[..found].chars().next_back();
+        let after = text[found + delimiter.len()..].chars().next();
+        if text[..found].ends_with('\\') || before.map_or(true, char::is_whitespace) {
+            // escaped or can't close
+        } else if delimiter.starts_with('_') && after.is_some_and(char::is_alphanumeric) {
+            // `_` within a word
+        } else if delimiter == "*" && after == Some('*') {
+            // part of a `**`, that is a different delimiter
+            search_from = found + 2;
+            continue;
+        } else {
+            return Some(found);
+        }
+        search_from = found + delimiter.len();
+    }
+    None
+}
+
+/// Parses the content of a `<font color="...">` tag
+fn font_color_attribute(tag: &str) -> Option<Color> {
+    let value = tag.strip_prefix("font")?.trim_start().strip_prefix("color")?;
+    let value = value.trim_start().strip_prefix('=')?.trim();
+    let value = value.trim_matches(|c| c == '"' || c == '\'');
+    parse_hex_color(value)
+}
+
+/// Parses a color in the `#rgb`, `#rgba`, `#rrggbb` or `#rrggbbaa` format
+fn parse_hex_color(value: &str) -> Option<Color> {
+    let hex = value.strip_prefix('#')?;
+    if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let digit = |i: usize| u8::from_str_radix(&hex[i..i + 1], 16).ok().map(|d| d * 0x11);
+    let byte = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+    let (r, g, b, a) = match hex.len() {
+        3 => (digit(0)?, digit(1)?, digit(2)?, 0xff),
+        4 => (digit(0)?, digit(1)?, digit(2)?, digit(3)?),
+        6 => (byte(0)?, byte(2)?, byte(4)?, 0xff),
+        8 => (byte(0)?, byte(2)?, byte(4)?, byte(6)?),
+        _ => return None,
+    };
+    Some(Color::from_argb_u8(a, r, g, b))
+}
+
+/// A piece of a [`StyledString`] placed by [`StyledTextLayout`]
+#[derive(Clone, Debug, PartialEq)]
+pub struct StyledTextLayoutRun {
+    /// The range of the text of the [`StyledString`]
+    pub range: Range<usize>,
+    /// The index of the [`StyledTextRun`] this piece of text belongs to
+    pub run_index: usize,
+    pub rect: LogicalRect,
+}
+
+#[derive(Clone, Debug, Default)]
+struct StyledTextLine {
+    /// The range in [`StyledTextLayout::runs`]
+    runs: Range<usize>,
+    /// The width without the trailing whitespace
+    width: Coord,
+    height: Coord,
+}
+
+/// The lines of a [`StyledString`], made of runs that each need to be drawn as a single line of text
+#[derive(Clone, Debug, Default)]
+pub struct StyledTextLayout {
+    pub runs: Vec<StyledTextLayoutRun>,
+    /// The size of the text: the width of the longest line and the height of all lines
+    pub size: LogicalSize,
+    lines: Vec<StyledTextLine>,
+}
+
+impl StyledTextLayout {
+    /// Breaks the text into lines no wider than `max_width` (unless a word doesn't fit), using
+    /// `measure` to get the size of a single line of text with the given style.
+    pub fn new(
+        styled: &StyledString,
+        mut measure: impl FnMut(&str, &TextStyle) -> LogicalSize,
+        max_width: Option<Coord>,
+    ) -> Self {
+        let text = styled.text.as_str();
+        let mut layout = Self::default();
+        let default_line_height = measure(" ", &TextStyle::default()).height;
+
+        let mut line = StyledTextLine { height: default_line_height, ..Default::default() };
+        let mut trailing_whitespace_width = 0 as Coord;
+
+        let finish_line = |layout: &mut Self,
+                           line: &mut StyledTextLine,
+                           trailing_whitespace_width: &mut Coord| {
+            line.runs.end = layout.runs.len();
+            let y = layout.size.height;
+            for run in &mut layout.runs[line.runs.clone()] {
+                run.rect.origin.y = y;
+            }
+            layout.size.width = layout.size.width.max(line.width);
+            layout.size.height += line.height;
+            let next = StyledTextLine {
+                runs: layout.runs.len()..layout.runs.len(),
+                width: 0 as Coord,
+                height: default_line_height,
+            };
+            layout.lines.push(core::mem::replace(line, next));
+            *trailing_whitespace_width = 0 as Coord;
+        };
+
+        let mut breaks = LineBreakIterator::new(text);
+        let mut fragment_start = 0;
+        while fragment_start < text.len() {
+            let (fragment_end, mandatory) = match breaks.next() {
+                Some((offset, opportunity)) => {
+                    (offset, matches!(opportunity, BreakOpportunity::Mandatory))
+                }
+                None => (text.len(), false),
+            };
+            if fragment_end <= fragment_start {
+                continue;
+            }
+            let fragment = &text[fragment_start..fragment_end];
+            let content_end = fragment_start
+                + fragment.trim_end_matches(['\n', '\r', '\u{2028}', '\u{2029}']).len();
+            let whitespace_start =
+                fragment_start + text[fragment_start..content_end].trim_end().len();
+
+            // Split the fragment along the style runs and measure each piece
+            let mut pieces = Vec::new();
+            let mut fragment_width = 0 as Coord;
+            let mut fragment_height = 0 as Coord;
+            for (run_index, run) in styled.runs.iter().enumerate() {
+                let start = run.range.start.max(fragment_start);
+                let end = run.range.end.min(content_end);
+                if start >= end {
+                    continue;
+                }
+                let size = measure(&text[start..end], &run.style);
+                fragment_width += size.width;
+                fragment_height = fragment_height.max(size.height);
+                pieces.push((start..end, run_index, size.width));
+            }
+            let whitespace_width = if whitespace_start < content_end {
+                let style = styled
+                    .runs
+                    .iter()
+                    .find(|run| run.range.contains(&whitespace_start))
+                    .map(|run| &run.style);
+                measure(
+                    &text[whitespace_start..content_end],
+                    style.unwrap_or(&TextStyle::default()),
+                )
+                .width
+            } else {
+                0 as Coord
+            };
+            let content_width = fragment_width - whitespace_width;
+
+            if max_width.is_some_and(|max_width| {
+                !line.runs.is_empty()
+                    && line.width + trailing_whitespace_width + content_width > max_width
+            }) {
+                finish_line(&mut layout, &mut line, &mut trailing_whitespace_width);
+            }
+
+            let mut x = line.width + trailing_whitespace_width;
+            for (range, run_index, width) in pieces {
+                match layout.runs.last_mut() {
+                    Some(last)
+                        if !line.runs.is_empty()
+                            && last.run_index == run_index
+                            && last.range.end == range.start =>
+                    {
+                        last.range.end = range.end;
+                        last.rect.size.width += width;
+                        last.rect.size.height = last.rect.size.height.max(fragment_height);
+                    }
+                    _ => layout.runs.push(StyledTextLayoutRun {
+                        range,
+                        run_index,
+                        rect: LogicalRect::new(
+                            LogicalPoint::new(x, 0 as Coord),
+                            LogicalSize::new(width, fragment_height),
+                        ),
+                    }),
+                }
+                line.runs.end = layout.runs.len();
+                x += width;
+            }
+            line.width += trailing_whitespace_width + content_width;
+            line.height = line.height.max(fragment_height);
+            trailing_whitespace_width = whitespace_width;
+
+            if mandatory {
+                finish_line(&mut layout, &mut line, &mut trailing_whitespace_width);
+            }
+            fragment_start = fragment_end;
+        }
+        if !line.runs.is_empty() || layout.lines.is_empty() {
+            finish_line(&mut layout, &mut line, &mut trailing_whitespace_width);
+        }
+        layout
+    }
+
+    /// Moves the runs to align the text within an element of the given size
+    pub fn align(
+        &mut self,
+        size: LogicalSize,
+        horizontal_alignment: TextHorizontalAlignment,
+        vertical_alignment: TextVerticalAlignment,
+    ) {
+        let dy = match vertical_alignment {
+            TextVerticalAlignment::Top => 0 as Coord,
+            TextVerticalAlignment::Center => (size.height - self.size.height) / 2 as Coord,
+            TextVerticalAlignment::Bottom => size.height - self.size.height,
+        };
+        for line in &self.lines {
+            let dx = match horizontal_alignment {
+                TextHorizontalAlignment::Left => 0 as Coord,
+                TextHorizontalAlignment::Center => (size.width - line.width) / 2 as Coord,
+                TextHorizontalAlignment::Right => size.width - line.width,
+            };
+            for run in &mut self.runs[line.runs.clone()] {
+                run.rect.origin.x += dx;
+                run.rect.origin.y += dy;
+            }
+        }
+    }
+
+    /// Returns the run at the given position
+    pub fn run_at(&self, position: LogicalPoint) -> Option<&StyledTextLayoutRun> {
+        self.runs.iter().find(|run| run.rect.contains(position))
+    }
+}
+
+/// Returns the underline and strikethrough lines of a run of text drawn in `rect` with the given style.
+///
+/// The renderers don't expose the font metrics, so the position of the lines is derived from the
+/// height of the line of text.
+pub fn text_decorations(
+    rect: &LogicalRect,
+    style: &TextStyle,
+    font_size: Coord,
+) -> impl Iterator<Item = LogicalRect> {
+    let thickness = (font_size / 14 as Coord).max(1 as Coord);
+    let line_at = |ratio: Coord| {
+        LogicalRect::new(
+            LogicalPoint::new(rect.origin.x, rect.origin.y + rect.size.height * ratio),
+            LogicalSize::new(rect.size.width, thickness),
+        )
+    };
+    let underline = (style.underline || style.link.is_some()).then(|| line_at(0.85 as Coord));
+    let strikethrough = style.strikethrough.then(|| line_at(0.5 as Coord));
+    underline.into_iter().chain(strikethrough)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn runs(styled: &StyledString) -> Vec<(&str, TextStyle)> {
+        styled.runs.iter().map(|run| (&styled.text[run.range.clone()], run.style.clone())).collect()
+    }
+
+    fn styles(markdown: &str) -> Vec<(String, TextStyle)> {
+        let styled = StyledString::from_markdown(markdown);
+        runs(&styled).into_iter().map(|(text, style)| (text.into(), style)).collect()
+    }
+
+    fn plain(text: &str) -> (String, TextStyle) {
+        (text.into(), TextStyle::default())
+    }
+
+    #[test]
+    fn parse_emphasis() {
+        let bold = TextStyle { bold: true, ..Default::default() };
+        let italic = TextStyle { italic: true, ..Default::default() };
+        assert_eq!(
+            styles("a **b** _c_ d"),
+            [
+                plain("a "),
+                ("b".into(), bold),
+                plain(" "),
+                ("c".into(), italic.clone()),
+                plain(" d")
+            ]
+        );
+        assert_eq!(
+            styles("*a __b__*"),
+            [
+                ("a ".into(), italic),
+                ("b".into(), TextStyle { bold: true, italic: true, ..Default::default() })
+            ]
+        );
+        // Unmatched or escaped delimiters and underscores within words are kept as is
+        assert_eq!(
+            styles("2 * 3 = \\*six* snake_case_name"),
+            [plain("2 * 3 = *six* snake_case_name")]
+        );
+        assert_eq!(
+            styles("~~gone~~"),
+            [("gone".into(), TextStyle { strikethrough: true, ..Default::default() })]
+        );
+    }
+
+    #[test]
+    fn parse_links_and_tags() {
+        let link = TextStyle { link: Some("https://slint.dev".into()), ..Default::default() };
+        assert_eq!(
+            styles("see [**the** site](https://slint.dev)!"),
+            [
+                plain("see "),
+                ("the".into(), TextStyle { bold: true, ..link.clone() }),
+                (" site".into(), link),
+                plain("!")
+            ]
+        );
+        assert_eq!(styles("[not a link] (x)"), [plain("[not a link] (x)")]);
+        let link = TextStyle { link: Some("t".into()), ..Default::default() };
+        assert_eq!(styles("[a\\]b](t) c"), [("a]b".into(), link.clone()), plain(" c")]);
+        assert_eq!(styles("[a\\](t) c"), [("a]".into(), link), plain(" c")]);
+        assert_eq!(
+            styles("<u>a</u><font color=\"#f00\">b<font color='#00ff0080'>c</font></font>d"),
+            [
+                ("a".into(), TextStyle { underline: true, ..Default::default() }),
+                (
+                    "b".into(),
+                    TextStyle { color: Some(Color::from_rgb_u8(0xff, 0, 0)), ..Default::default() }
+                ),
+                (
+                    "c".into(),
+                    TextStyle {
+                        color: Some(Color::from_argb_u8(0x80, 0, 0xff, 0)),
+                        ..Default::default()
+                    }
+                ),
+                plain("d"),
+            ]
+        );
+        assert_eq!(styles("a <b> c"), [plain("a <b> c")]);
+    }
+
+    #[test]
+    fn layout_lines() {
+        // Every character is 10 wide and 10 high, bold characters are 20 wide.
+        let measure = |text: &str, style: &TextStyle| {
+            let char_width = if style.bold { 20. } else { 10. };
+            LogicalSize::new(text.chars().count() as Coord * char_width, 10.)
+        };
+        let styled = StyledString::from_markdown("Hello **big** world\nnext");
+        let positions = |layout: &StyledTextLayout| {
+            layout
+                .runs
+                .iter()
+                .map(|run| (&styled.text[run.range.clone()], run.rect.origin.x, run.rect.origin.y))
+                .collect::<Vec<_>>()
+        };
+
+        let layout = StyledTextLayout::new(&styled, measure, None);
+        assert_eq!(layout.size, LogicalSize::new(180., 20.));
+        assert_eq!(
+            positions(&layout),
+            [("Hello ", 0., 0.), ("big", 60., 0.), (" world", 120., 0.), ("next", 0., 10.)]
+        );
+
+        let mut layout = StyledTextLayout::new(&styled, measure, Some(100.));
+        assert_eq!(layout.size, LogicalSize::new(60., 40.));
+        assert_eq!(
+            positions(&layout),
+            [
+                ("Hello ", 0., 0.),
+                ("big", 0., 10.),
+                (" ", 60., 10.),
+                ("world", 0., 20.),
+                ("next", 0., 30.)
+            ]
+        );
+
+        layout.align(
+            LogicalSize::new(200., 60.),
+            TextHorizontalAlignment::Right,
+            TextVerticalAlignment::Bottom,
+        );
+        assert_eq!(layout.runs[0].rect.origin, LogicalPoint::new(150., 20.));
+        assert_eq!(layout.runs[1].rect.origin, LogicalPoint::new(140., 30.));
+        assert_eq!(layout.run_at(LogicalPoint::new(145., 35.)).map(|r| r.run_index), Some(1));
+        assert!(layout.run_at(LogicalPoint::new(10., 35.)).is_none());
+    }
+}
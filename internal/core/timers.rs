@@ -167,6 +167,13 @@ pub fn set_interval(&self, interval: core::time::Duration) {
         }
     }
 
+    /// Returns the interval of the timer. Returns a zero duration if the timer was never started.
+    pub fn interval(&self) -> core::time::Duration {
+        self.id()
+            .map(|timer_id| CURRENT_TIMERS.with(|timers| timers.borrow().timers[timer_id].duration))
+            .unwrap_or_default()
+    }
+
     fn id(&self) -> Option<usize> {
         self.id.get().map(|v| usize::from(v) - 1)
     }
@@ -533,6 +540,18 @@ pub extern "C" fn slint_timer_running(id: usize) -> bool {
         timer.id.take(); // Make sure that dropping the Timer doesn't unregister it. C++ will call destroy() in the destructor.
         running
     }
+
+    /// Returns the interval in milliseconds. 0 when the timer was never started.
+    #[no_mangle]
+    pub extern "C" fn slint_timer_interval(id: usize) -> u64 {
+        if id == 0 {
+            return 0;
+        }
+        let timer = Timer { id: Cell::new(NonZeroUsize::new(id)) };
+        let duration = timer.interval();
+        timer.id.take(); // Make sure that dropping the Timer doesn't unregister it. C++ will call destroy() in the destructor.
+        duration.as_millis() as u64
+    }
 }
 
 /**
@@ -17,7 +17,7 @@
 };
 use crate::item_tree::ItemRc;
 use crate::item_tree::{ItemTreeRc, ItemTreeRef, ItemTreeVTable, ItemTreeWeak};
-use crate::items::{InputType, ItemRef, MouseCursor};
+use crate::items::{DropEvent, InputType, ItemRef, MouseCursor, PopupClosePolicy, Shortcut};
 use crate::lengths::{LogicalLength, LogicalPoint, LogicalRect, SizeLengths};
 use crate::properties::{Property, PropertyTracker};
 use crate::renderer::Renderer;
@@ -25,7 +25,10 @@
 #[cfg(not(feature = "std"))]
 use alloc::boxed::Box;
 use alloc::rc::{Rc, Weak};
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
 use core::cell::{Cell, RefCell};
+use core::num::NonZeroU32;
 use core::pin::Pin;
 use euclid::num::Zero;
 use vtable::VRcMapped;
@@ -343,9 +346,10 @@ struct PopupWindow {
     location: PopupWindowLocation,
     /// The component that is responsible for providing the popup content.
     component: ItemTreeRc,
-    /// If true, Slint will close the popup after any mouse click within the popup.
-    /// Set to false and call close() on the PopupWindow to close it manually.
-    close_on_click: bool,
+    /// The identifier returned by [`WindowInner::show_popup()`].
+    popup_id: NonZeroU32,
+    /// Defines when Slint closes the popup on its own.
+    close_policy: PopupClosePolicy,
 }
 
 #[pin_project::pin_project]
@@ -370,6 +374,8 @@ pub struct WindowInner {
     /// When the window is visible, keep a strong reference
     strong_component_ref: RefCell<Option<ItemTreeRc>>,
     mouse_input_state: Cell<MouseInputState>,
+    /// The data of the drag and drop operation in progress, if any
+    drag_data: RefCell<Option<DropEvent>>,
     pub(crate) modifiers: Cell<InternalKeyboardModifierState>,
 
     /// itemRC will retrieve on wasms
@@ -388,8 +394,11 @@ pub struct WindowInner {
     maximized: Cell<bool>,
     minimized: Cell<bool>,
 
-    active_popup: RefCell<Option<PopupWindow>>,
-    had_popup_on_press: Cell<bool>,
+    /// The popups that are shown, the topmost one last.
+    active_popups: RefCell<Vec<PopupWindow>>,
+    next_popup_id: Cell<NonZeroU32>,
+    /// The topmost popup when the last mouse press happened
+    popup_on_press: Cell<Option<NonZeroU32>>,
     close_requested: Callback<(), CloseRequestResponse>,
     click_state: ClickState,
     pub(crate) ctx: once_cell::unsync::Lazy<crate::SlintContext>,
@@ -429,6 +438,7 @@ pub fn new(window_adapter_weak: Weak<dyn WindowAdapter>) -> Self {
             component: Default::default(),
             strong_component_ref: Default::default(),
             mouse_input_state: Default::default(),
+            drag_data: Default::default(),
             modifiers: Default::default(),
             pinned_fields: Box::pin(WindowPinnedFields {
                 redraw_tracker,
@@ -449,8 +459,9 @@ pub fn new(window_adapter_weak: Weak<dyn WindowAdapter>) -> Self {
             focus_item: Default::default(),
             last_ime_text: Default::default(),
             cursor_blinker: Default::default(),
-            active_popup: Default::default(),
-            had_popup_on_press: Default::default(),
+            active_popups: Default::default(),
+            next_popup_id: Cell::new(NonZeroU32::MIN),
+            popup_on_press: Default::default(),
             close_requested: Default::default(),
             click_state: ClickState::default(),
             prevent_focus_change: Default::default(),
@@ -465,9 +476,10 @@ pub fn new(window_adapter_weak: Weak<dyn WindowAdapter>) -> Self {
     /// Associates this window with the specified component. Further event handling and rendering, etc. will be
     /// done with that component.
     pub fn set_component(&self, component: &ItemTreeRc) {
-        self.close_popup();
+        self.close_all_popups();
         self.focus_item.replace(Default::default());
         self.mouse_input_state.replace(Default::default());
+        self.drag_data.replace(None);
         self.modifiers.replace(Default::default());
         self.component.replace(ItemTreeRc::downgrade(component));
         self.pinned_fields.window_properties_tracker.set_dirty(); // component changed, layout constraints for sure must be re-calculated
@@ -519,6 +531,7 @@ pub fn process_mouse_input(&self, mut event: MouseEvent) {
 
         // handle multiple press release
         event = self.click_state.check_repeat(event, self.ctx.0.platform.click_interval());
+        event = self.to_drag_event(event);
 
         let pressed_event = matches!(event, MouseEvent::Pressed { .. });
         let released_event = matches!(event, MouseEvent::Released { .. });
@@ -531,29 +544,45 @@ pub fn process_mouse_input(&self, mut event: MouseEvent) {
                 crate::input::process_delayed_event(&window_adapter, mouse_input_state);
         }
 
+        if pressed_event || matches!(event, MouseEvent::Wheel { .. }) {
+            self.close_popups_on_input();
+        }
+
         if pressed_event {
-            self.had_popup_on_press.set(self.active_popup.borrow().is_some());
+            self.popup_on_press.set(self.active_popups.borrow().last().map(|p| p.popup_id));
         }
 
-        let close_popup_on_click = self.close_popup_on_click();
-        let mut mouse_inside_popup = false;
+        // The popup receiving the event, if the event is within the topmost popup
+        let mut target_popup = None;
 
-        mouse_input_state = if let Some(mut event) =
+        mouse_input_state = if let Some(event) =
             crate::input::handle_mouse_grab(event, &window_adapter, &mut mouse_input_state)
         {
+            // The item that had the grab may just have started a drag
+            let mut event = self.to_drag_event(event);
+            if matches!(event, MouseEvent::DragMove { .. }) {
+                // The DropArea accepting the drag will change the cursor
+                self.set_mouse_cursor(MouseCursor::NoDrop);
+            }
+            // Only the topmost popup receives the events
             let (item_tree, offset) = if let Some(PopupWindow {
                 location: PopupWindowLocation::ChildWindow(coordinates),
                 component,
+                popup_id,
                 ..
-            }) = self.active_popup.borrow().as_ref()
+            }) = self
+                .active_popups
+                .borrow()
+                .iter()
+                .rev()
+                .find(|popup| popup.close_policy != PopupClosePolicy::CloseOnInput)
             {
-                let geom = ItemTreeRc::borrow_pin(component).as_ref().item_geometry(0);
-
-                mouse_inside_popup = event
+                let mouse_inside_popup = event
                     .position()
-                    .map_or(true, |pos| geom.contains(pos - coordinates.to_vector()));
+                    .map_or(true, |pos| self.popup_contains(component, *coordinates, pos));
 
                 if mouse_inside_popup {
+                    target_popup = Some(*popup_id);
                     (Some(component.clone()), *coordinates)
                 } else {
                     (None, LogicalPoint::default())
@@ -594,11 +623,105 @@ pub fn process_mouse_input(&self, mut event: MouseEvent) {
 
         self.mouse_input_state.set(mouse_input_state);
 
-        if close_popup_on_click
-            && ((mouse_inside_popup && released_event && self.had_popup_on_press.get())
-                || (!mouse_inside_popup && pressed_event))
+        if matches!(event, MouseEvent::Drop { .. } | MouseEvent::Exit)
+            && self.drag_data.take().is_some()
         {
-            self.close_popup();
+            self.set_mouse_cursor(MouseCursor::Default);
+        }
+
+        if pressed_event {
+            if let Some(position) = event.position() {
+                self.close_popups_outside(position);
+            }
+        } else if released_event && target_popup.is_some() {
+            // Close the popup that was clicked, unless the click opened another popup on top of it
+            let close = self.active_popups.borrow().last().is_some_and(|popup| {
+                Some(popup.popup_id) == target_popup
+                    && self.popup_on_press.get() == target_popup
+                    && popup.close_policy == PopupClosePolicy::CloseOnClick
+            });
+            if let Some(popup_id) = target_popup.filter(|_| close) {
+                self.close_popup(popup_id);
+            }
+        }
+    }
+
+    fn popup_contains(
+        &self,
+        component: &ItemTreeRc,
+        coordinates: LogicalPoint,
+        position: LogicalPoint,
+    ) -> bool {
+        let geom = ItemTreeRc::borrow_pin(component).as_ref().item_geometry(0);
+        geom.contains(position - coordinates.to_vector())
+    }
+
+    /// Closes the popups on top of the stack that don't contain the position of a mouse press,
+    /// unless their close policy prevents it.
+    fn close_popups_outside(&self, position: LogicalPoint) {
+        loop {
+            let to_close = match self.active_popups.borrow().last() {
+                Some(popup) if popup.close_policy != PopupClosePolicy::NoAutoClose => {
+                    let inside = match popup.location {
+                        PopupWindowLocation::ChildWindow(coordinates) => {
+                            self.popup_contains(&popup.component, coordinates, position)
+                        }
+                        // Events within a top-level popup are not received by this window
+                        PopupWindowLocation::TopLevel(..) => false,
+                    };
+                    (!inside).then_some(popup.popup_id)
+                }
+                _ => None,
+            };
+            match to_close {
+                Some(popup_id) => self.close_popup(popup_id),
+                None => break,
+            }
+        }
+    }
+
+    /// Closes the popups with the [`PopupClosePolicy::CloseOnInput`] policy, but not the other
+    /// popups shown on top of them.
+    fn close_popups_on_input(&self) {
+        let closed_popups = {
+            let mut active_popups = self.active_popups.borrow_mut();
+            let (closed, kept): (Vec<_>, Vec<_>) = core::mem::take(&mut *active_popups)
+                .into_iter()
+                .partition(|popup| popup.close_policy == PopupClosePolicy::CloseOnInput);
+            *active_popups = kept;
+            closed
+        };
+        for popup in closed_popups.into_iter().rev() {
+            self.discard_popup(popup);
+        }
+    }
+
+    /// Start a drag and drop operation with the given data.
+    /// Until the data is dropped, the mouse move and release events are sent to the items as
+    /// [`MouseEvent::DragMove`] and [`MouseEvent::Drop`].
+    pub fn start_drag(&self, data: DropEvent) {
+        self.drag_data.replace(Some(data));
+    }
+
+    /// Returns the data of the drag and drop operation in progress, if any.
+    pub fn drag_data(&self) -> Option<DropEvent> {
+        self.drag_data.borrow().clone()
+    }
+
+    fn to_drag_event(&self, event: MouseEvent) -> MouseEvent {
+        if self.drag_data.borrow().is_none() {
+            return event;
+        }
+        match event {
+            MouseEvent::Moved { position } => MouseEvent::DragMove { position },
+            MouseEvent::Released { position, .. } => MouseEvent::Drop { position },
+            event => event,
+        }
+    }
+
+    fn set_mouse_cursor(&self, cursor: MouseCursor) {
+        if let Some(x) = self.window_adapter().internal(crate::InternalToken) {
+            x.set_mouse_cursor(cursor);
         }
     }
 
@@ -628,6 +751,10 @@ pub fn process_key_input(&self, mut event: KeyEvent) {
 
         event.modifiers = self.modifiers.get().into();
 
+        if event.event_type == KeyEventType::KeyPressed {
+            self.close_popups_on_input();
+        }
+
         let mut item = self.focus_item.borrow().clone().upgrade();
         while let Some(focus_item) = item {
             if !focus_item.is_visible() {
@@ -644,6 +771,17 @@ pub fn process_key_input(&self, mut event: KeyEvent) {
             item = focus_item.parent_item();
         }
 
+        if event.event_type == KeyEventType::KeyPressed && self.activate_shortcut(&event) {
+            return;
+        }
+
+        if event.event_type == KeyEventType::KeyPressed
+            && event.text.starts_with(key_codes::Escape)
+            && self.close_top_popup_on_escape()
+        {
+            return;
+        }
+
         // Make Tab/Backtab handle keyboard focus
         if event.text.starts_with(key_codes::Tab)
             && !event.modifiers.shift
@@ -658,6 +796,55 @@ pub fn process_key_input(&self, mut event: KeyEvent) {
         }
     }
 
+    /// Activates the `Shortcut` item matching the key event, if any. Returns true if one was activated.
+    ///
+    /// When several shortcuts match, the one with the innermost scope wins. The scope of a shortcut
+    /// is its parent element, and is only considered if it contains the focused item. If there is
+    /// still a tie, the first one in tree order is activated. The shortcuts of the popups have
+    /// precedence over the ones of the window, starting with the topmost popup.
+    fn activate_shortcut(&self, event: &KeyEvent) -> bool {
+        let focus_ancestors =
+            core::iter::successors(self.focus_item.borrow().upgrade(), ItemRc::parent_item)
+                .collect::<Vec<_>>();
+        // How far the scope of the shortcut is from the focus item, if it contains it
+        let scope_distance = |item: &ItemRc| {
+            item.parent_item()
+                .and_then(|scope| focus_ancestors.iter().position(|a| *a == scope))
+                .unwrap_or(focus_ancestors.len())
+        };
+
+        let popup_components = self
+            .active_popups
+            .borrow()
+            .iter()
+            .rev()
+            .map(|popup| popup.component.clone())
+            .collect::<Vec<_>>();
+        for component in popup_components.into_iter().chain(self.try_component()) {
+            let mut best: Option<(usize, ItemRc)> = None;
+            crate::item_tree::visit_items(
+                &component,
+                crate::item_tree::TraversalOrder::BackToFront,
+                |item_tree, item, index, _| {
+                    if ItemRef::downcast_pin::<Shortcut>(item).is_some_and(|s| s.matches(event)) {
+                        let item = ItemRc::new(item_tree.clone(), index);
+                        let distance = scope_distance(&item);
+                        if best.as_ref().map_or(true, |(d, _)| distance < *d) && item.is_visible() {
+                            best = Some((distance, item));
+                        }
+                    }
+                    crate::item_tree::ItemVisitorResult::Continue(())
+                },
+                (),
+            );
+            if let Some((_, item)) = best {
+                item.downcast::<Shortcut>().unwrap().as_pin_ref().activate();
+                return true;
+            }
+        }
+        false
+    }
+
     /// Installs a binding on the specified property that's toggled whenever the text cursor is supposed to be visible or not.
     pub fn set_cursor_blink_binding(&self, prop: &crate::Property<bool>) {
         let existing_blinker = self.cursor_blinker.borrow().clone();
@@ -825,25 +1012,32 @@ pub fn draw_contents<T>(
         &self,
         render_components: impl FnOnce(&[(&ItemTreeRc, LogicalPoint)]) -> T,
     ) -> Option<T> {
+        // Let the `changed` callbacks run so that their effects are part of this frame
+        crate::properties::ChangeTracker::run_change_handlers();
+
         let draw_fn = || {
             let component_rc = self.try_component()?;
 
-            let popup_component =
-                self.active_popup.borrow().as_ref().and_then(|popup| match popup.location {
+            let popup_components = self
+                .active_popups
+                .borrow()
+                .iter()
+                .filter_map(|popup| match popup.location {
                     PopupWindowLocation::TopLevel(..) => None,
                     PopupWindowLocation::ChildWindow(coordinates) => {
                         Some((popup.component.clone(), coordinates))
                     }
-                });
-
-            Some(if let Some((popup_component, popup_coordinates)) = popup_component {
-                render_components(&[
-                    (&component_rc, LogicalPoint::default()),
-                    (&popup_component, popup_coordinates),
-                ])
-            } else {
-                render_components(&[(&component_rc, LogicalPoint::default())])
-            })
+                })
+                .collect::<Vec<_>>();
+
+            let components = core::iter::once((&component_rc, LogicalPoint::default()))
+                .chain(
+                    popup_components
+                        .iter()
+                        .map(|(component, coordinates)| (component, *coordinates)),
+                )
+                .collect::<Vec<_>>();
+            Some(render_components(&components))
         };
 
         self.pinned_fields
@@ -895,15 +1089,39 @@ pub fn dark_color_scheme(&self) -> bool {
             .map_or(false, |x| x.dark_color_scheme())
     }
 
-    /// Show a popup at the given position relative to the item
+    /// Like [`ItemRc::map_to_window`], but when the item is within a popup shown as a child window,
+    /// the position of that popup is taken into account.
+    fn map_to_window_including_popups(&self, item: &ItemRc, p: LogicalPoint) -> LogicalPoint {
+        let mut current = item.clone();
+        let mut result = p;
+        loop {
+            if let Some(popup) =
+                self.active_popups.borrow().iter().find(|p| current.is_root_item_of(&p.component))
+            {
+                if let PopupWindowLocation::ChildWindow(location) = popup.location {
+                    result += location.to_vector();
+                }
+                return result;
+            }
+            let Some(parent) = current.parent_item() else { return result };
+            result += parent.geometry().origin.to_vector();
+            current = parent;
+        }
+    }
+
+    /// Show a popup at the given position relative to the item, on top of the popups
+    /// that are already shown.
+    ///
+    /// Returns an identifier that can be passed to [`Self::close_popup()`] to close this popup.
     pub fn show_popup(
         &self,
         popup_componentrc: &ItemTreeRc,
         position: Point,
-        close_on_click: bool,
+        close_policy: PopupClosePolicy,
         parent_item: &ItemRc,
-    ) {
-        let position = parent_item.map_to_window(
+    ) -> NonZeroU32 {
+        let position = self.map_to_window_including_popups(
+            parent_item,
             parent_item.geometry().origin + LogicalPoint::from_untyped(position).to_vector(),
         );
         let popup_component = ItemTreeRc::borrow_pin(popup_componentrc);
@@ -933,6 +1151,17 @@ pub fn show_popup(
 
         let size = crate::lengths::LogicalSize::from_lengths(w, h);
 
+        // Popups that let the input through, like tooltips, are kept within the window
+        let position = if close_policy == PopupClosePolicy::CloseOnInput {
+            let window_size = self.window_adapter().size().to_logical(self.scale_factor());
+            LogicalPoint::new(
+                position.x.min(window_size.width - size.width).max(0.),
+                position.y.min(window_size.height - size.height).max(0.),
+            )
+        } else {
+            position
+        };
+
         if let Some(window_item) = ItemRef::downcast_pin(popup_root) {
             let width_property =
                 crate::items::WindowItem::FIELD_OFFSETS.width.apply_pin(window_item);
@@ -942,9 +1171,11 @@ pub fn show_popup(
             height_property.set(size.height_length());
         };
 
+        // Top-level popup windows grab the input, so popups that let it through are child windows
         let location = match self
             .window_adapter()
             .internal(crate::InternalToken)
+            .filter(|_| close_policy != PopupClosePolicy::CloseOnInput)
             .and_then(|x| x.create_popup(LogicalRect::new(position, size)))
         {
             None => {
@@ -958,42 +1189,85 @@ pub fn show_popup(
             }
         };
 
-        self.active_popup.replace(Some(PopupWindow {
+        let popup_id = self.next_popup_id.get();
+        self.next_popup_id.set(popup_id.checked_add(1).unwrap_or(NonZeroU32::MIN));
+        self.active_popups.borrow_mut().push(PopupWindow {
             location,
             component: popup_componentrc.clone(),
-            close_on_click,
-        }));
-    }
-
-    /// Removes any active popup.
-    /// TODO: this function should take a component ref as parameter, to close a specific popup - i.e. when popup menus create a hierarchy of popups.
-    pub fn close_popup(&self) {
-        if let Some(current_popup) = self.active_popup.replace(None) {
-            match current_popup.location {
-                PopupWindowLocation::ChildWindow(offset) => {
-                    // Refresh the area that was previously covered by the popup.
-                    let popup_region = crate::properties::evaluate_no_tracking(|| {
-                        let popup_component = ItemTreeRc::borrow_pin(&current_popup.component);
-                        popup_component.as_ref().item_geometry(0)
-                    })
-                    .translate(offset.to_vector());
-
-                    if !popup_region.is_empty() {
-                        let window_adapter = self.window_adapter();
-                        window_adapter.renderer().mark_dirty_region(popup_region.to_box2d());
-                        window_adapter.request_redraw();
-                    }
-                }
-                PopupWindowLocation::TopLevel(adapter) => {
-                    let _ = adapter.set_visible(false);
+            popup_id,
+            close_policy,
+        });
+        popup_id
+    }
+
+    /// Closes the popup with the given identifier, as well as all the popups that were shown
+    /// on top of it. Does nothing if the popup is already closed.
+    pub fn close_popup(&self, popup_id: NonZeroU32) {
+        let closed_popups = {
+            let mut active_popups = self.active_popups.borrow_mut();
+            match active_popups.iter().position(|popup| popup.popup_id == popup_id) {
+                Some(index) => active_popups.split_off(index),
+                None => return,
+            }
+        };
+        // Close the topmost popups first
+        for popup in closed_popups.into_iter().rev() {
+            self.discard_popup(popup);
+        }
+    }
+
+    /// Closes all the popups.
+    pub fn close_all_popups(&self) {
+        let closed_popups = core::mem::take(&mut *self.active_popups.borrow_mut());
+        for popup in closed_popups.into_iter().rev() {
+            self.discard_popup(popup);
+        }
+    }
+
+    /// Closes the topmost popup, unless its close policy is [`PopupClosePolicy::NoAutoClose`].
+    /// Returns true if a popup was closed.
+    pub fn close_top_popup_on_escape(&self) -> bool {
+        let popup_id = self
+            .active_popups
+            .borrow()
+            .last()
+            .filter(|popup| popup.close_policy != PopupClosePolicy::NoAutoClose)
+            .map(|popup| popup.popup_id);
+        if let Some(popup_id) = popup_id {
+            self.close_popup(popup_id);
+        }
+        popup_id.is_some()
+    }
+
+    fn discard_popup(&self, current_popup: PopupWindow) {
+        match current_popup.location {
+            PopupWindowLocation::ChildWindow(offset) => {
+                // Refresh the area that was previously covered by the popup.
+                let popup_region = crate::properties::evaluate_no_tracking(|| {
+                    let popup_component = ItemTreeRc::borrow_pin(&current_popup.component);
+                    popup_component.as_ref().item_geometry(0)
+                })
+                .translate(offset.to_vector());
+
+                if !popup_region.is_empty() {
+                    let window_adapter = self.window_adapter();
+                    window_adapter.renderer().mark_dirty_region(popup_region.into());
+                    window_adapter.request_redraw();
                 }
             }
+            PopupWindowLocation::TopLevel(adapter) => {
+                let _ = adapter.set_visible(false);
+            }
         }
     }
 
-    /// Returns true if the currently active popup is configured to close on click. None if there is no active popup.
-    pub fn close_popup_on_click(&self) -> bool {
-        self.active_popup.borrow().as_ref().map_or(false, |popup| popup.close_on_click)
+    /// Returns the identifier of the topmost popup if it is configured to close when clicked.
+    pub fn top_popup_to_close_on_click(&self) -> Option<NonZeroU32> {
+        self.active_popups
+            .borrow()
+            .last()
+            .filter(|popup| popup.close_policy == PopupClosePolicy::CloseOnClick)
+            .map(|popup| popup.popup_id)
     }
 
     /// Returns the scale factor set on the window, as provided by the windowing system.
@@ -1247,28 +1521,30 @@ pub enum GraphicsAPI {
         WindowInner::from_pub(window_adapter.window()).set_component(component)
     }
 
-    /// Show a popup.
+    /// Show a popup and return its identifier.
     #[no_mangle]
     pub unsafe extern "C" fn slint_windowrc_show_popup(
         handle: *const WindowAdapterRcOpaque,
         popup: &ItemTreeRc,
         position: crate::graphics::Point,
-        close_on_click: bool,
+        close_policy: PopupClosePolicy,
         parent_item: &ItemRc,
-    ) {
+    ) -> u32 {
         let window_adapter = &*(handle as *const Rc<dyn WindowAdapter>);
-        WindowInner::from_pub(window_adapter.window()).show_popup(
-            popup,
-            position,
-            close_on_click,
-            parent_item,
-        );
+        WindowInner::from_pub(window_adapter.window())
+            .show_popup(popup, position, close_policy, parent_item)
+            .get()
     }
-    /// Close the current popup
+    /// Close the popup with the given identifier, and the popups on top of it
     #[no_mangle]
-    pub unsafe extern "C" fn slint_windowrc_close_popup(handle: *const WindowAdapterRcOpaque) {
+    pub unsafe extern "C" fn slint_windowrc_close_popup(
+        handle: *const WindowAdapterRcOpaque,
+        popup_id: u32,
+    ) {
         let window_adapter = &*(handle as *const Rc<dyn WindowAdapter>);
-        WindowInner::from_pub(window_adapter.window()).close_popup();
+        if let Some(popup_id) = NonZeroU32::new(popup_id) {
+            WindowInner::from_pub(window_adapter.window()).close_popup(popup_id);
+        }
     }
 
     /// C binding to the set_rendering_notifier() API of Window
@@ -24,6 +24,7 @@
     Brush, Color, Image, LoadImageError, Rgb8Pixel, Rgba8Pixel, RgbaColor, SharedPixelBuffer,
 };
 use i_slint_core::items::*;
+pub use i_slint_core::items::{Date, Time};
 
 use crate::dynamic_item_tree::ErasedItemTreeBox;
 #[cfg(any(feature = "internal", target_arch = "wasm32"))]
@@ -324,6 +325,7 @@ macro_rules! handle_private {
 
 declare_value_struct_conversion!(struct i_slint_core::layout::LayoutInfo { min, max, min_percent, max_percent, preferred, stretch });
 declare_value_struct_conversion!(struct i_slint_core::graphics::Point { x, y, ..Default::default()});
+declare_value_struct_conversion!(struct i_slint_core::items::AnimationKeyframe { position, progress, easing });
 
 i_slint_common::for_each_builtin_structs!(declare_value_struct_conversion);
 
@@ -383,6 +385,23 @@ fn try_from(v: Value) -> Result<i_slint_core::animations::Instant, Self::Error>
     }
 }
 
+impl From<SharedVector<AnimationKeyframe>> for Value {
+    fn from(keyframes: SharedVector<AnimationKeyframe>) -> Self {
+        Value::Model(ModelRc::new(i_slint_core::model::VecModel::from(
+            keyframes.iter().cloned().map(Value::from).collect::<Vec<_>>(),
+        )))
+    }
+}
+impl TryFrom<Value> for SharedVector<AnimationKeyframe> {
+    type Error = ();
+    fn try_from(v: Value) -> Result<SharedVector<AnimationKeyframe>, Self::Error> {
+        match v {
+            Value::Model(model) => model.iter().map(AnimationKeyframe::try_from).collect(),
+            _ => Err(()),
+        }
+    }
+}
+
 impl From<()> for Value {
     #[inline]
     fn from(_: ()) -> Self {
@@ -11,7 +11,9 @@
 use i_slint_compiler::object_tree::ElementRc;
 use i_slint_compiler::{diagnostics::BuildDiagnostics, object_tree::PropertyDeclaration};
 use i_slint_compiler::{generator, object_tree, parser, CompilerConfiguration};
-use i_slint_core::accessibility::AccessibleStringProperty;
+use i_slint_core::accessibility::{
+    AccessibilityAction, AccessibleStringProperty, SupportedAccessibilityAction,
+};
 use i_slint_core::component_factory::ComponentFactory;
 use i_slint_core::item_tree::{
     IndexRange, ItemTree, ItemTreeRef, ItemTreeRefPin, ItemTreeVTable, ItemTreeWeak,
@@ -20,7 +22,9 @@
     ItemRc, ItemTreeNode, ItemVisitorRefMut, ItemVisitorVTable, ItemWeak, TraversalOrder,
     VisitChildrenResult,
 };
-use i_slint_core::items::{AccessibleRole, ItemRef, ItemVTable, PropertyAnimation};
+use i_slint_core::items::{
+    AccessibleRole, ItemRef, ItemVTable, PopupClosePolicy, PropertyAnimation,
+};
 use i_slint_core::layout::{BoxLayoutCellData, LayoutInfo, Orientation};
 use i_slint_core::lengths::{LogicalLength, LogicalRect};
 use i_slint_core::model::RepeatedItemTree;
@@ -34,7 +38,8 @@
 use once_cell::unsync::OnceCell;
 use std::collections::BTreeMap;
 use std::collections::HashMap;
-use std::{pin::Pin, rc::Rc};
+use std::num::NonZeroU32;
+use std::{cell::RefCell, pin::Pin, rc::Rc};
 
 pub struct ItemTreeBox<'id> {
     instance: InstanceBox<'id>,
@@ -236,6 +241,17 @@ fn accessible_string_property(
         self.borrow().as_ref().accessible_string_property(index, what, result)
     }
 
+    fn accessibility_action(self: Pin<&Self>, index: u32, action: &AccessibilityAction) {
+        self.borrow().as_ref().accessibility_action(index, action)
+    }
+
+    fn supported_accessibility_actions(
+        self: Pin<&Self>,
+        index: u32,
+    ) -> SupportedAccessibilityAction {
+        self.borrow().as_ref().supported_accessibility_actions(index)
+    }
+
     fn window_adapter(self: Pin<&Self>, do_create: bool, result: &mut Option<WindowAdapterRc>) {
         self.borrow().as_ref().window_adapter(do_create, result);
     }
@@ -269,6 +285,12 @@ pub(crate) struct ComponentExtraData {
     pub(crate) embedding_position: OnceCell<(ItemTreeWeak, u32)>,
     // resource id -> file path
     pub(crate) embedded_file_resources: OnceCell<HashMap<usize, String>>,
+    /// The trackers of the `changed` callbacks
+    pub(crate) change_trackers: OnceCell<Vec<i_slint_core::properties::ChangeTracker>>,
+    /// The timers of the `Timer` elements
+    pub(crate) timers: OnceCell<Vec<i_slint_core::timers::Timer>>,
+    /// The identifiers of the popups that are shown, by index in `popup_windows`
+    pub(crate) popup_ids: RefCell<HashMap<usize, NonZeroU32>>,
     #[cfg(target_arch = "wasm32")]
     pub(crate) canvas_id: OnceCell<String>,
 }
@@ -835,10 +857,15 @@ pub(crate) fn generate_item_tree<'id>(
                 rtti_for::<ImageItem>(),
                 rtti_for::<ClippedImage>(),
                 rtti_for::<Text>(),
+                rtti_for::<StyledText>(),
                 rtti_for::<Rectangle>(),
                 rtti_for::<BasicBorderRectangle>(),
                 rtti_for::<BorderRectangle>(),
                 rtti_for::<TouchArea>(),
+                rtti_for::<DragArea>(),
+                rtti_for::<DropArea>(),
+                rtti_for::<Shortcut>(),
+                rtti_for::<ContextMenuArea>(),
                 rtti_for::<FocusScope>(),
                 rtti_for::<Path>(),
                 rtti_for::<Flickable>(),
@@ -1163,6 +1190,8 @@ macro_rules! match_enum_type {
         item_geometry,
         accessible_role,
         accessible_string_property,
+        accessibility_action,
+        supported_accessibility_actions,
         window_adapter,
         drop_in_place,
         dealloc,
@@ -1591,6 +1620,40 @@ pub fn run_setup_code(&self) {
         generativity::make_guard!(guard);
         let compo_box = self.unerase(guard);
         let instance_ref = compo_box.borrow_instance();
+        let self_weak = instance_ref.self_weak().get().unwrap();
+        let mut change_trackers = Vec::new();
+        object_tree::recurse_elem_no_borrow(
+            &self.0.description.original.root_element,
+            &(),
+            &mut |elem, _| {
+                let elem = elem.borrow();
+                if elem.repeated.is_some() {
+                    return;
+                }
+                for (nr, handler) in &elem.change_callbacks {
+                    let tracker = i_slint_core::properties::ChangeTracker::default();
+                    let eval = make_binding_eval_closure(
+                        Expression::PropertyReference(nr.clone()),
+                        self_weak,
+                    );
+                    let handler = make_binding_eval_closure(handler.clone(), self_weak);
+                    tracker.init(
+                        (),
+                        move |_| eval(),
+                        move |_, _| {
+                            handler();
+                        },
+                    );
+                    change_trackers.push(tracker);
+                }
+            },
+        );
+        if !change_trackers.is_empty() {
+            let extra_data =
+                instance_ref.description.extra_data_offset.apply(instance_ref.as_ref());
+            extra_data.change_trackers.set(change_trackers).ok();
+        }
+        update_timers(instance_ref);
         for extra_init_code in self.0.description.original.init_code.borrow().iter() {
             eval::eval_expression(
                 extra_init_code,
@@ -1609,6 +1672,51 @@ fn from(inner: ItemTreeBox<'id>) -> Self {
     }
 }
 
+/// Start, stop or restart the timers of the component according to their properties
+pub(crate) fn update_timers(instance_ref: InstanceRef) {
+    let timers = instance_ref.description.original.timers.borrow();
+    if timers.is_empty() {
+        return;
+    }
+    let extra_data = instance_ref.description.extra_data_offset.apply(instance_ref.as_ref());
+    let actual_timers =
+        extra_data.timers.get_or_init(|| timers.iter().map(|_| Default::default()).collect());
+    for (timer, actual_timer) in timers.iter().zip(actual_timers) {
+        let load = |nr: &NamedReference| {
+            eval::load_property(instance_ref, &nr.element(), nr.name()).unwrap()
+        };
+        let running: bool = load(&timer.running).try_into().unwrap();
+        let interval: f64 = load(&timer.interval).try_into().unwrap();
+        if running && interval > 0. {
+            let interval = core::time::Duration::from_millis(interval as u64);
+            if !actual_timer.running() || actual_timer.interval() != interval {
+                let self_weak = instance_ref.self_weak().get().unwrap().clone();
+                let triggered = timer.triggered.clone();
+                actual_timer.start(
+                    i_slint_core::timers::TimerMode::Repeated,
+                    interval,
+                    move || {
+                        if let Some(self_rc) = self_weak.upgrade() {
+                            generativity::make_guard!(guard);
+                            let self_ = self_rc.unerase(guard);
+                            let instance_ref = self_.borrow_instance();
+                            eval::invoke_callback(
+                                eval::ComponentInstance::InstanceRef(instance_ref),
+                                &triggered.element(),
+                                triggered.name(),
+                                &[],
+                            )
+                            .unwrap();
+                        }
+                    },
+                );
+            }
+        } else {
+            actual_timer.stop();
+        }
+    }
+}
+
 pub fn get_repeater_by_name<'a, 'id>(
     instance_ref: InstanceRef<'a, '_>,
     name: &str,
@@ -1881,6 +1989,60 @@ extern "C" fn accessible_string_property(
     }
 }
 
+extern "C" fn accessibility_action(
+    component: ItemTreeRefPin,
+    item_index: u32,
+    action: &AccessibilityAction,
+) {
+    let perform = |prop_name: &str, args: &[Value]| {
+        generativity::make_guard!(guard);
+        let instance_ref = unsafe { InstanceRef::from_pin_ref(component, guard) };
+        let nr = instance_ref.description.original_elements[item_index as usize]
+            .borrow()
+            .accessibility_props
+            .0
+            .get(prop_name)
+            .cloned();
+        if let Some(nr) = nr {
+            let instance_ref = eval::ComponentInstance::InstanceRef(instance_ref);
+            crate::eval::invoke_callback(instance_ref, &nr.element(), nr.name(), args).unwrap();
+        }
+    };
+
+    match action {
+        AccessibilityAction::Default => perform("accessible-action-default", &[]),
+        AccessibilityAction::Decrement => perform("accessible-action-decrement", &[]),
+        AccessibilityAction::Increment => perform("accessible-action-increment", &[]),
+        AccessibilityAction::SetValue(a) => {
+            perform("accessible-action-set-value", &[Value::String(a.clone())])
+        }
+    };
+}
+
+extern "C" fn supported_accessibility_actions(
+    component: ItemTreeRefPin,
+    item_index: u32,
+) -> SupportedAccessibilityAction {
+    generativity::make_guard!(guard);
+    let instance_ref = unsafe { InstanceRef::from_pin_ref(component, guard) };
+    let val = instance_ref.description.original_elements[item_index as usize]
+        .borrow()
+        .accessibility_props
+        .0
+        .keys()
+        .filter_map(|x| x.strip_prefix("accessible-action-"))
+        .fold(SupportedAccessibilityAction::default(), |acc, value| {
+            acc | match value {
+                "default" => SupportedAccessibilityAction::Default,
+                "decrement" => SupportedAccessibilityAction::Decrement,
+                "increment" => SupportedAccessibilityAction::Increment,
+                "set-value" => SupportedAccessibilityAction::SetValue,
+                _ => unreachable!("Unknown accessibility action: {value}"),
+            }
+        });
+    val
+}
+
 extern "C" fn window_adapter(
     component: ItemTreeRefPin,
     do_create: bool,
@@ -2071,15 +2233,15 @@ pub fn toplevel_instance<'id2>(
     }
 }
 
-/// Show the popup at the given location
+/// Show the popup at the given location, and return its identifier
 pub fn show_popup(
     popup: &object_tree::PopupWindow,
     pos: i_slint_core::graphics::Point,
-    close_on_click: bool,
+    close_policy: PopupClosePolicy,
     parent_comp: ErasedItemTreeBoxWeak,
     parent_window_adapter: WindowAdapterRc,
     parent_item: &ItemRc,
-) {
+) -> NonZeroU32 {
     generativity::make_guard!(guard);
     // FIXME: we should compile once and keep the cached compiled component
     let compiled = generate_item_tree(&popup.component, guard);
@@ -2094,7 +2256,7 @@ pub fn show_popup(
     WindowInner::from_pub(parent_window_adapter.window()).show_popup(
         &vtable::VRc::into_dyn(inst),
         pos,
-        close_on_click,
+        close_policy,
         parent_item,
-    );
+    )
 }
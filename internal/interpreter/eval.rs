@@ -559,8 +559,11 @@ fn call_builtin_function(
                     .upgrade()
                     .unwrap();
                 let popup_list = parent_component.popup_windows.borrow();
-                let popup =
-                    popup_list.iter().find(|p| Rc::ptr_eq(&p.component, &pop_comp)).unwrap();
+                let (popup_index, popup) = popup_list
+                    .iter()
+                    .enumerate()
+                    .find(|(_, p)| Rc::ptr_eq(&p.component, &pop_comp))
+                    .unwrap();
                 let x = load_property_helper(
                     local_context.component_instance,
                     &popup.x.element(),
@@ -586,17 +589,34 @@ fn call_builtin_function(
                     parent_item_info.item_index(),
                 );
 
-                crate::dynamic_item_tree::show_popup(
+                let close_policy = Value::EnumerationValue(
+                    popup.close_policy.enumeration.name.clone(),
+                    popup.close_policy.to_string(),
+                )
+                .try_into()
+                .expect("Invalid internal enumeration representation for close policy");
+
+                // Showing the popup again replaces the previous instance
+                let popup_ids = &enclosing_component
+                    .description
+                    .extra_data_offset
+                    .apply(enclosing_component.as_ref())
+                    .popup_ids;
+                if let Some(popup_id) = popup_ids.borrow_mut().remove(&popup_index) {
+                    component.access_window(|window| window.close_popup(popup_id));
+                }
+                let popup_id = crate::dynamic_item_tree::show_popup(
                     popup,
                     i_slint_core::graphics::Point::new(
                         x.try_into().unwrap(),
                         y.try_into().unwrap(),
                     ),
-                    popup.close_on_click,
+                    close_policy,
                     component.self_weak().get().unwrap().clone(),
                     component.window_adapter(),
                     &parent_item,
                 );
+                popup_ids.borrow_mut().insert(popup_index, popup_id);
                 Value::Void
             } else {
                 panic!("internal error: argument to SetFocusItem must be an element")
@@ -610,9 +630,36 @@ fn call_builtin_function(
                 }
             };
 
-            component.access_window(|window| window.close_popup());
+            if let [Expression::ElementReference(popup_window)] = arguments {
+                let popup_window = popup_window.upgrade().unwrap();
+                let pop_comp = popup_window.borrow().enclosing_component.upgrade().unwrap();
+                let parent_element = pop_comp.parent_element.upgrade().unwrap();
+                let parent_component =
+                    parent_element.borrow().enclosing_component.upgrade().unwrap();
+                let popup_index = parent_component
+                    .popup_windows
+                    .borrow()
+                    .iter()
+                    .position(|p| Rc::ptr_eq(&p.component, &pop_comp))
+                    .unwrap();
 
-            Value::Void
+                generativity::make_guard!(guard);
+                let enclosing_component =
+                    enclosing_component_for_element(&parent_element, component, guard);
+                let popup_id = enclosing_component
+                    .description
+                    .extra_data_offset
+                    .apply(enclosing_component.as_ref())
+                    .popup_ids
+                    .borrow_mut()
+                    .remove(&popup_index);
+                if let Some(popup_id) = popup_id {
+                    component.access_window(|window| window.close_popup(popup_id));
+                }
+                Value::Void
+            } else {
+                panic!("internal error: argument to ClosePopupWindow must be an element")
+            }
         }
         BuiltinFunction::SetSelectionOffsets => {
             if arguments.len() != 3 {
@@ -877,6 +924,29 @@ fn call_builtin_function(
                 panic!("Cannot get the window from a global component")
             }
         },
+        BuiltinFunction::FirstDayOfWeek => {
+            Value::Number(corelib::date_time::first_day_of_week() as f64)
+        }
+        BuiltinFunction::FilterStrings => {
+            if arguments.len() != 2 {
+                panic!("internal error: incorrect argument count to FilterStrings")
+            }
+            let filter: SharedString =
+                eval_expression(&arguments[1], local_context).try_into().unwrap();
+            match eval_expression(&arguments[0], local_context) {
+                Value::Model(model) => {
+                    let rows = corelib::model::filter_strings(&model, &filter);
+                    Value::Model(ModelRc::new(corelib::model::SharedVectorModel::from(
+                        rows.iter()
+                            .map(|row| Value::Number(row as f64))
+                            .collect::<SharedVector<_>>(),
+                    )))
+                }
+                _ => {
+                    panic!("First argument not an array");
+                }
+            }
+        }
         BuiltinFunction::TextInputFocused => match local_context.component_instance {
             ComponentInstance::InstanceRef(component) => {
                 Value::Bool(component.access_window(|window| window.text_input_focused()) as _)
@@ -1009,6 +1079,15 @@ fn from_index(&self, index: usize) -> Option<SharedString> {
                 &SharedString::try_from(eval_expression(&arguments[5], local_context)).unwrap(),
             ))
         }
+        BuiltinFunction::UpdateTimers => match local_context.component_instance {
+            ComponentInstance::InstanceRef(component) => {
+                crate::dynamic_item_tree::update_timers(component);
+                Value::Void
+            }
+            ComponentInstance::GlobalComponent(_) => {
+                panic!("Cannot have timers in a global component")
+            }
+        },
     }
 }
 
@@ -6,7 +6,9 @@
 use crate::Value;
 use i_slint_compiler::expression_tree::Expression;
 use i_slint_compiler::langtype::Type;
-use i_slint_compiler::layout::{Layout, LayoutConstraints, LayoutGeometry, Orientation};
+use i_slint_compiler::layout::{
+    Layout, LayoutConstraints, LayoutGeometry, LayoutItem, Orientation,
+};
 use i_slint_compiler::namedreference::NamedReference;
 use i_slint_compiler::object_tree::ElementRc;
 use i_slint_core::items::DialogButtonRole;
@@ -67,6 +69,15 @@ pub(crate) fn compute_layout_info(
             }
             .into()
         }
+        Layout::FlexboxLayout(flexbox_layout) => flexbox_layout_data(
+            flexbox_layout,
+            orientation,
+            false,
+            component,
+            &expr_eval,
+            |data, _| core_layout::flexbox_layout_info(data, to_runtime(orientation)),
+        )
+        .into(),
     }
 }
 
@@ -135,6 +146,17 @@ pub(crate) fn solve_layout(
             )
             .into()
         }
+        Layout::FlexboxLayout(flexbox_layout) => flexbox_layout_data(
+            flexbox_layout,
+            orientation,
+            true,
+            component,
+            &expr_eval,
+            |data, repeated_indices| {
+                core_layout::solve_flexbox_layout(data, to_runtime(orientation), repeated_indices)
+            },
+        )
+        .into(),
     }
 }
 
@@ -185,11 +207,24 @@ fn box_layout_data(
     orientation: Orientation,
     component: InstanceRef,
     expr_eval: &impl Fn(&NamedReference) -> f32,
-    mut repeater_indices: Option<&mut Vec<u32>>,
+    repeater_indices: Option<&mut Vec<u32>>,
 ) -> (Vec<core_layout::BoxLayoutCellData>, i_slint_core::items::LayoutAlignment) {
+    let cells =
+        box_layout_cells(&box_layout.elems, orientation, component, expr_eval, repeater_indices);
+    let alignment = load_enum_property(component, &box_layout.geometry.alignment);
+    (cells, alignment)
+}
+
+fn box_layout_cells(
+    elems: &[LayoutItem],
+    orientation: Orientation,
+    component: InstanceRef,
+    expr_eval: &impl Fn(&NamedReference) -> f32,
+    mut repeater_indices: Option<&mut Vec<u32>>,
+) -> Vec<core_layout::BoxLayoutCellData> {
     let window_adapter = component.window_adapter();
-    let mut cells = Vec::with_capacity(box_layout.elems.len());
-    for cell in &box_layout.elems {
+    let mut cells = Vec::with_capacity(elems.len());
+    for cell in elems {
         if cell.element.borrow().repeated.is_some() {
             generativity::make_guard!(guard);
             let rep = crate::dynamic_item_tree::get_repeater_by_name(
@@ -229,18 +264,79 @@ fn box_layout_data(
             cells.push(core_layout::BoxLayoutCellData { constraint: layout_info });
         }
     }
-    let alignment = box_layout
-        .geometry
-        .alignment
-        .as_ref()
+    cells
+}
+
+/// Return the value of an enum property of a layout, or the default value if it isn't set
+fn load_enum_property<T: TryFrom<Value> + Default>(
+    component: InstanceRef,
+    nr: &Option<NamedReference>,
+) -> T {
+    nr.as_ref()
         .map(|nr| {
             eval::load_property(component, &nr.element(), nr.name())
                 .unwrap()
                 .try_into()
                 .unwrap_or_default()
         })
-        .unwrap_or_default();
-    (cells, alignment)
+        .unwrap_or_default()
+}
+
+/// Call `f` with the data to solve a FlexboxLayout (if `solve` is true) or to compute its layout info
+/// in the given orientation, and the repeated indices of the cells.
+/// Like in the generated code, what's only needed to know how the items wrap is only computed
+/// across the main axis.
+fn flexbox_layout_data<R>(
+    flexbox_layout: &i_slint_compiler::layout::FlexboxLayout,
+    orientation: Orientation,
+    solve: bool,
+    component: InstanceRef,
+    expr_eval: &impl Fn(&NamedReference) -> f32,
+    f: impl FnOnce(&core_layout::FlexboxLayoutData, Slice<u32>) -> R,
+) -> R {
+    let direction = flexbox_layout.direction;
+    let mut repeated_indices = Vec::new();
+    let mut cells = |o: Orientation| {
+        if o == orientation || o == direction {
+            let ri = (o == orientation).then_some(&mut repeated_indices);
+            box_layout_cells(&flexbox_layout.elems, o, component, expr_eval, ri)
+        } else {
+            Vec::new()
+        }
+    };
+    let cells_horizontal = cells(Orientation::Horizontal);
+    let cells_vertical = cells(Orientation::Vertical);
+    let size = |o: Orientation| {
+        let needed = if solve {
+            o == orientation || o == direction
+        } else {
+            o == direction && orientation != direction
+        };
+        let size_ref = flexbox_layout.geometry.rect.size_reference(o);
+        size_ref.filter(|_| needed).map_or(0., expr_eval)
+    };
+    let (padding_horizontal, spacing_horizontal) =
+        padding_and_spacing(&flexbox_layout.geometry, Orientation::Horizontal, expr_eval);
+    let (padding_vertical, spacing_vertical) =
+        padding_and_spacing(&flexbox_layout.geometry, Orientation::Vertical, expr_eval);
+    let data = core_layout::FlexboxLayoutData {
+        width: size(Orientation::Horizontal),
+        height: size(Orientation::Vertical),
+        spacing_horizontal,
+        spacing_vertical,
+        padding_horizontal,
+        padding_vertical,
+        direction: match direction {
+            Orientation::Horizontal => i_slint_core::items::FlexDirection::Row,
+            Orientation::Vertical => i_slint_core::items::FlexDirection::Column,
+        },
+        wrap: load_enum_property(component, &flexbox_layout.wrap),
+        justify_content: load_enum_property(component, &flexbox_layout.geometry.alignment),
+        align_items: load_enum_property(component, &flexbox_layout.align_items),
+        cells_horizontal: Slice::from(cells_horizontal.as_slice()),
+        cells_vertical: Slice::from(cells_vertical.as_slice()),
+    };
+    f(&data, Slice::from(repeated_indices.as_slice()))
 }
 
 pub(crate) fn fill_layout_info_constraints(
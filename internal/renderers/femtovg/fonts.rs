@@ -8,9 +8,13 @@
 use i_slint_common::sharedfontdb::{self, fontdb};
 use i_slint_core::graphics::euclid;
 use i_slint_core::graphics::FontRequest;
-use i_slint_core::items::{TextHorizontalAlignment, TextOverflow, TextVerticalAlignment, TextWrap};
+use i_slint_core::items::{
+    TextDirection, TextHorizontalAlignment, TextOverflow, TextVerticalAlignment, TextWrap,
+};
 use i_slint_core::lengths::{LogicalLength, LogicalSize, ScaleFactor, SizeLengths};
+use i_slint_core::textlayout::BidiParagraphs;
 use i_slint_core::{SharedString, SharedVector};
+use std::borrow::Cow;
 use std::cell::RefCell;
 use std::collections::{HashMap, HashSet};
 
@@ -543,9 +547,41 @@ fn check_and_update_script_coverage(
     }
 }
 
+/// femtovg always lays out a line with a left-to-right base direction. Starting the line with this
+/// right-to-left embedding lays it out like a right-to-left paragraph.
+const RIGHT_TO_LEFT_EMBEDDING: &str = "\u{202B}";
+
+/// Returns the text to pass to femtovg to draw a line of a paragraph laid out from right to left if `rtl`
+/// is true, or from left to right otherwise.
+pub(crate) fn text_in_direction(text: &str, rtl: bool) -> Cow<'_, str> {
+    if rtl {
+        format!("{RIGHT_TO_LEFT_EMBEDDING}{text}").into()
+    } else {
+        text.into()
+    }
+}
+
+/// Returns the x position of the cursor before the character at `byte_offset` in a line laid out by
+/// [`layout_text_lines`], or at the end of the line if no glyph starts at that offset.
+pub(crate) fn cursor_x_in_line(
+    metrics: &femtovg::TextMetrics,
+    byte_offset: usize,
+    rtl: bool,
+) -> f32 {
+    match metrics.glyphs.iter().find(|glyph| glyph.byte_index == byte_offset) {
+        // The start of a glyph laid out from right to left is its right edge
+        Some(glyph) if rtl => glyph.x - glyph.bearing_x + glyph.advance_x,
+        Some(glyph) => glyph.x,
+        None if rtl => 0.,
+        None => metrics.width(),
+    }
+}
+
 /// Layout the given string in lines, and call the `layout_line` callback with the line to draw at position y.
-/// The signature of the `layout_line` function is: `(text, pos, start_index, line_metrics)`.
-/// start index is the starting byte of the text in the string.
+/// The signature of the `layout_line` function is: `(text, pos, start_index, line_metrics, rtl)`.
+/// start index is the starting byte of the text in the string. `rtl` is true when the paragraph of the line
+/// is laid out from right to left, in which case the text must be drawn with [`text_in_direction`]. The
+/// glyphs of the metrics are in visual order, and their byte index is relative to the line.
 /// Returns the y coordinate of where to place the cursor if it is at the end of the text
 pub(crate) fn layout_text_lines(
     string: &str,
@@ -554,9 +590,10 @@ pub(crate) fn layout_text_lines(
     (horizontal_alignment, vertical_alignment): (TextHorizontalAlignment, TextVerticalAlignment),
     wrap: TextWrap,
     overflow: TextOverflow,
+    text_direction: TextDirection,
     single_line: bool,
     paint: &femtovg::Paint,
-    mut layout_line: impl FnMut(&str, PhysicalPoint, usize, &femtovg::TextMetrics),
+    mut layout_line: impl FnMut(&str, PhysicalPoint, usize, &femtovg::TextMetrics, bool),
 ) -> PhysicalLength {
     let wrap = wrap == TextWrap::WordWrap;
     let elide = overflow == TextOverflow::Elide;
@@ -593,19 +630,38 @@ pub(crate) fn layout_text_lines(
         }
     };
 
-    let mut process_line =
-        |text: &str, y: PhysicalLength, start: usize, line_metrics: &femtovg::TextMetrics| {
-            let x = match horizontal_alignment {
-                TextHorizontalAlignment::Left => PhysicalLength::default(),
-                TextHorizontalAlignment::Center => {
-                    max_width / 2. - max_width.min(PhysicalLength::new(line_metrics.width())) / 2.
-                }
-                TextHorizontalAlignment::Right => {
-                    max_width - max_width.min(PhysicalLength::new(line_metrics.width()))
-                }
-            };
-            layout_line(text, PhysicalPoint::from_lengths(x, y), start, line_metrics);
+    let bidi = BidiParagraphs::new(string, text_direction);
+
+    let mut process_line = |text: &str,
+                            y: PhysicalLength,
+                            start: usize,
+                            line_metrics: &femtovg::TextMetrics| {
+        let x = match horizontal_alignment {
+            TextHorizontalAlignment::Left => PhysicalLength::default(),
+            TextHorizontalAlignment::Center => {
+                max_width / 2. - max_width.min(PhysicalLength::new(line_metrics.width())) / 2.
+            }
+            TextHorizontalAlignment::Right => {
+                max_width - max_width.min(PhysicalLength::new(line_metrics.width()))
+            }
         };
+        let pos = PhysicalPoint::from_lengths(x, y);
+        if bidi.is_rtl_paragraph(start) {
+            let mut metrics =
+                text_context.measure_text(0., 0., text_in_direction(text, true), paint).unwrap();
+            metrics.glyphs.retain_mut(|glyph| {
+                let Some(byte_index) = glyph.byte_index.checked_sub(RIGHT_TO_LEFT_EMBEDDING.len())
+                else {
+                    return false;
+                };
+                glyph.byte_index = byte_index;
+                true
+            });
+            layout_line(text, pos, start, &metrics, true);
+        } else {
+            layout_line(text, pos, start, line_metrics, false);
+        }
+    };
 
     let baseline_y = match vertical_alignment {
         TextVerticalAlignment::Top => PhysicalLength::default(),
@@ -15,8 +15,8 @@
     CachedRenderingData, ItemCache, ItemRenderer, RenderBorderRectangle, RenderImage,
 };
 use i_slint_core::items::{
-    self, Clip, FillRule, ImageRendering, ItemRc, Layer, Opacity, RenderingResult,
-    TextHorizontalAlignment,
+    self, Clip, FillRule, ImageRendering, ItemRc, Layer, Opacity, RenderingResult, TextDirection,
+    TextHorizontalAlignment, TextOverflow, TextVerticalAlignment, TextWrap,
 };
 use i_slint_core::lengths::{
     LogicalBorderRadius, LogicalLength, LogicalPoint, LogicalRect, LogicalSize, LogicalVector,
@@ -331,14 +331,57 @@ fn draw_text(&mut self, text: Pin<&items::Text>, _: &ItemRc, size: LogicalSize)
             (text.horizontal_alignment(), text.vertical_alignment()),
             text.wrap(),
             text.overflow(),
+            text.text_direction(),
             false,
             &paint,
-            |to_draw, pos, _, _| {
-                canvas.fill_text(pos.x, pos.y, to_draw.trim_end(), &paint).unwrap();
+            |to_draw, pos, _, _, rtl| {
+                let to_draw = fonts::text_in_direction(to_draw.trim_end(), rtl);
+                canvas.fill_text(pos.x, pos.y, to_draw, &paint).unwrap();
             },
         );
     }
 
+    fn draw_styled_text(&mut self, text: Pin<&items::StyledText>, _: &ItemRc, size: LogicalSize) {
+        if size.width <= 0. || size.height <= 0. || self.global_alpha_transparent() {
+            return;
+        }
+
+        let window_adapter = WindowInner::from_pub(self.window).window_adapter();
+        for run in text.render_runs(size, &window_adapter) {
+            let rect = run.rect * self.scale_factor;
+            let font = fonts::FONT_CACHE.with(|cache| {
+                cache.borrow_mut().font(run.font_request.clone(), self.scale_factor, &run.text)
+            });
+            let paint = match self.brush_to_paint(run.brush.clone(), &rect_to_path(rect)) {
+                Some(paint) => font.init_paint(PhysicalLength::default(), paint),
+                None => continue,
+            };
+
+            let mut canvas = self.canvas.borrow_mut();
+            for decoration in run.decorations.iter() {
+                canvas.fill_path(&rect_to_path(*decoration * self.scale_factor), &paint);
+            }
+            fonts::layout_text_lines(
+                &run.text,
+                &font,
+                // The run was measured to fit, don't let rounding errors drop it
+                PhysicalSize::new(rect.width(), f32::MAX),
+                (TextHorizontalAlignment::Left, TextVerticalAlignment::Top),
+                TextWrap::NoWrap,
+                TextOverflow::Clip,
+                TextDirection::Auto,
+                true,
+                &paint,
+                |to_draw, pos, _, _, rtl| {
+                    let to_draw = fonts::text_in_direction(to_draw, rtl);
+                    canvas
+                        .fill_text(rect.origin.x + pos.x, rect.origin.y + pos.y, to_draw, &paint)
+                        .unwrap();
+                },
+            );
+        }
+    }
+
     fn draw_text_input(
         &mut self,
         text_input: Pin<&items::TextInput>,
@@ -398,15 +441,57 @@ fn draw_text_input(
             (text_input.horizontal_alignment(), text_input.vertical_alignment()),
             text_input.wrap(),
             items::TextOverflow::Clip,
+            text_input.text_direction(),
             text_input.single_line(),
             &paint,
-            |to_draw, pos, start, metrics| {
+            |to_draw, pos, start, metrics, rtl| {
                 let range = start..(start + to_draw.len());
-                if min_select != max_select
+                let has_selection = min_select != max_select
                     && (range.contains(&min_select)
                         || range.contains(&max_select)
-                        || (min_select..max_select).contains(&start))
-                {
+                        || (min_select..max_select).contains(&start));
+                if has_selection && rtl {
+                    // The selection may not be contiguous on screen, highlight the span covering all
+                    // selected glyphs and draw the text of that span with the selection color.
+                    let selected = min_select.saturating_sub(start)..max_select - start;
+                    let (selection_start_x, selection_end_x) = metrics
+                        .glyphs
+                        .iter()
+                        .filter(|glyph| selected.contains(&glyph.byte_index))
+                        .fold((f32::MAX, f32::MIN), |(start_x, end_x), glyph| {
+                            let x = glyph.x - glyph.bearing_x;
+                            (start_x.min(x), end_x.max(x + glyph.advance_x))
+                        });
+                    let to_draw = fonts::text_in_direction(to_draw.trim_end(), true);
+                    canvas.fill_text(pos.x, pos.y, &to_draw, &paint).unwrap();
+                    if selection_start_x < selection_end_x {
+                        let selection_rect = PhysicalRect::new(
+                            pos + euclid::vec2(selection_start_x, 0.),
+                            PhysicalSize::from_lengths(
+                                PhysicalLength::new(selection_end_x - selection_start_x),
+                                font_height,
+                            ),
+                        );
+                        canvas.fill_path(
+                            &rect_to_path(selection_rect),
+                            &femtovg::Paint::color(to_femtovg_color(
+                                &text_input.selection_background_color(),
+                            )),
+                        );
+                        let mut selected_paint = paint.clone();
+                        selected_paint
+                            .set_color(to_femtovg_color(&text_input.selection_foreground_color()));
+                        canvas.save();
+                        canvas.intersect_scissor(
+                            selection_rect.origin.x,
+                            selection_rect.origin.y,
+                            selection_rect.size.width,
+                            selection_rect.size.height,
+                        );
+                        canvas.fill_text(pos.x, pos.y, &to_draw, &selected_paint).unwrap();
+                        canvas.restore();
+                    }
+                } else if has_selection {
                     let mut selection_start_x = PhysicalLength::default();
                     let mut selection_end_x = PhysicalLength::default();
                     let mut after_selection_x = PhysicalLength::default();
@@ -479,7 +564,8 @@ fn draw_text_input(
                         .unwrap();
                 } else {
                     // no selection on this line
-                    canvas.fill_text(pos.x, pos.y, to_draw.trim_end(), &paint).unwrap();
+                    let to_draw = fonts::text_in_direction(to_draw.trim_end(), rtl);
+                    canvas.fill_text(pos.x, pos.y, to_draw, &paint).unwrap();
                 };
                 if cursor_visible
                     && (range.contains(&cursor_pos)
@@ -487,19 +573,11 @@ fn draw_text_input(
                             && cursor_pos == text.len()
                             && !text.ends_with('\n')))
                 {
-                    let cursor_x = PhysicalLength::new(
-                        metrics
-                            .glyphs
-                            .iter()
-                            .find_map(|glyph| {
-                                if glyph.byte_index == (cursor_pos - start) {
-                                    Some(glyph.x)
-                                } else {
-                                    None
-                                }
-                            })
-                            .unwrap_or_else(|| metrics.width()),
-                    );
+                    let cursor_x = PhysicalLength::new(fonts::cursor_x_in_line(
+                        metrics,
+                        cursor_pos - start,
+                        rtl,
+                    ));
                     cursor_point = Some(PhysicalPoint::from_lengths(
                         pos.x_length() + cursor_x,
                         pos.y_length(),
@@ -1041,6 +1119,18 @@ fn rotate(&mut self, angle_in_degrees: f32) {
         *clip = LogicalRect::new(origin, (end - origin).into());
     }
 
+    fn scale(&mut self, x_factor: f32, y_factor: f32) {
+        let clip = &mut self.state.last_mut().unwrap().scissor;
+        if !x_factor.is_normal() || !y_factor.is_normal() {
+            // Scaled down to nothing: the inverse of the scale is not defined and nothing is visible
+            *clip = LogicalRect::default();
+            return;
+        }
+        self.canvas.borrow_mut().scale(x_factor, y_factor);
+        let scaled = clip.to_box2d().scale(1. / x_factor, 1. / y_factor);
+        *clip = LogicalRect::from_points([scaled.min, scaled.max]);
+    }
+
     fn apply_opacity(&mut self, opacity: f32) {
         let state = &mut self.state.last_mut().unwrap().global_alpha;
         *state *= opacity;
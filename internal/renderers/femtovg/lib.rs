@@ -405,19 +405,28 @@ fn text_input_byte_offset_for_position(
             (text_input.horizontal_alignment(), text_input.vertical_alignment()),
             text_input.wrap(),
             i_slint_core::items::TextOverflow::Clip,
+            text_input.text_direction(),
             text_input.single_line(),
             &paint,
-            |line_text, line_pos, start, metrics| {
+            |line_text, line_pos, start, metrics, rtl| {
                 if (line_pos.y..(line_pos.y + font_height)).contains(&pos.y) {
                     let mut current_x = 0.;
                     for glyph in &metrics.glyphs {
                         if line_pos.x + current_x + glyph.advance_x / 2. >= pos.x {
+                            // Within a right-to-left line, the glyph starts at its right edge
                             result = start + glyph.byte_index;
+                            if rtl {
+                                result += line_text[glyph.byte_index..]
+                                    .chars()
+                                    .next()
+                                    .map_or(0, char::len_utf8);
+                            }
                             return;
                         }
                         current_x += glyph.advance_x;
                     }
-                    result = start + line_text.trim_end().len();
+                    // Past the end of the line on screen, which is its start if it is right-to-left
+                    result = if rtl { start } else { start + line_text.trim_end().len() };
                 }
             },
         );
@@ -458,15 +467,15 @@ fn text_input_cursor_rect_for_byte_offset(
             (text_input.horizontal_alignment(), text_input.vertical_alignment()),
             text_input.wrap(),
             i_slint_core::items::TextOverflow::Clip,
+            text_input.text_direction(),
             text_input.single_line(),
             &paint,
-            |line_text, line_pos, start, metrics| {
+            |line_text, line_pos, start, metrics, rtl| {
                 if (start..=(start + line_text.len())).contains(&byte_offset) {
-                    for glyph in &metrics.glyphs {
-                        if glyph.byte_index == (byte_offset - start) {
-                            result = line_pos + PhysicalPoint::new(glyph.x, 0.0).to_vector();
-                            return;
-                        }
+                    if rtl || metrics.glyphs.iter().any(|g| g.byte_index == byte_offset - start) {
+                        let x = fonts::cursor_x_in_line(metrics, byte_offset - start, rtl);
+                        result = line_pos + euclid::vec2(x, 0.0);
+                        return;
                     }
                     if let Some(last) = metrics.glyphs.last() {
                         if line_text.ends_with('\n') {
@@ -467,12 +467,58 @@ fn draw_text(
             text.vertical_alignment(),
             text.wrap(),
             text.overflow(),
+            text.text_direction(),
             None,
         );
 
         layout.paint(&mut self.canvas, to_skia_point(layout_top_left));
     }
 
+    fn draw_styled_text(
+        &mut self,
+        text: std::pin::Pin<&i_slint_core::items::StyledText>,
+        _self_rc: &i_slint_core::items::ItemRc,
+        size: LogicalSize,
+    ) {
+        if size.width <= 0. || size.height <= 0. {
+            return;
+        }
+
+        let window_adapter = WindowInner::from_pub(self.window).window_adapter();
+        for run in text.render_runs(size, &window_adapter) {
+            let rect = run.rect * self.scale_factor;
+            let paint = match self.brush_to_paint(
+                run.brush.clone(),
+                rect.width_length(),
+                rect.height_length(),
+            ) {
+                Some(paint) => paint,
+                None => continue,
+            };
+            for decoration in run.decorations.iter() {
+                self.canvas.draw_rect(to_skia_rect(&(*decoration * self.scale_factor)), &paint);
+            }
+
+            let mut text_style = skia_safe::textlayout::TextStyle::new();
+            text_style.set_foreground_paint(&paint);
+            let (layout, _) = super::textlayout::create_layout(
+                run.font_request,
+                self.scale_factor,
+                &run.text,
+                Some(text_style),
+                None,
+                rect.height_length(),
+                i_slint_core::items::TextHorizontalAlignment::Left,
+                i_slint_core::items::TextVerticalAlignment::Top,
+                i_slint_core::items::TextWrap::NoWrap,
+                i_slint_core::items::TextOverflow::Clip,
+                i_slint_core::items::TextDirection::Auto,
+                None,
+            );
+            layout.paint(&mut self.canvas, to_skia_point(rect.origin));
+        }
+    }
+
     fn draw_text_input(
         &mut self,
         text_input: std::pin::Pin<&i_slint_core::items::TextInput>,
@@ -528,6 +574,7 @@ fn draw_text_input(
             text_input.vertical_alignment(),
             text_input.wrap(),
             i_slint_core::items::TextOverflow::Clip,
+            text_input.text_direction(),
             selection.as_ref(),
         );
 
@@ -739,6 +786,10 @@ fn rotate(&mut self, angle_in_degrees: f32) {
         self.canvas.rotate(angle_in_degrees, None);
     }
 
+    fn scale(&mut self, x_factor: f32, y_factor: f32) {
+        self.canvas.scale((x_factor, y_factor));
+    }
+
     fn apply_opacity(&mut self, opacity: f32) {
         self.current_state.alpha *= opacity;
     }
@@ -356,6 +356,7 @@ fn text_size(
             Default::default(),
             Default::default(),
             Default::default(),
+            Default::default(),
             None,
         );
 
@@ -391,6 +392,7 @@ fn text_input_byte_offset_for_position(
             text_input.vertical_alignment(),
             text_input.wrap(),
             i_slint_core::items::TextOverflow::Clip,
+            text_input.text_direction(),
             None,
         );
 
@@ -439,6 +441,7 @@ fn text_input_cursor_rect_for_byte_offset(
             text_input.vertical_alignment(),
             text_input.wrap(),
             i_slint_core::items::TextOverflow::Clip,
+            text_input.text_direction(),
             None,
         );
 
@@ -63,6 +63,7 @@ pub fn create_layout(
     v_align: TextVerticalAlignment,
     wrap: items::TextWrap,
     overflow: items::TextOverflow,
+    text_direction: items::TextDirection,
     selection: Option<&Selection>,
 ) -> (skia_safe::textlayout::Paragraph, PhysicalPoint) {
     let mut text_style = text_style.unwrap_or_default();
@@ -104,6 +105,15 @@ pub fn create_layout(
         items::TextHorizontalAlignment::Right => skia_safe::textlayout::TextAlign::Right,
     });
 
+    // Skia doesn't detect the base direction, use the one of the first paragraph
+    style.set_text_direction(
+        if i_slint_core::textlayout::is_rtl_paragraph(text, 0, text_direction) {
+            skia_safe::textlayout::TextDirection::RTL
+        } else {
+            skia_safe::textlayout::TextDirection::LTR
+        },
+    );
+
     style.set_text_style(&text_style);
 
     let mut builder = FONT_CACHE.with(|font_cache| {
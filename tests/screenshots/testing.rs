@@ -9,10 +9,10 @@
 
 use i_slint_core::{
     graphics::{
-        euclid::{self, Box2D, Point2D},
+        euclid::{self, Point2D, Size2D},
         IntRect, Rgb8Pixel, SharedPixelBuffer,
     },
-    item_rendering::DirtyRegion,
+    lengths::LogicalRect,
     platform::PlatformError,
     renderer::RendererSealed,
     software_renderer::{LineBufferProvider, MinimalSoftwareWindow, RenderingRotation},
@@ -74,10 +74,9 @@ pub fn screenshot(
     // render to buffer
     window.request_redraw();
     window.draw_if_needed(|renderer| {
-        renderer.mark_dirty_region(Box2D::new(
-            Point2D::new(0., 0.),
-            Point2D::new(width as f32, height as f32),
-        ));
+        renderer.mark_dirty_region(
+            LogicalRect::new(Point2D::new(0., 0.), Size2D::new(width as f32, height as f32)).into(),
+        );
         renderer.set_rendering_rotation(rotated);
         renderer.render(buffer.make_mut_slice(), width as usize);
         renderer.set_rendering_rotation(RenderingRotation::NoRotation);
@@ -308,12 +307,15 @@ pub fn screenshot_render_by_line(
 
     window.draw_if_needed(|renderer| {
         match region {
-            None => renderer.mark_dirty_region(Box2D::new(
-                euclid::point2(0., 0.),
-                euclid::point2(buffer.width() as f32, buffer.height() as f32),
-            )),
+            None => renderer.mark_dirty_region(
+                LogicalRect::new(
+                    euclid::point2(0., 0.),
+                    euclid::size2(buffer.width() as f32, buffer.height() as f32),
+                )
+                .into(),
+            ),
             Some(r) => renderer.mark_dirty_region(
-                DirtyRegion::from_untyped(&r.to_box2d().cast()) / window.scale_factor(),
+                (LogicalRect::from_untyped(&r.cast()) / window.scale_factor()).into(),
             ),
         }
         renderer.render_by_line(TestingLineBuffer {
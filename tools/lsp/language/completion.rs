@@ -1071,6 +1071,9 @@ fn animation_completion() {
         res.iter().find(|ci| ci.label == "duration").unwrap();
         res.iter().find(|ci| ci.label == "iteration-count").unwrap();
         res.iter().find(|ci| ci.label == "easing").unwrap();
+        res.iter().find(|ci| ci.label == "direction").unwrap();
+        res.iter().find(|ci| ci.label == "fill-mode").unwrap();
+        res.iter().find(|ci| ci.label == "keyframes").unwrap();
     }
 
     #[test]
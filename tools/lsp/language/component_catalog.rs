@@ -16,16 +16,21 @@
 
 fn builtin_component_info(name: &str, fills_parent: bool) -> ComponentInformation {
     let (category, is_layout) = match name {
-        "GridLayout" | "HorizontalLayout" | "VerticalLayout" => ("Layout", true),
+        "GridLayout" | "HorizontalLayout" | "VerticalLayout" | "FlexboxLayout" => ("Layout", true),
         "Dialog" | "Window" | "PopupWindow" => ("Window Management", false),
-        "FocusScope" | "TouchArea" => ("Event Handling", false),
-        "Text" => ("Text Handling", false),
+        "FocusScope" | "TouchArea" | "DragArea" | "DropArea" | "Shortcut" => {
+            ("Event Handling", false)
+        }
+        "Text" | "StyledText" => ("Text Handling", false),
         _ => ("Primitives", false),
     };
 
     let default_properties = match name {
-        "Text" | "TextInput" => vec![PropertyChange::new("text", format!("\"{name}\""))],
+        "Text" | "StyledText" | "TextInput" => {
+            vec![PropertyChange::new("text", format!("\"{name}\""))]
+        }
         "Image" => vec![PropertyChange::new("source", "@image-url(\"EDIT_ME.png\")".to_string())],
+        "Timer" => vec![PropertyChange::new("interval", "1s".to_string())],
         _ => vec![],
     };
 
@@ -69,13 +69,17 @@ fn get_reserved_properties<'a>(
     group: &'a str,
     properties: &'a [(&'a str, Type)],
 ) -> impl Iterator<Item = PropertyInformation> + 'a {
-    properties.iter().map(|p| PropertyInformation {
+    properties.iter().map(|p| get_reserved_property(group, p))
+}
+
+fn get_reserved_property(group: &str, p: &(&str, Type)) -> PropertyInformation {
+    PropertyInformation {
         name: p.0.to_string(),
         type_name: format!("{}", p.1),
         declared_at: None,
         defined_at: None,
         group: group.to_string(),
-    })
+    }
 }
 
 fn property_is_editable(property: &PropertyDeclaration, is_local_element: bool) -> bool {
@@ -334,13 +338,6 @@ fn get_properties(element: &common::ElementRcNode) -> Vec<PropertyInformation> {
                     group: String::new(),
                 });
 
-                if b.name == "Image" {
-                    result.extend(get_reserved_properties(
-                        "rotation",
-                        i_slint_compiler::typeregister::RESERVED_ROTATION_PROPERTIES,
-                    ));
-                }
-
                 if b.name == "Rectangle" {
                     result.extend(get_reserved_properties(
                         "drop-shadow",
@@ -372,6 +369,10 @@ fn get_properties(element: &common::ElementRcNode) -> Vec<PropertyInformation> {
             "layout",
             i_slint_compiler::typeregister::RESERVED_GRIDLAYOUT_PROPERTIES,
         ));
+        result.extend(get_reserved_properties(
+            "transform",
+            i_slint_compiler::typeregister::RESERVED_TRANSFORM_PROPERTIES,
+        ));
         result.push(PropertyInformation {
             name: "accessible-role".into(),
             type_name: Type::Enumeration(
@@ -383,10 +384,12 @@ fn get_properties(element: &common::ElementRcNode) -> Vec<PropertyInformation> {
             group: "accessibility".into(),
         });
         if current_element.borrow().is_binding_set("accessible-role", true) {
-            result.extend(get_reserved_properties(
-                "accessibility",
-                i_slint_compiler::typeregister::RESERVED_ACCESSIBILITY_PROPERTIES,
-            ));
+            result.extend(
+                i_slint_compiler::typeregister::reserved_accessibility_properties()
+                    // Filter away the accessible-action callbacks
+                    .filter(|p| p.1.is_property_type())
+                    .map(|p| get_reserved_property("accessibility", &p)),
+            );
         }
         break;
     }
@@ -55,6 +55,7 @@ pub fn get_semantic_tokens(
                 SyntaxKind::PropertyDeclaration => Some((self::KEYWORD, 0)),
                 SyntaxKind::Function => Some((self::KEYWORD, 0)),
                 SyntaxKind::PropertyAnimation => Some((self::KEYWORD, 0)),
+                SyntaxKind::PropertyChangedCallback => Some((self::KEYWORD, 0)),
                 SyntaxKind::EnumValue => Some((self::ENUM_MEMBER, 1 << self::DEFINITION)),
                 SyntaxKind::QualifiedName => match token.parent().parent()?.kind() {
                     SyntaxKind::Type => Some((self::TYPE, 0)),
@@ -81,6 +82,7 @@ pub fn get_semantic_tokens(
                         SyntaxKind::PropertyDeclaration => {
                             Some((self::PROPERTY, 1 << self::DEFINITION))
                         }
+                        SyntaxKind::PropertyChangedCallback => Some((self::PROPERTY, 0)),
                         SyntaxKind::State | SyntaxKind::Transition => {
                             // This is the state name, but what semantic type is that?
                             None